@@ -19,6 +19,27 @@ impl Config {
         }
     }
 
+    pub fn buffer_pool_size() -> usize {
+        match env::var("BUFFER_POOL_SIZE") {
+            Ok(value) => match value.parse::<usize>() {
+                Ok(value2) => value2,
+                Err(_) => 1024usize
+            },
+            Err(_) => 1024usize
+        }
+    }
+
+    // Bytes of rows a sort keeps in memory before it spills to files.
+    pub fn sort_memory_size() -> usize {
+        match env::var("SORT_MEMORY_SIZE") {
+            Ok(value) => match value.parse::<usize>() {
+                Ok(value2) => value2,
+                Err(_) => 4194304usize
+            },
+            Err(_) => 4194304usize
+        }
+    }
+
     pub fn data_folder() -> String {
         match env::var("DATA_FOLDER") {
             Ok(value) => value.to_string(),
@@ -29,6 +29,14 @@ impl SysDb {
         return String::from("indexes");
     }
 
+    pub fn tblname_foreign_keys() -> String {
+        return String::from("foreign_keys");
+    }
+
+    pub fn tblname_check_constraints() -> String {
+        return String::from("check_constraints");
+    }
+
     pub fn table_databases() -> Table {
         return Table::new(SysDb::dbname(), SysDb::tblname_databases());
     }
@@ -49,4 +57,12 @@ impl SysDb {
         return Table::new(SysDb::dbname(), SysDb::tblname_indexes());
     }
 
+    pub fn table_foreign_keys() -> Table {
+        return Table::new(SysDb::dbname(), SysDb::tblname_foreign_keys());
+    }
+
+    pub fn table_check_constraints() -> Table {
+        return Table::new(SysDb::dbname(), SysDb::tblname_check_constraints());
+    }
+
 }
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::machine::Index;
+use crate::machine::Table;
+
+use crate::storage::Data;
+use crate::storage::Tuple;
+use crate::storage::index_value_compare;
+use crate::storage::index_file_is_hash;
+
+// How the rows of a table are reached by a query.
+#[derive(Debug, Clone)]
+pub enum AccessPath {
+    FullScan(Table),
+    IndexScan(Table, Index, Bound<Tuple>, Bound<Tuple>)
+}
+
+fn format_value(value: &Data) -> String {
+    return match value {
+        Data::Varchar(value) | Data::Text(value) => format!("'{}'", value),
+        other => other.to_string()
+    };
+}
+
+// Value of the bound on the given column of the key, and whether it is
+// excluded. Only the last value of an excluded bound is.
+fn bound_value(bound: &Bound<Tuple>, position: usize) -> Option<(&Data, bool)> {
+    return match bound {
+        Bound::Included(key) => key.get(position).map(|value| (value, false)),
+        Bound::Excluded(key) => key.get(position).map(|value| (value, position + 1 == key.len())),
+        Bound::Unbounded => None
+    };
+}
+
+fn format_bounds(column_names: &Vec<String>, lower: &Bound<Tuple>, upper: &Bound<Tuple>) -> String {
+    let mut conditions: Vec<String> = Vec::new();
+
+    for (position, column_name) in column_names.iter().enumerate() {
+        let lower_value = bound_value(lower, position);
+        let upper_value = bound_value(upper, position);
+
+        if let (Some((lower_value, false)), Some((upper_value, false))) = (lower_value, upper_value) {
+            if index_value_compare(lower_value, upper_value) == Ordering::Equal {
+                conditions.push(format!("{} = {}", column_name, format_value(lower_value)));
+                continue;
+            }
+        }
+
+        if let Some((value, excluded)) = lower_value {
+            let operator = if excluded { ">" } else { ">=" };
+            conditions.push(format!("{} {} {}", column_name, operator, format_value(value)));
+        }
+        if let Some((value, excluded)) = upper_value {
+            let operator = if excluded { "<" } else { "<=" };
+            conditions.push(format!("{} {} {}", column_name, operator, format_value(value)));
+        }
+        break;
+    }
+
+    return conditions.join(" AND ");
+}
+
+impl fmt::Display for AccessPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccessPath::FullScan(table) => write!(f, "Seq Scan on {}", table.name),
+            AccessPath::IndexScan(table, index, lower, upper) => write!(
+                f,
+                "{} Scan using {} on {} ({})",
+                if index_file_is_hash(&index.index_type) { "Hash" } else { "Index" },
+                index.name,
+                table.name,
+                format_bounds(&index.column_names(), lower, upper)
+            )
+        }
+    }
+}
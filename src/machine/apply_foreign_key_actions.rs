@@ -0,0 +1,232 @@
+use std::cmp::Ordering;
+
+use crate::machine::Table;
+use crate::machine::Column;
+use crate::machine::Machine;
+use crate::machine::ForeignKey;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_column_positions;
+use crate::machine::get_foreign_keys;
+use crate::machine::read_tuples_with_address;
+use crate::machine::stage_drop_rows;
+use crate::machine::stage_update_rows;
+
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::Data;
+use crate::storage::index_key_compare;
+use crate::storage::index_key_from_tuple;
+use crate::storage::index_key_format;
+
+use crate::utils::ExecutionError;
+
+struct ReferencingRows {
+    table: Table,
+    columns: Vec<Column>,
+    positions: Vec<usize>,
+    // Each row goes with the position of the key it references.
+    rows: Vec<(TupleAddress, Tuple, usize)>
+}
+
+// Rejects deleting (`tuples` is None) or changing the key of rows that are
+// still referenced through a RESTRICT or NO ACTION foreign key. It runs
+// before the rows are touched.
+pub fn check_foreign_key_actions(
+    machine: &mut Machine,
+    table: &Table,
+    original: &Vec<(TupleAddress, Tuple)>,
+    tuples: Option<&Vec<Tuple>>
+) -> Result<(), ExecutionError> {
+    for foreign_key in referencing_foreign_keys(machine, table).iter() {
+        let action = if tuples.is_none() { &foreign_key.on_delete } else { &foreign_key.on_update };
+        if action != "RESTRICT" && action != "NO ACTION" {
+            continue;
+        }
+
+        let keys = match changed_keys(machine, table, foreign_key, original, tuples) {
+            Ok(keys) => keys,
+            Err(error) => return Err(error)
+        };
+        let referencing = match referencing_rows(machine, table, foreign_key, &keys, original) {
+            Ok(referencing) => referencing,
+            Err(error) => return Err(error)
+        };
+
+        if let Some((_, _, key_position)) = referencing.rows.first() {
+            let (key, _) = keys.get(*key_position).unwrap();
+            return Err(ExecutionError::ForeignKeyViolation(foreign_key.name.clone(), index_key_format(key)));
+        }
+    }
+
+    return Ok(());
+}
+
+// Carries out the CASCADE, SET NULL and SET DEFAULT actions on the rows
+// referencing the deleted or updated ones, once those have been changed on
+// the pager. Nothing is flushed, the caller commits the parent rows and the
+// referencing ones together.
+pub fn apply_foreign_key_actions(
+    machine: &mut Machine,
+    table: &Table,
+    original: &Vec<(TupleAddress, Tuple)>,
+    tuples: Option<&Vec<Tuple>>
+) -> Result<(), ExecutionError> {
+    for foreign_key in referencing_foreign_keys(machine, table).iter() {
+        let action = if tuples.is_none() { &foreign_key.on_delete } else { &foreign_key.on_update };
+        if action != "CASCADE" && action != "SET NULL" && action != "SET DEFAULT" {
+            continue;
+        }
+
+        let keys = match changed_keys(machine, table, foreign_key, original, tuples) {
+            Ok(keys) => keys,
+            Err(error) => return Err(error)
+        };
+        let referencing = match referencing_rows(machine, table, foreign_key, &keys, &Vec::new()) {
+            Ok(referencing) => referencing,
+            Err(error) => return Err(error)
+        };
+        if referencing.rows.len() == 0 {
+            continue;
+        }
+
+        let original_rows: Vec<(TupleAddress, Tuple)> = referencing.rows.iter()
+            .map(|(address, tuple, _)| (*address, tuple.clone()))
+            .collect();
+
+        if action == "CASCADE" && tuples.is_none() {
+            if let Err(error) = stage_drop_rows(machine, &referencing.table, &original_rows) {
+                return Err(error);
+            }
+            continue;
+        }
+
+        let mut new_rows: Vec<(TupleAddress, Tuple)> = Vec::new();
+        for (address, tuple, key_position) in referencing.rows.iter() {
+            let mut new_tuple = tuple.clone();
+
+            for (idx, position) in referencing.positions.iter().enumerate() {
+                let column = referencing.columns.get(*position).unwrap();
+                let value = match action.as_str() {
+                    "CASCADE" => {
+                        let (_, new_key) = keys.get(*key_position).unwrap();
+                        new_key.as_ref().unwrap().get(idx).unwrap().clone()
+                    },
+                    "SET NULL" => Data::Null,
+                    _ => match column.default_value() {
+                        Ok(value) => value,
+                        Err(error) => return Err(error)
+                    }
+                };
+
+                if column.not_null && matches!(value, Data::Null) {
+                    return Err(ExecutionError::ColumnCantBeNull(
+                        referencing.table.database_name.clone(),
+                        referencing.table.name.clone(),
+                        column.name.clone()
+                    ));
+                }
+                match column.coerce(value) {
+                    Ok(value) => new_tuple[*position] = value,
+                    Err(error) => return Err(error)
+                }
+            }
+
+            new_rows.push((*address, new_tuple));
+        }
+
+        if let Err(error) = stage_update_rows(machine, &referencing.table, &original_rows, &mut new_rows) {
+            return Err(error);
+        }
+    }
+
+    return Ok(());
+}
+
+fn referencing_foreign_keys(machine: &mut Machine, table: &Table) -> Vec<ForeignKey> {
+    return get_foreign_keys(machine, &table.database_name)
+        .into_iter()
+        .filter(|foreign_key| foreign_key.referenced_table == table.name)
+        .collect();
+}
+
+// Referenced keys that go away, each with its new value on an UPDATE.
+// Keys an UPDATE leaves as they were are not affected.
+fn changed_keys(
+    machine: &mut Machine,
+    table: &Table,
+    foreign_key: &ForeignKey,
+    original: &Vec<(TupleAddress, Tuple)>,
+    tuples: Option<&Vec<Tuple>>
+) -> Result<Vec<(Tuple, Option<Tuple>)>, ExecutionError> {
+    let columns = get_columns_with_rowid(machine, table);
+    let positions = match get_column_positions(&columns, &foreign_key.referenced_column_names()) {
+        Ok(positions) => positions,
+        Err(error) => return Err(error)
+    };
+
+    let mut keys: Vec<(Tuple, Option<Tuple>)> = Vec::new();
+
+    for (idx, (_, tuple)) in original.iter().enumerate() {
+        let key = index_key_from_tuple(tuple, &positions);
+        if key.iter().any(|value| matches!(value, Data::Null)) {
+            continue;
+        }
+
+        match tuples {
+            Some(tuples) => {
+                let new_key = index_key_from_tuple(tuples.get(idx).unwrap(), &positions);
+                if index_key_compare(&key, &new_key) != Ordering::Equal {
+                    keys.push((key, Some(new_key)));
+                }
+            },
+            None => keys.push((key, None))
+        }
+    }
+
+    return Ok(keys);
+}
+
+// Rows of the referencing table pointing to one of the keys, leaving out
+// the given rows of the table itself.
+fn referencing_rows(
+    machine: &mut Machine,
+    table: &Table,
+    foreign_key: &ForeignKey,
+    keys: &Vec<(Tuple, Option<Tuple>)>,
+    excluded: &Vec<(TupleAddress, Tuple)>
+) -> Result<ReferencingRows, ExecutionError> {
+    let referencing_table = Table::new(table.database_name.clone(), foreign_key.table_name.clone());
+    let columns = get_columns_with_rowid(machine, &referencing_table);
+    let positions = match get_column_positions(&columns, &foreign_key.column_names()) {
+        Ok(positions) => positions,
+        Err(error) => return Err(error)
+    };
+
+    let mut rows: Vec<(TupleAddress, Tuple, usize)> = Vec::new();
+
+    if keys.len() > 0 {
+        let self_reference = referencing_table.name == table.name;
+
+        for (address, tuple) in read_tuples_with_address(machine, &referencing_table).into_iter() {
+            if self_reference && excluded.iter().any(|(excluded_address, _)| *excluded_address == address) {
+                continue;
+            }
+
+            let key = index_key_from_tuple(&tuple, &positions);
+            if key.iter().any(|value| matches!(value, Data::Null)) {
+                continue;
+            }
+
+            if let Some(key_position) = keys.iter().position(|(other, _)| index_key_compare(other, &key) == Ordering::Equal) {
+                rows.push((address, tuple, key_position));
+            }
+        }
+    }
+
+    return Ok(ReferencingRows {
+        table: referencing_table,
+        columns,
+        positions,
+        rows
+    });
+}
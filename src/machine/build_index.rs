@@ -0,0 +1,72 @@
+use std::cmp::Ordering;
+
+use crate::machine::Index;
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_index_positions;
+use crate::machine::read_tuples_with_address;
+
+use crate::storage::Tuple;
+use crate::storage::Data;
+use crate::storage::format_index_name;
+use crate::storage::pager_flush_blocks;
+use crate::storage::pager_discard;
+use crate::storage::index_file_create;
+use crate::storage::index_file_insert;
+use crate::storage::index_key_fits;
+use crate::storage::index_key_from_tuple;
+use crate::storage::index_key_compare;
+use crate::storage::index_key_format;
+
+use crate::utils::ExecutionError;
+use crate::utils::Logger;
+
+// (Re)creates the index file from the rows currently on the table.
+pub fn build_index(machine: &mut Machine, index: &Index) -> Result<(), ExecutionError> {
+    let table = Table::new(index.database_name.clone(), index.table_name.clone());
+    let columns = get_columns_with_rowid(machine, &table);
+
+    let positions = match get_index_positions(&columns, index) {
+        Ok(positions) => positions,
+        Err(error) => return Err(error)
+    };
+
+    let tuples = read_tuples_with_address(machine, &table);
+    for (_, tuple) in tuples.iter() {
+        if index_key_fits(&index_key_from_tuple(tuple, &positions)) == false {
+            return Err(ExecutionError::IndexKeyTooLarge(index.name.clone()));
+        }
+    }
+
+    if index.unique {
+        let mut keys: Vec<Tuple> = tuples
+            .iter()
+            .map(|(_, tuple)| index_key_from_tuple(tuple, &positions))
+            .filter(|key| key.iter().any(|value| matches!(value, Data::Null)) == false)
+            .collect();
+        keys.sort_by(|a, b| index_key_compare(a, b));
+
+        for pair in keys.windows(2) {
+            if index_key_compare(&pair[0], &pair[1]) == Ordering::Equal {
+                return Err(ExecutionError::UniqueViolation(index.name.clone(), index_key_format(&pair[0])));
+            }
+        }
+    }
+
+    Logger::info(format!("Building index {} with {} entries", index.name, tuples.len()).leak());
+
+    let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+    index_file_create(&mut machine.pager, &index_key, &index.index_type);
+
+    for (address, tuple) in tuples.iter() {
+        let key = index_key_from_tuple(tuple, &positions);
+        if let Err(error) = index_file_insert(&mut machine.pager, &index_key, &index.index_type, &key, address) {
+            pager_discard(&mut machine.pager, &index_key);
+            return Err(error);
+        }
+    }
+    pager_flush_blocks(&mut machine.pager);
+
+    return Ok(());
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::config::SysDb;
+
+use crate::machine::Column;
+use crate::machine::ColumnType;
+
+// `expression` keeps the SQL text of the condition, it is parsed again
+// every time the constraint is evaluated.
+#[derive(Debug, Clone)]
+pub struct CheckConstraint {
+    pub database_name: String,
+    pub table_name: String,
+    pub name: String,
+    pub expression: String
+}
+
+impl CheckConstraint {
+
+    pub fn new(
+        database_name: String,
+        table_name: String,
+        name: String,
+        expression: String
+    ) -> Self {
+        CheckConstraint {
+            database_name,
+            table_name,
+            name,
+            expression
+        }
+    }
+}
+
+impl PartialEq for CheckConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        self.database_name == other.database_name && self.name == other.name
+    }
+}
+impl Eq for CheckConstraint {}
+
+impl fmt::Display for CheckConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CheckConstraint({}, {} CHECK ({}))", self.name, self.table_name, self.expression)
+    }
+}
+
+pub fn get_check_constraints_table_definition() -> Vec<Column> {
+    let mut data = vec![
+        Column::new(
+            39u64,
+            SysDb::dbname(),
+            SysDb::tblname_check_constraints(),
+            String::from("id"),
+            ColumnType::UnsignedBigint(0),
+            true,
+            true,
+            true,
+            String::from("")
+        )
+    ];
+    data.append(&mut get_check_constraints_table_definition_without_id());
+    return data;
+}
+
+pub fn get_check_constraints_table_definition_without_id() -> Vec<Column> {
+    return vec![
+        Column::new(
+            40u64,
+            SysDb::dbname(),
+            SysDb::tblname_check_constraints(),
+            String::from("database_name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            41u64,
+            SysDb::dbname(),
+            SysDb::tblname_check_constraints(),
+            String::from("table_name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            42u64,
+            SysDb::dbname(),
+            SysDb::tblname_check_constraints(),
+            String::from("name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            43u64,
+            SysDb::dbname(),
+            SysDb::tblname_check_constraints(),
+            String::from("expression"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+}
@@ -19,7 +19,7 @@ pub fn check_database_exists(machine: &mut Machine, database_name: &String) -> b
     let columns = get_columns(machine, &SysDb::table_databases()).iter().map(|e| e.name.clone()).collect();
     let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_databases())
         .into_iter()
-        .filter(|tuple| condition.result(tuple, &columns).is_true())
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
         .collect();
 
     return tuples.len() > 0;
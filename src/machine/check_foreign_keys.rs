@@ -0,0 +1,113 @@
+use std::cmp::Ordering;
+
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::ForeignKey;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_column_positions;
+use crate::machine::get_foreign_keys;
+use crate::machine::get_table_indexes;
+use crate::machine::read_tuples;
+
+use crate::storage::Tuple;
+use crate::storage::Data;
+use crate::storage::format_index_name;
+use crate::storage::index_file_search;
+use crate::storage::index_key_compare;
+use crate::storage::index_key_from_tuple;
+use crate::storage::index_key_format;
+
+use crate::utils::ExecutionError;
+
+// Rejects rows of a referencing table whose key has no row on the
+// referenced table. Keys with a NULL never do, and rows of a table that
+// references itself may point to other rows of the same batch.
+pub fn check_foreign_keys(machine: &mut Machine, table: &Table, tuples: &Vec<Tuple>) -> Result<(), ExecutionError> {
+    let foreign_keys: Vec<ForeignKey> = get_foreign_keys(machine, &table.database_name)
+        .into_iter()
+        .filter(|foreign_key| foreign_key.table_name == table.name)
+        .collect();
+    if foreign_keys.len() == 0 {
+        return Ok(());
+    }
+
+    let columns = get_columns_with_rowid(machine, table);
+
+    for foreign_key in foreign_keys.iter() {
+        let positions = match get_column_positions(&columns, &foreign_key.column_names()) {
+            Ok(positions) => positions,
+            Err(error) => return Err(error)
+        };
+        let referenced_table = Table::new(table.database_name.clone(), foreign_key.referenced_table.clone());
+        let referenced_keys = match ReferencedKeys::new(machine, &referenced_table, &foreign_key.referenced_column_names()) {
+            Ok(referenced_keys) => referenced_keys,
+            Err(error) => return Err(error)
+        };
+
+        let mut batch_keys: Vec<Tuple> = Vec::new();
+        if foreign_key.referenced_table == table.name {
+            match get_column_positions(&columns, &foreign_key.referenced_column_names()) {
+                Ok(referenced_positions) => {
+                    batch_keys = tuples.iter().map(|tuple| index_key_from_tuple(tuple, &referenced_positions)).collect();
+                },
+                Err(error) => return Err(error)
+            }
+        }
+
+        for tuple in tuples.iter() {
+            let key = index_key_from_tuple(tuple, &positions);
+            if key.iter().any(|value| matches!(value, Data::Null)) {
+                continue;
+            }
+
+            let in_batch = batch_keys.iter().any(|other| index_key_compare(other, &key) == Ordering::Equal);
+
+            if in_batch == false && referenced_keys.contains(machine, &key)? == false {
+                return Err(ExecutionError::ForeignKeyViolation(foreign_key.name.clone(), index_key_format(&key)));
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+// Looks keys up through the unique index over the referenced columns, or
+// on the rows themselves when the index has been dropped.
+enum ReferencedKeys {
+    Index(String, String),
+    Rows(Vec<Tuple>)
+}
+
+impl ReferencedKeys {
+    fn new(machine: &mut Machine, table: &Table, column_names: &Vec<String>) -> Result<Self, ExecutionError> {
+        let indexes = match get_table_indexes(machine, table) {
+            Ok(indexes) => indexes,
+            Err(error) => return Err(error)
+        };
+        if let Some(index) = indexes.iter().find(|index| index.column_names() == *column_names) {
+            return Ok(ReferencedKeys::Index(
+                format_index_name(&index.database_name, &index.table_name, &index.name),
+                index.index_type.clone()
+            ));
+        }
+
+        let columns = get_columns_with_rowid(machine, table);
+        let keys: Vec<Tuple> = match get_column_positions(&columns, column_names) {
+            Ok(positions) => read_tuples(machine, table)
+                .iter()
+                .map(|tuple| index_key_from_tuple(tuple, &positions))
+                .collect(),
+            Err(_) => Vec::new()
+        };
+        return Ok(ReferencedKeys::Rows(keys));
+    }
+
+    fn contains(&self, machine: &Machine, key: &Tuple) -> Result<bool, ExecutionError> {
+        return match self {
+            ReferencedKeys::Index(index_key, index_type) => {
+                Ok(index_file_search(&machine.pager, index_key, index_type, key)?.len() > 0)
+            },
+            ReferencedKeys::Rows(keys) => Ok(keys.iter().any(|other| index_key_compare(other, key) == Ordering::Equal))
+        };
+    }
+}
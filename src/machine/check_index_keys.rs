@@ -0,0 +1,36 @@
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_table_indexes;
+use crate::machine::get_index_positions;
+
+use crate::storage::Tuple;
+use crate::storage::index_key_fits;
+use crate::storage::index_key_from_tuple;
+
+use crate::utils::ExecutionError;
+
+// Rejects rows whose values are too big to be a key of one of the table indexes.
+pub fn check_index_keys(machine: &mut Machine, table: &Table, tuples: &Vec<Tuple>) -> Result<(), ExecutionError> {
+    let indexes = match get_table_indexes(machine, table) {
+        Ok(indexes) => indexes,
+        Err(error) => return Err(error)
+    };
+    if indexes.len() == 0 {
+        return Ok(());
+    }
+
+    let columns = get_columns_with_rowid(machine, table);
+
+    for index in indexes.iter() {
+        if let Ok(positions) = get_index_positions(&columns, index) {
+            for tuple in tuples.iter() {
+                if index_key_fits(&index_key_from_tuple(tuple, &positions)) == false {
+                    return Err(ExecutionError::IndexKeyTooLarge(index.name.clone()));
+                }
+            }
+        }
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,66 @@
+use std::cmp::Ordering;
+
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_table_indexes;
+use crate::machine::get_index_positions;
+
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::Data;
+use crate::storage::format_index_name;
+use crate::storage::index_file_search;
+use crate::storage::index_key_compare;
+use crate::storage::index_key_from_tuple;
+use crate::storage::index_key_format;
+
+use crate::utils::ExecutionError;
+
+// Rejects rows whose key is already on one of the unique indexes of the
+// table, or repeated among the rows themselves. Rows being replaced by the
+// given ones (an UPDATE) don't conflict. Keys with a NULL never do.
+pub fn check_unique_keys(
+    machine: &mut Machine,
+    table: &Table,
+    tuples: &Vec<Tuple>,
+    replaced: &Vec<TupleAddress>
+) -> Result<(), ExecutionError> {
+    let indexes = match get_table_indexes(machine, table) {
+        Ok(indexes) => indexes,
+        Err(error) => return Err(error)
+    };
+    if indexes.iter().all(|index| index.unique == false) {
+        return Ok(());
+    }
+
+    let columns = get_columns_with_rowid(machine, table);
+
+    for index in indexes.iter().filter(|index| index.unique) {
+        let positions = match get_index_positions(&columns, index) {
+            Ok(positions) => positions,
+            Err(_) => continue
+        };
+        let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+        let mut keys: Vec<Tuple> = Vec::new();
+
+        for tuple in tuples.iter() {
+            let key = index_key_from_tuple(tuple, &positions);
+            if key.iter().any(|value| matches!(value, Data::Null)) {
+                continue;
+            }
+
+            let repeated = keys.iter().any(|other| index_key_compare(other, &key) == Ordering::Equal);
+            let stored = index_file_search(&machine.pager, &index_key, &index.index_type, &key)?
+                .into_iter()
+                .any(|address| replaced.contains(&address) == false);
+
+            if repeated || stored {
+                return Err(ExecutionError::UniqueViolation(index.name.clone(), index_key_format(&key)));
+            }
+            keys.push(key);
+        }
+    }
+
+    return Ok(());
+}
@@ -1,11 +1,29 @@
 use std::fmt;
 
+use ordered_float::OrderedFloat;
+
 use crate::machine::Table;
 
 use crate::storage::Data;
+use crate::storage::decimal_parse;
+use crate::storage::decimal_rescale;
+use crate::storage::decimal_digits;
+use crate::storage::decimal_pow10;
+use crate::storage::DECIMAL_MAX_PRECISION;
+use crate::storage::temporal_cast;
+use crate::storage::bytes_cast;
+use crate::storage::uuid_cast;
+use crate::storage::JsonValue;
+use crate::storage::json_cast;
+use crate::storage::json_format;
+use crate::storage::json_normalize;
+
+use crate::parser::parse_expression;
 
 use crate::config::SysDb;
 
+use crate::utils::ExecutionError;
+
 pub type ColumnType = Data;
 
 #[derive(Debug, Clone)]
@@ -96,10 +114,185 @@ impl Column {
             ColumnType::Varchar(_) => String::from("VARCHAR"),
             ColumnType::Text(_) => String::from("TEXT"),
             ColumnType::Boolean(_) => String::from("UNSIGNED TINYINT"),
+            ColumnType::Float32(_) => String::from("REAL"),
+            ColumnType::Float64(_) => String::from("DOUBLE"),
+            ColumnType::Decimal(_, precision, scale) => format!("DECIMAL({},{})", precision, scale),
+            ColumnType::Date(_) => String::from("DATE"),
+            ColumnType::Time(_) => String::from("TIME"),
+            ColumnType::Timestamp(_) => String::from("TIMESTAMP"),
+            ColumnType::TimestampTz(_) => String::from("TIMESTAMP WITH TIME ZONE"),
+            ColumnType::Interval(_, _, _) => String::from("INTERVAL"),
+            ColumnType::Bytes(_) => String::from("BYTEA"),
+            ColumnType::Uuid(_) => String::from("UUID"),
+            ColumnType::Json(_) => String::from("JSON"),
+            ColumnType::Jsonb(_) => String::from("JSONB"),
             _ => String::from("UNDEFINED")
         };
     }
 
+    // Value taken by the column when none is given, NULL without a default.
+    // CREATE TABLE checks it once, so a default that does not fit the column
+    // is refused there.
+    pub fn default_value(&self) -> Result<Data, ExecutionError> {
+        if self.default == String::from("") {
+            return Ok(Data::Null);
+        }
+
+        return match &self.column_type {
+            // Temporal and UUID defaults are literals or expressions such as
+            // NOW() and gen_random_uuid(), evaluated on each insert.
+            ColumnType::Uuid(_) |
+            ColumnType::Date(_) |
+            ColumnType::Time(_) |
+            ColumnType::Timestamp(_) |
+            ColumnType::TimestampTz(_) |
+            ColumnType::Interval(_, _, _) => {
+                if let Ok(value) = self.coerce(Data::Varchar(self.default.clone())) {
+                    return Ok(value);
+                }
+                match parse_expression(&self.default) {
+                    Ok(expression) if expression.column_names().len() == 0 => {
+                        match expression.result(&Vec::new(), &Vec::new()) {
+                            Ok(value) => self.coerce(value),
+                            Err(error) => Err(error)
+                        }
+                    },
+                    _ => Err(ExecutionError::ColumnTypeNotMatch(
+                        self.database_name.clone(),
+                        self.table_name.clone(),
+                        self.name.clone()
+                    ))
+                }
+            },
+            ColumnType::Boolean(_) => Ok(Data::Boolean(self.default == String::from("1"))),
+            ColumnType::Undefined => Ok(Data::Null),
+            _ => self.coerce(Data::Varchar(self.default.clone()))
+        };
+    }
+
+    // Converts a value to the type of the column: integers of any size
+    // within its range, booleans as 1 and 0, and strings holding a number.
+    // Floats take any number, integers only floats and decimals without a
+    // fraction. Decimals round to their scale and reject values with more
+    // digits than their precision. Temporal types take strings in ISO-8601
+    // and the temporal values they can be cast from. Binary types take
+    // strings in hex after a \x and the bytes of any other string, UUIDs
+    // strings in their canonical form. JSON types take valid JSON texts, JSON
+    // keeps the text as it was given.
+    pub fn coerce(&self, value: Data) -> Result<Data, ExecutionError> {
+        if matches!(value, Data::Null) {
+            return Ok(value);
+        }
+
+        let not_match = ExecutionError::ColumnTypeNotMatch(
+            self.database_name.clone(),
+            self.table_name.clone(),
+            self.name.clone()
+        );
+
+        if matches!(self.column_type, ColumnType::Varchar(_)) {
+            return Ok(Data::Varchar(value.to_string()));
+        }
+        if matches!(self.column_type, ColumnType::Text(_)) {
+            return Ok(Data::Text(value.to_string()));
+        }
+        if matches!(self.column_type, ColumnType::Float32(_) | ColumnType::Float64(_)) {
+            let float = match &value {
+                Data::Boolean(value) => Some(*value as u8 as f64),
+                Data::Varchar(value) | Data::Text(value) => value.trim().parse::<f64>().ok(),
+                other => other.to_float()
+            };
+            let Some(float) = float else { return Err(not_match) };
+
+            if matches!(self.column_type, ColumnType::Float32(_)) {
+                return Ok(Data::Float32(OrderedFloat(float as f32)));
+            }
+            return Ok(Data::Float64(OrderedFloat(float)));
+        }
+        if self.column_type.is_temporal() {
+            return match temporal_cast(&self.column_type, &value) {
+                Some(value) => Ok(value),
+                None => Err(not_match)
+            };
+        }
+        if matches!(self.column_type, ColumnType::Bytes(_)) {
+            return match bytes_cast(&value) {
+                Some(bytes) => Ok(Data::Bytes(bytes)),
+                None => Err(not_match)
+            };
+        }
+        if matches!(self.column_type, ColumnType::Uuid(_)) {
+            return match uuid_cast(&value) {
+                Some(uuid) => Ok(Data::Uuid(uuid)),
+                None => Err(not_match)
+            };
+        }
+        if matches!(self.column_type, ColumnType::Json(_) | ColumnType::Jsonb(_)) {
+            let Some(json) = json_cast(&value) else { return Err(not_match) };
+
+            return match (&self.column_type, value) {
+                (ColumnType::Jsonb(_), _) => Ok(Data::Jsonb(json_normalize(json))),
+                (_, Data::Varchar(text) | Data::Text(text) | Data::Json(text)) => Ok(Data::Json(text)),
+                _ => Ok(Data::Json(json_format(&json)))
+            };
+        }
+        if let ColumnType::Decimal(_, precision, scale) = self.column_type {
+            let decimal = match &value {
+                Data::Boolean(value) => Some((*value as i128, 0)),
+                Data::Varchar(value) | Data::Text(value) => decimal_parse(value),
+                Data::Float32(_) | Data::Float64(_) => decimal_parse(&value.to_string()),
+                other => other.to_decimal()
+            };
+            let decimal = decimal.and_then(|(value, value_scale)| decimal_rescale(value, value_scale, scale));
+
+            return match decimal {
+                Some(decimal) if decimal_digits(decimal) <= precision => Ok(Data::Decimal(decimal, precision, scale)),
+                _ => Err(not_match)
+            };
+        }
+
+        let integer = match &value {
+            Data::Boolean(value) => Some(*value as i128),
+            Data::Varchar(value) | Data::Text(value) => value.trim().parse::<i128>().ok(),
+            Data::Float32(_) | Data::Float64(_) => {
+                value.to_float().filter(|value| value.fract() == 0.0).map(|value| value as i128)
+            },
+            Data::Decimal(value, _, scale) if value % decimal_pow10(*scale) == 0 => {
+                Some(value / decimal_pow10(*scale))
+            },
+            other => other.to_integer()
+        };
+        let Some(integer) = integer else { return Err(not_match) };
+
+        let coerced = match self.column_type {
+            ColumnType::Boolean(_) if integer == 0 || integer == 1 => Some(Data::Boolean(integer == 1)),
+            ColumnType::UnsignedTinyint(_) => u8::try_from(integer).ok().map(Data::UnsignedTinyint),
+            ColumnType::UnsignedSmallint(_) => u16::try_from(integer).ok().map(Data::UnsignedSmallint),
+            ColumnType::UnsignedInt(_) => u32::try_from(integer).ok().map(Data::UnsignedInt),
+            ColumnType::UnsignedBigint(_) => u64::try_from(integer).ok().map(Data::UnsignedBigint),
+            ColumnType::SignedTinyint(_) => i8::try_from(integer).ok().map(Data::SignedTinyint),
+            ColumnType::SignedSmallint(_) => i16::try_from(integer).ok().map(Data::SignedSmallint),
+            ColumnType::SignedInt(_) => i32::try_from(integer).ok().map(Data::SignedInt),
+            ColumnType::SignedBigint(_) => i64::try_from(integer).ok().map(Data::SignedBigint),
+            ColumnType::Undefined => Some(value),
+            _ => None
+        };
+
+        return match coerced {
+            Some(coerced) => Ok(coerced),
+            None => Err(not_match)
+        };
+    }
+
+    // Name expressions reach the column by, its alias after the alias of
+    // its table when it has one.
+    pub fn qualified_name(&self) -> String {
+        if self.table_alias.is_empty() {
+            return self.alias.clone();
+        }
+        return format!("{}.{}", self.table_alias, self.alias);
+    }
+
     pub fn check_column_name(&self, other_name: &String) -> bool {
         return self.name == *other_name; 
     }
@@ -114,7 +307,10 @@ impl Column {
             ColumnType::SignedTinyint(_) |
             ColumnType::SignedSmallint(_) |
             ColumnType::SignedInt(_) |
-            ColumnType::SignedBigint(_)
+            ColumnType::SignedBigint(_) |
+            ColumnType::Float32(_) |
+            ColumnType::Float64(_) |
+            ColumnType::Decimal(_, _, _)
         );
     }
 }
@@ -156,10 +352,66 @@ pub fn map_column_type(value: String) -> ColumnType {
         "SIGNED BIGINT" => ColumnType::SignedBigint(0),
         "VARCHAR" => ColumnType::Varchar("".to_string()),
         "TEXT" => ColumnType::Text("".to_string()),
+        "REAL" => ColumnType::Float32(OrderedFloat(0.0)),
+        "DOUBLE" => ColumnType::Float64(OrderedFloat(0.0)),
+        "DATE" => ColumnType::Date(0),
+        "TIME" => ColumnType::Time(0),
+        "TIMESTAMP" => ColumnType::Timestamp(0),
+        "TIMESTAMP WITH TIME ZONE" => ColumnType::TimestampTz(0),
+        "INTERVAL" => ColumnType::Interval(0, 0, 0),
+        "BYTEA" => ColumnType::Bytes(Vec::new()),
+        "UUID" => ColumnType::Uuid(0),
+        "JSON" => ColumnType::Json(String::new()),
+        "JSONB" => ColumnType::Jsonb(JsonValue::Null),
+        other if other.starts_with("DECIMAL") || other.starts_with("NUMERIC") => {
+            // A type the catalog can not hold any more is left undefined, so
+            // writing to the column fails instead of the whole read.
+            map_decimal_name(other).unwrap_or(ColumnType::Undefined)
+        },
         _ => ColumnType::Varchar("".to_string())
     }
 }
 
+// Types stored as DECIMAL(precision,scale), NUMERIC and the forms without
+// scale are read as well.
+fn map_decimal_name(value: &str) -> Result<ColumnType, ExecutionError> {
+    let invalid = ExecutionError::ParserError(format!("invalid decimal type {}", value));
+
+    let arguments = match value.split_once('(') {
+        Some((_, arguments)) => match arguments.strip_suffix(')') {
+            Some(arguments) => arguments,
+            None => return Err(invalid)
+        },
+        None => return map_decimal_type(None, None)
+    };
+    let (precision, scale) = match arguments.split_once(',') {
+        Some((precision, scale)) => (precision, Some(scale)),
+        None => (arguments, None)
+    };
+
+    let Ok(precision) = precision.trim().parse::<u64>() else { return Err(invalid) };
+    let scale = match scale.map(|scale| scale.trim().parse::<u64>()) {
+        Some(Ok(scale)) => Some(scale),
+        Some(Err(_)) => return Err(invalid),
+        None => None
+    };
+    return map_decimal_type(Some(precision), scale);
+}
+
+// Like PostgreSQL, DECIMAL without a precision holds the most digits we can
+// and the scale is 0 unless one is given.
+pub fn map_decimal_type(precision: Option<u64>, scale: Option<u64>) -> Result<ColumnType, ExecutionError> {
+    let precision = precision.unwrap_or(DECIMAL_MAX_PRECISION as u64);
+    let scale = scale.unwrap_or(0);
+
+    if precision == 0 || precision > DECIMAL_MAX_PRECISION as u64 || scale > precision {
+        return Err(ExecutionError::ParserError(
+            format!("DECIMAL({},{}) must have a precision from 1 to {} and a scale up to it", precision, scale, DECIMAL_MAX_PRECISION)
+        ));
+    }
+    return Ok(ColumnType::Decimal(0, precision as u8, scale as u8));
+}
+
 pub fn get_rowid_column_for_table(table: &Table) -> Column {
     Column::new(
         08u64,
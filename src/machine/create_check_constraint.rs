@@ -0,0 +1,69 @@
+use crate::machine::CheckConstraint;
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::insert_row;
+use crate::machine::read_tuples;
+use crate::machine::check_table_exists;
+use crate::machine::evaluate_check_constraints;
+use crate::machine::drop_check_constraint;
+use crate::machine::get_columns;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_check_constraints_table_definition_without_id;
+
+use crate::parser::parse_expression;
+
+use crate::storage::Tuple;
+use crate::storage::get_tuple_check_constraint;
+use crate::storage::ResultSet;
+use crate::storage::ResultSetType;
+
+use crate::config::SysDb;
+
+use crate::utils::ExecutionError;
+
+pub fn create_check_constraint(machine: &mut Machine, check_constraint: &CheckConstraint) -> Result<ResultSet, ExecutionError>{
+    let table = Table::new(check_constraint.database_name.clone(), check_constraint.table_name.clone());
+    if check_table_exists(machine, &table) == false {
+        return Err(ExecutionError::TableNotExists(check_constraint.table_name.clone()));
+    }
+
+    let expression = match parse_expression(&check_constraint.expression) {
+        Ok(expression) => expression,
+        Err(error) => return Err(error)
+    };
+    let columns = get_columns_with_rowid(machine, &table);
+    for column_name in expression.column_names().iter() {
+        if columns.iter().any(|column| column.name == *column_name) == false {
+            return Err(ExecutionError::ColumnNotExists(column_name.clone()));
+        }
+    }
+
+    let mut tuples: Vec<Tuple> = Vec::new();
+    tuples.push(
+        get_tuple_check_constraint(
+            &check_constraint.database_name,
+            &check_constraint.table_name,
+            &check_constraint.name,
+            &check_constraint.expression
+        )
+    );
+
+    let table_columns = &get_columns(machine, &SysDb::table_check_constraints());
+    let _ = insert_row(
+        machine,
+        &SysDb::table_check_constraints(),
+        table_columns,
+        &get_check_constraints_table_definition_without_id(),
+        &mut tuples,
+        false
+    );
+
+    // Rows already on the table must satisfy the new constraint.
+    let rows = read_tuples(machine, &table);
+    if let Err(error) = evaluate_check_constraints(machine, &table, &rows) {
+        let _ = drop_check_constraint(machine, &check_constraint.database_name, &check_constraint.name);
+        return Err(error);
+    }
+
+    Ok(ResultSet::new_command(ResultSetType::Change, String::from("ADD CONSTRAINT")))
+}
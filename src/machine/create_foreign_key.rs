@@ -0,0 +1,99 @@
+use crate::machine::ForeignKey;
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::insert_row;
+use crate::machine::read_tuples;
+use crate::machine::check_table_exists;
+use crate::machine::check_foreign_keys;
+use crate::machine::drop_foreign_key;
+use crate::machine::get_columns;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_column_positions;
+use crate::machine::get_table_indexes;
+use crate::machine::get_foreign_keys_table_definition_without_id;
+
+use crate::storage::Tuple;
+use crate::storage::get_tuple_foreign_key;
+use crate::storage::ResultSet;
+use crate::storage::ResultSetType;
+
+use crate::config::SysDb;
+
+use crate::utils::ExecutionError;
+
+pub fn create_foreign_key(machine: &mut Machine, foreign_key: &ForeignKey) -> Result<ResultSet, ExecutionError>{
+    let table = Table::new(foreign_key.database_name.clone(), foreign_key.table_name.clone());
+    if check_table_exists(machine, &table) == false {
+        return Err(ExecutionError::TableNotExists(foreign_key.table_name.clone()));
+    }
+    let referenced_table = Table::new(foreign_key.database_name.clone(), foreign_key.referenced_table.clone());
+    if check_table_exists(machine, &referenced_table) == false {
+        return Err(ExecutionError::TableNotExists(foreign_key.referenced_table.clone()));
+    }
+
+    let column_names = foreign_key.column_names();
+    let referenced_column_names = foreign_key.referenced_column_names();
+    if column_names.len() != referenced_column_names.len() {
+        return Err(ExecutionError::WrongLength);
+    }
+
+    let columns = get_columns_with_rowid(machine, &table);
+    if let Err(error) = get_column_positions(&columns, &column_names) {
+        return Err(error);
+    }
+    let referenced_columns = get_columns_with_rowid(machine, &referenced_table);
+    if let Err(error) = get_column_positions(&referenced_columns, &referenced_column_names) {
+        return Err(error);
+    }
+
+    // The referenced columns must be the key of a unique index, so each
+    // referencing row points to exactly one row.
+    let referenced_indexes = match get_table_indexes(machine, &referenced_table) {
+        Ok(indexes) => indexes,
+        Err(error) => return Err(error)
+    };
+    let referenced_key = referenced_indexes
+        .iter()
+        .any(|index| index.unique && index.column_names() == referenced_column_names);
+    if referenced_key == false {
+        return Err(ExecutionError::ReferencedKeyNotUnique(
+            foreign_key.referenced_table.clone(),
+            foreign_key.referenced_column.clone()
+        ));
+    }
+
+    let mut tuples: Vec<Tuple> = Vec::new();
+    tuples.push(
+        get_tuple_foreign_key(
+            &foreign_key.database_name,
+            &foreign_key.table_name,
+            &foreign_key.column_name,
+            &foreign_key.name,
+            &foreign_key.referenced_table,
+            &foreign_key.referenced_column,
+            &foreign_key.on_delete,
+            &foreign_key.on_update
+        )
+    );
+
+    let table_columns = &get_columns(machine, &SysDb::table_foreign_keys());
+    if let Err(error) = insert_row(
+        machine,
+        &SysDb::table_foreign_keys(),
+        table_columns,
+        &get_foreign_keys_table_definition_without_id(),
+        &mut tuples,
+        false
+    ) {
+        return Err(error);
+    }
+
+    // Rows already on the table must satisfy the new constraint.
+    let rows = read_tuples(machine, &table);
+    if let Err(error) = check_foreign_keys(machine, &table, &rows) {
+        let _ = drop_foreign_key(machine, &foreign_key.database_name, &foreign_key.name);
+        return Err(error);
+    }
+
+    Ok(ResultSet::new_command(ResultSetType::Change, String::from("ADD CONSTRAINT")))
+}
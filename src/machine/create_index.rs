@@ -1,5 +1,9 @@
+use crate::machine::Index;
+use crate::machine::Table;
 use crate::machine::Machine;
 use crate::machine::insert_row;
+use crate::machine::build_index;
+use crate::machine::check_table_exists;
 use crate::machine::get_indexes_table_definition_without_id;
 use crate::machine::get_columns;
 
@@ -7,6 +11,9 @@ use crate::storage::Tuple;
 use crate::storage::get_tuple_index;
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
+use crate::storage::destroy_file;
+use crate::storage::pager_discard;
+use crate::storage::format_index_name;
 
 use crate::config::SysDb;
 
@@ -18,14 +25,33 @@ pub fn create_index(
     table_name: &String,
     column_name: &String,
     index_name: &String,
-    index_type: &String
+    index_type: &String,
+    unique: bool,
+    primary_key: bool
 ) -> Result<ResultSet, ExecutionError>{
+    let table = Table::new(database_name.clone(), table_name.clone());
+    if check_table_exists(machine, &table) == false {
+        return Err(ExecutionError::TableNotExists(table_name.to_string()));
+    }
+
+    let index = Index::new(
+        database_name.clone(),
+        table_name.clone(),
+        column_name.clone(),
+        index_name.clone(),
+        index_type.clone(),
+        unique,
+        primary_key
+    );
+    if let Err(error) = build_index(machine, &index) {
+        return Err(error);
+    }
 
     let mut tuples: Vec<Tuple> = Vec::new();
-    tuples.push(get_tuple_index(&database_name, &table_name, &column_name, &index_name, &index_type));
+    tuples.push(get_tuple_index(&database_name, &table_name, &column_name, &index_name, &index_type, unique, primary_key));
 
     let table_columns = &get_columns(machine, &SysDb::table_indexes());
-    let _ = insert_row(
+    let inserted = insert_row(
         machine,
         &SysDb::table_indexes(),
         table_columns,
@@ -33,6 +59,13 @@ pub fn create_index(
         &mut tuples,
         false
     );
+    // The index file is already flushed, so it goes away with the catalog row.
+    if let Err(error) = inserted {
+        let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+        pager_discard(&mut machine.pager, &index_key);
+        destroy_file(&index_key);
+        return Err(error);
+    }
 
     Ok(ResultSet::new_command(ResultSetType::Change, String::from("CREATE INDEX")))
 }
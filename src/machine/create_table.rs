@@ -1,12 +1,14 @@
 
 use crate::machine::Table;
 use crate::machine::Column;
+use crate::machine::ColumnType;
 use crate::machine::Machine;
 use crate::machine::insert_row;
 use crate::machine::get_tables_table_definition_without_id;
 use crate::machine::create_sequence;
 use crate::machine::create_columns;
 use crate::machine::get_columns;
+use crate::machine::create_index;
 
 use crate::storage::create_file;
 use crate::storage::get_tuple_table;
@@ -14,6 +16,7 @@ use crate::storage::format_table_name;
 use crate::storage::header_serialize;
 use crate::storage::header_new;
 use crate::storage::write_data;
+use crate::storage::pager_discard;
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
 
@@ -44,8 +47,10 @@ pub fn create_table(
         return Err(err);
     }
 
+    // UUID keys take their default, such as gen_random_uuid(), instead of
+    // counting from a sequence.
     for column in columns.iter() {
-        if column.primary_key {
+        if column.primary_key && !matches!(column.column_type, ColumnType::Uuid(_)) {
             let _ = create_sequence(
                 machine,
                 &table.database_name,
@@ -66,6 +71,32 @@ pub fn create_table(
     let table_key = format_table_name(&table.database_name, &table.name);
     create_file(&table_key);
     write_data(&table_key, 0, &header_serialize(&header_new()));
+    pager_discard(&mut machine.pager, &table_key);
+
+    // UNIQUE and PRIMARY KEY columns are enforced through unique indexes.
+    for column in columns.iter() {
+        if column.unique == false && column.primary_key == false {
+            continue;
+        }
+
+        let index_name = if column.primary_key {
+            format!("{}_pkey", table.name)
+        } else {
+            format!("{}_{}_key", table.name, column.name)
+        };
+        if let Err(err) = create_index(
+            machine,
+            &table.database_name,
+            &table.name,
+            &column.name,
+            &index_name,
+            &String::from("btree"),
+            true,
+            column.primary_key
+        ) {
+            return Err(err);
+        }
+    }
 
     Ok(ResultSet::new_command(ResultSetType::Change, String::from("CREATE TABLE")))
 }
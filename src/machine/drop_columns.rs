@@ -24,5 +24,5 @@ pub fn drop_columns(machine: &mut Machine, table: &Table) {
         ))
     );
 
-    drop_tuples(machine, &SysDb::table_columns(), get_columns_table_definition(), &condition);
+    let _ = drop_tuples(machine, &SysDb::table_columns(), get_columns_table_definition(), &condition);
 }
@@ -1,5 +1,9 @@
 use crate::machine::Machine;
 use crate::machine::get_tables;
+use crate::machine::get_foreign_keys;
+use crate::machine::drop_foreign_key;
+use crate::machine::get_check_constraints;
+use crate::machine::drop_check_constraint;
 use crate::machine::drop_columns;
 use crate::machine::drop_table_ref;
 use crate::machine::drop_database_ref;
@@ -7,6 +11,8 @@ use crate::machine::check_database_exists;
 
 use crate::storage::destroy_folder;
 use crate::storage::format_database_name;
+use crate::storage::format_table_name;
+use crate::storage::pager_discard;
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
 
@@ -23,6 +29,13 @@ pub fn drop_database(machine: &mut Machine, database_name: String, if_exists: bo
     for table in get_tables(machine, &database_name) {
         drop_columns(machine, &table);
         drop_table_ref(machine, &table);
+        pager_discard(&mut machine.pager, &format_table_name(&table.database_name, &table.name));
+    }
+    for foreign_key in get_foreign_keys(machine, &database_name) {
+        let _ = drop_foreign_key(machine, &database_name, &foreign_key.name);
+    }
+    for check_constraint in get_check_constraints(machine, &database_name) {
+        let _ = drop_check_constraint(machine, &database_name, &check_constraint.name);
     }
     drop_database_ref(machine, &database_name);
 
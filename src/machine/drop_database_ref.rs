@@ -17,5 +17,5 @@ pub fn drop_database_ref(machine: &mut Machine, database_name: &String) {
         Box::new(Expression::Const(Data::Varchar(database_name.clone())))
     );
 
-    drop_tuples(machine, &SysDb::table_databases(), columns, &condition);
+    let _ = drop_tuples(machine, &SysDb::table_databases(), columns, &condition);
 }
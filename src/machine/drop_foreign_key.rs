@@ -0,0 +1,37 @@
+use crate::machine::Machine;
+use crate::machine::drop_tuples;
+use crate::machine::get_columns;
+
+use crate::storage::Data;
+use crate::storage::Expression;
+use crate::storage::Expression2Type;
+use crate::storage::ResultSet;
+use crate::storage::ResultSetType;
+
+use crate::utils::ExecutionError;
+
+use crate::config::SysDb;
+
+pub fn drop_foreign_key(machine: &mut Machine, database_name: &String, name: &String) -> Result<ResultSet, ExecutionError>{
+    let columns = get_columns(machine, &SysDb::table_foreign_keys());
+
+    let condition = Expression::Func2(
+        Expression2Type::And,
+        Box::new(Expression::Func2(
+            Expression2Type::Equal,
+            Box::new(Expression::ColName(String::from("database_name"))),
+            Box::new(Expression::Const(Data::Varchar(database_name.clone())))
+        )),
+        Box::new(Expression::Func2(
+            Expression2Type::Equal,
+            Box::new(Expression::ColName(String::from("name"))),
+            Box::new(Expression::Const(Data::Varchar(name.clone())))
+        ))
+    );
+
+    if let Err(error) = drop_tuples(machine, &SysDb::table_foreign_keys(), columns, &condition) {
+        return Err(error);
+    }
+
+    Ok(ResultSet::new_command(ResultSetType::Change, String::from("DROP CONSTRAINT")))
+}
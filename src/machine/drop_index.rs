@@ -1,27 +1,45 @@
 use crate::machine::Machine;
 use crate::machine::drop_tuples;
 use crate::machine::get_columns;
+use crate::machine::get_indexes;
 
 use crate::storage::Data;
 use crate::storage::Expression;
 use crate::storage::Expression2Type;
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
+use crate::storage::destroy_file;
+use crate::storage::pager_discard;
+use crate::storage::format_index_name;
 
 use crate::utils::ExecutionError;
 
 use crate::config::SysDb;
 
-pub fn drop_index(machine: &mut Machine, index_name: &String) -> Result<ResultSet, ExecutionError>{
+pub fn drop_index(machine: &mut Machine, database_name: &String, index_name: &String) -> Result<ResultSet, ExecutionError>{
+    for index in get_indexes(machine, database_name).iter().filter(|index| index.name == *index_name) {
+        let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+        pager_discard(&mut machine.pager, &index_key);
+        destroy_file(&index_key);
+    }
+
     let columns = get_columns(machine, &SysDb::table_indexes());
 
     let condition = Expression::Func2(
-        Expression2Type::Equal,
-        Box::new(Expression::ColName(String::from("name"))),
-        Box::new(Expression::Const(Data::Varchar(index_name.clone())))
+        Expression2Type::And,
+        Box::new(Expression::Func2(
+            Expression2Type::Equal,
+            Box::new(Expression::ColName(String::from("database_name"))),
+            Box::new(Expression::Const(Data::Varchar(database_name.clone())))
+        )),
+        Box::new(Expression::Func2(
+            Expression2Type::Equal,
+            Box::new(Expression::ColName(String::from("name"))),
+            Box::new(Expression::Const(Data::Varchar(index_name.clone())))
+        ))
     );
 
-    drop_tuples(machine, &SysDb::table_indexes(), columns, &condition);
+    let _ = drop_tuples(machine, &SysDb::table_indexes(), columns, &condition);
 
     Ok(ResultSet::new_command(ResultSetType::Change, String::from("DROP INDEX")))
 }
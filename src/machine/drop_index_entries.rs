@@ -0,0 +1,38 @@
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_table_indexes;
+use crate::machine::get_index_positions;
+
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::format_index_name;
+use crate::storage::index_key_from_tuple;
+use crate::storage::index_file_delete;
+
+use crate::utils::ExecutionError;
+
+// Removes the rows stored on the given addresses from every index of the table.
+pub fn drop_index_entries(machine: &mut Machine, table: &Table, tuples: &Vec<(TupleAddress, Tuple)>) -> Result<(), ExecutionError> {
+    let indexes = match get_table_indexes(machine, table) {
+        Ok(indexes) => indexes,
+        Err(error) => return Err(error)
+    };
+    if indexes.len() == 0 {
+        return Ok(());
+    }
+
+    let columns = get_columns_with_rowid(machine, table);
+
+    for index in indexes.iter() {
+        let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+
+        if let Ok(positions) = get_index_positions(&columns, index) {
+            for (address, tuple) in tuples.iter() {
+                index_file_delete(&mut machine.pager, &index_key, &index.index_type, &index_key_from_tuple(tuple, &positions), address)?;
+            }
+        }
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,47 @@
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::drop_index_entries;
+use crate::machine::check_foreign_key_actions;
+use crate::machine::apply_foreign_key_actions;
+
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::format_table_name;
+use crate::storage::pager_delete_tuple;
+use crate::storage::pager_flush_all;
+use crate::storage::pager_rollback;
+
+use crate::utils::ExecutionError;
+
+// Deletes the rows stored on the given addresses, along with their index
+// entries and whatever the foreign keys referencing the table ask for. The
+// changes of every table are committed on one flush, or thrown away when
+// any of them fails.
+pub fn drop_rows(machine: &mut Machine, table: &Table, tuples: &Vec<(TupleAddress, Tuple)>) -> Result<(), ExecutionError> {
+    if let Err(error) = stage_drop_rows(machine, table, tuples) {
+        pager_rollback(&mut machine.pager);
+        return Err(error);
+    }
+    pager_flush_all(&mut machine.pager);
+
+    return Ok(());
+}
+
+// Same as drop_rows, leaving the changes on the pager for the caller to
+// flush or roll back.
+pub fn stage_drop_rows(machine: &mut Machine, table: &Table, tuples: &Vec<(TupleAddress, Tuple)>) -> Result<(), ExecutionError> {
+    if let Err(error) = check_foreign_key_actions(machine, table, tuples, None) {
+        return Err(error);
+    }
+
+    let page_key = format_table_name(&table.database_name, &table.name);
+
+    for (address, _) in tuples.iter() {
+        pager_delete_tuple(&mut machine.pager, &page_key, address);
+    }
+    if let Err(error) = drop_index_entries(machine, table, tuples) {
+        return Err(error);
+    }
+
+    return apply_foreign_key_actions(machine, table, tuples, None);
+}
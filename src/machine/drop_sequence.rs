@@ -21,7 +21,7 @@ pub fn drop_sequence(machine: &mut Machine, index_name: &String) -> Result<Resul
         Box::new(Expression::Const(Data::Varchar(index_name.clone())))
     );
 
-    drop_tuples(machine, &SysDb::table_sequences(), columns, &condition);
+    let _ = drop_tuples(machine, &SysDb::table_sequences(), columns, &condition);
 
     Ok(ResultSet::new_command(ResultSetType::Change, String::from("DROP SEQUENCE")))
 }
@@ -1,17 +1,26 @@
 use crate::machine::Table;
 use crate::machine::Machine;
+use crate::machine::ForeignKey;
 use crate::machine::drop_columns;
+use crate::machine::drop_index;
+use crate::machine::get_indexes;
+use crate::machine::get_foreign_keys;
+use crate::machine::drop_foreign_key;
+use crate::machine::get_check_constraints;
+use crate::machine::drop_check_constraint;
 use crate::machine::drop_table_ref;
 use crate::machine::check_table_exists;
 
 use crate::storage::destroy_file;
 use crate::storage::format_table_name;
+use crate::storage::format_fsm_name;
+use crate::storage::pager_discard;
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
 
 use crate::utils::ExecutionError;
 
-pub fn drop_table(machine: &mut Machine, table: &Table, if_exists: bool) -> Result<ResultSet, ExecutionError>{
+pub fn drop_table(machine: &mut Machine, table: &Table, if_exists: bool, cascade: bool) -> Result<ResultSet, ExecutionError>{
     if check_table_exists(machine, table) == false && if_exists {
         return Ok(
             ResultSet::new_command(ResultSetType::Change, String::from("DROP TABLE"))
@@ -21,10 +30,38 @@ pub fn drop_table(machine: &mut Machine, table: &Table, if_exists: bool) -> Resu
         return Err(ExecutionError::TableNotExists(table.database_name.to_string()));
     }
 
+    // Other tables referencing this one keep it alive, unless CASCADE drops
+    // their constraints along with it.
+    let foreign_keys: Vec<ForeignKey> = get_foreign_keys(machine, &table.database_name)
+        .into_iter()
+        .filter(|foreign_key| foreign_key.table_name == table.name || foreign_key.referenced_table == table.name)
+        .collect();
+    if cascade == false {
+        if let Some(foreign_key) = foreign_keys.iter().find(|foreign_key| foreign_key.table_name != table.name) {
+            return Err(ExecutionError::TableReferenced(table.name.clone(), foreign_key.name.clone()));
+        }
+    }
+    for foreign_key in foreign_keys.iter() {
+        let _ = drop_foreign_key(machine, &table.database_name, &foreign_key.name);
+    }
+
+    let check_constraints = get_check_constraints(machine, &table.database_name);
+    for check_constraint in check_constraints.iter().filter(|check_constraint| check_constraint.table_name == table.name) {
+        let _ = drop_check_constraint(machine, &table.database_name, &check_constraint.name);
+    }
+
+    let indexes = get_indexes(machine, &table.database_name);
+    for index in indexes.iter().filter(|index| index.table_name == table.name) {
+        let _ = drop_index(machine, &table.database_name, &index.name);
+    }
+
     drop_columns(machine, table);
     drop_table_ref(machine, table);
 
-    destroy_file(&format_table_name(&table.database_name, &table.name));
+    let table_key = format_table_name(&table.database_name, &table.name);
+    pager_discard(&mut machine.pager, &table_key);
+    destroy_file(&table_key);
+    destroy_file(&format_fsm_name(&table_key));
 
     Ok(ResultSet::new_command(ResultSetType::Change, String::from("DROP TABLE")))
 }
@@ -26,5 +26,5 @@ pub fn drop_table_ref(machine: &mut Machine, table: &Table) {
         ))
     );
 
-    drop_tuples(machine, &SysDb::table_tables(), columns, &condition);
+    let _ = drop_tuples(machine, &SysDb::table_tables(), columns, &condition);
 }
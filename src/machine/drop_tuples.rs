@@ -1,22 +1,28 @@
 use crate::machine::Column;
 use crate::machine::Table;
 use crate::machine::Machine;
+use crate::machine::drop_rows;
 
-use crate::storage::Expression;
 use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::Expression;
 use crate::storage::format_table_name;
-use crate::storage::pager_read_tuples;
-use crate::storage::pager_update_tuples;
-use crate::storage::pager_flush_page;
+use crate::storage::pager_read_tuples_with_address;
+
+use crate::utils::ExecutionError;
 
-pub fn drop_tuples(machine: &mut Machine, table: &Table, columns: Vec<Column>, condition: &Expression) {
+pub fn drop_tuples(machine: &mut Machine, table: &Table, columns: Vec<Column>, condition: &Expression) -> Result<(), ExecutionError> {
     let page_key = format_table_name(&table.database_name, &table.name);
+    let column_names: Vec<String> = columns.iter().map(|e| e.name.clone()).collect();
 
-    let mut tuples: Vec<Tuple> = pager_read_tuples(&mut machine.pager, &page_key)
-        .into_iter()
-        .filter(|tuple| !&condition.result(tuple, &columns.iter().map(|e| e.name.clone() ).collect()).is_true())
-        .collect();
+    let mut tuples: Vec<(TupleAddress, Tuple)> = Vec::new();
+    for (address, tuple) in pager_read_tuples_with_address(&mut machine.pager, &page_key) {
+        match condition.result(&tuple, &column_names) {
+            Ok(value) if value.is_true() => tuples.push((address, tuple)),
+            Ok(_) => {},
+            Err(error) => return Err(error)
+        }
+    }
 
-    pager_update_tuples(&mut machine.pager, &page_key, &mut tuples);
-    pager_flush_page(&mut machine.pager, &page_key);
+    return drop_rows(machine, table, &tuples);
 }
@@ -0,0 +1,51 @@
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::CheckConstraint;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::get_check_constraints;
+
+use crate::parser::parse_expression;
+
+use crate::storage::Tuple;
+use crate::storage::Data;
+
+use crate::utils::ExecutionError;
+
+// Rejects rows for which a CHECK constraint of the table is false. As in
+// SQL, a condition over a NULL column is unknown and lets the row through.
+pub fn evaluate_check_constraints(machine: &mut Machine, table: &Table, tuples: &Vec<Tuple>) -> Result<(), ExecutionError> {
+    let check_constraints: Vec<CheckConstraint> = get_check_constraints(machine, &table.database_name)
+        .into_iter()
+        .filter(|check_constraint| check_constraint.table_name == table.name)
+        .collect();
+    if check_constraints.len() == 0 {
+        return Ok(());
+    }
+
+    let columns: Vec<String> = get_columns_with_rowid(machine, table).iter().map(|e| e.name.clone()).collect();
+
+    for check_constraint in check_constraints.iter() {
+        let expression = match parse_expression(&check_constraint.expression) {
+            Ok(expression) => expression,
+            Err(error) => return Err(error)
+        };
+        let positions: Vec<usize> = expression.column_names()
+            .iter()
+            .filter_map(|column_name| columns.iter().position(|column| column == column_name))
+            .collect();
+
+        for tuple in tuples.iter() {
+            if positions.iter().any(|position| matches!(tuple.get(*position), Some(Data::Null))) {
+                continue;
+            }
+
+            match expression.result(tuple, &columns) {
+                Ok(Data::Boolean(false)) => return Err(ExecutionError::CheckViolation(check_constraint.name.clone())),
+                Ok(_) => {},
+                Err(error) => return Err(error)
+            }
+        }
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::config::SysDb;
+
+use crate::machine::Column;
+use crate::machine::ColumnType;
+
+use crate::utils::split_column_names;
+
+// `column_name` and `referenced_column` keep the columns of composite keys
+// in the same order on both sides, see `join_column_names`. The actions are the
+// SQL keywords: NO ACTION, RESTRICT, CASCADE, SET NULL or SET DEFAULT.
+#[derive(Debug, Clone)]
+pub struct ForeignKey {
+    pub database_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub name: String,
+    pub referenced_table: String,
+    pub referenced_column: String,
+    pub on_delete: String,
+    pub on_update: String
+}
+
+impl ForeignKey {
+
+    pub fn new(
+        database_name: String,
+        table_name: String,
+        column_name: String,
+        name: String,
+        referenced_table: String,
+        referenced_column: String,
+        on_delete: String,
+        on_update: String
+    ) -> Self {
+        ForeignKey {
+            database_name,
+            table_name,
+            column_name,
+            name,
+            referenced_table,
+            referenced_column,
+            on_delete,
+            on_update
+        }
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        return split_column_names(&self.column_name);
+    }
+
+    pub fn referenced_column_names(&self) -> Vec<String> {
+        return split_column_names(&self.referenced_column);
+    }
+}
+
+impl PartialEq for ForeignKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.database_name == other.database_name && self.name == other.name
+    }
+}
+impl Eq for ForeignKey {}
+
+impl fmt::Display for ForeignKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ForeignKey({}, {}({}) -> {}({}))",
+            self.name,
+            self.table_name,
+            self.column_name,
+            self.referenced_table,
+            self.referenced_column
+        )
+    }
+}
+
+pub fn get_foreign_keys_table_definition() -> Vec<Column> {
+    let mut data = vec![
+        Column::new(
+            30u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("id"),
+            ColumnType::UnsignedBigint(0),
+            true,
+            true,
+            true,
+            String::from("")
+        )
+    ];
+    data.append(&mut get_foreign_keys_table_definition_without_id());
+    return data;
+}
+
+pub fn get_foreign_keys_table_definition_without_id() -> Vec<Column> {
+    return vec![
+        Column::new(
+            31u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("database_name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            32u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("table_name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            33u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("column_name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            34u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("name"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            35u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("referenced_table"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            36u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("referenced_column"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            37u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("on_delete"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            38u64,
+            SysDb::dbname(),
+            SysDb::tblname_foreign_keys(),
+            String::from("on_update"),
+            ColumnType::Varchar("".to_string()),
+            true,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+}
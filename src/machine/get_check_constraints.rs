@@ -0,0 +1,45 @@
+use crate::machine::CheckConstraint;
+use crate::machine::Machine;
+use crate::machine::get_columns;
+use crate::machine::read_tuples;
+
+use crate::config::SysDb;
+
+use crate::storage::Data;
+use crate::storage::Expression;
+use crate::storage::Expression2Type;
+use crate::storage::Tuple;
+
+pub fn get_check_constraints(machine: &mut Machine, database_name: &String) -> Vec<CheckConstraint> {
+    let mut check_constraints: Vec<CheckConstraint> = Vec::new();
+
+    if *database_name == SysDb::dbname() {
+        return check_constraints;
+    }
+
+    let condition = Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("database_name"))),
+        Box::new(Expression::Const(Data::Varchar(database_name.clone())))
+    );
+
+    let columns = get_columns(machine, &SysDb::table_check_constraints()).iter().map(|e| e.name.clone()).collect();
+
+    let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_check_constraints())
+        .into_iter()
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
+        .collect();
+
+    for elem in tuples.into_iter() {
+        check_constraints.push(
+            CheckConstraint::new(
+                elem.get(1).unwrap().to_string(),
+                elem.get(2).unwrap().to_string(),
+                elem.get(3).unwrap().to_string(),
+                elem.get(4).unwrap().to_string()
+            )
+        );
+    }
+
+    return check_constraints;
+}
@@ -31,7 +31,7 @@ pub fn get_columns(machine: &mut Machine, table: &Table) -> Vec<Column> {
 
     let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_columns())
         .into_iter()
-        .filter(|tuple| condition.result(tuple, &column_names).is_true())
+        .filter(|tuple| condition.result(tuple, &column_names).is_ok_and(|value| value.is_true()))
         .collect();
 
     let mut columns: Vec<Column> = Vec::new();
@@ -0,0 +1,29 @@
+use crate::machine::Table;
+use crate::machine::Column;
+use crate::machine::Machine;
+use crate::machine::get_columns;
+use crate::machine::get_rowid_column_for_table;
+
+// Columns in the order they are stored on the tuples, tables without a
+// primary key keep the rowid before the declared columns.
+pub fn get_columns_with_rowid(machine: &mut Machine, table: &Table) -> Vec<Column> {
+    let mut table_columns = get_columns(machine, table);
+
+    let mut has_primary_key: bool = false;
+
+    for item in table_columns.iter() {
+        if item.primary_key {
+            has_primary_key = true;
+        }
+    }
+
+    let mut columns: Vec<Column> = vec![];
+
+    if has_primary_key == false {
+        columns.push(get_rowid_column_for_table(table));
+    }
+
+    columns.append(&mut table_columns);
+
+    return columns;
+}
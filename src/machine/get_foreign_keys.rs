@@ -0,0 +1,49 @@
+use crate::machine::ForeignKey;
+use crate::machine::Machine;
+use crate::machine::get_columns;
+use crate::machine::read_tuples;
+
+use crate::config::SysDb;
+
+use crate::storage::Data;
+use crate::storage::Expression;
+use crate::storage::Expression2Type;
+use crate::storage::Tuple;
+
+pub fn get_foreign_keys(machine: &mut Machine, database_name: &String) -> Vec<ForeignKey> {
+    let mut foreign_keys: Vec<ForeignKey> = Vec::new();
+
+    if *database_name == SysDb::dbname() {
+        return foreign_keys;
+    }
+
+    let condition = Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("database_name"))),
+        Box::new(Expression::Const(Data::Varchar(database_name.clone())))
+    );
+
+    let columns = get_columns(machine, &SysDb::table_foreign_keys()).iter().map(|e| e.name.clone()).collect();
+
+    let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_foreign_keys())
+        .into_iter()
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
+        .collect();
+
+    for elem in tuples.into_iter() {
+        foreign_keys.push(
+            ForeignKey::new(
+                elem.get(1).unwrap().to_string(),
+                elem.get(2).unwrap().to_string(),
+                elem.get(3).unwrap().to_string(),
+                elem.get(4).unwrap().to_string(),
+                elem.get(5).unwrap().to_string(),
+                elem.get(6).unwrap().to_string(),
+                elem.get(7).unwrap().to_string(),
+                elem.get(8).unwrap().to_string()
+            )
+        );
+    }
+
+    return foreign_keys;
+}
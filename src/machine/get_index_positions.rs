@@ -0,0 +1,22 @@
+use crate::machine::Index;
+use crate::machine::Column;
+
+use crate::utils::ExecutionError;
+
+// Positions on the tuples of the indexed columns, in the order of the key.
+pub fn get_index_positions(columns: &Vec<Column>, index: &Index) -> Result<Vec<usize>, ExecutionError> {
+    return get_column_positions(columns, &index.column_names());
+}
+
+pub fn get_column_positions(columns: &Vec<Column>, column_names: &Vec<String>) -> Result<Vec<usize>, ExecutionError> {
+    let mut positions: Vec<usize> = Vec::new();
+
+    for column_name in column_names.iter() {
+        match columns.iter().position(|column| column.name == *column_name) {
+            Some(position) => positions.push(position),
+            None => return Err(ExecutionError::ColumnNotExists(column_name.clone()))
+        }
+    }
+
+    return Ok(positions);
+}
@@ -23,11 +23,28 @@ pub fn get_indexes(machine: &mut Machine, database_name: &String) -> Vec<Index>
 
     let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_indexes())
         .into_iter()
-        .filter(|tuple| condition.result(tuple, &columns).is_true())
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
         .collect();
 
     for elem in tuples.into_iter() {
-        indexes.push(Index::new(elem.get(4).unwrap().clone().to_string()));
+        // Catalogs written before the `unique` and `primary_key` columns were
+        // appended lack them. Primary keys were then told by their name.
+        let unique = matches!(elem.get(6), Some(Data::Boolean(true)));
+        let primary_key = match elem.get(7) {
+            Some(value) => *value == Data::Boolean(true),
+            None => unique && elem.get(4).unwrap().to_string() == format!("{}_pkey", elem.get(2).unwrap())
+        };
+        indexes.push(
+            Index::new(
+                elem.get(1).unwrap().to_string(),
+                elem.get(2).unwrap().to_string(),
+                elem.get(3).unwrap().to_string(),
+                elem.get(4).unwrap().to_string(),
+                elem.get(5).unwrap().to_string(),
+                unique,
+                primary_key
+            )
+        );
     }
 
     return indexes;
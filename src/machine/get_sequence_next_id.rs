@@ -39,7 +39,7 @@ pub fn get_sequence_next_id(machine: &mut Machine, column: &Column) -> Option<u6
     let columns = get_columns(machine, &SysDb::table_sequences()).iter().map(|e| e.name.clone()).collect();
     let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_sequences())
         .into_iter()
-        .filter(|tuple| condition.result(tuple, &columns).is_true())
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
         .collect();
 
     let mut next_id: Option<u64> = None;
@@ -24,7 +24,7 @@ pub fn get_sequences(machine: &mut Machine, database_name: &String) -> Vec<Seque
 
     let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_sequences())
         .into_iter()
-        .filter(|tuple| condition.result(tuple, &columns).is_true())
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
         .collect();
 
     for elem in tuples.into_iter() {
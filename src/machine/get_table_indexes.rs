@@ -0,0 +1,34 @@
+use crate::machine::Index;
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::get_indexes;
+
+use crate::config::SysDb;
+
+use crate::storage::path_exists;
+use crate::storage::format_index_name;
+
+use crate::utils::ExecutionError;
+
+// Indexes of the table backed by an index file. The indexes declared for the
+// rusticodb tables only exist on the catalog, any other index without its
+// file is an error.
+pub fn get_table_indexes(machine: &mut Machine, table: &Table) -> Result<Vec<Index>, ExecutionError> {
+    if table.database_name == SysDb::dbname() {
+        return Ok(Vec::new());
+    }
+
+    let indexes: Vec<Index> = get_indexes(machine, &table.database_name)
+        .into_iter()
+        .filter(|index| index.table_name == table.name)
+        .collect();
+
+    for index in indexes.iter() {
+        let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+        if path_exists(&index_key) == false {
+            return Err(ExecutionError::IndexCorrupted(index_key));
+        }
+    }
+
+    return Ok(indexes);
+}
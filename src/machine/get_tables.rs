@@ -23,7 +23,7 @@ pub fn get_tables(machine: &mut Machine, database_name: &String) -> Vec<Table> {
 
     let tuples: Vec<Tuple> = read_tuples(machine, &SysDb::table_tables())
         .into_iter()
-        .filter(|tuple| condition.result(tuple, &columns).is_true())
+        .filter(|tuple| condition.result(tuple, &columns).is_ok_and(|value| value.is_true()))
         .collect();
 
     for elem in tuples.into_iter() {
@@ -5,20 +5,38 @@ use crate::config::SysDb;
 use crate::machine::Column;
 use crate::machine::ColumnType;
 
+use crate::utils::split_column_names;
+
 #[derive(Debug, Clone)]
 pub struct Index {
+    pub database_name: String,
+    pub table_name: String,
+    pub column_name: String,
     pub name: String,
-    pub alias: String
+    pub alias: String,
+    pub index_type: String,
+    pub unique: bool,
+    pub primary_key: bool
 }
 
 impl Index {
 
-    pub fn new(name: String) -> Self {
-        Index { name: name.clone(), alias: name }
+    pub fn new(
+        database_name: String,
+        table_name: String,
+        column_name: String,
+        name: String,
+        index_type: String,
+        unique: bool,
+        primary_key: bool
+    ) -> Self {
+        Index { database_name, table_name, column_name, name: name.clone(), alias: name, index_type, unique, primary_key }
     }
 
-    pub fn new_with_alias(name: String, alias: String) -> Self {
-        Index { name, alias }
+    // Composite indexes keep their columns on `column_name`, in the order of
+    // the key, see `join_column_names`.
+    pub fn column_names(&self) -> Vec<String> {
+        return split_column_names(&self.column_name);
     }
 }
 
@@ -109,6 +127,28 @@ pub fn get_indexes_table_definition_without_id() -> Vec<Column> {
             false,
             false,
             String::from("")
+        ),
+        Column::new(
+            29u64,
+            SysDb::dbname(),
+            SysDb::tblname_indexes(),
+            String::from("unique"),
+            ColumnType::Boolean(true),
+            true,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            44u64,
+            SysDb::dbname(),
+            SysDb::tblname_indexes(),
+            String::from("primary_key"),
+            ColumnType::Boolean(true),
+            true,
+            false,
+            false,
+            String::from("")
         )
     ];
 }
@@ -2,12 +2,19 @@ use crate::machine::Column;
 use crate::machine::Table;
 use crate::machine::Machine;
 use crate::machine::get_sequence_next_id;
+use crate::machine::check_index_keys;
+use crate::machine::evaluate_check_constraints;
+use crate::machine::check_unique_keys;
+use crate::machine::check_foreign_keys;
+use crate::machine::insert_index_entries;
 
 use crate::storage::Tuple;
+use crate::storage::TupleAddress;
 use crate::storage::Data;
 use crate::storage::format_table_name;
 use crate::storage::pager_insert_tuples;
 use crate::storage::pager_flush_page;
+use crate::storage::pager_rollback;
 use crate::storage::pager_get_next_rowid;
 use crate::storage::tuple_new;
 use crate::storage::ResultSet;
@@ -62,7 +69,27 @@ pub fn insert_row(
 
     let mut adjusted_tuples = adjusted_tuples_result.unwrap();
 
-    pager_insert_tuples(&mut machine.pager, &page_key, &mut adjusted_tuples);
+    if let Err(error) = evaluate_check_constraints(machine, table, &adjusted_tuples) {
+        return Err(error);
+    }
+    if let Err(error) = check_index_keys(machine, table, &adjusted_tuples) {
+        return Err(error);
+    }
+    if let Err(error) = check_unique_keys(machine, table, &adjusted_tuples, &Vec::new()) {
+        return Err(error);
+    }
+    if let Err(error) = check_foreign_keys(machine, table, &adjusted_tuples) {
+        return Err(error);
+    }
+
+    // The index entries are committed on the same flush as the rows, when an
+    // index can not take them the rows are thrown away too.
+    let addresses = pager_insert_tuples(&mut machine.pager, &page_key, &mut adjusted_tuples);
+    let entries: Vec<(TupleAddress, Tuple)> = addresses.into_iter().zip(adjusted_tuples.into_iter()).collect();
+    if let Err(error) = insert_index_entries(machine, table, &entries) {
+        pager_rollback(&mut machine.pager);
+        return Err(error);
+    }
     pager_flush_page(&mut machine.pager, &page_key);
 
     return Ok(ResultSet::new_command(ResultSetType::Change, String::from("INSERT")))
@@ -25,6 +25,7 @@ pub mod check_table_exists;
 // COLUMN FUNCTIONS
 pub mod drop_columns;
 pub mod get_columns;
+pub mod get_columns_with_rowid;
 
 // SEQUENCE FUNCTIONS
 pub mod sequence;
@@ -40,6 +41,28 @@ pub mod create_index;
 pub mod check_index_exists;
 pub mod get_indexes;
 pub mod drop_index;
+pub mod get_table_indexes;
+pub mod get_index_positions;
+pub mod build_index;
+pub mod check_index_keys;
+pub mod check_unique_keys;
+pub mod insert_index_entries;
+pub mod drop_index_entries;
+
+// FOREIGN KEY FUNCTIONS
+pub mod foreign_key;
+pub mod create_foreign_key;
+pub mod get_foreign_keys;
+pub mod drop_foreign_key;
+pub mod check_foreign_keys;
+pub mod apply_foreign_key_actions;
+
+// CHECK CONSTRAINT FUNCTIONS
+pub mod check_constraint;
+pub mod create_check_constraint;
+pub mod get_check_constraints;
+pub mod drop_check_constraint;
+pub mod evaluate_check_constraints;
 
 // VIEW FUNCTIONS
 pub mod create_view;
@@ -47,17 +70,28 @@ pub mod check_view_exists;
 
 // TUPLE FUNCTIONS
 pub mod read_tuples;
+pub mod read_tuples_with_address;
 pub mod insert_row;
 pub mod insert_tuples;
 pub mod update_tuples;
 pub mod drop_tuples;
+pub mod drop_rows;
+pub mod update_rows;
 pub mod attribution;
 pub mod update_row;
 
 // SELECT FUNCTIONS
+pub mod access_path;
+pub mod plan_access_path;
+pub mod scan_table;
 pub mod product_cartesian;
 
+// MAINTENANCE FUNCTIONS
+pub mod vacuum_table;
+pub mod vacuum_database;
+
 pub use self::attribution::Attribution;
+pub use self::access_path::AccessPath;
 
 pub use self::database::{ Database, get_databases_table_definition, get_databases_table_definition_without_id };
 pub use self::table::{ Table, get_tables_table_definition, get_tables_table_definition_without_id };
@@ -67,7 +101,8 @@ pub use self::column::{
     get_columns_table_definition,
     get_columns_table_definition_without_id,
     get_rowid_column_for_table,
-    map_column_type
+    map_column_type,
+    map_decimal_type
 };
 pub use self::sequence::{ 
     Sequence,
@@ -76,6 +111,16 @@ pub use self::sequence::{
     get_sequences_next_id_column_definition
 };
 pub use self::index::{ Index, get_indexes_table_definition, get_indexes_table_definition_without_id };
+pub use self::foreign_key::{
+    ForeignKey,
+    get_foreign_keys_table_definition,
+    get_foreign_keys_table_definition_without_id
+};
+pub use self::check_constraint::{
+    CheckConstraint,
+    get_check_constraints_table_definition,
+    get_check_constraints_table_definition_without_id
+};
 
 pub use create_file::create_file;
 pub use path_exists::path_exists;
@@ -95,6 +140,7 @@ pub use check_table_exists::check_table_exists;
 
 pub use drop_columns::drop_columns;
 pub use get_columns::get_columns;
+pub use get_columns_with_rowid::get_columns_with_rowid;
 
 pub use create_sequence::create_sequence;
 pub use get_sequence_next_id::get_sequence_next_id;
@@ -106,20 +152,51 @@ pub use create_index::create_index;
 pub use get_indexes::get_indexes;
 pub use check_index_exists::check_index_exists;
 pub use drop_index::drop_index;
+pub use get_table_indexes::get_table_indexes;
+pub use get_index_positions::get_index_positions;
+pub use get_index_positions::get_column_positions;
+pub use build_index::build_index;
+pub use check_index_keys::check_index_keys;
+pub use check_unique_keys::check_unique_keys;
+pub use insert_index_entries::insert_index_entries;
+pub use drop_index_entries::drop_index_entries;
+
+pub use create_foreign_key::create_foreign_key;
+pub use get_foreign_keys::get_foreign_keys;
+pub use drop_foreign_key::drop_foreign_key;
+pub use check_foreign_keys::check_foreign_keys;
+pub use apply_foreign_key_actions::check_foreign_key_actions;
+pub use apply_foreign_key_actions::apply_foreign_key_actions;
+
+pub use create_check_constraint::create_check_constraint;
+pub use get_check_constraints::get_check_constraints;
+pub use drop_check_constraint::drop_check_constraint;
+pub use evaluate_check_constraints::evaluate_check_constraints;
 
 pub use create_view::create_view;
 pub use check_view_exists::check_view_exists;
 
+pub use plan_access_path::plan_access_path;
+pub use scan_table::scan_table;
 pub use product_cartesian::product_cartesian;
+pub use product_cartesian::product_cartesian_with_access_paths;
 
 pub use insert_row::insert_row;
 pub use insert_row::adjust_rows;
 pub use insert_tuples::insert_tuples;
 pub use update_tuples::update_tuples;
 pub use read_tuples::read_tuples;
+pub use read_tuples_with_address::read_tuples_with_address;
 pub use drop_tuples::drop_tuples;
+pub use drop_rows::drop_rows;
+pub use drop_rows::stage_drop_rows;
+pub use update_rows::update_rows;
+pub use update_rows::stage_update_rows;
 pub use update_row::update_row;
 
+pub use vacuum_table::vacuum_table;
+pub use vacuum_database::vacuum_database;
+
 use crate::storage::Pager;
 
 #[derive(Debug)]
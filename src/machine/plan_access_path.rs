@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::machine::AccessPath;
+use crate::machine::Index;
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::get_table_indexes;
+use crate::machine::get_columns;
+use crate::machine::Column;
+
+use crate::storage::Data;
+use crate::storage::Tuple;
+use crate::storage::Expression;
+use crate::storage::Expression2Type;
+use crate::storage::index_value_compare;
+use crate::storage::index_file_is_hash;
+
+use crate::utils::ExecutionError;
+
+fn collect_conjuncts<'a>(condition: &'a Expression, conjuncts: &mut Vec<&'a Expression>) {
+    match condition {
+        Expression::Func2(Expression2Type::And, left, right) => {
+            collect_conjuncts(left, conjuncts);
+            collect_conjuncts(right, conjuncts);
+        },
+        other => conjuncts.push(other)
+    }
+}
+
+// Reads `column op constant` (or `constant op column`) as the bounds it puts
+// on the column. Anything else can't be answered by an index.
+fn get_column_bounds(condition: &Expression) -> Option<(String, Bound<Data>, Bound<Data>)> {
+    let (operator, column_name, value, flipped) = match condition {
+        Expression::Func2(operator, left, right) => match (left.as_ref(), right.as_ref()) {
+            (Expression::ColName(name), Expression::Const(value)) => (operator, name, value, false),
+            (Expression::Const(value), Expression::ColName(name)) => (operator, name, value, true),
+            _ => return None
+        },
+        _ => return None
+    };
+
+    if *value == Data::Null {
+        return None;
+    }
+
+    let included = Bound::Included(value.clone());
+    let excluded = Bound::Excluded(value.clone());
+
+    let bounds = match (operator, flipped) {
+        (Expression2Type::Equal, _) => (included.clone(), included),
+        (Expression2Type::GreatherThan, false) | (Expression2Type::LessThan, true) => (excluded, Bound::Unbounded),
+        (Expression2Type::GreatherOrEqual, false) | (Expression2Type::LessOrEqual, true) => (included, Bound::Unbounded),
+        (Expression2Type::LessThan, false) | (Expression2Type::GreatherThan, true) => (Bound::Unbounded, excluded),
+        (Expression2Type::LessOrEqual, false) | (Expression2Type::GreatherOrEqual, true) => (Bound::Unbounded, included),
+        _ => return None
+    };
+
+    return Some((column_name.clone(), bounds.0, bounds.1));
+}
+
+// Bounds are read as the type of their column, so the index is searched with
+// keys like the ones it holds. A value that changes on the way (rounded or
+// compared as another type) stays as written, like a full scan compares it.
+fn coerce_bound(column: &Column, bound: Bound<Data>) -> Bound<Data> {
+    let coerce = |value: Data| match column.coerce(value.clone()) {
+        Ok(coerced) if coerced.cmp(&value) == Ordering::Equal => coerced,
+        _ => value
+    };
+
+    return match bound {
+        Bound::Included(value) => Bound::Included(coerce(value)),
+        Bound::Excluded(value) => Bound::Excluded(coerce(value)),
+        Bound::Unbounded => Bound::Unbounded
+    };
+}
+
+// Keeps the tighter of two bounds. `side` is the ordering a value must have
+// against the other to be the tighter one: Greater for lower bounds and Less
+// for upper bounds.
+fn tighten_bound(current: Bound<Data>, other: Bound<Data>, side: Ordering) -> Bound<Data> {
+    let current_value = match &current {
+        Bound::Included(value) | Bound::Excluded(value) => value.clone(),
+        Bound::Unbounded => return other
+    };
+    let other_value = match &other {
+        Bound::Included(value) | Bound::Excluded(value) => value.clone(),
+        Bound::Unbounded => return current
+    };
+
+    let ordering = index_value_compare(&other_value, &current_value);
+    if ordering == side {
+        return other;
+    }
+    if ordering == Ordering::Equal && matches!(other, Bound::Excluded(_)) {
+        return other;
+    }
+    return current;
+}
+
+fn score_bounds(lower: &Bound<Data>, upper: &Bound<Data>) -> u8 {
+    return match (lower, upper) {
+        (Bound::Included(lower_value), Bound::Included(upper_value))
+            if index_value_compare(lower_value, upper_value) == Ordering::Equal => 3,
+        (Bound::Unbounded, Bound::Unbounded) => 0,
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => 1,
+        _ => 2
+    };
+}
+
+// Extends the values of the leading columns with the bound of the next one.
+fn prefix_bound(prefix: &Tuple, bound: Bound<Data>) -> Bound<Tuple> {
+    let mut key = prefix.clone();
+
+    return match bound {
+        Bound::Included(value) => { key.push(value); Bound::Included(key) },
+        Bound::Excluded(value) => { key.push(value); Bound::Excluded(key) },
+        Bound::Unbounded if prefix.len() > 0 => Bound::Included(key),
+        Bound::Unbounded => Bound::Unbounded
+    };
+}
+
+// Picks an index scan when the WHERE restricts indexed columns with =, <,
+// <=, >, >= or BETWEEN on its top level ANDs. Composite indexes are used on
+// a prefix of their columns: equalities on the leading ones and at most a
+// range on the column after them. Each equality weighs more than any range,
+// closed ranges are preferred over open ones, and hash indexes only take
+// part when every column of the key has an equality. The rows it returns are
+// only candidates, the whole condition still has to be applied on them.
+pub fn plan_access_path(machine: &mut Machine, table: &Table, condition: &Expression) -> Result<AccessPath, ExecutionError> {
+    let indexes: Vec<Index> = match get_table_indexes(machine, table) {
+        Ok(indexes) => indexes,
+        Err(error) => return Err(error)
+    };
+    if indexes.len() == 0 {
+        return Ok(AccessPath::FullScan(table.clone()));
+    }
+
+    let mut conjuncts: Vec<&Expression> = Vec::new();
+    collect_conjuncts(condition, &mut conjuncts);
+
+    let columns = get_columns(machine, table);
+    let column_bounds: Vec<(String, Bound<Data>, Bound<Data>)> = conjuncts
+        .into_iter()
+        .filter_map(|conjunct| get_column_bounds(conjunct))
+        .map(|(column_name, lower, upper)| match columns.iter().find(|column| column.name == column_name) {
+            Some(column) => (column_name, coerce_bound(column, lower), coerce_bound(column, upper)),
+            None => (column_name, lower, upper)
+        })
+        .collect();
+
+    let mut best_path = AccessPath::FullScan(table.clone());
+    let mut best_score = 0;
+
+    for index in indexes.iter() {
+        let index_columns = index.column_names();
+        let mut prefix: Tuple = Vec::new();
+        let mut lower: Bound<Data> = Bound::Unbounded;
+        let mut upper: Bound<Data> = Bound::Unbounded;
+        let mut score = 0;
+
+        for index_column in index_columns.iter() {
+            let mut column_lower: Bound<Data> = Bound::Unbounded;
+            let mut column_upper: Bound<Data> = Bound::Unbounded;
+
+            for (column_name, bound_lower, bound_upper) in column_bounds.iter() {
+                if column_name == index_column {
+                    column_lower = tighten_bound(column_lower, bound_lower.clone(), Ordering::Greater);
+                    column_upper = tighten_bound(column_upper, bound_upper.clone(), Ordering::Less);
+                }
+            }
+
+            let column_score = score_bounds(&column_lower, &column_upper);
+            score += column_score;
+
+            if column_score != 3 {
+                lower = column_lower;
+                upper = column_upper;
+                break;
+            }
+            if let Bound::Included(value) = column_lower {
+                prefix.push(value);
+            }
+        }
+
+        // Hash indexes can only answer equalities on the whole key.
+        if index_file_is_hash(&index.index_type) && prefix.len() != index_columns.len() {
+            continue;
+        }
+
+        if score > best_score {
+            best_score = score;
+            best_path = AccessPath::IndexScan(
+                table.clone(),
+                index.clone(),
+                prefix_bound(&prefix, lower),
+                prefix_bound(&prefix, upper)
+            );
+        }
+    }
+
+    return Ok(best_path);
+}
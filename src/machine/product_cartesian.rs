@@ -1,41 +1,29 @@
-
 use crate::machine::Table;
-use crate::machine::Column;
 use crate::machine::Machine;
-use crate::machine::get_columns;
-use crate::machine::read_tuples;
-use crate::machine::get_rowid_column_for_table;
+use crate::machine::AccessPath;
+use crate::machine::scan_table;
 
 use crate::storage::ResultSet;
-use crate::storage::Tuple;
-
-pub fn product_cartesian(machine: &mut Machine, tables: Vec<Table>) -> ResultSet {
-    let mut result_set = ResultSet::new_empty();
-    
-    for (_dx, table) in tables.iter().enumerate() {
-        let mut table_columns = get_columns(machine, &table);
-
-        let mut has_primary_key: bool = false;
 
-        for item in table_columns.iter() {
-            if item.primary_key {
-                has_primary_key = true;
-            }
-        }
+use crate::utils::ExecutionError;
 
-        let mut columns: Vec<Column> = vec![];
+pub fn product_cartesian(machine: &mut Machine, tables: Vec<Table>) -> Result<ResultSet, ExecutionError> {
+    let access_paths: Vec<AccessPath> = tables.into_iter().map(|table| AccessPath::FullScan(table)).collect();
 
-        if has_primary_key == false {
-            columns.push(get_rowid_column_for_table(table));
-        }
-
-        columns.append(&mut table_columns);
+    return product_cartesian_with_access_paths(machine, &access_paths);
+}
 
-        let tuples: Vec<Tuple> = read_tuples(machine, &table);
-        let result_set1 = ResultSet::new_select(columns, tuples);
+pub fn product_cartesian_with_access_paths(
+    machine: &mut Machine,
+    access_paths: &Vec<AccessPath>
+) -> Result<ResultSet, ExecutionError> {
+    let mut result_set = ResultSet::new_empty();
+    
+    for access_path in access_paths.iter() {
+        let result_set1 = scan_table(machine, access_path)?;
 
         result_set = result_set.cartesian_product(&result_set1);
     }
 
-    return result_set;
+    return Ok(result_set);
 }
@@ -0,0 +1,17 @@
+use crate::machine::Machine;
+use crate::machine::Table;
+
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::format_table_name;
+use crate::storage::pager_read_tuples_with_address;
+
+use crate::utils::Logger;
+
+pub fn read_tuples_with_address(machine: &mut Machine, table: &Table) -> Vec<(TupleAddress, Tuple)> {
+    let page_key = format_table_name(&table.database_name, &table.name);
+
+    Logger::debug(format!("read tuples with address from {}",page_key).leak());
+
+    return pager_read_tuples_with_address(&mut machine.pager, &page_key)
+}
@@ -0,0 +1,59 @@
+use std::ops::Bound;
+
+use crate::machine::AccessPath;
+use crate::machine::Machine;
+use crate::machine::get_columns_with_rowid;
+use crate::machine::read_tuples;
+
+use crate::storage::Tuple;
+use crate::storage::ResultSet;
+use crate::storage::format_table_name;
+use crate::storage::format_index_name;
+use crate::storage::btree_range;
+use crate::storage::index_file_is_hash;
+use crate::storage::index_file_search;
+use crate::storage::pager_read_tuple;
+
+use crate::utils::Logger;
+use crate::utils::ExecutionError;
+
+pub fn scan_table(machine: &mut Machine, access_path: &AccessPath) -> Result<ResultSet, ExecutionError> {
+    Logger::debug(format!("{}", access_path).leak());
+
+    match access_path {
+        AccessPath::FullScan(table) => {
+            let columns = get_columns_with_rowid(machine, table);
+            let tuples: Vec<Tuple> = read_tuples(machine, table);
+
+            return Ok(ResultSet::new_select(columns, tuples));
+        },
+        AccessPath::IndexScan(table, index, lower, upper) => {
+            let columns = get_columns_with_rowid(machine, table);
+            let page_key = format_table_name(&table.database_name, &table.name);
+            let index_key = format_index_name(&index.database_name, &index.table_name, &index.name);
+
+            // Hash indexes are only planned for equality, so both bounds hold the key.
+            let addresses = match (index_file_is_hash(&index.index_type), lower) {
+                (true, Bound::Included(key)) => index_file_search(&machine.pager, &index_key, &index.index_type, key)?,
+                _ => btree_range(&machine.pager, &index_key, bound_as_ref(lower), bound_as_ref(upper))?
+            };
+
+            let mut tuples: Vec<Tuple> = Vec::new();
+            for address in addresses.iter() {
+                if let Some(tuple) = pager_read_tuple(&mut machine.pager, &page_key, address) {
+                    tuples.push(tuple);
+                }
+            }
+
+            return Ok(ResultSet::new_select(columns, tuples));
+        }
+    }
+}
+
+fn bound_as_ref<T>(bound: &Bound<T>) -> Bound<&T> {
+    return match bound {
+        Bound::Included(value) => Bound::Included(value),
+        Bound::Excluded(value) => Bound::Excluded(value),
+        Bound::Unbounded => Bound::Unbounded
+    };
+}
@@ -1,11 +1,13 @@
 use crate::machine::Table;
+use crate::machine::Column;
 use crate::machine::Machine;
-use crate::machine::get_columns;
+use crate::machine::get_columns_with_rowid;
 use crate::machine::Attribution;
-use crate::machine::read_tuples;
-use crate::machine::update_tuples;
+use crate::machine::read_tuples_with_address;
+use crate::machine::update_rows;
 
 use crate::storage::Tuple;
+use crate::storage::TupleAddress;
 use crate::storage::Data;
 use crate::storage::tuple_new;
 use crate::storage::Expression;
@@ -20,18 +22,28 @@ pub fn update_row(
     attributions: &Vec<Attribution>,
     expressions: Expression
 ) -> Result<ResultSet, ExecutionError> {
-    let mut original_tuples = read_tuples(machine, table);
+    let columns = get_columns_with_rowid(machine, table);
+    let table_columns: Vec<String> = columns.iter().map(|e| e.name.clone()).collect();
+
+    let mut original_tuples: Vec<(TupleAddress, Tuple)> = Vec::new();
+    for (address, tuple) in read_tuples_with_address(machine, table) {
+        match expressions.result(&tuple, &table_columns) {
+            Ok(Data::Boolean(true)) => original_tuples.push((address, tuple)),
+            Ok(_) => {},
+            Err(error) => return Err(error)
+        }
+    }
 
     let updated_tuples_result = adjust_tuples(
-        machine,
-        table,
+        &columns,
         &mut original_tuples,
-        attributions,
-        expressions
+        attributions
     );
     if let Ok(mut new_tuples) = updated_tuples_result {
-        update_tuples(machine, table, &mut new_tuples);
-        
+        if let Err(error) = update_rows(machine, table, &original_tuples, &mut new_tuples) {
+            return Err(error);
+        }
+
         return Ok(
             ResultSet::new_command(
                ResultSetType::Change,
@@ -48,33 +60,40 @@ pub fn update_row(
 
 
 fn adjust_tuples(
-    machine: &mut Machine,
-    table: &Table,
-    tuples: &mut Vec<Tuple>,
-    attributions: &Vec<Attribution>,
-    expression: Expression
-) -> Result<Vec<Tuple>, ExecutionError> {
-    let table_columns = get_columns(machine, table).iter().map(|e| e.name.clone()).collect();
+    columns: &Vec<Column>,
+    tuples: &mut Vec<(TupleAddress, Tuple)>,
+    attributions: &Vec<Attribution>
+) -> Result<Vec<(TupleAddress, Tuple)>, ExecutionError> {
+    let table_columns: Vec<String> = columns.iter().map(|e| e.name.clone()).collect();
+    let mut new_tuples: Vec<(TupleAddress, Tuple)> = Vec::new();
 
-    let new_tuples: Vec<Tuple> = tuples.iter_mut()
-        .map(|tuple| { 
-            if let Data::Boolean(true) = &expression.result(tuple, &table_columns) {
-                let mut new_tuple = tuple_new();
-                for (idx, column) in table_columns.iter().enumerate() {
-                    let index_result = attributions.iter().position(|e| e.target.name == *column);
-                    if let Some(index) = index_result {
-                        let attr = attributions.get(index).unwrap();
-                        new_tuple.push(attr.expr.result(&tuple, &table_columns));
-                    } else {
-                        new_tuple.push(tuple.get(idx).unwrap().clone());
-                    } 
+    for (address, tuple) in tuples.iter() {
+        let mut new_tuple = tuple_new();
+        for (idx, column) in columns.iter().enumerate() {
+            let index_result = attributions.iter().position(|e| e.target.name == column.name);
+            if let Some(index) = index_result {
+                let attr = attributions.get(index).unwrap();
+                let value = match attr.expr.result(&tuple, &table_columns) {
+                    Ok(value) => value,
+                    Err(error) => return Err(error)
+                };
+                if matches!(value, Data::Null) && column.not_null {
+                    return Err(ExecutionError::ColumnCantBeNull(
+                        column.database_name.clone(),
+                        column.table_name.clone(),
+                        column.name.clone()
+                    ));
+                }
+                match column.coerce(value) {
+                    Ok(value) => new_tuple.push(value),
+                    Err(error) => return Err(error)
                 }
-                new_tuple
             } else {
-                tuple.clone()
+                new_tuple.push(tuple.get(idx).unwrap().clone());
             }
-        })
-        .collect::<Vec<_>>();
+        }
+        new_tuples.push((*address, new_tuple));
+    }
 
     return Ok(new_tuples);
 }
@@ -0,0 +1,82 @@
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::check_index_keys;
+use crate::machine::evaluate_check_constraints;
+use crate::machine::check_unique_keys;
+use crate::machine::check_foreign_keys;
+use crate::machine::check_foreign_key_actions;
+use crate::machine::apply_foreign_key_actions;
+use crate::machine::insert_index_entries;
+use crate::machine::drop_index_entries;
+
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::format_table_name;
+use crate::storage::pager_update_tuple;
+use crate::storage::pager_flush_all;
+use crate::storage::pager_rollback;
+
+use crate::utils::ExecutionError;
+
+// Replaces the `original` rows by the `tuples` stored on the same
+// addresses, keeping the indexes and the foreign keys consistent. The
+// changes of every table are committed on one flush, or thrown away when
+// any of them fails.
+pub fn update_rows(
+    machine: &mut Machine,
+    table: &Table,
+    original: &Vec<(TupleAddress, Tuple)>,
+    tuples: &mut Vec<(TupleAddress, Tuple)>
+) -> Result<(), ExecutionError> {
+    if let Err(error) = stage_update_rows(machine, table, original, tuples) {
+        pager_rollback(&mut machine.pager);
+        return Err(error);
+    }
+    pager_flush_all(&mut machine.pager);
+
+    return Ok(());
+}
+
+// Same as update_rows, leaving the changes on the pager for the caller to
+// flush or roll back.
+pub fn stage_update_rows(
+    machine: &mut Machine,
+    table: &Table,
+    original: &Vec<(TupleAddress, Tuple)>,
+    tuples: &mut Vec<(TupleAddress, Tuple)>
+) -> Result<(), ExecutionError> {
+    let rows: Vec<Tuple> = tuples.iter().map(|(_, tuple)| tuple.clone()).collect();
+    if let Err(error) = evaluate_check_constraints(machine, table, &rows) {
+        return Err(error);
+    }
+    if let Err(error) = check_index_keys(machine, table, &rows) {
+        return Err(error);
+    }
+
+    let addresses: Vec<TupleAddress> = original.iter().map(|(address, _)| *address).collect();
+    if let Err(error) = check_unique_keys(machine, table, &rows, &addresses) {
+        return Err(error);
+    }
+    if let Err(error) = check_foreign_keys(machine, table, &rows) {
+        return Err(error);
+    }
+    if let Err(error) = check_foreign_key_actions(machine, table, original, Some(&rows)) {
+        return Err(error);
+    }
+
+    // Rows keep their addresses, so the index entries can be replaced before
+    // the rows are.
+    if let Err(error) = drop_index_entries(machine, table, original) {
+        return Err(error);
+    }
+    if let Err(error) = insert_index_entries(machine, table, tuples) {
+        return Err(error);
+    }
+
+    let page_key = format_table_name(&table.database_name, &table.name);
+    for (address, tuple) in tuples.iter() {
+        pager_update_tuple(&mut machine.pager, &page_key, address, tuple.clone());
+    }
+
+    return apply_foreign_key_actions(machine, table, original, Some(&rows));
+}
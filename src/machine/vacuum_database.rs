@@ -0,0 +1,23 @@
+use crate::machine::Machine;
+use crate::machine::get_tables;
+use crate::machine::vacuum_table;
+use crate::machine::check_database_exists;
+
+use crate::utils::ExecutionError;
+
+// Returns the amount of bytes reclaimed over every table of the database.
+pub fn vacuum_database(machine: &mut Machine, database_name: &String) -> Result<u64, ExecutionError> {
+    if check_database_exists(machine, database_name) == false {
+        return Err(ExecutionError::DatabaseNotExists(database_name.to_string()));
+    }
+
+    let mut reclaimed: u64 = 0;
+    for table in get_tables(machine, database_name) {
+        match vacuum_table(machine, &table) {
+            Ok(bytes) => reclaimed += bytes,
+            Err(err) => return Err(err)
+        }
+    }
+
+    return Ok(reclaimed);
+}
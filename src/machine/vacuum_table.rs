@@ -0,0 +1,36 @@
+use crate::machine::Table;
+use crate::machine::Machine;
+use crate::machine::check_table_exists;
+use crate::machine::get_table_indexes;
+use crate::machine::build_index;
+
+use crate::storage::format_table_name;
+use crate::storage::pager_vacuum;
+
+use crate::utils::ExecutionError;
+use crate::utils::Logger;
+
+// Returns the amount of bytes reclaimed.
+pub fn vacuum_table(machine: &mut Machine, table: &Table) -> Result<u64, ExecutionError> {
+    if check_table_exists(machine, table) == false {
+        return Err(ExecutionError::TableNotExists(table.name.to_string()));
+    }
+
+    Logger::info(format!("VACUUM {}.{}", table.database_name, table.name).leak());
+
+    let table_key = format_table_name(&table.database_name, &table.name);
+    let reclaimed = pager_vacuum(&mut machine.pager, &table_key);
+
+    // Rows got new addresses, the indexes are rebuilt from scratch.
+    let indexes = match get_table_indexes(machine, table) {
+        Ok(indexes) => indexes,
+        Err(error) => return Err(error)
+    };
+    for index in indexes.iter() {
+        if let Err(error) = build_index(machine, index) {
+            return Err(error);
+        }
+    }
+
+    return Ok(reclaimed);
+}
@@ -1,9 +1,11 @@
 use sqlparser::ast::CreateIndex;
+use sqlparser::ast::Expr;
 
 use crate::machine::Machine;
 use crate::machine::check_index_exists;
 use crate::machine::create_index as machine_create_index;
 use crate::utils::ExecutionError;
+use crate::utils::join_column_names;
 
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
@@ -12,17 +14,28 @@ pub fn create_index(machine: &mut Machine, create_index: CreateIndex) -> Result<
     if let Some(db_name) = machine.actual_database.clone() {
         let if_not_exists = create_index.if_not_exists;
         let table_name = create_index.table_name.to_string();
-        let mut column_name = String::from("");
-
-        if let Some(column_obj) = create_index.columns.get(0) {
-            column_name = column_obj.expr.to_string();
-        }
+        let column_names: Vec<String> = create_index.columns
+            .iter()
+            .map(|column_obj| match &column_obj.expr {
+                Expr::Identifier(ident) => ident.value.clone(),
+                expr => expr.to_string()
+            })
+            .collect();
+        let column_name = join_column_names(&column_names);
 
         let name;
         if let Some(name_obj) = create_index.name {
             name = name_obj.to_string();
         } else {
-            name = format!("index_{}_{}", table_name, column_name);
+            name = format!("index_{}_{}", table_name, column_names.join("_"));
+        }
+
+        let index_type = match create_index.using {
+            Some(using) => using.to_string().to_lowercase(),
+            None => String::from("btree")
+        };
+        if index_type != "btree" && index_type != "hash" {
+            return Err(ExecutionError::IndexTypeNotSupported(index_type));
         }
 
         if check_index_exists(machine, &db_name, &name.to_string()) && if_not_exists {
@@ -38,7 +51,9 @@ pub fn create_index(machine: &mut Machine, create_index: CreateIndex) -> Result<
             &table_name,
             &column_name,
             &name,
-            &String::from("btree")
+            &index_type,
+            create_index.unique,
+            false
         )
     } else {
         return Err(ExecutionError::DatabaseNotSetted);
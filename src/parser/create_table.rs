@@ -3,19 +3,53 @@ use sqlparser::ast::ColumnOption;
 use sqlparser::ast::DataType;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Value;
-use sqlparser::ast::UnaryOperator;
+use sqlparser::ast::TableConstraint;
+use sqlparser::ast::ReferentialAction;
+use sqlparser::ast::ExactNumberInfo;
+
+use ordered_float::OrderedFloat;
 
 use crate::machine::Machine;
 use crate::machine::Column;
 use crate::machine::ColumnType;
+use crate::machine::map_decimal_type;
 use crate::machine::Table;
 use crate::machine::create_table as machine_create_table;
 use crate::machine::check_table_exists;
+use crate::machine::create_index as machine_create_index;
+use crate::machine::create_foreign_key as machine_create_foreign_key;
+use crate::machine::drop_table as machine_drop_table;
+use crate::machine::get_table_indexes;
+use crate::machine::ForeignKey;
+use crate::machine::CheckConstraint;
+use crate::machine::create_check_constraint as machine_create_check_constraint;
 
+use crate::storage::Data;
+use crate::storage::tuple_new;
+use crate::storage::JsonValue;
 use crate::storage::ResultSet;
 use crate::storage::ResultSetType;
 
 use crate::utils::ExecutionError;
+use crate::utils::join_column_names;
+
+use crate::parser::expression::convert_to_native_expr;
+use crate::parser::expression::map_temporal_type;
+
+fn map_referential_action(action: &Option<ReferentialAction>) -> String {
+    return match action {
+        Some(action) => action.to_string(),
+        None => String::from("NO ACTION")
+    };
+}
+
+fn map_exact_number_info(info: &ExactNumberInfo) -> Result<ColumnType, ExecutionError> {
+    return match *info {
+        ExactNumberInfo::None => map_decimal_type(None, None),
+        ExactNumberInfo::Precision(precision) => map_decimal_type(Some(precision), None),
+        ExactNumberInfo::PrecisionAndScale(precision, scale) => map_decimal_type(Some(precision), Some(scale))
+    };
+}
 
 pub fn create_table(machine: &mut Machine, create_table: CreateTable) -> Result<ResultSet, ExecutionError> { 
     if let Some(db_name) = machine.actual_database.clone() {
@@ -34,12 +68,15 @@ pub fn create_table(machine: &mut Machine, create_table: CreateTable) -> Result<
         }
 
         let mut columns: Vec<Column> = Vec::new();
+        let mut foreign_keys: Vec<ForeignKey> = Vec::new();
+        let mut check_constraints: Vec<CheckConstraint> = Vec::new();
 
         for column in create_table.columns.iter() {
             let mut notnull_column: bool = false;
             let mut unique_column: bool = false;
             let mut primary_key: bool = false;
             let mut default: String = String::from("");
+            let column_name = column.name.value.clone();
 
             for option in &column.options {
                 match &option.option {
@@ -61,8 +98,54 @@ pub fn create_table(machine: &mut Machine, create_table: CreateTable) -> Result<
                     ColumnOption::Default(Expr::Value(Value::SingleQuotedString(default_value))) => {
                         default = default_value.clone()
                     }
-                    ColumnOption::Default(Expr::UnaryOp { op: UnaryOperator::Minus, expr }) => {
-                        default = format!("-{}", expr.to_string())
+                    ColumnOption::Default(expr) => {
+                        // Constant expressions are folded when the table is created.
+                        match convert_to_native_expr(expr) {
+                            // Those reading the clock or drawing random values are kept
+                            // as SQL, to run on each insert.
+                            Ok(expression) if expression.column_names().len() == 0 && expression.is_volatile() => {
+                                default = expr.to_string();
+                            },
+                            Ok(expression) if expression.column_names().len() == 0 => {
+                                let value = match expression.result(&tuple_new(), &Vec::new()) {
+                                    Ok(value) => value,
+                                    Err(error) => return Err(error)
+                                };
+                                if matches!(value, Data::Null) == false {
+                                    default = value.to_string();
+                                }
+                            },
+                            _ => return Err(ExecutionError::ParserError(
+                                format!("DEFAULT of column {} must be a constant expression: {}", column_name, expr)
+                            ))
+                        }
+                    }
+                    ColumnOption::Check(expr) => {
+                        check_constraints.push(
+                            CheckConstraint::new(
+                                table.database_name.clone(),
+                                table.name.clone(),
+                                match &option.name {
+                                    Some(name) => name.to_string(),
+                                    None => format!("{}_{}_check", table.name, column_name)
+                                },
+                                expr.to_string()
+                            )
+                        );
+                    }
+                    ColumnOption::ForeignKey { foreign_table, referred_columns, on_delete, on_update, .. } => {
+                        foreign_keys.push(
+                            ForeignKey::new(
+                                table.database_name.clone(),
+                                table.name.clone(),
+                                join_column_names(&vec![column_name.clone()]),
+                                format!("{}_{}_fkey", table.name, column_name),
+                                foreign_table.to_string(),
+                                join_column_names(&referred_columns.iter().map(|column| column.value.clone()).collect()),
+                                map_referential_action(on_delete),
+                                map_referential_action(on_update)
+                            )
+                        );
                     }
                     other => {
                         println!(" create table default other {:?}", other);
@@ -92,6 +175,30 @@ pub fn create_table(machine: &mut Machine, create_table: CreateTable) -> Result<
                 DataType::Varchar(_) => ColumnType::Varchar("".to_string()),
                 DataType::Text => ColumnType::Text("".to_string()),
                 DataType::Boolean => ColumnType::UnsignedTinyint(0),
+
+                DataType::Real | DataType::Float4 | DataType::Float32 => ColumnType::Float32(OrderedFloat(0.0)),
+                DataType::Float(Some(precision)) if precision <= 24 => ColumnType::Float32(OrderedFloat(0.0)),
+                DataType::Float(_) => ColumnType::Float64(OrderedFloat(0.0)),
+                DataType::Double | DataType::DoublePrecision | DataType::Float8 | DataType::Float64 => {
+                    ColumnType::Float64(OrderedFloat(0.0))
+                },
+                DataType::Date |
+                DataType::Time(_, _) |
+                DataType::Timestamp(_, _) |
+                DataType::Datetime(_) |
+                DataType::Interval => map_temporal_type(&column.data_type).unwrap(),
+                DataType::Uuid => ColumnType::Uuid(0),
+                DataType::JSON => ColumnType::Json(String::new()),
+                DataType::JSONB => ColumnType::Jsonb(JsonValue::Null),
+                DataType::Bytea | DataType::Blob(_) | DataType::Binary(_) | DataType::Varbinary(_) => {
+                    ColumnType::Bytes(Vec::new())
+                },
+                DataType::Decimal(ref info) | DataType::Numeric(ref info) | DataType::Dec(ref info) => {
+                    match map_exact_number_info(info) {
+                        Ok(column_type) => column_type,
+                        Err(error) => return Err(error)
+                    }
+                },
                 _ => ColumnType::Undefined
             };
 
@@ -99,17 +206,135 @@ pub fn create_table(machine: &mut Machine, create_table: CreateTable) -> Result<
                 0u64,
                 table.database_name.clone(),
                 table.name.clone(),
-                column.name.to_string().clone(),
+                column_name,
                 type_column,
                 notnull_column,
                 unique_column,
                 primary_key,
                 default
             );
+            if let Err(error) = tcolumn.default_value() {
+                return Err(error);
+            }
             columns.push(tcolumn);
         }
 
-        return machine_create_table(machine, &table, columns);
+        // Table level PRIMARY KEY and UNIQUE constraints, single column ones
+        // are flagged on the columns themselves.
+        let mut unique_keys: Vec<(String, Vec<String>, bool)> = Vec::new();
+
+        for constraint in create_table.constraints.iter() {
+            let (name, key_columns, primary_key) = match constraint {
+                TableConstraint::PrimaryKey { name, columns, .. } => (name, columns, true),
+                TableConstraint::Unique { name, columns, .. } => (name, columns, false),
+                TableConstraint::ForeignKey { name, columns: key_columns, foreign_table, referred_columns, on_delete, on_update, .. } => {
+                    let column_names: Vec<String> = key_columns.iter().map(|column| column.value.clone()).collect();
+                    foreign_keys.push(
+                        ForeignKey::new(
+                            table.database_name.clone(),
+                            table.name.clone(),
+                            join_column_names(&column_names),
+                            match name {
+                                Some(name) => name.to_string(),
+                                None => format!("{}_{}_fkey", table.name, column_names.join("_"))
+                            },
+                            foreign_table.to_string(),
+                            join_column_names(&referred_columns.iter().map(|column| column.value.clone()).collect()),
+                            map_referential_action(on_delete),
+                            map_referential_action(on_update)
+                        )
+                    );
+                    continue;
+                },
+                TableConstraint::Check { name, expr } => {
+                    let check_name = match name {
+                        Some(name) => name.to_string(),
+                        None if check_constraints.iter().any(|other| other.name == format!("{}_check", table.name)) => {
+                            format!("{}_check{}", table.name, check_constraints.len())
+                        },
+                        None => format!("{}_check", table.name)
+                    };
+                    check_constraints.push(
+                        CheckConstraint::new(
+                            table.database_name.clone(),
+                            table.name.clone(),
+                            check_name,
+                            expr.to_string()
+                        )
+                    );
+                    continue;
+                },
+                _ => continue
+            };
+            let column_names: Vec<String> = key_columns.iter().map(|column| column.value.clone()).collect();
+
+            for column_name in column_names.iter() {
+                match columns.iter_mut().find(|column| column.name == *column_name) {
+                    Some(column) => { if primary_key { column.not_null = true } },
+                    None => return Err(ExecutionError::ColumnNotExists(column_name.clone()))
+                }
+            }
+
+            let index_name = match name {
+                Some(name) => name.to_string(),
+                None if primary_key => format!("{}_pkey", table.name),
+                None => format!("{}_{}_key", table.name, column_names.join("_"))
+            };
+            unique_keys.push((index_name, column_names, primary_key));
+        }
+
+        let result_set = machine_create_table(machine, &table, columns);
+        if result_set.is_err() {
+            return result_set;
+        }
+
+        for (index_name, column_names, primary_key) in unique_keys.iter() {
+            if let Err(error) = machine_create_index(
+                machine,
+                &table.database_name,
+                &table.name,
+                &join_column_names(column_names),
+                index_name,
+                &String::from("btree"),
+                true,
+                *primary_key
+            ) {
+                let _ = machine_drop_table(machine, &table, false, true);
+                return Err(error);
+            }
+        }
+
+        for check_constraint in check_constraints.iter() {
+            if let Err(error) = machine_create_check_constraint(machine, check_constraint) {
+                let _ = machine_drop_table(machine, &table, false, true);
+                return Err(error);
+            }
+        }
+
+        // Foreign keys go last, a table may reference itself. Without
+        // referenced columns they point to the primary key.
+        for foreign_key in foreign_keys.iter_mut() {
+            if foreign_key.referenced_column == String::from("") {
+                let referenced_table = Table::new(table.database_name.clone(), foreign_key.referenced_table.clone());
+                let referenced_indexes = match get_table_indexes(machine, &referenced_table) {
+                    Ok(indexes) => indexes,
+                    Err(error) => {
+                        let _ = machine_drop_table(machine, &table, false, true);
+                        return Err(error);
+                    }
+                };
+                if let Some(index) = referenced_indexes.iter().find(|index| index.primary_key) {
+                    foreign_key.referenced_column = index.column_name.clone();
+                }
+            }
+
+            if let Err(error) = machine_create_foreign_key(machine, foreign_key) {
+                let _ = machine_drop_table(machine, &table, false, true);
+                return Err(error);
+            }
+        }
+
+        return result_set;
     } else {
         return Err(ExecutionError::DatabaseNotSetted);
     }
@@ -24,7 +24,7 @@ pub fn drop_index(machine: &mut Machine, names: Vec<ObjectName>, if_exists: bool
         if check_index_exists(machine, &db_name, &name) == false {
             return Err(ExecutionError::IndexNotExists(name));
         }
-        return machine_drop_index(machine, &name);
+        return machine_drop_index(machine, &db_name, &name);
     } else {
         return Err(ExecutionError::DatabaseNotSetted);
     }
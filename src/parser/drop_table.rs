@@ -8,10 +8,10 @@ use crate::storage::ResultSet;
 
 use crate::utils::ExecutionError;
 
-pub fn drop_table(machine: &mut Machine, names: Vec<ObjectName>, if_exists: bool) -> Result<ResultSet, ExecutionError> { 
+pub fn drop_table(machine: &mut Machine, names: Vec<ObjectName>, if_exists: bool, cascade: bool) -> Result<ResultSet, ExecutionError> { 
     if let Some(db_name) = machine.actual_database.clone() {
         let table = Table::new(db_name, names[0].to_string());
-        return machine_drop_table(machine, &table, if_exists);
+        return machine_drop_table(machine, &table, if_exists, cascade);
     } else {
         return Err(ExecutionError::DatabaseNotSetted);
     }
@@ -0,0 +1,46 @@
+use sqlparser::ast::Statement;
+
+use crate::machine::Machine;
+use crate::machine::Column;
+use crate::machine::ColumnType;
+use crate::parser::query::query_plan;
+
+use crate::storage::Data;
+use crate::storage::Tuple;
+use crate::storage::ResultSet;
+
+use crate::utils::ExecutionError;
+
+// Shows the access path chosen for every table of a SELECT, one per line.
+pub fn explain(machine: &mut Machine, statement: Statement) -> Result<ResultSet, ExecutionError> { 
+    let access_paths = match statement {
+        Statement::Query(query) => query_plan(machine, query),
+        _ => return Err(ExecutionError::NotImplementedYet)
+    };
+
+    match access_paths {
+        Ok(access_paths) => {
+            let columns = vec![
+                Column::new(
+                    0u64,
+                    machine.get_actual_database_name(),
+                    String::from(""),
+                    String::from("plan"),
+                    ColumnType::Varchar(String::from("")),
+                    true,
+                    false,
+                    false,
+                    String::from("")
+                )
+            ];
+
+            let tuples: Vec<Tuple> = access_paths
+                .iter()
+                .map(|access_path| vec![Data::Varchar(access_path.to_string())])
+                .collect();
+
+            return Ok(ResultSet::new_select(columns, tuples));
+        },
+        Err(error) => return Err(error)
+    }
+}
@@ -0,0 +1,382 @@
+extern crate sqlparser;
+
+use sqlparser::ast::{Expr as ASTNode, *};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+use sqlparser::parser::ParserError;
+
+use crate::storage::Data;
+use crate::storage::decimal_parse;
+use crate::storage::bytes_parse_hex;
+use crate::storage::Expression;
+use crate::storage::Expression0Type;
+use crate::storage::Expression1Type;
+use crate::storage::temporal_cast;
+use crate::storage::temporal_is_extract_field;
+use crate::storage::temporal_is_trunc_field;
+use crate::storage::Expression2Type;
+use crate::storage::json_parse;
+use crate::storage::json_normalize;
+use crate::storage::Aggregate;
+use crate::storage::AggregateType;
+use crate::storage::aggregate_argument_count;
+
+use crate::utils::ExecutionError;
+use crate::utils::QueryError;
+
+fn strip_quotes(ident: &str) -> String {
+    if ident.starts_with('`') || ident.starts_with('"') {
+        ident[1..ident.len() - 1].to_string()
+    } else {
+        ident.to_string()
+    }
+}
+
+fn map_binary_operator(o: &BinaryOperator) -> Result<Expression2Type, QueryError> {
+    Ok(match o {
+        BinaryOperator::And => Expression2Type::And,
+        BinaryOperator::Or => Expression2Type::Or,
+        BinaryOperator::Eq => Expression2Type::Equal,
+        BinaryOperator::NotEq => Expression2Type::NotEqual,
+        BinaryOperator::Plus => Expression2Type::Add,
+        BinaryOperator::Minus => Expression2Type::Sub,
+        BinaryOperator::Multiply => Expression2Type::Mul,
+        BinaryOperator::Divide => Expression2Type::Div,
+//        BinaryOperator::Modulo => Func2Type::Modulo,
+        BinaryOperator::Gt => Expression2Type::GreatherThan,
+        BinaryOperator::GtEq => Expression2Type::GreatherOrEqual,
+        BinaryOperator::Lt => Expression2Type::LessThan,
+        BinaryOperator::LtEq => Expression2Type::LessOrEqual,
+        BinaryOperator::Arrow => Expression2Type::JsonGet,
+        BinaryOperator::LongArrow => Expression2Type::JsonGetText,
+        BinaryOperator::HashArrow => Expression2Type::JsonGetPath,
+        BinaryOperator::HashLongArrow => Expression2Type::JsonGetPathText,
+        BinaryOperator::AtArrow => Expression2Type::JsonContains,
+        BinaryOperator::ArrowAt => Expression2Type::JsonContainedIn,
+        other => {
+            return Err(QueryError::NotImplemented(format!(
+                "Unsupported operator {:?}",
+                other
+            )))
+        }
+    })
+}
+
+fn map_unary_operator(op: &UnaryOperator) -> Result<Expression1Type, QueryError> {
+    Ok(match op {
+        UnaryOperator::Not => Expression1Type::Not,
+        UnaryOperator::Minus => Expression1Type::Negate,
+        other => {
+            return Err(QueryError::NotImplemented(format!(
+                "Unsupported operator {:?}",
+                other
+            )))
+        }
+    })
+}
+
+
+// Fn to map sqlparser-rs `Value` to LocustDB's `RawVal`.
+fn get_raw_val(constant: &Value) -> Result<Data, QueryError> {
+    match constant {
+        Value::Number(num, _) => {
+            if num.parse::<u64>().is_ok() {
+                Ok(Data::UnsignedBigint(num.parse::<u64>().unwrap()))
+            } else {
+                match (decimal_parse(num), num.parse::<f64>()) {
+                    (Some(value), _) => Ok(Data::from_decimal(Some(value))),
+                    (None, Ok(value)) => Ok(Data::Float64(ordered_float::OrderedFloat(value))),
+                    (None, Err(_)) => Ok(Data::Null)
+                }
+            }
+        },
+        Value::SingleQuotedString(string) => Ok(Data::Varchar(string.to_string())),
+        // X'0aff', strings written as '\x0aff' become binary where a BYTEA is expected.
+        Value::HexStringLiteral(digits) => match bytes_parse_hex(digits) {
+            Some(bytes) => Ok(Data::Bytes(bytes)),
+            None => Err(QueryError::ParseError(format!("Invalid hex literal X'{}'", digits)))
+        },
+        Value::Boolean(value) => Ok(Data::Boolean(*value)),
+        Value::Null => Ok(Data::Null),
+        _ => Err(QueryError::NotImplemented(format!("{:?}", constant))),
+    }
+}
+
+// Temporal column types, also used by literals such as DATE '2024-01-31'.
+pub fn map_temporal_type(data_type: &DataType) -> Option<Data> {
+    return match data_type {
+        DataType::Date => Some(Data::Date(0)),
+        DataType::Time(_, _) => Some(Data::Time(0)),
+        DataType::Timestamp(_, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz) => Some(Data::TimestampTz(0)),
+        DataType::Timestamp(_, _) | DataType::Datetime(_) => Some(Data::Timestamp(0)),
+        DataType::Interval => Some(Data::Interval(0, 0, 0)),
+        _ => None
+    };
+}
+
+// Fields as the temporal functions name them: EXTRACT(MILLISECOND ...) reads
+// milliseconds.
+fn map_temporal_field(field: &str) -> String {
+    let field = field.to_lowercase();
+    return match field.as_str() {
+        "millisecond" => String::from("milliseconds"),
+        "microsecond" => String::from("microseconds"),
+        "millenium" => String::from("millennium"),
+        _ => field
+    };
+}
+
+fn get_function_args(function: &Function) -> Result<Vec<&ASTNode>, QueryError> {
+    return match &function.args {
+        FunctionArguments::None => Ok(Vec::new()),
+        FunctionArguments::List(list) => list.args.iter().map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+            other => Err(QueryError::NotImplemented(format!("Unsupported function argument {}", other)))
+        }).collect(),
+        other => Err(QueryError::NotImplemented(format!("Unsupported function arguments {}", other)))
+    };
+}
+
+fn map_aggregate_type(name: &str) -> Option<AggregateType> {
+    return match name {
+        "count" => Some(AggregateType::Count),
+        "sum" => Some(AggregateType::Sum),
+        "avg" => Some(AggregateType::Avg),
+        "min" => Some(AggregateType::Min),
+        "max" => Some(AggregateType::Max),
+        "string_agg" => Some(AggregateType::StringAgg),
+        "array_agg" => Some(AggregateType::ArrayAgg),
+        _ => None
+    };
+}
+
+// Aggregate calls, COUNT(*) counting every tuple. Aggregates inside the
+// arguments of another are refused, as are ORDER BY and FILTER clauses.
+fn convert_aggregate(function: &Function, aggregate_type: AggregateType) -> Result<Expression, QueryError> {
+    let (aggregate_type, distinct, args) = match &function.args {
+        FunctionArguments::List(list) if !list.clauses.is_empty() => {
+            return Err(QueryError::NotImplemented(format!("Unsupported aggregate clauses in {}", function)));
+        },
+        FunctionArguments::List(list) => match (&aggregate_type, list.args.as_slice()) {
+            (AggregateType::Count, [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)]) if list.duplicate_treatment.is_none() => {
+                (AggregateType::CountAll, false, Vec::new())
+            },
+            _ => (
+                aggregate_type,
+                list.duplicate_treatment == Some(DuplicateTreatment::Distinct),
+                get_function_args(function)?
+            )
+        },
+        _ => (aggregate_type, false, get_function_args(function)?)
+    };
+
+    if function.filter.is_some() {
+        return Err(QueryError::NotImplemented(format!("Unsupported aggregate filter in {}", function)));
+    }
+    if args.len() != aggregate_argument_count(&aggregate_type) {
+        return Err(QueryError::ParseError(format!("Wrong number of arguments in {}", function)));
+    }
+
+    let arguments: Vec<Expression> = args
+        .into_iter()
+        .map(convert_to_native_expr)
+        .collect::<Result<Vec<Expression>, QueryError>>()?;
+    if arguments.iter().any(|argument| argument.has_aggregate()) {
+        return Err(QueryError::ParseError(format!("Aggregate function calls cannot be nested in {}", function)));
+    }
+
+    return Ok(Expression::Aggregate(Aggregate {
+        aggregate_type,
+        distinct,
+        arguments,
+        name: function.to_string()
+    }));
+}
+
+fn convert_function(function: &Function) -> Result<Expression, QueryError> {
+    let name = function.name.to_string().to_lowercase();
+    if let Some(aggregate_type) = map_aggregate_type(&name) {
+        return convert_aggregate(function, aggregate_type);
+    }
+
+    let args = get_function_args(function)?;
+
+    return match (name.as_str(), args.len()) {
+        ("now" | "current_timestamp", 0) => Ok(Expression::Func0(Expression0Type::Now)),
+        ("current_date", 0) => Ok(Expression::Func0(Expression0Type::CurrentDate)),
+        ("current_time" | "localtime", 0) => Ok(Expression::Func0(Expression0Type::CurrentTime)),
+        ("localtimestamp", 0) => Ok(Expression::Func0(Expression0Type::LocalTimestamp)),
+        ("gen_random_uuid" | "uuid_generate_v4", 0) => Ok(Expression::Func0(Expression0Type::GenRandomUuid)),
+        ("date_trunc", 2) => {
+            let field = match args[0] {
+                ASTNode::Value(Value::SingleQuotedString(field)) => map_temporal_field(field),
+                other => return Err(QueryError::ParseError(format!("DATE_TRUNC expects a field name, got {}", other)))
+            };
+            if !temporal_is_trunc_field(&field) {
+                return Err(QueryError::ParseError(format!("DATE_TRUNC does not know the field {}", field)));
+            }
+            Ok(Expression::Func1(Expression1Type::DateTrunc(field), Box::new(convert_to_native_expr(args[1])?)))
+        },
+        // The path goes one step at a time, as #> does.
+        ("json_extract_path" | "jsonb_extract_path", count) if count >= 2 => {
+            let mut expression = convert_to_native_expr(args[0])?;
+            for step in args[1..].iter() {
+                expression = Expression::Func2(
+                    Expression2Type::JsonGetPath,
+                    Box::new(expression),
+                    Box::new(convert_to_native_expr(step)?)
+                );
+            }
+            Ok(expression)
+        },
+        ("json_extract_path_text" | "jsonb_extract_path_text", count) if count >= 2 => {
+            let mut expression = convert_to_native_expr(args[0])?;
+            for (position, step) in args[1..].iter().enumerate() {
+                let operator = if position == count - 2 {
+                    Expression2Type::JsonGetPathText
+                } else {
+                    Expression2Type::JsonGetPath
+                };
+                expression = Expression::Func2(operator, Box::new(expression), Box::new(convert_to_native_expr(step)?));
+            }
+            Ok(expression)
+        },
+        ("json_array_length" | "jsonb_array_length", 1) => {
+            Ok(Expression::Func1(Expression1Type::JsonArrayLength, Box::new(convert_to_native_expr(args[0])?)))
+        },
+        ("json_typeof" | "jsonb_typeof", 1) => {
+            Ok(Expression::Func1(Expression1Type::JsonTypeof, Box::new(convert_to_native_expr(args[0])?)))
+        },
+        _ => Err(QueryError::NotImplemented(format!("Unsupported function {}", function)))
+    };
+}
+
+// JSON literals such as JSONB '{"a": 1}', checked when they are parsed.
+fn convert_json_literal(data_type: &DataType, value: &String) -> Result<Expression, QueryError> {
+    let Some(json) = json_parse(value) else {
+        return Err(QueryError::ParseError(format!("Invalid literal {} '{}'", data_type, value)));
+    };
+
+    return match data_type {
+        DataType::JSONB => Ok(Expression::Const(Data::Jsonb(json_normalize(json)))),
+        _ => Ok(Expression::Const(Data::Json(value.clone())))
+    };
+}
+
+// Intervals are constants: INTERVAL '1 day' or INTERVAL '3' HOUR.
+fn convert_interval(interval: &Interval) -> Result<Expression, QueryError> {
+    let value = match interval.value.as_ref() {
+        ASTNode::Value(Value::SingleQuotedString(value)) => value.clone(),
+        ASTNode::Value(Value::Number(value, _)) => value.clone(),
+        other => return Err(QueryError::ParseError(format!("Invalid interval {}", other)))
+    };
+    let value = match (&interval.leading_field, &interval.last_field) {
+        (None, None) => value,
+        (Some(field), None) => format!("{} {}", value, field.to_string().to_lowercase()),
+        _ => return Err(QueryError::NotImplemented(format!("Unsupported interval {}", interval)))
+    };
+
+    return match temporal_cast(&Data::Interval(0, 0, 0), &Data::Varchar(value.clone())) {
+        Some(value) => Ok(Expression::Const(value)),
+        None => Err(QueryError::ParseError(format!("Invalid interval {}", value)))
+    };
+}
+
+pub fn convert_to_native_expr(node: &ASTNode) -> Result<Expression, QueryError> {
+    Ok(match node {
+        ASTNode::BinaryOp {
+            ref left,
+            ref op,
+            ref right,
+        } => Expression::Func2(
+            map_binary_operator(op)?,
+            Box::new(convert_to_native_expr(left)?),
+            Box::new(convert_to_native_expr(right)?)
+        ),
+        ASTNode::UnaryOp {
+            ref op,
+            expr: ref expression,
+        } => Expression::Func1(map_unary_operator(op)?, Box::new(convert_to_native_expr(expression)?)),
+        ASTNode::Between {
+            ref expr,
+            negated,
+            ref low,
+            ref high
+        } => {
+            let between = Expression::Func2(
+                Expression2Type::And,
+                Box::new(Expression::Func2(
+                    Expression2Type::GreatherOrEqual,
+                    Box::new(convert_to_native_expr(expr)?),
+                    Box::new(convert_to_native_expr(low)?)
+                )),
+                Box::new(Expression::Func2(
+                    Expression2Type::LessOrEqual,
+                    Box::new(convert_to_native_expr(expr)?),
+                    Box::new(convert_to_native_expr(high)?)
+                ))
+            );
+            if *negated {
+                Expression::Func1(Expression1Type::Not, Box::new(between))
+            } else {
+                between
+            }
+        },
+        ASTNode::Nested(ref expr) => convert_to_native_expr(expr)?,
+        ASTNode::Value(ref literal) => Expression::Const(get_raw_val(literal)?),
+        ASTNode::Identifier(ref identifier) => {
+            Expression::ColName(strip_quotes(identifier.value.as_ref()))
+        }
+        ASTNode::CompoundIdentifier(ref identifiers) => {
+            let names: Vec<String> = identifiers.iter().map(|identifier| strip_quotes(identifier.value.as_ref())).collect();
+            Expression::ColName(names.join("."))
+        }
+        ASTNode::Function(ref function) => convert_function(function)?,
+        ASTNode::Extract { ref field, ref expr, .. } => {
+            let field = map_temporal_field(&field.to_string());
+            if !temporal_is_extract_field(&field) {
+                return Err(QueryError::ParseError(format!("EXTRACT does not know the field {}", field)));
+            }
+            Expression::Func1(Expression1Type::Extract(field), Box::new(convert_to_native_expr(expr)?))
+        },
+        ASTNode::Interval(ref interval) => convert_interval(interval)?,
+        ASTNode::TypedString { data_type: ref data_type @ (DataType::JSON | DataType::JSONB), ref value } => {
+            convert_json_literal(data_type, value)?
+        },
+        ASTNode::Cast { ref expr, data_type: DataType::JSON, .. } => {
+            Expression::Func1(Expression1Type::ToJson, Box::new(convert_to_native_expr(expr)?))
+        },
+        ASTNode::Cast { ref expr, data_type: DataType::JSONB, .. } => {
+            Expression::Func1(Expression1Type::ToJsonb, Box::new(convert_to_native_expr(expr)?))
+        },
+        ASTNode::TypedString { ref data_type, ref value } => {
+            let literal = map_temporal_type(data_type)
+                .and_then(|kind| temporal_cast(&kind, &Data::Varchar(value.clone())));
+            match literal {
+                Some(literal) => Expression::Const(literal),
+                None => return Err(QueryError::ParseError(format!("Invalid literal {} '{}'", data_type, value)))
+            }
+        },
+        _ => {
+            println!("Parsing for this ASTNode not implemented: {:?}", node);
+            return Err(QueryError::NotImplemented(format!("Parsing for this ASTNode not implemented: {:?}", node)))
+        }
+    })
+}
+
+// Expressions kept as SQL text on the catalog, such as CHECK constraints,
+// go through here when they are loaded.
+pub fn parse_expression(sql_expression: &String) -> Result<Expression, ExecutionError> {
+    let dialect = PostgreSqlDialect {};
+    let expression = match Parser::new(&dialect).try_with_sql(sql_expression).and_then(|mut parser| parser.parse_expr()) {
+        Ok(expression) => expression,
+        Err(ParserError::ParserError(err)) => return Err(ExecutionError::ParserError(err)),
+        Err(ParserError::TokenizerError(err)) => return Err(ExecutionError::TokenizerError(err)),
+        Err(ParserError::RecursionLimitExceeded) => return Err(ExecutionError::RecursionLimitExceeded)
+    };
+
+    return match convert_to_native_expr(&expression) {
+        Ok(expression) => Ok(expression),
+        Err(err) => Err(ExecutionError::ParserError(err.to_string()))
+    };
+}
@@ -5,12 +5,10 @@ use sqlparser::ast::Ident;
 use sqlparser::ast::Query;
 use sqlparser::ast::SetExpr;
 use sqlparser::ast::Expr;
-use sqlparser::ast::Value;
 
 use crate::machine::Machine;
 use crate::machine::Table;
 use crate::machine::Column;
-use crate::machine::ColumnType;
 use crate::machine::check_table_exists;
 use crate::machine::insert_row;
 use crate::machine::get_columns;
@@ -23,6 +21,86 @@ use crate::storage::ResultSet;
 
 use crate::utils::ExecutionError;
 
+use crate::parser::expression::convert_to_native_expr;
+
+// The DEFAULT keyword inside VALUES comes as an unquoted identifier.
+fn is_default_keyword(expr: &Expr) -> bool {
+    return match expr {
+        Expr::Identifier(ident) => ident.quote_style.is_none() && ident.value.to_uppercase() == "DEFAULT",
+        _ => false
+    };
+}
+
+// Value of a column left out of the INSERT, or given as DEFAULT.
+fn get_default_value(machine: &mut Machine, table: &Table, tcolumn: &Column) -> Result<Data, ExecutionError> {
+    if tcolumn.primary_key {
+        if let Some(next_id) = get_sequence_next_id(machine, tcolumn) {
+            return tcolumn.coerce(Data::UnsignedBigint(next_id));
+        }
+    }
+
+    if tcolumn.default != String::from("") {
+        return tcolumn.default_value();
+    }
+    if tcolumn.not_null {
+        return Err(ExecutionError::ColumnCantBeNull(
+            table.database_name.clone(),
+            table.name.clone(),
+            tcolumn.name.clone()
+        ));
+    }
+    return Ok(Data::Null);
+}
+
+fn get_tuple(
+    machine: &mut Machine,
+    table: &Table,
+    columns: &Vec<Column>,
+    query_columns: &Vec<Ident>,
+    items: &Vec<Expr>
+) -> Result<Tuple, ExecutionError> {
+    let mut tuple = tuple_new();
+
+    for (idx, tcolumn) in columns.iter().enumerate() {
+        // Without a column list the values go to the columns in order.
+        let item = if query_columns.len() == 0 {
+            items.get(idx)
+        } else {
+            query_columns.iter().position(|e| e.value == tcolumn.name).and_then(|position| items.get(position))
+        };
+
+        if item.is_none() || is_default_keyword(item.unwrap()) {
+            match get_default_value(machine, table, tcolumn) {
+                Ok(value) => tuple.push(value),
+                Err(error) => return Err(error)
+            }
+            continue;
+        }
+
+        let value = match convert_to_native_expr(item.unwrap()) {
+            Ok(expression) if expression.column_names().len() == 0 => match expression.result(&tuple_new(), &Vec::new()) {
+                Ok(value) => value,
+                Err(error) => return Err(error)
+            },
+            _ => return Err(ExecutionError::ParserError(format!("Inserted value not supported: {}", item.unwrap())))
+        };
+
+        if matches!(value, Data::Null) && tcolumn.not_null {
+            return Err(ExecutionError::ColumnCantBeNull(
+                table.database_name.clone(),
+                table.name.clone(),
+                tcolumn.name.clone()
+            ));
+        }
+        match tcolumn.coerce(value) {
+            Ok(value) => tuple.push(value),
+            Err(error) => return Err(error)
+        }
+    }
+
+    return Ok(tuple);
+}
+
 fn get_tuples(
     machine: &mut Machine,
     table: &Table,
@@ -32,111 +110,27 @@ fn get_tuples(
 ) -> Result<Vec<Tuple>, ExecutionError> {
     let mut tuples: Vec<Tuple> = Vec::new();
 
-    if let Some(query) = source {
-        let rows = (*query).body;
-        match *rows {
-            SetExpr::Values(values) => {
-              for items in values.rows {
-                let mut tuple = tuple_new();
-
-                for tcolumn in columns.iter() {
-                    let column_position_option = query_columns.iter().position(|e| e.value == tcolumn.name);
-
-                    if let Some(column_position) = column_position_option {
-                        match items.get(column_position) {
-                            Some(Expr::Identifier(_)) => {},
-                            Some(Expr::Value(Value::Number(value, _))) => {
-                                if tcolumn.clone().is_number() {
-                                    let my_integer: Result<u64, _> = value.parse();
-                                    match my_integer {
-                                        Ok(number) => tuple.push(Data::UnsignedBigint(number)),
-                                        Err(_) => println!("Failed to parse string to integer"),
-                                    }
-                                }
-                            },
-                            Some(Expr::Value(Value::Null)) => {
-                                if tcolumn.not_null == false {
-                                    tuple.push(Data::Null);
-                                } else {
-                                    return Err(ExecutionError::ColumnCantBeNull(
-                                        table.database_name.clone(),
-                                        table.name.clone(),
-                                        tcolumn.name.clone()
-                                    ))
-                                }
-                            },
-                            Some(Expr::Value(Value::SingleQuotedString(value))) => {
-                                if matches!(tcolumn.column_type,ColumnType::Varchar(_)) {
-                                    tuple.push(Data::Varchar(value.clone()));
-                                }
-                            },
-                            other => {
-                                println!("inserted value not identified {:?}", other);
-                            }
-                        }
-                    } else if tcolumn.primary_key {
-                        if let Some(next_id) = get_sequence_next_id(machine, tcolumn) {
-                            tuple.push(Data::UnsignedBigint(next_id));
+    match source {
+        Some(query) => {
+            let rows = (*query).body;
+            match *rows {
+                SetExpr::Values(values) => {
+                    for items in values.rows {
+                        match get_tuple(machine, table, columns, &query_columns, &items) {
+                            Ok(tuple) => tuples.push(tuple),
+                            Err(error) => return Err(error)
                         }
-                    } else if tcolumn.not_null && tcolumn.default == String::from("") {
-                        return Err(ExecutionError::ColumnCantBeNull(
-                            table.database_name.clone(),
-                            table.name.clone(),
-                            tcolumn.name.clone()
-                        ))
-                    } else if tcolumn.default != String::from("") {
-                        match &tcolumn.column_type {
-                            ColumnType::UnsignedBigint(_) => {
-                                let num_parse: u64 = tcolumn.default.parse::<u64>().unwrap();
-                                tuple.push(Data::UnsignedBigint(num_parse));
-                            },
-                            ColumnType::UnsignedInt(_) => {
-                                let num_parse: u32 = tcolumn.default.parse::<u32>().unwrap();
-                                tuple.push(Data::UnsignedInt(num_parse));
-                            },
-                            ColumnType::UnsignedSmallint(_) => {
-                                let num_parse: u16 = tcolumn.default.parse::<u16>().unwrap();
-                                tuple.push(Data::UnsignedSmallint(num_parse));
-                            },
-                            ColumnType::UnsignedTinyint(_) => {
-                                let num_parse: u8 = tcolumn.default.parse::<u8>().unwrap();
-                                tuple.push(Data::UnsignedTinyint(num_parse));
-                            },
-                            ColumnType::SignedBigint(_) => {
-                                let num_parse: i64 = tcolumn.default.parse::<i64>().unwrap();
-                                tuple.push(Data::SignedBigint(num_parse));
-                            },
-                            ColumnType::SignedInt(_) => {
-                                let num_parse: i32 = tcolumn.default.parse::<i32>().unwrap();
-                                tuple.push(Data::SignedInt(num_parse));
-                            },
-                            ColumnType::SignedSmallint(_) => {
-                                let num_parse: i16 = tcolumn.default.parse::<i16>().unwrap();
-                                tuple.push(Data::SignedSmallint(num_parse));
-                            },
-                            ColumnType::SignedTinyint(_) => {
-                                let num_parse: i8 = tcolumn.default.parse::<i8>().unwrap();
-                                tuple.push(Data::SignedTinyint(num_parse));
-                            },
-                            ColumnType::Boolean(_) => {
-                                let value = tcolumn.default == String::from("1");
-                                tuple.push(Data::Boolean(value));
-                            },
-                            ColumnType::Varchar(_) => tuple.push(Data::Varchar(tcolumn.default.clone())),
-                            ColumnType::Text(_) => tuple.push(Data::Text(tcolumn.default.clone())),
-                            other => {
-                                println!("insert value and column_type not identified {:?}", other);
-                            }
-                        }
-                    } else if tcolumn.not_null == false {
-                        tuple.push(Data::Null);
-                    } 
-                }
-
-                tuples.push(tuple)
-              }
-            },
-            _ => {}
+                    }
+                },
+                _ => {}
+            }
+        },
+        // INSERT ... DEFAULT VALUES
+        None => {
+            match get_tuple(machine, table, columns, &query_columns, &Vec::new()) {
+                Ok(tuple) => tuples.push(tuple),
+                Err(error) => return Err(error)
+            }
         }
     }
 
@@ -3,6 +3,7 @@ pub mod process_command;
 
 pub use process_command::process_command;
 pub use parse_command::parse_command;
+pub use expression::parse_expression;
 
 pub mod use_database;
 pub mod create_database;
@@ -21,10 +22,14 @@ pub mod drop_index;
 
 pub mod create_view;
 
+pub mod expression;
 pub mod query;
+pub mod explain;
 
 pub mod insert;
 
 pub mod update;
 
 pub mod delete;
+
+pub mod vacuum;
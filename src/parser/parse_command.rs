@@ -1,22 +1,65 @@
-
+use sqlparser::ast::Statement;
 use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::Parser;
 use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
 
 use crate::parser::process_command;
+use crate::parser::vacuum::VacuumStatement;
+use crate::parser::vacuum::parse_vacuum;
+use crate::parser::vacuum::vacuum;
 use crate::machine::Machine;
 use crate::utils::ExecutionError;
 
 use crate::storage::ResultSet;
 
-pub fn parse_command(machine: &mut Machine, sql_command: &str) -> Result<Vec<ResultSet>, ExecutionError> { 
+enum Command {
+    Statement(Statement),
+    Vacuum(VacuumStatement)
+}
+
+// Same loop as Parser::parse_statements, plus the commands that the SQL
+// parser does not know about.
+fn parse_commands(sql_command: &str) -> Result<Vec<Command>, ParserError> { 
     let dialect = PostgreSqlDialect {};
+    let mut parser = Parser::new(&dialect).try_with_sql(sql_command)?;
+    let mut commands: Vec<Command> = Vec::new();
+    let mut expecting_statement_delimiter = false;
+
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+
+        if parser.peek_token().token == Token::EOF {
+            break;
+        }
+        if expecting_statement_delimiter {
+            return parser.expected("end of statement", parser.peek_token());
+        }
 
-    match Parser::parse_sql(&dialect, sql_command) {
+        if parser.parse_keyword(Keyword::VACUUM) {
+            commands.push(Command::Vacuum(parse_vacuum(&mut parser)?));
+        } else {
+            commands.push(Command::Statement(parser.parse_statement()?));
+        }
+        expecting_statement_delimiter = true;
+    }
+
+    return Ok(commands);
+}
+
+pub fn parse_command(machine: &mut Machine, sql_command: &str) -> Result<Vec<ResultSet>, ExecutionError> { 
+    match parse_commands(sql_command) {
         Ok(commands) => {
             let mut result_sets: Vec<ResultSet> = Vec::new();
             for command in commands {
-                match process_command(machine, command) {
+                let result = match command {
+                    Command::Statement(statement) => process_command(machine, statement),
+                    Command::Vacuum(statement) => vacuum(machine, statement)
+                };
+                match result {
                     Ok(result_set) => {
                         result_sets.push(result_set);
                     },
@@ -33,4 +76,3 @@ pub fn parse_command(machine: &mut Machine, sql_command: &str) -> Result<Vec<Res
         Err(ParserError::RecursionLimitExceeded) => Err(ExecutionError::RecursionLimitExceeded)
     }
 }
-
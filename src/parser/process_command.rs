@@ -21,6 +21,7 @@ use crate::parser::drop_index::drop_index;
 use crate::parser::create_view::create_view;
 
 use crate::parser::query::query;
+use crate::parser::explain::explain;
 
 use crate::parser::insert::insert;
 use crate::parser::update::update;
@@ -47,8 +48,8 @@ pub fn process_command(machine: &mut Machine, statement: Statement) -> Result<Re
         Statement::Drop { object_type: ObjectType::Database, if_exists, names, .. } => {
             drop_database(machine, names, if_exists)
         },
-        Statement::Drop { object_type: ObjectType::Table, if_exists, names, .. } => {
-            drop_table(machine, names, if_exists)
+        Statement::Drop { object_type: ObjectType::Table, if_exists, names, cascade, .. } => {
+            drop_table(machine, names, if_exists, cascade)
         },
         Statement::Drop { object_type: ObjectType::Index, if_exists, names, .. } => {
             drop_index(machine, names, if_exists)
@@ -62,6 +63,7 @@ pub fn process_command(machine: &mut Machine, statement: Statement) -> Result<Re
             update(machine, table, assignments, selection, returning)
         },
         Statement::Query(statement) => query(machine, statement),
+        Statement::Explain { statement, .. } => explain(machine, *statement),
         Statement::ShowDatabases { .. } => show_databases(machine),
         Statement::ShowTables { .. } => show_tables(machine),
         value => { 
@@ -9,17 +9,24 @@ use crate::machine::Column;
 use crate::machine::ColumnType;
 use crate::machine::get_columns as machine_get_columns;
 use crate::machine::check_table_exists;
-use crate::machine::product_cartesian;
+use crate::machine::AccessPath;
+use crate::machine::plan_access_path;
+use crate::machine::product_cartesian_with_access_paths;
 
-use crate::storage::Data;
 use crate::storage::Expression;
-use crate::storage::Expression1Type;
-use crate::storage::Expression2Type;
 use crate::storage::ResultSet;
+use crate::storage::SortKey;
+use crate::storage::Aggregate;
+use crate::storage::column_name_matches;
+use crate::storage::Tuple;
+
+use crate::config::Config;
 
 use crate::utils::ExecutionError;
 use crate::utils::QueryError;
 
+use crate::parser::expression::convert_to_native_expr;
+
 fn strip_quotes(ident: &str) -> String {
     if ident.starts_with('`') || ident.starts_with('"') {
         ident[1..ident.len() - 1].to_string()
@@ -29,17 +36,6 @@ fn strip_quotes(ident: &str) -> String {
 }
 
 /*
-fn get_order_by(order_by: Option<Vec<OrderByExpr>>) -> Result<Vec<(Expr, bool)>, QueryError> {
-    let mut order = Vec::new();
-    if let Some(sql_order_by_exprs) = order_by {
-        for e in sql_order_by_exprs {
-            order.push((*(convert_to_native_expr(&e.expr))?, !e.asc.unwrap_or(true)));
-        }
-    }
-    Ok(order)
-}
-
-
 fn function_arg_to_expr(node: &FunctionArg) -> Result<&ASTNode, QueryError> {
     match node {
         FunctionArg::Named { name, .. } => Err(QueryError::NotImplemented(format!(
@@ -62,13 +58,32 @@ fn func_arg_to_native_expr(node: &FunctionArg) -> Result<Box<Expr>, QueryError>
 
 */
 
-type QueryResult = (Vec<SelectItem>, Vec<TableWithJoins>, Option<ASTNode>, Option<Expr>, Option<Offset>);
+struct QueryComponents {
+    projection: Vec<SelectItem>,
+    relations: Vec<TableWithJoins>,
+    distinct: Option<Distinct>,
+    selection: Option<ASTNode>,
+    group_by: GroupByExpr,
+    having: Option<ASTNode>,
+    order_by: Vec<OrderByExpr>,
+    limit: Option<ASTNode>,
+    offset: Option<Offset>
+}
 
-#[allow(clippy::type_complexity)]
-fn get_query_components(query: Box<Select>) -> Result<QueryResult, QueryError> {
+fn get_query_components(query: Box<Select>) -> Result<QueryComponents, QueryError> {
     match *query.body {
         SetExpr::Select(select) => {
-            Ok((select.projection, select.from, select.selection, query.limit, query.offset))
+            Ok(QueryComponents {
+                projection: select.projection,
+                relations: select.from,
+                distinct: select.distinct,
+                selection: select.selection,
+                group_by: select.group_by,
+                having: select.having,
+                order_by: query.order_by.map(|order_by| order_by.exprs).unwrap_or_default(),
+                limit: query.limit,
+                offset: query.offset
+            })
         },
         _ => Err(
             QueryError::NotImplemented(
@@ -105,55 +120,63 @@ fn get_table_name(db_name: String, relations: Vec<TableWithJoins>) -> Vec<Table>
     return result;
 }
 
+// Column of the result computed by an expression of the projection, named
+// after the column it reads when it is a plain reference.
+fn get_projection_column(source_columns: &Vec<Column>, expr: &ASTNode, expression: &Expression, alias: Option<String>) -> Column {
+    let reference = match expression {
+        Expression::ColName(colname) => source_columns
+            .iter()
+            .find(|column| column.qualified_name() == *colname || column.alias == *colname),
+        _ => None
+    };
+
+    let mut column = match reference {
+        Some(column) => column.clone(),
+        None => Column::new(
+            0u64,
+            String::from(""),
+            String::from(""),
+            expr.to_string(),
+            ColumnType::Undefined,
+            false,
+            false,
+            false,
+            String::from("")
+        )
+    };
+
+    if let Some(alias) = alias {
+        column.alias = alias;
+    }
+    return column;
+}
+
+// Columns of the result and the expressions computing them, wildcards are
+// expanded to every column of their tables.
 pub fn get_columns(
     machine: &mut Machine,
     projection: Vec<SelectItem>,
-    tables: Vec<Table>
-) -> Vec<Column> {
+    tables: Vec<Table>,
+    source_columns: &Vec<Column>
+) -> Result<(Vec<Column>, Vec<Expression>), ExecutionError> {
     let mut columns = Vec::<Column>::new();
+    let mut expressions = Vec::<Expression>::new();
     for elem in &projection {
         match elem {
             SelectItem::UnnamedExpr(e) => {
-                columns.push(
-                    Column::new(
-                        0u64,
-                        tables[0].database_name.clone(),
-                        tables[0].name.clone(),
-                        e.to_string(),
-                        ColumnType::Undefined,
-                        false,
-                        false,
-                        false,
-                        String::from("")
-                    )
-                )
+                let expression = get_expression(e)?;
+                columns.push(get_projection_column(source_columns, e, &expression, None));
+                expressions.push(expression);
             },
             SelectItem::ExprWithAlias { expr, alias } => {
-                columns.push(
-                    Column::new_with_alias(
-                        0u64,
-                        tables[0].database_name.clone(),
-                        tables[0].database_alias.clone(),
-                        tables[0].name.clone(),
-                        tables[0].alias.clone(),
-                        match expr {
-                            ASTNode::CompoundIdentifier(quotes) => quotes[1].to_string(),
-                            ASTNode::Identifier(Ident { value, .. }) => value.clone(),
-                            other => other.to_string()
-                        }, 
-                        alias.to_string(),
-                        ColumnType::Undefined,
-                        false,
-                        false,
-                        false,
-                        String::from("")
-                    )
-                )
+                let expression = get_expression(expr)?;
+                columns.push(get_projection_column(source_columns, expr, &expression, Some(alias.value.clone())));
+                expressions.push(expression);
             },
             SelectItem::Wildcard(_) => {
                 for table in &tables {
-                    let table_columns = machine_get_columns(machine, table);
-                    for column in table_columns {
+                    for column in machine_get_columns(machine, table) {
+                        expressions.push(Expression::ColName(column.qualified_name()));
                         columns.push(column);
                     }
                 }
@@ -161,34 +184,172 @@ pub fn get_columns(
             SelectItem::QualifiedWildcard(name, _options) => {
                 for table in &tables {
                     if table.name == name.to_string() || table.alias == name.to_string() { 
-                        for line in machine_get_columns(machine, &tables[0]) { 
-                            columns.push(line);
+                        for column in machine_get_columns(machine, table) { 
+                            expressions.push(Expression::ColName(column.qualified_name()));
+                            columns.push(column);
                         }
                     }
                 }
             },
         }
     }
-    return columns;
+    return Ok((columns, expressions));
+}
+
+// Expressions of the GROUP BY. As in PostgreSQL a number is the position of
+// an expression of the select list, and a name that is not a column of the
+// tables is an alias of one.
+fn get_group_by(group_by: &GroupByExpr, projection: &Vec<SelectItem>, source_columns: &Vec<Column>) -> Result<Vec<Expression>, ExecutionError> {
+    let exprs = match group_by {
+        GroupByExpr::Expressions(exprs, modifiers) if modifiers.is_empty() => exprs,
+        other => return Err(ExecutionError::ParserError(format!("Unsupported GROUP BY clause {}", other)))
+    };
+
+    let mut expressions: Vec<Expression> = Vec::new();
+    for expr in exprs {
+        let select_item = match expr {
+            ASTNode::Value(Value::Number(position, _)) => match position.parse::<usize>() {
+                Ok(position) if position >= 1 && position <= projection.len() => Some(&projection[position - 1]),
+                _ => return Err(ExecutionError::ParserError(format!(
+                    "GROUP BY position {} is not in select list",
+                    position
+                )))
+            },
+            ASTNode::Identifier(ident) if !source_columns.iter().any(|column| column_name_matches(&column.qualified_name(), &ident.value)) => {
+                projection.iter().find(|item| matches!(item, SelectItem::ExprWithAlias { alias, .. } if alias.value == ident.value))
+            },
+            _ => None
+        };
+
+        expressions.push(match select_item {
+            Some(SelectItem::UnnamedExpr(expr)) | Some(SelectItem::ExprWithAlias { expr, .. }) => get_expression(expr)?,
+            Some(_) => return Err(ExecutionError::ParserError(format!("Cannot group by {}", expr))),
+            None => get_expression(expr)?
+        });
+    }
+    return Ok(expressions);
+}
+
+// Outside of aggregates a grouped query only reads the columns it groups by,
+// the ORDER BY may also read the columns of the result.
+fn check_grouped_columns(group_by: &Vec<Expression>, expressions: &Vec<&Expression>, columns: &Vec<Column>) -> Result<(), ExecutionError> {
+    let group_names: Vec<String> = group_by.iter().flat_map(|expression| expression.column_names()).collect();
+
+    for expression in expressions {
+        for colname in expression.column_names() {
+            let grouped = group_names.iter().any(|name| column_name_matches(name, &colname) || column_name_matches(&colname, name));
+            let result_column = columns.iter().any(|column| column.alias == colname);
+            if !grouped && !result_column {
+                return Err(ExecutionError::ColumnNotGrouped(colname));
+            }
+        }
+    }
+    return Ok(());
+}
+
+// Expressions of the ORDER BY and DISTINCT ON, a number being the position
+// of a column of the result.
+fn get_result_expression(expr: &ASTNode, columns: &Vec<Column>, clause: &str) -> Result<Expression, ExecutionError> {
+    return match expr {
+        ASTNode::Value(Value::Number(position, _)) => match position.parse::<usize>() {
+            Ok(position) if position >= 1 && position <= columns.len() => {
+                Ok(Expression::ColName(columns[position - 1].qualified_name()))
+            },
+            _ => Err(ExecutionError::ParserError(format!(
+                "{} position {} is not in select list",
+                clause,
+                position
+            )))
+        },
+        expr => get_expression(expr)
+    };
+}
+
+fn get_order_by(order_by: &Vec<OrderByExpr>, columns: &Vec<Column>) -> Result<Vec<SortKey>, ExecutionError> {
+    let mut keys: Vec<SortKey> = Vec::new();
+    for order_by_expr in order_by {
+        let expression = get_result_expression(&order_by_expr.expr, columns, "ORDER BY")?;
+        keys.push(SortKey::new(expression, order_by_expr.asc == Some(false), order_by_expr.nulls_first));
+    }
+    return Ok(keys);
+}
+
+fn get_distinct_on(distinct: &Option<Distinct>, columns: &Vec<Column>) -> Result<Vec<Expression>, ExecutionError> {
+    return match distinct {
+        Some(Distinct::On(exprs)) => exprs
+            .iter()
+            .map(|expr| get_result_expression(expr, columns, "DISTINCT ON"))
+            .collect(),
+        _ => Ok(Vec::new())
+    };
+}
+
+// With SELECT DISTINCT the rows are compared once computed, so the ORDER BY
+// can only read the columns of the result.
+fn check_distinct_order_by(keys: &Vec<SortKey>, columns: &Vec<Column>) -> Result<(), ExecutionError> {
+    for key in keys {
+        for colname in key.expression.column_names() {
+            if !columns.iter().any(|column| column_name_matches(&column.qualified_name(), &colname)) {
+                return Err(ExecutionError::ParserError(format!(
+                    "For SELECT DISTINCT, ORDER BY expressions must appear in select list: {}",
+                    colname
+                )));
+            }
+        }
+    }
+    return Ok(());
 }
 
-fn get_limit(limit: Option<ASTNode>) -> Result<u64, QueryError> {
+// ORDER BY and DISTINCT ON read the columns of the result, aliases included,
+// and the columns of the tables. They run over both side by side, the columns
+// of the result first so they win on equal names, and the others are dropped
+// after. DISTINCT ON keeps the first row of each key in the order of the sort.
+fn order_result_set(
+    source_set: &ResultSet,
+    result_set: ResultSet,
+    keys: &Vec<SortKey>,
+    distinct_on: &Vec<Expression>
+) -> Result<ResultSet, ExecutionError> {
+    let column_count = result_set.column_count();
+    let columns: Vec<Column> = vec![result_set.columns.clone(), source_set.columns.clone()].concat();
+    let tuples: Vec<Tuple> = result_set.tuples
+        .into_iter()
+        .zip(source_set.tuples.iter())
+        .map(|(tuple, source_tuple)| vec![tuple, source_tuple.clone()].concat())
+        .collect();
+
+    let mut sorted_set = ResultSet::new_select(columns, tuples);
+    if keys.len() > 0 {
+        sorted_set = sorted_set.sort(keys, Config::sort_memory_size())?;
+    }
+    if distinct_on.len() > 0 {
+        sorted_set = sorted_set.distinct_on(distinct_on)?;
+    }
+
+    sorted_set.columns.truncate(column_count);
+    for tuple in sorted_set.tuples.iter_mut() {
+        tuple.truncate(column_count);
+    }
+    return Ok(sorted_set);
+}
+
+fn get_limit(limit: Option<ASTNode>) -> Result<u64, ExecutionError> {
     match limit {
         Some(ASTNode::Value(Value::Number(int, _))) => Ok(int.parse::<u64>().unwrap()),
         None => Ok(u64::MAX),
-        _ => Err(QueryError::NotImplemented(format!(
+        _ => Err(ExecutionError::ParserError(format!(
             "Invalid expression in limit clause: {:?}",
             limit
         ))),
     }
 }
 
-fn get_offset(offset: Option<Offset>) -> Result<u64, QueryError> {
+fn get_offset(offset: Option<Offset>) -> Result<u64, ExecutionError> {
     match offset {
         None => Ok(0),
         Some(offset) => match offset.value {
             ASTNode::Value(Value::Number(rows, _)) => Ok(rows.parse::<u64>().unwrap()),
-            expr => Err(QueryError::ParseError(format!(
+            expr => Err(ExecutionError::ParserError(format!(
                 "Invalid expression in offset clause: Expected constant integer, got {:?}",
                 expr,
             ))),
@@ -196,91 +357,53 @@ fn get_offset(offset: Option<Offset>) -> Result<u64, QueryError> {
     }
 }
 
-fn map_binary_operator(o: &BinaryOperator) -> Result<Expression2Type, QueryError> {
-    Ok(match o {
-        BinaryOperator::And => Expression2Type::And,
-        BinaryOperator::Or => Expression2Type::Or,
-        BinaryOperator::Eq => Expression2Type::Equal,
-        BinaryOperator::NotEq => Expression2Type::NotEqual,
-//        BinaryOperator::Plus => Func2Type::Add,
-//        BinaryOperator::Minus => Func2Type::Subtract,
-//        BinaryOperator::Multiply => Func2Type::Multiply,
-//        BinaryOperator::Divide => Func2Type::Divide,
-//        BinaryOperator::Modulo => Func2Type::Modulo,
-//        BinaryOperator::Gt => Func2Type::GT,
-//        BinaryOperator::GtEq => Func2Type::GTE,
-//        BinaryOperator::Lt => Func2Type::LT,
-//        BinaryOperator::LtEq => Func2Type::LTE,
-        other => {
-            return Err(QueryError::NotImplemented(format!(
-                "Unsupported operator {:?}",
-                other
-            )))
-        }
-    })
-}
 
-fn map_unary_operator(op: &UnaryOperator) -> Result<Expression1Type, QueryError> {
-    Ok(match op {
-        UnaryOperator::Not => Expression1Type::Not,
-        UnaryOperator::Minus => Expression1Type::Negate,
-        other => {
-            return Err(QueryError::NotImplemented(format!(
-                "Unsupported operator {:?}",
-                other
-            )))
-        }
-    })
+// Index scans are only planned for single table queries, the WHERE of a
+// join may mix the columns of every table.
+fn get_access_paths(machine: &mut Machine, tables: &Vec<Table>, condition: &Expression) -> Result<Vec<AccessPath>, ExecutionError> {
+    if tables.len() == 1 {
+        return plan_access_path(machine, &tables[0], condition).map(|access_path| vec![access_path]);
+    }
+
+    return Ok(tables.iter().map(|table| AccessPath::FullScan(table.clone())).collect());
 }
 
+fn get_expression(expr: &ASTNode) -> Result<Expression, ExecutionError> {
+    return match convert_to_native_expr(expr) {
+        Ok(expression) => Ok(expression),
+        Err(err) => Err(ExecutionError::ParserError(err.to_string()))
+    };
+}
 
-// Fn to map sqlparser-rs `Value` to LocustDB's `RawVal`.
-fn get_raw_val(constant: &Value) -> Result<Data, QueryError> {
-    match constant {
-        Value::Number(num, _) => {
-            if num.parse::<u64>().is_ok() {
-                Ok(Data::UnsignedBigint(num.parse::<u64>().unwrap()))
-            } else {
-                // Ok(RawVal::Float(ordered_float::OrderedFloat(num.parse::<f64>().unwrap())))
-                Ok(Data::Null)
-            }
-        },
-        Value::SingleQuotedString(string) => Ok(Data::Varchar(string.to_string())),
-        Value::Null => Ok(Data::Null),
-        _ => Err(QueryError::NotImplemented(format!("{:?}", constant))),
-    }
+fn get_condition(selection: &Option<ASTNode>) -> Result<Expression, ExecutionError> {
+    return match selection {
+        Some(selection_value) => get_expression(selection_value),
+        None => Ok(Expression::Empty)
+    };
 }
 
-fn convert_to_native_expr(node: &ASTNode) -> Result<Expression, QueryError> {
-    Ok(match node {
-        ASTNode::BinaryOp {
-            ref left,
-            ref op,
-            ref right,
-        } => Expression::Func2(
-            map_binary_operator(op)?,
-            Box::new(convert_to_native_expr(left)?),
-            Box::new(convert_to_native_expr(right)?)
-        ),
-        ASTNode::UnaryOp {
-            ref op,
-            expr: ref expression,
-        } => Expression::Func1(map_unary_operator(op)?, Box::new(convert_to_native_expr(expression)?)),
-        ASTNode::Value(ref literal) => Expression::Const(get_raw_val(literal)?),
-        ASTNode::Identifier(ref identifier) => {
-            Expression::ColName(strip_quotes(identifier.value.as_ref()))
-        }
-        _ => {
-            println!("Parsing for this ASTNode not implemented: {:?}", node);
-            return Err(QueryError::NotImplemented(format!("Parsing for this ASTNode not implemented: {:?}", node)))
+pub fn query_plan(machine: &mut Machine, query: Box<Select>) -> Result<Vec<AccessPath>, ExecutionError> { 
+    if let Some(db_name) = machine.actual_database.clone() {
+        let components = get_query_components(query).unwrap();
+        let tables: Vec<Table> = get_table_name(db_name.clone(), components.relations);
+
+        for table in &tables {
+            if check_table_exists(machine, &table) == false {
+                return Err(ExecutionError::TableNotExists(table.name.to_string()));
+            }
         }
-    })
+
+        let condition = get_condition(&components.selection)?;
+        return get_access_paths(machine, &tables, &condition);
+    } else {
+        return Err(ExecutionError::DatabaseNotSetted);
+    }
 }
 
 pub fn query(machine: &mut Machine, query: Box<Select>) -> Result<ResultSet, ExecutionError> { 
     if let Some(db_name) = machine.actual_database.clone() {
-        let (projection, relations, selection, limit, offset) = get_query_components(query).unwrap();
-        let tables: Vec<Table> = get_table_name(db_name.clone(), relations);
+        let components = get_query_components(query).unwrap();
+        let tables: Vec<Table> = get_table_name(db_name.clone(), components.relations);
 
         for table in &tables {
             if check_table_exists(machine, &table) == false {
@@ -288,25 +411,70 @@ pub fn query(machine: &mut Machine, query: Box<Select>) -> Result<ResultSet, Exe
             }
         }
 
-        let columns = get_columns(machine, projection, tables.clone());
+        // FROM
+        let condition = get_condition(&components.selection)?;
+        if condition.has_aggregate() {
+            return Err(ExecutionError::ParserError(String::from("Aggregate functions are not allowed in WHERE")));
+        }
+        let access_paths = get_access_paths(machine, &tables, &condition)?;
+        let mut source_set = product_cartesian_with_access_paths(machine, &access_paths)?;
+
+        // WHERE
+        if matches!(condition, Expression::Empty) == false {
+            source_set = source_set.selection(condition)?;
+        }
 
-        let mut result_set = product_cartesian(machine, tables);
-        result_set = result_set.projection(columns).unwrap();
+        // GROUP BY and HAVING. The select list and the ORDER BY are read
+        // first, the grouping computes the aggregates all of them hold.
+        let group_by = get_group_by(&components.group_by, &components.projection, &source_set.columns)?;
+        let having = get_condition(&components.having)?;
+        let (columns, expressions) = get_columns(machine, components.projection, tables.clone(), &source_set.columns)?;
+        let keys = get_order_by(&components.order_by, &columns)?;
+        let distinct_on = get_distinct_on(&components.distinct, &columns)?;
 
-        if let Ok(limit_size) = get_limit(limit) {
-          result_set = result_set.limit(limit_size as usize);
+        let mut aggregated: Vec<&Expression> = vec![&having];
+        aggregated.extend(expressions.iter());
+        let mut aggregates: Vec<&Aggregate> = Vec::new();
+        for aggregate in aggregated.iter().flat_map(|expression| expression.aggregates()) {
+            if !aggregates.iter().any(|other| other.name == aggregate.name) {
+                aggregates.push(aggregate);
+            }
+        }
+        for aggregate in keys.iter().flat_map(|key| key.expression.aggregates()) {
+            if !aggregates.iter().any(|other| other.name == aggregate.name) {
+                aggregates.push(aggregate);
+            }
         }
 
-        if let Ok(offset_size) = get_offset(offset) {
-          result_set = result_set.offset(offset_size as usize);
+        if group_by.len() > 0 || aggregates.len() > 0 || components.having.is_some() {
+            check_grouped_columns(&group_by, &aggregated, &Vec::new())?;
+            check_grouped_columns(&group_by, &keys.iter().map(|key| &key.expression).collect(), &columns)?;
+            check_grouped_columns(&group_by, &distinct_on.iter().collect(), &columns)?;
+
+            source_set = source_set.aggregation(&group_by, &aggregates)?;
+            if matches!(having, Expression::Empty) == false {
+                source_set = source_set.selection(having)?;
+            }
         }
 
-        if let Some(selection_value) = &selection {
-            if let Ok(condition) = convert_to_native_expr(selection_value) {
-              result_set = result_set.selection(condition).unwrap();
+        // SELECT
+        let mut result_set = source_set.extended_projection(columns, &expressions)?;
+
+        // DISTINCT and ORDER BY
+        if matches!(components.distinct, Some(Distinct::Distinct)) {
+            check_distinct_order_by(&keys, &result_set.columns)?;
+            result_set = result_set.distinct();
+            if keys.len() > 0 {
+                result_set = result_set.sort(&keys, Config::sort_memory_size())?;
             }
+        } else if keys.len() > 0 || distinct_on.len() > 0 {
+            result_set = order_result_set(&source_set, result_set, &keys, &distinct_on)?;
         }
 
+        // OFFSET and LIMIT
+        result_set = result_set.offset(get_offset(components.offset)? as usize);
+        result_set = result_set.limit(get_limit(components.limit)? as usize);
+
         return Ok(result_set)
     } else {
         return Err(ExecutionError::DatabaseNotSetted);
@@ -8,6 +8,6 @@ use crate::utils::ExecutionError;
 use crate::config::SysDb;
 
 pub fn show_databases(machine: &mut Machine) -> Result<ResultSet, ExecutionError> { 
-    return Ok(product_cartesian(machine, vec![SysDb::table_databases()]));
+    return product_cartesian(machine, vec![SysDb::table_databases()]);
 }
 
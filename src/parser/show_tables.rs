@@ -12,7 +12,7 @@ use crate::config::SysDb;
 
 pub fn show_tables(machine: &mut Machine) -> Result<ResultSet, ExecutionError> { 
     if let Some(db_name) = machine.actual_database.clone() {
-        let result_set = product_cartesian(machine, vec![SysDb::table_tables()]);
+        let result_set = product_cartesian(machine, vec![SysDb::table_tables()])?;
 
         let condition = Expression::Func2(
             Expression2Type::Equal,
@@ -0,0 +1,69 @@
+use sqlparser::ast::ObjectName;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::machine::Machine;
+use crate::machine::Table;
+use crate::machine::vacuum_table;
+use crate::machine::vacuum_database;
+
+use crate::storage::ResultSet;
+use crate::storage::ResultSetType;
+
+use crate::utils::ExecutionError;
+
+/*
+ * The SQL parser has no VACUUM statement, so parse_command handles it:
+ *
+ *   VACUUM                    every table of the current database
+ *   VACUUM DATABASE database  every table of the database
+ *   VACUUM [database.]table   a single table
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VacuumStatement {
+    pub database: bool,
+    pub name: Option<ObjectName>
+}
+
+// Called right after the VACUUM keyword was consumed.
+pub fn parse_vacuum(parser: &mut Parser) -> Result<VacuumStatement, ParserError> {
+    let database = parser.parse_keyword(Keyword::DATABASE);
+
+    let name = match parser.peek_token().token {
+        Token::Word(_) => Some(parser.parse_object_name(false)?),
+        _ if database => return parser.expected("database name", parser.peek_token()),
+        _ => None
+    };
+
+    return Ok(VacuumStatement { database, name });
+}
+
+pub fn vacuum(machine: &mut Machine, statement: VacuumStatement) -> Result<ResultSet, ExecutionError> { 
+    let reclaimed = match (statement.database, statement.name) {
+        (true, Some(name)) => vacuum_database(machine, &name.to_string()),
+        (_, Some(name)) if name.0.len() > 1 => {
+            let table = Table::new(name.0[0].value.clone(), name.0[1].value.clone());
+            vacuum_table(machine, &table)
+        },
+        (_, name) => {
+            let Some(db_name) = machine.actual_database.clone() else {
+                return Err(ExecutionError::DatabaseNotSetted);
+            };
+
+            match name {
+                Some(name) => vacuum_table(machine, &Table::new(db_name, name.to_string())),
+                None => vacuum_database(machine, &db_name)
+            }
+        }
+    };
+
+    return match reclaimed {
+        Ok(bytes) => Ok(
+            ResultSet::new_command(ResultSetType::Change, format!("VACUUM {} bytes reclaimed", bytes))
+        ),
+        Err(err) => Err(err)
+    };
+}
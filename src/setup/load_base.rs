@@ -12,6 +12,8 @@ use crate::machine::get_columns_table_definition;
 use crate::machine::get_indexes_table_definition;
 use crate::machine::get_indexes_table_definition_without_id;
 use crate::machine::get_sequences_table_definition;
+use crate::machine::get_foreign_keys_table_definition;
+use crate::machine::get_check_constraints_table_definition;
 
 use crate::storage::Tuple;
 use crate::storage::get_tuple_sequence;
@@ -32,9 +34,11 @@ pub fn setup_base(machine: &mut Machine) {
     let mut tuples: Vec<Tuple> = Vec::new();
     tuples.push(get_tuple_sequence(1u64, &SysDb::dbname(), &SysDb::tblname_databases(), &String::from("id"), &String::from("rusticodb_databases_id"), 1u64));
     tuples.push(get_tuple_sequence(2u64, &SysDb::dbname(), &SysDb::tblname_tables()   , &String::from("id"), &String::from("rusticodb_tables_id"), 1u64));
-    tuples.push(get_tuple_sequence(3u64, &SysDb::dbname(), &SysDb::tblname_columns()  , &String::from("id"), &String::from("rusticodb_columns_id"), 29u64));
-    tuples.push(get_tuple_sequence(4u64, &SysDb::dbname(), &SysDb::tblname_sequences(), &String::from("id"), &String::from("rusticodb_sequences_id"), 6u64));
+    tuples.push(get_tuple_sequence(3u64, &SysDb::dbname(), &SysDb::tblname_columns()  , &String::from("id"), &String::from("rusticodb_columns_id"), 45u64));
+    tuples.push(get_tuple_sequence(4u64, &SysDb::dbname(), &SysDb::tblname_sequences(), &String::from("id"), &String::from("rusticodb_sequences_id"), 8u64));
     tuples.push(get_tuple_sequence(5u64, &SysDb::dbname(), &SysDb::tblname_indexes()  , &String::from("id"), &String::from("rusticodb_indexes_id"), 1u64));
+    tuples.push(get_tuple_sequence(6u64, &SysDb::dbname(), &SysDb::tblname_foreign_keys(), &String::from("id"), &String::from("rusticodb_foreign_keys_id"), 1u64));
+    tuples.push(get_tuple_sequence(7u64, &SysDb::dbname(), &SysDb::tblname_check_constraints(), &String::from("id"), &String::from("rusticodb_check_constraints_id"), 1u64));
 
     create_file(machine, &SysDb::table_sequences());
     insert_tuples(machine, &SysDb::table_sequences(), &mut tuples);
@@ -49,6 +53,8 @@ pub fn setup_base(machine: &mut Machine) {
     columns.append(&mut get_columns_table_definition());
     columns.append(&mut get_indexes_table_definition());
     columns.append(&mut get_sequences_table_definition());
+    columns.append(&mut get_foreign_keys_table_definition());
+    columns.append(&mut get_check_constraints_table_definition());
 
     for column in columns.iter() {
         tuples.push(
@@ -92,6 +98,8 @@ pub fn setup_base(machine: &mut Machine) {
     tuples.push(get_tuple_table(&SysDb::dbname(), &SysDb::tblname_columns()));
     tuples.push(get_tuple_table(&SysDb::dbname(), &SysDb::tblname_sequences()));
     tuples.push(get_tuple_table(&SysDb::dbname(), &SysDb::tblname_indexes()));
+    tuples.push(get_tuple_table(&SysDb::dbname(), &SysDb::tblname_foreign_keys()));
+    tuples.push(get_tuple_table(&SysDb::dbname(), &SysDb::tblname_check_constraints()));
 
     create_file(machine, &SysDb::table_tables());
     let _ = insert_row(
@@ -106,11 +114,13 @@ pub fn setup_base(machine: &mut Machine) {
     Logger::info("setup indexes table");
 
     let mut tuples: Vec<Tuple> = Vec::new();
-    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_databases(), &String::from("id"), &String::from("rusticodb_databases_id"), &String::from("btree")));
-    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_tables()   , &String::from("id"), &String::from("rusticodb_tables_id"), &String::from("btree")));
-    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_columns()  , &String::from("id"), &String::from("rusticodb_columns_id"), &String::from("btree")));
-    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_sequences(), &String::from("id"), &String::from("rusticodb_sequences_id"), &String::from("btree")));
-    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_indexes()  , &String::from("id"), &String::from("rusticodb_indexes_id"), &String::from("btree")));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_databases(), &String::from("id"), &String::from("rusticodb_databases_id"), &String::from("btree"), true, true));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_tables()   , &String::from("id"), &String::from("rusticodb_tables_id"), &String::from("btree"), true, true));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_columns()  , &String::from("id"), &String::from("rusticodb_columns_id"), &String::from("btree"), true, true));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_sequences(), &String::from("id"), &String::from("rusticodb_sequences_id"), &String::from("btree"), true, true));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_indexes()  , &String::from("id"), &String::from("rusticodb_indexes_id"), &String::from("btree"), true, true));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_foreign_keys(), &String::from("id"), &String::from("rusticodb_foreign_keys_id"), &String::from("btree"), true, true));
+    tuples.push(get_tuple_index(&SysDb::dbname(), &SysDb::tblname_check_constraints(), &String::from("id"), &String::from("rusticodb_check_constraints_id"), &String::from("btree"), true, true));
 
     create_file(machine, &SysDb::table_indexes());
     let table_columns = &get_columns(machine, &SysDb::table_indexes());
@@ -123,6 +133,14 @@ pub fn setup_base(machine: &mut Machine) {
         false
     );
 
+    Logger::info("setup foreign keys table");
+
+    create_file(machine, &SysDb::table_foreign_keys());
+
+    Logger::info("setup check constraints table");
+
+    create_file(machine, &SysDb::table_check_constraints());
+
     Logger::info("############################################################");
     Logger::info("#######################END LOAD BASE########################");
     Logger::info("############################################################\n\n\n");
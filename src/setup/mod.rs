@@ -1,9 +1,11 @@
 mod load_base;
+mod upgrade_base;
 
 use crate::config::Config;
 use crate::config::SysDb;
 
 use crate::setup::load_base::setup_base;
+use crate::setup::upgrade_base::upgrade_base;
 
 use crate::machine::Machine;
 use crate::machine::path_exists;
@@ -11,12 +13,18 @@ use crate::machine::database_exists;
 
 use crate::storage::create_folder_if_not_exists;
 use crate::storage::format_database_name;
+use crate::storage::wal_recover;
 
 use crate::utils::Logger;
 
 pub fn setup_system(machine: &mut Machine) {
     create_folder_if_not_exists(&Config::data_folder());
 
+    // Starting over a log that could not be replayed would lose or tear the
+    // writes it holds.
+    if let Err(err) = wal_recover() {
+        panic!("failed to recover the write-ahead log: {:?}", err);
+    }
     load_context(machine);
 }
 
@@ -28,6 +36,8 @@ pub fn load_context(machine: &mut Machine) {
 
     if path_exists(machine,&SysDb::table_sequences()) == false {
         setup_base(machine);
+    } else {
+        upgrade_base(machine);
     }
 
 }
@@ -0,0 +1,125 @@
+use crate::machine::Machine;
+use crate::machine::Table;
+use crate::machine::Column;
+use crate::machine::insert_row;
+use crate::machine::get_columns;
+use crate::machine::path_exists;
+use crate::machine::create_file;
+use crate::machine::get_databases_table_definition;
+use crate::machine::get_tables_table_definition;
+use crate::machine::get_tables_table_definition_without_id;
+use crate::machine::get_columns_table_definition;
+use crate::machine::get_columns_table_definition_without_id;
+use crate::machine::get_indexes_table_definition;
+use crate::machine::get_indexes_table_definition_without_id;
+use crate::machine::get_sequences_table_definition;
+use crate::machine::get_sequences_table_definition_without_id;
+use crate::machine::get_foreign_keys_table_definition;
+use crate::machine::get_check_constraints_table_definition;
+
+use crate::storage::Tuple;
+use crate::storage::get_tuple_column_without_id;
+use crate::storage::get_tuple_sequence_without_id;
+use crate::storage::get_tuple_table;
+use crate::storage::get_tuple_index;
+
+use crate::config::SysDb;
+
+use crate::utils::Logger;
+
+fn upgrade_columns(machine: &mut Machine) {
+    let mut columns: Vec<Column> = Vec::new();
+
+    columns.append(&mut get_databases_table_definition());
+    columns.append(&mut get_tables_table_definition());
+    columns.append(&mut get_columns_table_definition());
+    columns.append(&mut get_indexes_table_definition());
+    columns.append(&mut get_sequences_table_definition());
+    columns.append(&mut get_foreign_keys_table_definition());
+    columns.append(&mut get_check_constraints_table_definition());
+
+    let mut tuples: Vec<Tuple> = Vec::new();
+    for column in columns.iter() {
+        let table = Table::new(column.database_name.clone(), column.table_name.clone());
+        if get_columns(machine, &table).iter().any(|other| other.name == column.name) {
+            continue;
+        }
+
+        Logger::info(format!("upgrade column {}.{}", column.table_name, column.name).leak());
+        tuples.push(
+            get_tuple_column_without_id(
+                &column.database_name,
+                &column.table_name,
+                &column.name,
+                &column.clone().get_type_column(),
+                column.not_null,
+                column.unique,
+                column.primary_key,
+                &column.default
+            )
+        );
+    }
+
+    if tuples.len() == 0 {
+        return;
+    }
+
+    let table_columns = &get_columns(machine, &SysDb::table_columns());
+    let _ = insert_row(
+        machine,
+        &SysDb::table_columns(),
+        table_columns,
+        &get_columns_table_definition_without_id(),
+        &mut tuples,
+        false
+    );
+}
+
+fn upgrade_table(machine: &mut Machine, table: &Table) {
+    if path_exists(machine, table) {
+        return;
+    }
+
+    Logger::info(format!("upgrade table {}", table.name).leak());
+    create_file(machine, table);
+
+    let _ = insert_row(
+        machine,
+        &SysDb::table_tables(),
+        &get_tables_table_definition(),
+        &get_tables_table_definition_without_id(),
+        &mut vec![get_tuple_table(&table.database_name, &table.name)],
+        true
+    );
+
+    let sequence_name = format!("{}_{}_id", table.database_name, table.name);
+
+    let table_columns = &get_columns(machine, &SysDb::table_sequences());
+    let _ = insert_row(
+        machine,
+        &SysDb::table_sequences(),
+        table_columns,
+        &get_sequences_table_definition_without_id(),
+        &mut vec![get_tuple_sequence_without_id(&table.database_name, &table.name, &String::from("id"), &sequence_name, 1u64)],
+        false
+    );
+
+    let table_columns = &get_columns(machine, &SysDb::table_indexes());
+    let _ = insert_row(
+        machine,
+        &SysDb::table_indexes(),
+        table_columns,
+        &get_indexes_table_definition_without_id(),
+        &mut vec![get_tuple_index(&table.database_name, &table.name, &String::from("id"), &sequence_name, &String::from("btree"), true, true)],
+        false
+    );
+}
+
+// Data folders set up by an older version lack the catalog tables and
+// columns added since. The columns go first so the rows written after them
+// are read with every column.
+pub fn upgrade_base(machine: &mut Machine) {
+    upgrade_columns(machine);
+    upgrade_table(machine, &SysDb::table_foreign_keys());
+    upgrade_table(machine, &SysDb::table_check_constraints());
+}
@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::storage::Data;
+use crate::storage::Expression;
+use crate::storage::JsonValue;
+use crate::storage::json_from_data;
+use crate::storage::index_key_hash;
+use crate::storage::index_value_compare;
+
+use crate::utils::ExecutionError;
+
+// Aggregates are computed over the tuples of each group, one state for each
+// aggregate and group. NULLs are skipped by every aggregate but COUNT(*)
+// and ARRAY_AGG, an aggregate that saw no value gives NULL, or 0 for COUNT.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateType {
+    CountAll,
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    StringAgg,
+    ArrayAgg
+}
+
+// An aggregate call, named after its SQL text. The set the aggregate is
+// computed on has a column of that name with its result.
+#[derive(Debug)]
+pub struct Aggregate {
+    pub aggregate_type: AggregateType,
+    pub distinct: bool,
+    pub arguments: Vec<Expression>,
+    pub name: String
+}
+
+#[derive(Debug)]
+pub struct AggregateState {
+    pub count: u64,
+    pub value: Data,
+    pub items: Vec<JsonValue>,
+    pub seen: HashMap<u64, Vec<Data>>
+}
+
+pub fn aggregate_state_new() -> AggregateState {
+    AggregateState {
+        count: 0,
+        value: Data::Null,
+        items: Vec::new(),
+        seen: HashMap::new()
+    }
+}
+
+// Arguments each aggregate takes, COUNT(*) has none.
+pub fn aggregate_argument_count(aggregate_type: &AggregateType) -> usize {
+    return match aggregate_type {
+        AggregateType::CountAll => 0,
+        AggregateType::StringAgg => 2,
+        _ => 1
+    };
+}
+
+// Integers add as wide integers, so small columns do not overflow on SUM.
+fn aggregate_add(a: Data, b: Data) -> Result<Data, ExecutionError> {
+    if let (Some(a), Some(b)) = (a.to_integer(), b.to_integer()) {
+        return match a.checked_add(b).and_then(Data::from_integer) {
+            Some(value) => Ok(value),
+            None => Err(ExecutionError::NumericOverflow(format!("{} + {}", a, b)))
+        };
+    }
+    return a.checked_add(b);
+}
+
+// Whether the value was already aggregated, for DISTINCT. Values that compare
+// equal hash the same.
+fn aggregate_seen(state: &mut AggregateState, value: &Data) -> bool {
+    let values = state.seen.entry(index_key_hash(&vec![value.clone()])).or_default();
+    if values.iter().any(|seen| index_value_compare(seen, value) == Ordering::Equal) {
+        return true;
+    }
+    values.push(value.clone());
+    return false;
+}
+
+// Adds the values of the arguments of one tuple to the state.
+pub fn aggregate_update(aggregate: &Aggregate, state: &mut AggregateState, arguments: Vec<Data>) -> Result<(), ExecutionError> {
+    if aggregate.aggregate_type == AggregateType::CountAll {
+        state.count += 1;
+        return Ok(());
+    }
+
+    let value = arguments[0].clone();
+    if matches!(value, Data::Null) && aggregate.aggregate_type != AggregateType::ArrayAgg {
+        return Ok(());
+    }
+    if aggregate.distinct && aggregate_seen(state, &value) {
+        return Ok(());
+    }
+
+    let not_match = || ExecutionError::AggregateTypeNotMatch(aggregate.name.clone(), value.to_string());
+
+    match aggregate.aggregate_type {
+        AggregateType::Sum if value.to_float().is_none() && !matches!(value, Data::Interval(_, _, _)) => {
+            return Err(not_match());
+        },
+        AggregateType::Avg if value.to_float().is_none() => return Err(not_match()),
+        AggregateType::Sum | AggregateType::Avg => {
+            state.value = match state.value {
+                Data::Null => value,
+                _ => aggregate_add(state.value.clone(), value)?
+            };
+        },
+        AggregateType::Min => {
+            if matches!(state.value, Data::Null) || value < state.value {
+                state.value = value;
+            }
+        },
+        AggregateType::Max => {
+            if matches!(state.value, Data::Null) || value > state.value {
+                state.value = value;
+            }
+        },
+        AggregateType::StringAgg => {
+            state.value = match &state.value {
+                Data::Null => Data::Text(value.to_string()),
+                text => {
+                    let delimiter = match &arguments[1] {
+                        Data::Null => String::from(""),
+                        delimiter => delimiter.to_string()
+                    };
+                    Data::Text(format!("{}{}{}", text, delimiter, value))
+                }
+            };
+        },
+        AggregateType::ArrayAgg => state.items.push(json_from_data(&value)),
+        AggregateType::Count | AggregateType::CountAll => {}
+    }
+
+    state.count += 1;
+    return Ok(());
+}
+
+// Result of the aggregate over the values added to the state. AVG divides as
+// decimals, or as doubles over floats. ARRAY_AGG gives a JSONB array.
+pub fn aggregate_result(aggregate: &Aggregate, state: &AggregateState) -> Result<Data, ExecutionError> {
+    return match aggregate.aggregate_type {
+        AggregateType::CountAll | AggregateType::Count => Ok(Data::UnsignedBigint(state.count)),
+        AggregateType::Avg if state.count == 0 => Ok(Data::Null),
+        AggregateType::Avg if state.value.is_float() => state.value.clone().checked_div(Data::Float64((state.count as f64).into())),
+        AggregateType::Avg => Data::from_decimal(state.value.to_decimal()).checked_div(Data::UnsignedBigint(state.count)),
+        AggregateType::ArrayAgg if state.count == 0 => Ok(Data::Null),
+        AggregateType::ArrayAgg => Ok(Data::Jsonb(JsonValue::Array(state.items.clone()))),
+        _ => Ok(state.value.clone())
+    };
+}
@@ -0,0 +1,410 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use bincode::serialize;
+use bincode::deserialize;
+use serde::Serialize;
+use serde::Deserialize;
+
+use crate::storage::BLOCK_SIZE;
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::tuple_deserialize;
+use crate::storage::index_key_compare;
+use crate::storage::index_key_size;
+use crate::storage::index_key_serialize;
+use crate::storage::Pager;
+use crate::storage::pager_read_block;
+use crate::storage::pager_write_block;
+use crate::storage::pager_discard;
+
+use crate::utils::v_u8_to_u16;
+use crate::utils::v_u8_to_u64;
+use crate::utils::v_u8_to_vec_u8;
+use crate::utils::ExecutionError;
+
+/*
+ * On disk B+tree used by the indexes, one file per index. Block 0 keeps the
+ * header, every other block is a node:
+ *   [kind u8][entry count u16][next u64]
+ *   entries: [key size u16][key][page u64][slot u16]
+ *   internal nodes only: (entry count + 1) children [block u64]
+ *
+ * Entries are ordered by (key, address), so repeated keys are still distinct
+ * entries and a single row can be removed from the tree. Leaves are chained
+ * left to right through `next` (0 closes the chain). Nodes are split when
+ * they don't fit a block anymore and are never merged, a leaf emptied by
+ * deletes stays on the chain until the index is rebuilt.
+ *
+ * Blocks are read and written through the pager, so the nodes a split
+ * touches are committed together with the rows of the statement.
+ */
+
+const BTREE_LEAF: u8 = 1;
+const BTREE_INTERNAL: u8 = 2;
+
+const NODE_HEADER_SIZE: usize = 11;
+const ENTRY_FIXED_SIZE: usize = 12;
+const CHILD_SIZE: usize = 8;
+
+// Keeps at least three entries per node, so a split always leaves both
+// halves inside a block.
+pub const INDEX_MAX_KEY_SIZE: usize = 1024;
+
+pub type BTreeEntry = (Tuple, TupleAddress);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct BTreeHeader {
+    pub root: u64,
+    pub page_count: u64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BTreeNode {
+    pub leaf: bool,
+    pub next: u64,
+    pub entries: Vec<BTreeEntry>,
+    pub children: Vec<u64>
+}
+
+impl BTreeNode {
+    pub fn new_leaf() -> Self {
+        BTreeNode { leaf: true, next: 0, entries: Vec::new(), children: Vec::new() }
+    }
+
+    pub fn new_internal(entries: Vec<BTreeEntry>, children: Vec<u64>) -> Self {
+        BTreeNode { leaf: false, next: 0, entries, children }
+    }
+}
+
+fn btree_compare(a: &BTreeEntry, b: &BTreeEntry) -> Ordering {
+    return index_key_compare(&a.0, &b.0).then(a.1.cmp(&b.1));
+}
+
+fn btree_entry_size(entry: &BTreeEntry) -> usize {
+    return ENTRY_FIXED_SIZE + index_key_size(&entry.0);
+}
+
+pub fn btree_node_size(node: &BTreeNode) -> usize {
+    let mut size = NODE_HEADER_SIZE + node.children.len() * CHILD_SIZE;
+
+    for entry in node.entries.iter() {
+        size += btree_entry_size(entry);
+    }
+
+    return size;
+}
+
+pub fn btree_node_serialize(node: &BTreeNode) -> [u8; BLOCK_SIZE] {
+    let mut raw_buffer: Vec<u8> = Vec::new();
+
+    raw_buffer.push(if node.leaf { BTREE_LEAF } else { BTREE_INTERNAL });
+    raw_buffer.append(&mut (node.entries.len() as u16).to_be_bytes().to_vec());
+    raw_buffer.append(&mut node.next.to_be_bytes().to_vec());
+
+    for (key, address) in node.entries.iter() {
+        let mut raw_key = index_key_serialize(key);
+        raw_buffer.append(&mut (raw_key.len() as u16).to_be_bytes().to_vec());
+        raw_buffer.append(&mut raw_key);
+        raw_buffer.append(&mut address.page.to_be_bytes().to_vec());
+        raw_buffer.append(&mut address.slot.to_be_bytes().to_vec());
+    }
+
+    for child in node.children.iter() {
+        raw_buffer.append(&mut child.to_be_bytes().to_vec());
+    }
+
+    let mut block = [0u8; BLOCK_SIZE];
+    block[..raw_buffer.len()].copy_from_slice(&raw_buffer);
+    return block;
+}
+
+pub fn btree_node_deserialize(raw_node: &[u8; BLOCK_SIZE]) -> BTreeNode {
+    let leaf = raw_node[0] == BTREE_LEAF;
+    let entry_count = v_u8_to_u16(raw_node, 1) as usize;
+    let next = v_u8_to_u64(raw_node, 3);
+
+    let mut entries: Vec<BTreeEntry> = Vec::new();
+    let mut position = NODE_HEADER_SIZE;
+
+    for _ in 0..entry_count {
+        let key_size = v_u8_to_u16(raw_node, position) as usize;
+        let raw_key = v_u8_to_vec_u8(raw_node, position + 2, key_size);
+        position += 2 + key_size;
+
+        let key = tuple_deserialize(&raw_key);
+        let address = TupleAddress::new(
+            v_u8_to_u64(raw_node, position),
+            v_u8_to_u16(raw_node, position + 8)
+        );
+        position += 10;
+
+        entries.push((key, address));
+    }
+
+    let mut children: Vec<u64> = Vec::new();
+
+    if leaf == false {
+        for _ in 0..(entry_count + 1) {
+            children.push(v_u8_to_u64(raw_node, position));
+            position += CHILD_SIZE;
+        }
+    }
+
+    return BTreeNode { leaf, next, entries, children };
+}
+
+fn btree_read_header(pager: &Pager, file_name: &String) -> Result<BTreeHeader, ExecutionError> {
+    let header: BTreeHeader = match deserialize(&pager_read_block(pager, file_name, 0)?) {
+        Ok(header) => header,
+        Err(_) => return Err(ExecutionError::IndexCorrupted(file_name.clone()))
+    };
+
+    if header.root == 0 || header.root > header.page_count {
+        return Err(ExecutionError::IndexCorrupted(file_name.clone()));
+    }
+    return Ok(header);
+}
+
+fn btree_write_header(pager: &mut Pager, file_name: &String, header: &BTreeHeader) {
+    let mut block = [0u8; BLOCK_SIZE];
+    let serialized = serialize(header).unwrap();
+
+    block[..serialized.len()].copy_from_slice(&serialized);
+    pager_write_block(pager, file_name, 0, block);
+}
+
+fn btree_read_node(pager: &Pager, file_name: &String, node_idx: u64) -> Result<BTreeNode, ExecutionError> {
+    return Ok(btree_node_deserialize(&pager_read_block(pager, file_name, node_idx)?));
+}
+
+fn btree_write_node(pager: &mut Pager, file_name: &String, node_idx: u64, node: &BTreeNode) {
+    pager_write_block(pager, file_name, node_idx, btree_node_serialize(node));
+}
+
+// Starts a new tree with an empty leaf as root, replacing the previous one.
+// Blocks of the old tree past the new ones are left unreferenced.
+pub fn btree_create(pager: &mut Pager, file_name: &String) {
+    pager_discard(pager, file_name);
+
+    btree_write_header(pager, file_name, &BTreeHeader { root: 1, page_count: 1 });
+    btree_write_node(pager, file_name, 1, &BTreeNode::new_leaf());
+}
+
+// Splits an overfull node around the middle of its bytes. Returns the
+// separator that goes up to the parent and the new right node.
+fn btree_split_node(node: &mut BTreeNode) -> (BTreeEntry, BTreeNode) {
+    let half = btree_node_size(node) / 2;
+    let mut size = NODE_HEADER_SIZE;
+    let mut middle = 0;
+
+    while middle < node.entries.len() - 2 && size < half {
+        size += btree_entry_size(&node.entries[middle]);
+        middle += 1;
+    }
+    if middle == 0 {
+        middle = 1;
+    }
+
+    if node.leaf {
+        let mut right = BTreeNode::new_leaf();
+        right.entries = node.entries.split_off(middle);
+        right.next = node.next;
+        return (right.entries[0].clone(), right);
+    }
+
+    let right_entries = node.entries.split_off(middle + 1);
+    let right_children = node.children.split_off(middle + 1);
+    let separator = node.entries.pop().unwrap();
+
+    return (separator, BTreeNode::new_internal(right_entries, right_children));
+}
+
+fn btree_insert_into(
+    pager: &mut Pager,
+    file_name: &String,
+    header: &mut BTreeHeader,
+    node_idx: u64,
+    entry: BTreeEntry
+) -> Result<Option<(BTreeEntry, u64)>, ExecutionError> {
+    let mut node = btree_read_node(pager, file_name, node_idx)?;
+
+    if node.leaf {
+        let position = node.entries.partition_point(|item| btree_compare(item, &entry) == Ordering::Less);
+        node.entries.insert(position, entry);
+    } else {
+        let position = node.entries.partition_point(|item| btree_compare(item, &entry) != Ordering::Greater);
+        match btree_insert_into(pager, file_name, header, node.children[position], entry)? {
+            Some((separator, child_idx)) => {
+                node.entries.insert(position, separator);
+                node.children.insert(position + 1, child_idx);
+            },
+            None => return Ok(None)
+        }
+    }
+
+    if btree_node_size(&node) <= BLOCK_SIZE {
+        btree_write_node(pager, file_name, node_idx, &node);
+        return Ok(None);
+    }
+
+    let (separator, right) = btree_split_node(&mut node);
+    header.page_count += 1;
+    let right_idx = header.page_count;
+
+    if node.leaf {
+        node.next = right_idx;
+    }
+
+    btree_write_node(pager, file_name, right_idx, &right);
+    btree_write_node(pager, file_name, node_idx, &node);
+
+    return Ok(Some((separator, right_idx)));
+}
+
+pub fn btree_insert(pager: &mut Pager, file_name: &String, key: &Tuple, address: &TupleAddress) -> Result<(), ExecutionError> {
+    let mut header = btree_read_header(pager, file_name)?;
+    let root = header.root;
+
+    if let Some((separator, right_idx)) = btree_insert_into(pager, file_name, &mut header, root, (key.clone(), *address))? {
+        header.page_count += 1;
+        header.root = header.page_count;
+
+        let new_root = BTreeNode::new_internal(vec![separator], vec![root, right_idx]);
+        btree_write_node(pager, file_name, header.root, &new_root);
+    }
+
+    btree_write_header(pager, file_name, &header);
+    return Ok(());
+}
+
+// Removes the entry of one row. Returns false when it is not on the tree.
+pub fn btree_delete(pager: &mut Pager, file_name: &String, key: &Tuple, address: &TupleAddress) -> Result<bool, ExecutionError> {
+    let header = btree_read_header(pager, file_name)?;
+    let entry: BTreeEntry = (key.clone(), *address);
+    let mut node_idx = header.root;
+
+    loop {
+        let mut node = btree_read_node(pager, file_name, node_idx)?;
+
+        if node.leaf == false {
+            let position = node.entries.partition_point(|item| btree_compare(item, &entry) != Ordering::Greater);
+            node_idx = node.children[position];
+            continue;
+        }
+
+        match node.entries.binary_search_by(|item| btree_compare(item, &entry)) {
+            Ok(position) => {
+                node.entries.remove(position);
+                btree_write_node(pager, file_name, node_idx, &node);
+                return Ok(true);
+            },
+            Err(_) => return Ok(false)
+        }
+    }
+}
+
+// Leaf where the entries with the key start, or the leftmost leaf without key.
+fn btree_find_leaf(pager: &Pager, file_name: &String, key: Option<&Tuple>) -> Result<u64, ExecutionError> {
+    let header = btree_read_header(pager, file_name)?;
+    let mut node_idx = header.root;
+
+    loop {
+        let node = btree_read_node(pager, file_name, node_idx)?;
+
+        if node.leaf {
+            return Ok(node_idx);
+        }
+
+        let position = match key {
+            Some(key) => node.entries.partition_point(|(item, _)| index_key_compare(item, key) == Ordering::Less),
+            None => 0
+        };
+        node_idx = node.children[position];
+    }
+}
+
+fn btree_above_lower(key: &Tuple, lower: &Bound<&Tuple>) -> bool {
+    return match lower {
+        Bound::Included(bound) => index_key_compare(key, bound) != Ordering::Less,
+        Bound::Excluded(bound) => index_key_compare(key, bound) == Ordering::Greater,
+        Bound::Unbounded => true
+    };
+}
+
+fn btree_below_upper(key: &Tuple, upper: &Bound<&Tuple>) -> bool {
+    return match upper {
+        Bound::Included(bound) => index_key_compare(key, bound) != Ordering::Greater,
+        Bound::Excluded(bound) => index_key_compare(key, bound) == Ordering::Less,
+        Bound::Unbounded => true
+    };
+}
+
+// Addresses of the entries whose key is inside the bounds, in key order.
+pub fn btree_range(
+    pager: &Pager,
+    file_name: &String,
+    lower: Bound<&Tuple>,
+    upper: Bound<&Tuple>
+) -> Result<Vec<TupleAddress>, ExecutionError> {
+    let mut addresses: Vec<TupleAddress> = Vec::new();
+    let start_key = match lower {
+        Bound::Included(key) | Bound::Excluded(key) => Some(key),
+        Bound::Unbounded => None
+    };
+    let mut node_idx = btree_find_leaf(pager, file_name, start_key)?;
+
+    loop {
+        let node = btree_read_node(pager, file_name, node_idx)?;
+
+        for (key, address) in node.entries.iter() {
+            if btree_below_upper(key, &upper) == false {
+                return Ok(addresses);
+            }
+            if btree_above_lower(key, &lower) {
+                addresses.push(*address);
+            }
+        }
+
+        if node.next == 0 {
+            return Ok(addresses);
+        }
+        node_idx = node.next;
+    }
+}
+
+pub fn btree_search(pager: &Pager, file_name: &String, key: &Tuple) -> Result<Vec<TupleAddress>, ExecutionError> {
+    return btree_range(pager, file_name, Bound::Included(key), Bound::Included(key));
+}
+
+// Every entry of the tree in key order.
+pub fn btree_entries(pager: &Pager, file_name: &String) -> Result<Vec<BTreeEntry>, ExecutionError> {
+    let mut entries: Vec<BTreeEntry> = Vec::new();
+    let mut node_idx = btree_find_leaf(pager, file_name, None)?;
+
+    loop {
+        let mut node = btree_read_node(pager, file_name, node_idx)?;
+        entries.append(&mut node.entries);
+
+        if node.next == 0 {
+            return Ok(entries);
+        }
+        node_idx = node.next;
+    }
+}
+
+pub fn btree_height(pager: &Pager, file_name: &String) -> Result<u64, ExecutionError> {
+    let header = btree_read_header(pager, file_name)?;
+    let mut node_idx = header.root;
+    let mut height = 1;
+
+    loop {
+        let node = btree_read_node(pager, file_name, node_idx)?;
+
+        if node.leaf {
+            return Ok(height);
+        }
+        node_idx = node.children[0];
+        height += 1;
+    }
+}
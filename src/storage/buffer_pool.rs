@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use crate::storage::BLOCK_SIZE;
+use crate::storage::Page;
+use crate::storage::page_new;
+use crate::storage::page_serialize;
+use crate::storage::page_deserialize;
+use crate::storage::read_data;
+
+use crate::utils::Logger;
+
+/*
+ * Fixed budget of in-memory pages shared by every table file. A page is
+ * pinned while a caller works on it and can only be evicted once its pin
+ * count drops to zero. The victim is the least recently used unpinned clean
+ * frame. Dirty pages are never stolen: they only reach the data files with
+ * the flush of their statement, so the write-ahead log never has to undo a
+ * page written ahead of its commit. When every frame is pinned or dirty the
+ * pool grows, and shrinks back to its capacity once the pages are flushed.
+ */
+
+#[derive(Debug)]
+pub struct Frame {
+    pub page_key: String,
+    pub page_idx: usize,
+    pub page: Page,
+    pub pin_count: u32,
+    pub dirty: bool,
+    pub last_used: u64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferPoolStats {
+    pub capacity: usize,
+    pub resident: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64
+}
+
+#[derive(Debug)]
+pub struct BufferPool {
+    pub capacity: usize,
+    pub frames: Vec<Frame>,
+    pub frame_table: HashMap<(String, usize), usize>,
+    pub tick: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64
+}
+
+impl BufferPool {
+    pub fn new(capacity: usize) -> Self {
+        BufferPool {
+            capacity: if capacity == 0 { 1 } else { capacity },
+            frames: Vec::new(),
+            frame_table: HashMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0
+        }
+    }
+}
+
+pub fn buffer_pool_new(capacity: usize) -> BufferPool {
+    return BufferPool::new(capacity);
+}
+
+fn buffer_pool_find_victim(pool: &BufferPool) -> Option<usize> {
+    let mut victim: Option<usize> = None;
+
+    for (idx, frame) in pool.frames.iter().enumerate() {
+        if frame.pin_count > 0 || frame.dirty {
+            continue;
+        }
+        match victim {
+            Some(victim_idx) if pool.frames[victim_idx].last_used <= frame.last_used => {},
+            _ => victim = Some(idx)
+        }
+    }
+
+    return victim;
+}
+
+fn buffer_pool_install(pool: &mut BufferPool, page_key: &String, page_idx: usize, page: Page, dirty: bool) -> usize {
+    pool.tick += 1;
+
+    let frame = Frame {
+        page_key: page_key.clone(),
+        page_idx,
+        page,
+        pin_count: 1,
+        dirty,
+        last_used: pool.tick
+    };
+
+    let frame_idx = if pool.frames.len() < pool.capacity {
+        pool.frames.push(frame);
+        pool.frames.len() - 1
+    } else if let Some(victim_idx) = buffer_pool_find_victim(pool) {
+        let victim = &pool.frames[victim_idx];
+        Logger::debug(format!("evict page {} of {}", victim.page_idx, victim.page_key).leak());
+
+        pool.frame_table.remove(&(victim.page_key.clone(), victim.page_idx));
+        pool.evictions += 1;
+        pool.frames[victim_idx] = frame;
+        victim_idx
+    } else {
+        Logger::debug("every frame of the buffer pool is pinned or dirty, growing it");
+        pool.frames.push(frame);
+        pool.frames.len() - 1
+    };
+
+    pool.frame_table.insert((page_key.clone(), page_idx), frame_idx);
+
+    return frame_idx;
+}
+
+// Pins the page, reading it from disk when it is not resident.
+pub fn buffer_pool_fetch_page(pool: &mut BufferPool, page_key: &String, page_idx: usize) -> usize {
+    if let Some(frame_idx) = pool.frame_table.get(&(page_key.clone(), page_idx)) {
+        let frame_idx = *frame_idx;
+
+        pool.tick += 1;
+        pool.hits += 1;
+        pool.frames[frame_idx].pin_count += 1;
+        pool.frames[frame_idx].last_used = pool.tick;
+
+        return frame_idx;
+    }
+
+    pool.misses += 1;
+    let page = page_deserialize(read_data(page_key, page_idx as u64));
+
+    return buffer_pool_install(pool, page_key, page_idx, page, false);
+}
+
+// Pins a brand new empty page that does not exist on disk yet.
+pub fn buffer_pool_new_page(pool: &mut BufferPool, page_key: &String, page_idx: usize) -> usize {
+    if let Some(frame_idx) = pool.frame_table.get(&(page_key.clone(), page_idx)) {
+        let frame_idx = *frame_idx;
+
+        pool.frames[frame_idx].page = page_new();
+        pool.frames[frame_idx].pin_count += 1;
+        pool.frames[frame_idx].dirty = true;
+
+        return frame_idx;
+    }
+
+    return buffer_pool_install(pool, page_key, page_idx, page_new(), true);
+}
+
+pub fn buffer_pool_unpin_page(pool: &mut BufferPool, frame_idx: usize, dirty: bool) {
+    if let Some(frame) = pool.frames.get_mut(frame_idx) {
+        if frame.pin_count > 0 {
+            frame.pin_count -= 1;
+        }
+        frame.dirty = frame.dirty || dirty;
+    }
+}
+
+pub fn buffer_pool_contains(pool: &BufferPool, page_key: &String, page_idx: usize) -> bool {
+    return pool.frame_table.contains_key(&(page_key.clone(), page_idx));
+}
+
+pub fn buffer_pool_dirty_pages(pool: &BufferPool, page_key: &String) -> Vec<(usize, [u8; BLOCK_SIZE])> {
+    let mut pages: Vec<(usize, [u8; BLOCK_SIZE])> = pool.frames.iter()
+        .filter(|frame| frame.dirty && frame.page_key == *page_key)
+        .map(|frame| (frame.page_idx, page_serialize(&frame.page)))
+        .collect();
+
+    pages.sort_by_key(|(page_idx, _)| *page_idx);
+
+    return pages;
+}
+
+// Files with pages changed since their last flush.
+pub fn buffer_pool_dirty_keys(pool: &BufferPool) -> Vec<String> {
+    let mut page_keys: Vec<String> = pool.frames.iter()
+        .filter(|frame| frame.dirty)
+        .map(|frame| frame.page_key.clone())
+        .collect();
+
+    page_keys.sort();
+    page_keys.dedup();
+
+    return page_keys;
+}
+
+pub fn buffer_pool_mark_clean(pool: &mut BufferPool, page_key: &String) {
+    for frame in pool.frames.iter_mut() {
+        if frame.page_key == *page_key {
+            frame.dirty = false;
+        }
+    }
+}
+
+// Evicts clean frames until the pool is back to its capacity. Frame indexes
+// move, so nothing may be pinned while it runs.
+pub fn buffer_pool_shrink(pool: &mut BufferPool) {
+    if pool.frames.iter().any(|frame| frame.pin_count > 0) {
+        return;
+    }
+
+    while pool.frames.len() > pool.capacity {
+        let Some(victim_idx) = buffer_pool_find_victim(pool) else { break };
+
+        pool.frames.swap_remove(victim_idx);
+        pool.evictions += 1;
+    }
+
+    pool.frame_table.clear();
+    for (frame_idx, frame) in pool.frames.iter().enumerate() {
+        pool.frame_table.insert((frame.page_key.clone(), frame.page_idx), frame_idx);
+    }
+}
+
+// Forgets every resident page of the file without writing it back, used when
+// the file is removed or rewritten outside the pool.
+pub fn buffer_pool_discard_pages(pool: &mut BufferPool, page_key: &String) {
+    let mut idx = 0;
+
+    while idx < pool.frames.len() {
+        if pool.frames[idx].page_key == *page_key {
+            pool.frames.swap_remove(idx);
+        } else {
+            idx += 1;
+        }
+    }
+
+    pool.frame_table.clear();
+    for (frame_idx, frame) in pool.frames.iter().enumerate() {
+        pool.frame_table.insert((frame.page_key.clone(), frame.page_idx), frame_idx);
+    }
+}
+
+pub fn buffer_pool_stats(pool: &BufferPool) -> BufferPoolStats {
+    BufferPoolStats {
+        capacity: pool.capacity,
+        resident: pool.frames.len(),
+        hits: pool.hits,
+        misses: pool.misses,
+        evictions: pool.evictions
+    }
+}
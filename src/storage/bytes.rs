@@ -0,0 +1,52 @@
+use std::cmp::Ordering;
+
+use crate::storage::Data;
+
+// Binary values are shown and written in the hex format of PostgreSQL: a
+// backslash and an x followed by two hex digits for each byte.
+pub const BYTES_HEX_PREFIX: &str = "\\x";
+
+// Hex digits in pairs, spaces between the pairs are allowed. None when a
+// digit is not hex or one is left without its pair.
+pub fn bytes_parse_hex(value: &str) -> Option<Vec<u8>> {
+    let digits: Vec<char> = value.chars().filter(|digit| !digit.is_whitespace()).collect();
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+
+    return digits
+        .chunks(2)
+        .map(|pair| {
+            let high = pair[0].to_digit(16)?;
+            let low = pair[1].to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect();
+}
+
+pub fn bytes_format_hex(value: &[u8]) -> String {
+    let digits: String = value.iter().map(|byte| format!("{:02x}", byte)).collect();
+    return format!("{}{}", BYTES_HEX_PREFIX, digits);
+}
+
+// Strings become binary values the way BYTEA columns read them: in hex
+// after a \x, as their UTF-8 bytes otherwise.
+pub fn bytes_cast(value: &Data) -> Option<Vec<u8>> {
+    return match value {
+        Data::Bytes(bytes) => Some(bytes.clone()),
+        Data::Varchar(string) | Data::Text(string) => match string.strip_prefix(BYTES_HEX_PREFIX) {
+            Some(digits) => bytes_parse_hex(digits),
+            None => Some(string.as_bytes().to_vec())
+        },
+        _ => None
+    };
+}
+
+// Binary values compare byte by byte, a shorter value first when it is a
+// prefix of the other. Strings against them are read as binary values.
+pub fn bytes_compare(a: &Data, b: &Data) -> Option<Ordering> {
+    if !matches!(a, Data::Bytes(_)) && !matches!(b, Data::Bytes(_)) {
+        return None;
+    }
+    return Some(bytes_cast(a)?.cmp(&bytes_cast(b)?));
+}
@@ -0,0 +1,189 @@
+use std::cmp::Ordering;
+
+use ordered_float::OrderedFloat;
+
+// Decimals are kept as an integer holding every digit plus a scale, the
+// count of those digits that go after the point: 12.50 is (1250, 2).
+pub const DECIMAL_MAX_PRECISION: u8 = 38;
+
+// Scale given to quotients beyond the scale of their operands.
+pub const DECIMAL_DIV_EXTRA_SCALE: u8 = 6;
+
+pub fn decimal_pow10(scale: u8) -> i128 {
+    return 10i128.pow(scale as u32);
+}
+
+// Number of digits of the value, zero has one.
+pub fn decimal_digits(value: i128) -> u8 {
+    let mut digits = 1;
+    let mut value = value.unsigned_abs();
+
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    return digits;
+}
+
+// Changes the scale of a value, rounding half away from zero when digits
+// are dropped. None when the value no longer fits.
+pub fn decimal_rescale(value: i128, scale: u8, new_scale: u8) -> Option<i128> {
+    if new_scale >= scale {
+        if new_scale - scale > DECIMAL_MAX_PRECISION {
+            return if value == 0 { Some(0) } else { None };
+        }
+        return value.checked_mul(decimal_pow10(new_scale - scale));
+    }
+    if scale - new_scale > DECIMAL_MAX_PRECISION {
+        return Some(0);
+    }
+
+    let divisor = decimal_pow10(scale - new_scale);
+    let quotient = value / divisor;
+    let remainder = (value % divisor).unsigned_abs();
+
+    if remainder >= divisor.unsigned_abs() - remainder {
+        return Some(quotient + value.signum());
+    }
+    return Some(quotient);
+}
+
+// Reads numbers as 12, -0.5 or .25, keeping every digit after the point up
+// to the maximum precision. Exponents are left to floats.
+pub fn decimal_parse(value: &str) -> Option<(i128, u8)> {
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value.strip_prefix('+').unwrap_or(value))
+    };
+    let (integer_part, fraction_part) = match digits.split_once('.') {
+        Some((integer_part, fraction_part)) => (integer_part, fraction_part),
+        None => (digits, "")
+    };
+
+    if integer_part.len() + fraction_part.len() == 0 {
+        return None;
+    }
+    if !integer_part.chars().chain(fraction_part.chars()).all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut mantissa: i128 = 0;
+    let mut scale: u8 = 0;
+    let mut dropped: Option<u32> = None;
+
+    for (idx, digit) in integer_part.chars().chain(fraction_part.chars()).enumerate() {
+        let is_fraction = idx >= integer_part.len();
+        if is_fraction && scale == DECIMAL_MAX_PRECISION {
+            dropped = Some(digit.to_digit(10).unwrap());
+            break;
+        }
+        mantissa = mantissa.checked_mul(10)?.checked_add(digit.to_digit(10).unwrap() as i128)?;
+        if is_fraction {
+            scale += 1;
+        }
+    }
+
+    if let Some(digit) = dropped {
+        if digit >= 5 {
+            mantissa = mantissa.checked_add(1)?;
+        }
+    }
+
+    if negative {
+        mantissa = -mantissa;
+    }
+    return Some((mantissa, scale));
+}
+
+pub fn decimal_format(value: i128, scale: u8) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale as usize + 1);
+    let (integer_part, fraction_part) = digits.split_at(digits.len() - scale as usize);
+    return format!("{}{}.{}", sign, integer_part, fraction_part);
+}
+
+pub fn decimal_to_float(value: i128, scale: u8) -> f64 {
+    return decimal_format(value, scale).parse::<f64>().unwrap();
+}
+
+// Brings both values to the larger scale, None when one no longer fits.
+fn decimal_align(a: (i128, u8), b: (i128, u8)) -> Option<(i128, i128, u8)> {
+    let scale = a.1.max(b.1);
+    return Some((decimal_rescale(a.0, a.1, scale)?, decimal_rescale(b.0, b.1, scale)?, scale));
+}
+
+pub fn decimal_compare(a: (i128, u8), b: (i128, u8)) -> Ordering {
+    if let Some((a, b, _)) = decimal_align(a, b) {
+        return a.cmp(&b);
+    }
+    return OrderedFloat(decimal_to_float(a.0, a.1)).cmp(&OrderedFloat(decimal_to_float(b.0, b.1)));
+}
+
+pub fn decimal_add(a: (i128, u8), b: (i128, u8)) -> Option<(i128, u8)> {
+    let (a, b, scale) = decimal_align(a, b)?;
+    return Some((a.checked_add(b)?, scale));
+}
+
+pub fn decimal_sub(a: (i128, u8), b: (i128, u8)) -> Option<(i128, u8)> {
+    let (a, b, scale) = decimal_align(a, b)?;
+    return Some((a.checked_sub(b)?, scale));
+}
+
+pub fn decimal_mul(a: (i128, u8), b: (i128, u8)) -> Option<(i128, u8)> {
+    let value = a.0.checked_mul(b.0)?;
+    let scale = a.1 + b.1;
+
+    if scale > DECIMAL_MAX_PRECISION {
+        return Some((decimal_rescale(value, scale, DECIMAL_MAX_PRECISION)?, DECIMAL_MAX_PRECISION));
+    }
+    return Some((value, scale));
+}
+
+// Quotients keep a few more digits than their operands, the last one rounded.
+// None on a zero divisor.
+pub fn decimal_div(a: (i128, u8), b: (i128, u8)) -> Option<(i128, u8)> {
+    if b.0 == 0 {
+        return None;
+    }
+
+    let scale = (a.1.max(b.1) + DECIMAL_DIV_EXTRA_SCALE).min(DECIMAL_MAX_PRECISION);
+    let numerator = decimal_rescale(a.0, a.1, scale + b.1 + 1)?;
+
+    return Some((decimal_rescale(numerator / b.0, scale + 1, scale)?, scale));
+}
+
+// Only the bytes needed for the value in two's complement, the size first.
+pub fn decimal_to_bytes(value: i128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut start = 0;
+
+    while start < bytes.len() - 1 {
+        let redundant = (bytes[start] == 0x00 && bytes[start + 1] & 0x80 == 0) ||
+            (bytes[start] == 0xff && bytes[start + 1] & 0x80 != 0);
+        if !redundant {
+            break;
+        }
+        start += 1;
+    }
+
+    let mut buffer = vec![(bytes.len() - start) as u8];
+    buffer.extend_from_slice(&bytes[start..]);
+    return buffer;
+}
+
+pub fn decimal_from_bytes(buffer: &Vec<u8>, pos: usize) -> i128 {
+    let size = buffer[pos] as usize;
+    let bytes = &buffer[(pos + 1)..(pos + 1 + size)];
+    let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0x00 };
+
+    let mut value = [fill; 16];
+    value[(16 - size)..].copy_from_slice(bytes);
+    return i128::from_be_bytes(value);
+}
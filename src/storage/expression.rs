@@ -2,20 +2,61 @@ use std::fmt;
 
 use crate::storage::Data;
 use crate::storage::Tuple;
+use crate::storage::Aggregate;
+use crate::storage::MICROS_PER_DAY;
+use crate::storage::temporal_now;
+use crate::storage::temporal_extract;
+use crate::storage::temporal_trunc;
+use crate::storage::uuid_generate;
+use crate::storage::json_get;
+use crate::storage::json_get_text;
+use crate::storage::json_get_path;
+use crate::storage::json_get_path_text;
+use crate::storage::json_contains;
+use crate::storage::json_array_length;
+use crate::storage::json_typeof;
+use crate::storage::json_cast;
+use crate::storage::json_format;
+use crate::storage::json_normalize;
+
+use crate::utils::ExecutionError;
 
 #[derive(Debug)]
 pub enum Expression {
     ColName(String),
     Const(Data),
+    Func0(Expression0Type),
     Func1(Expression1Type, Box<Expression>),
     Func2(Expression2Type, Box<Expression>, Box<Expression>),
+    Aggregate(Aggregate),
     Empty
 }
 
+// Functions without arguments, all of them read the clock or draw random
+// values.
+#[derive(Debug)]
+pub enum Expression0Type {
+    Now,
+    CurrentDate,
+    CurrentTime,
+    LocalTimestamp,
+    GenRandomUuid
+}
+
 #[derive(Debug)]
 pub enum Expression1Type {
     Not,
-    Negate
+    Negate,
+
+    // Temporal functions, with the field they work on
+    Extract(String),
+    DateTrunc(String),
+
+    // JSON functions and casts
+    JsonArrayLength,
+    JsonTypeof,
+    ToJson,
+    ToJsonb
 }
 
 #[derive(Debug)]
@@ -38,63 +79,187 @@ pub enum Expression2Type {
     Add,
     Sub,
     Mul,
-    Div
+    Div,
+
+    // JSON operators: ->, ->>, #>, #>>, @> and <@
+    JsonGet,
+    JsonGetText,
+    JsonGetPath,
+    JsonGetPathText,
+    JsonContains,
+    JsonContainedIn
 }
 
 impl Expression {
-    pub fn result(&self, tuple: &Tuple, columns: &Vec<String>) -> Data {
+    // Whether the expression reads the clock or draws random values, so it
+    // gives a new value on each evaluation.
+    pub fn is_volatile(&self) -> bool {
+        return match self {
+            Expression::Func0(_) => true,
+            Expression::Func1(_, opr1) => opr1.is_volatile(),
+            Expression::Func2(_, opr1, opr2) => opr1.is_volatile() || opr2.is_volatile(),
+            _ => false
+        };
+    }
+
+    // Aggregates the expression holds, in order of appearance.
+    pub fn aggregates(&self) -> Vec<&Aggregate> {
+        return match self {
+            Expression::Aggregate(aggregate) => vec![aggregate],
+            Expression::Func1(_, opr1) => opr1.aggregates(),
+            Expression::Func2(_, opr1, opr2) => {
+                let mut aggregates = opr1.aggregates();
+                aggregates.append(&mut opr2.aggregates());
+                aggregates
+            },
+            _ => Vec::new()
+        };
+    }
+
+    pub fn has_aggregate(&self) -> bool {
+        return self.aggregates().len() > 0;
+    }
+
+    // Names of the columns the expression reads, in order of appearance. The
+    // columns read inside aggregates are left out.
+    pub fn column_names(&self) -> Vec<String> {
+        return match self {
+            Expression::ColName(colname) => vec![colname.clone()],
+            Expression::Func1(_, opr1) => opr1.column_names(),
+            Expression::Func2(_, opr1, opr2) => {
+                let mut column_names = opr1.column_names();
+                column_names.append(&mut opr2.column_names());
+                column_names
+            },
+            _ => Vec::new()
+        };
+    }
+
+    // Value of the expression over the tuple. Arithmetic fails on results
+    // that do not fit their type and on operands it is not defined for.
+    pub fn result(&self, tuple: &Tuple, columns: &Vec<String>) -> Result<Data, ExecutionError> {
         match self {
-            Expression::Empty => Data::Null,
-            Expression::ColName(colname)=> {
-                for (idx, column) in columns.iter().enumerate() {
-                    if *column == *colname {
-                        return tuple.get(idx).unwrap().clone();
-                    }
-                }
-                return Data::Null;
+            Expression::Empty => Ok(Data::Null),
+            Expression::ColName(colname) => Ok(column_value(tuple, columns, colname)),
+            // Aggregates are computed before, the result is on a column
+            // named after them.
+            Expression::Aggregate(aggregate) => Ok(column_value(tuple, columns, &aggregate.name)),
+            Expression::Const(value) => Ok(value.clone()),
+            Expression::Func0(operator) => {
+                let now = temporal_now();
+                return Ok(match operator {
+                    Expression0Type::Now => Data::TimestampTz(now),
+                    Expression0Type::CurrentDate => Data::Date(now.div_euclid(MICROS_PER_DAY) as i32),
+                    Expression0Type::CurrentTime => Data::Time(now.rem_euclid(MICROS_PER_DAY)),
+                    Expression0Type::LocalTimestamp => Data::Timestamp(now),
+                    Expression0Type::GenRandomUuid => Data::Uuid(uuid_generate())
+                });
             },
-            Expression::Const(value) => value.clone(),
             Expression::Func1(operator, opr1) => { 
-                let value_opr1 = opr1.result(tuple, columns);
-                return match operator {
+                let value_opr1 = match opr1.result(tuple, columns) {
+                    Ok(value) => value,
+                    Err(error) => return Err(error)
+                };
+                return Ok(match operator {
                     // Logic implementation
                     Expression1Type::Not => !value_opr1,
-                    Expression1Type::Negate => -value_opr1
-                }
+                    Expression1Type::Negate => -value_opr1,
+
+                    // Temporal implementation
+                    Expression1Type::Extract(field) => temporal_extract(field, &value_opr1),
+                    Expression1Type::DateTrunc(field) => temporal_trunc(field, &value_opr1),
+
+                    // JSON implementation
+                    Expression1Type::JsonArrayLength => json_array_length(&value_opr1),
+                    Expression1Type::JsonTypeof => json_typeof(&value_opr1),
+                    Expression1Type::ToJson => match json_cast(&value_opr1) {
+                        Some(json) => Data::Json(json_format(&json)),
+                        None => Data::Null
+                    },
+                    Expression1Type::ToJsonb => match json_cast(&value_opr1) {
+                        Some(json) => Data::Jsonb(json_normalize(json)),
+                        None => Data::Null
+                    }
+                });
             },
             Expression::Func2(operator, opr1, opr2) => {
-                let value_opr1 = opr1.result(tuple, columns);
-                let value_opr2 = opr2.result(tuple, columns);
+                let value_opr1 = match opr1.result(tuple, columns) {
+                    Ok(value) => value,
+                    Err(error) => return Err(error)
+                };
+                let value_opr2 = match opr2.result(tuple, columns) {
+                    Ok(value) => value,
+                    Err(error) => return Err(error)
+                };
                 return match operator {
                     // Logic implementation
-                    Expression2Type::And => value_opr1.and(&value_opr2),
-                    Expression2Type::Or => value_opr1.or(&value_opr2),
+                    Expression2Type::And => Ok(value_opr1.and(&value_opr2)),
+                    Expression2Type::Or => Ok(value_opr1.or(&value_opr2)),
 
                     // Comparison implementation
-                    Expression2Type::GreatherOrEqual => Data::Boolean(value_opr1 >= value_opr2),
-                    Expression2Type::GreatherThan => Data::Boolean(value_opr1 > value_opr2),
-                    Expression2Type::LessOrEqual => Data::Boolean(value_opr1 <= value_opr2),
-                    Expression2Type::LessThan => Data::Boolean(value_opr1 < value_opr2),
-                    Expression2Type::Equal => Data::Boolean(value_opr1 == value_opr2),
-                    Expression2Type::NotEqual => Data::Boolean(value_opr1 != value_opr2),
+                    Expression2Type::GreatherOrEqual => Ok(Data::Boolean(value_opr1 >= value_opr2)),
+                    Expression2Type::GreatherThan => Ok(Data::Boolean(value_opr1 > value_opr2)),
+                    Expression2Type::LessOrEqual => Ok(Data::Boolean(value_opr1 <= value_opr2)),
+                    Expression2Type::LessThan => Ok(Data::Boolean(value_opr1 < value_opr2)),
+                    Expression2Type::Equal => Ok(Data::Boolean(value_opr1 == value_opr2)),
+                    Expression2Type::NotEqual => Ok(Data::Boolean(value_opr1 != value_opr2)),
 
                     // Aritmetic implementation
-                    Expression2Type::Add => value_opr1 + value_opr2, 
-                    Expression2Type::Sub => value_opr1 - value_opr2,
-                    Expression2Type::Mul => value_opr1 * value_opr2,
-                    Expression2Type::Div => value_opr1 / value_opr2
+                    Expression2Type::Add => value_opr1.checked_add(value_opr2),
+                    Expression2Type::Sub => value_opr1.checked_sub(value_opr2),
+                    Expression2Type::Mul => value_opr1.checked_mul(value_opr2),
+                    Expression2Type::Div => value_opr1.checked_div(value_opr2),
+
+                    // JSON implementation
+                    Expression2Type::JsonGet => Ok(json_get(&value_opr1, &value_opr2)),
+                    Expression2Type::JsonGetText => Ok(json_get_text(&value_opr1, &value_opr2)),
+                    Expression2Type::JsonGetPath => Ok(json_get_path(&value_opr1, &value_opr2)),
+                    Expression2Type::JsonGetPathText => Ok(json_get_path_text(&value_opr1, &value_opr2)),
+                    Expression2Type::JsonContains => Ok(json_contains(&value_opr1, &value_opr2)),
+                    Expression2Type::JsonContainedIn => Ok(json_contains(&value_opr2, &value_opr1))
                 }
             }
         }
     }
 }
 
+fn column_value(tuple: &Tuple, columns: &Vec<String>, colname: &String) -> Data {
+    for (idx, column) in columns.iter().enumerate() {
+        if column_name_matches(column, colname) {
+            return tuple.get(idx).unwrap().clone();
+        }
+    }
+    return Data::Null;
+}
+
+// A column is reached by its own name or qualified by its table, so
+// "name" matches the column "b.name" while "a.name" does not.
+pub fn column_name_matches(column: &String, colname: &String) -> bool {
+    if *column == *colname {
+        return true;
+    }
+    return match column.strip_suffix(colname.as_str()) {
+        Some(prefix) => prefix.ends_with('.'),
+        None => false
+    };
+}
+
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let str_ref = match self {
             Expression::Empty => String::from(""),
             Expression::ColName(colname) => colname.to_string(),
+            Expression::Aggregate(aggregate) => aggregate.name.to_string(),
             Expression::Const(value) => value.to_string(),
+            Expression::Func0(operator) => {
+                match operator {
+                    Expression0Type::Now => String::from("NOW()"),
+                    Expression0Type::CurrentDate => String::from("CURRENT_DATE"),
+                    Expression0Type::CurrentTime => String::from("CURRENT_TIME"),
+                    Expression0Type::LocalTimestamp => String::from("LOCALTIMESTAMP"),
+                    Expression0Type::GenRandomUuid => String::from("gen_random_uuid()")
+                }
+            },
             Expression::Func1(operator, opr1) => { 
                 match operator {
                     Expression1Type::Not => format!("NOT {}", opr1),
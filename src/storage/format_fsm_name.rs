@@ -0,0 +1,3 @@
+pub fn format_fsm_name(page_key: &String) -> String {
+    return format!("{}.fsm", page_key.trim_end_matches(".db"));
+}
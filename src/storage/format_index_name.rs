@@ -0,0 +1,5 @@
+use crate::config::Config;
+
+pub fn format_index_name(database_name: &String, table_name: &String, index_name: &String) -> String {
+    return format!("{}/{}/{}.{}.idx", Config::data_folder(), database_name, table_name, index_name);
+}
@@ -0,0 +1,5 @@
+use crate::config::Config;
+
+pub fn format_sort_name(sort_id: u64, run: usize) -> String {
+    return format!("{}/rusticodb_sort_{}_{}.tmp", Config::data_folder(), sort_id, run);
+}
@@ -0,0 +1,5 @@
+use crate::config::Config;
+
+pub fn format_wal_name() -> String{
+    return format!("{}/rusticodb.wal", Config::data_folder());
+}
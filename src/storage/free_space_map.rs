@@ -0,0 +1,65 @@
+use crate::storage::BLOCK_SIZE;
+
+/*
+ * Approximate free space of every data page of a table, one byte per page
+ * holding the free bytes divided by FSM_CATEGORY_SIZE. It is kept in a
+ * sidecar file next to the table and written on flush without going through
+ * the write-ahead log: it is only a hint, inserts double check the page before
+ * using it and a missing or short map is rebuilt from the pages themselves.
+ */
+
+pub const FSM_CATEGORY_SIZE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeSpaceMap {
+    pub pages: Vec<u8>
+}
+
+impl FreeSpaceMap {
+    pub fn new() -> Self {
+        FreeSpaceMap { pages: Vec::new() }
+    }
+}
+
+pub fn fsm_new() -> FreeSpaceMap {
+    return FreeSpaceMap::new();
+}
+
+pub fn fsm_category(free_bytes: usize) -> u8 {
+    return usize::min(free_bytes / FSM_CATEGORY_SIZE, u8::MAX as usize) as u8;
+}
+
+pub fn fsm_set(fsm: &mut FreeSpaceMap, page_idx: u64, free_bytes: usize) {
+    let position = (page_idx - 1) as usize;
+
+    if fsm.pages.len() <= position {
+        fsm.pages.resize(position + 1, 0);
+    }
+    fsm.pages[position] = fsm_category(free_bytes);
+}
+
+pub fn fsm_get(fsm: &FreeSpaceMap, page_idx: u64) -> usize {
+    return match fsm.pages.get((page_idx - 1) as usize) {
+        Some(category) => *category as usize * FSM_CATEGORY_SIZE,
+        None => 0
+    };
+}
+
+// First page that is known to have at least the given amount of free bytes.
+pub fn fsm_find(fsm: &FreeSpaceMap, size: usize) -> Option<u64> {
+    if size >= BLOCK_SIZE {
+        return None;
+    }
+
+    return fsm.pages.iter()
+        .position(|category| *category as usize * FSM_CATEGORY_SIZE >= size)
+        .map(|position| position as u64 + 1);
+}
+
+pub fn fsm_serialize(fsm: &FreeSpaceMap) -> Vec<u8> {
+    return fsm.pages.clone();
+}
+
+pub fn fsm_deserialize(buffer: &Vec<u8>) -> FreeSpaceMap {
+    return FreeSpaceMap { pages: buffer.clone() };
+}
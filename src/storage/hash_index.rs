@@ -0,0 +1,355 @@
+use std::cmp::Ordering;
+
+use bincode::serialize;
+use bincode::deserialize;
+use serde::Serialize;
+use serde::Deserialize;
+
+use crate::storage::BLOCK_SIZE;
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::tuple_deserialize;
+use crate::storage::Pager;
+use crate::storage::pager_read_block;
+use crate::storage::pager_write_block;
+use crate::storage::pager_discard;
+use crate::storage::index_key_compare;
+use crate::storage::index_key_hash;
+use crate::storage::index_key_size;
+use crate::storage::index_key_serialize;
+
+use crate::utils::v_u8_to_u16;
+use crate::utils::v_u8_to_u64;
+use crate::utils::v_u8_to_vec_u8;
+use crate::utils::ExecutionError;
+
+/*
+ * On disk extendible hash used by the HASH indexes, one file per index.
+ * Block 0 keeps the header with the global depth and the blocks holding the
+ * directory, 512 bucket numbers per block. The low `global depth` bits of
+ * the key hash pick the directory slot.
+ *
+ * Buckets are chains of blocks:
+ *   [local depth u8][entry count u16][next u64]
+ *   entries: [key size u16][key][page u64][slot u16]
+ *
+ * A bucket that outgrows its block is split, doubling the directory when its
+ * local depth already is the global one. When splitting can't help (every
+ * entry has the same hash, or the directory is at its maximum) the bucket
+ * grows a chain instead. Buckets are never merged back.
+ *
+ * Blocks are read and written through the pager, like the B+tree ones.
+ */
+
+const BUCKET_HEADER_SIZE: usize = 11;
+const ENTRY_FIXED_SIZE: usize = 12;
+const DIRECTORY_SLOTS_PER_BLOCK: usize = BLOCK_SIZE / 8;
+
+pub const HASH_INDEX_MAX_GLOBAL_DEPTH: u32 = 16;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct HashIndexHeader {
+    pub global_depth: u32,
+    pub page_count: u64,
+    pub directory_pages: Vec<u64>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashBucket {
+    pub local_depth: u8,
+    pub next: u64,
+    pub entries: Vec<(Tuple, TupleAddress)>
+}
+
+fn hash_entry_size(entry: &(Tuple, TupleAddress)) -> usize {
+    return ENTRY_FIXED_SIZE + index_key_size(&entry.0);
+}
+
+fn hash_entries_fit(entries: &[(Tuple, TupleAddress)]) -> bool {
+    let size: usize = entries.iter().map(|entry| hash_entry_size(entry)).sum();
+    return BUCKET_HEADER_SIZE + size <= BLOCK_SIZE;
+}
+
+pub fn hash_bucket_serialize(bucket: &HashBucket) -> [u8; BLOCK_SIZE] {
+    let mut raw_buffer: Vec<u8> = Vec::new();
+
+    raw_buffer.push(bucket.local_depth);
+    raw_buffer.append(&mut (bucket.entries.len() as u16).to_be_bytes().to_vec());
+    raw_buffer.append(&mut bucket.next.to_be_bytes().to_vec());
+
+    for (key, address) in bucket.entries.iter() {
+        let mut raw_key = index_key_serialize(key);
+        raw_buffer.append(&mut (raw_key.len() as u16).to_be_bytes().to_vec());
+        raw_buffer.append(&mut raw_key);
+        raw_buffer.append(&mut address.page.to_be_bytes().to_vec());
+        raw_buffer.append(&mut address.slot.to_be_bytes().to_vec());
+    }
+
+    let mut block = [0u8; BLOCK_SIZE];
+    block[..raw_buffer.len()].copy_from_slice(&raw_buffer);
+    return block;
+}
+
+pub fn hash_bucket_deserialize(raw_bucket: &[u8; BLOCK_SIZE]) -> HashBucket {
+    let entry_count = v_u8_to_u16(raw_bucket, 1) as usize;
+    let mut entries: Vec<(Tuple, TupleAddress)> = Vec::new();
+    let mut position = BUCKET_HEADER_SIZE;
+
+    for _ in 0..entry_count {
+        let key_size = v_u8_to_u16(raw_bucket, position) as usize;
+        let raw_key = v_u8_to_vec_u8(raw_bucket, position + 2, key_size);
+        position += 2 + key_size;
+
+        let key = tuple_deserialize(&raw_key);
+        let address = TupleAddress::new(
+            v_u8_to_u64(raw_bucket, position),
+            v_u8_to_u16(raw_bucket, position + 8)
+        );
+        position += 10;
+
+        entries.push((key, address));
+    }
+
+    return HashBucket { local_depth: raw_bucket[0], next: v_u8_to_u64(raw_bucket, 3), entries };
+}
+
+fn hash_read_header(pager: &Pager, file_name: &String) -> Result<HashIndexHeader, ExecutionError> {
+    let header: HashIndexHeader = match deserialize(&pager_read_block(pager, file_name, 0)?) {
+        Ok(header) => header,
+        Err(_) => return Err(ExecutionError::IndexCorrupted(file_name.clone()))
+    };
+
+    let directory_size = 1usize << header.global_depth.min(HASH_INDEX_MAX_GLOBAL_DEPTH);
+    if header.global_depth > HASH_INDEX_MAX_GLOBAL_DEPTH
+        || header.directory_pages.len() * DIRECTORY_SLOTS_PER_BLOCK < directory_size {
+        return Err(ExecutionError::IndexCorrupted(file_name.clone()));
+    }
+    return Ok(header);
+}
+
+fn hash_write_header(pager: &mut Pager, file_name: &String, header: &HashIndexHeader) {
+    let mut block = [0u8; BLOCK_SIZE];
+    let serialized = serialize(header).unwrap();
+
+    block[..serialized.len()].copy_from_slice(&serialized);
+    pager_write_block(pager, file_name, 0, block);
+}
+
+fn hash_read_directory(pager: &Pager, file_name: &String, header: &HashIndexHeader) -> Result<Vec<u64>, ExecutionError> {
+    let size = 1usize << header.global_depth;
+    let mut directory: Vec<u64> = Vec::new();
+
+    for page_idx in header.directory_pages.iter() {
+        let block = pager_read_block(pager, file_name, *page_idx)?;
+
+        for slot in 0..DIRECTORY_SLOTS_PER_BLOCK {
+            if directory.len() == size {
+                return Ok(directory);
+            }
+            directory.push(v_u8_to_u64(&block, slot * 8));
+        }
+    }
+
+    return Ok(directory);
+}
+
+fn hash_write_directory(pager: &mut Pager, file_name: &String, header: &mut HashIndexHeader, directory: &Vec<u64>) {
+    for (chunk_idx, chunk) in directory.chunks(DIRECTORY_SLOTS_PER_BLOCK).enumerate() {
+        if chunk_idx == header.directory_pages.len() {
+            header.page_count += 1;
+            header.directory_pages.push(header.page_count);
+        }
+
+        let mut block = [0u8; BLOCK_SIZE];
+        for (slot, bucket_idx) in chunk.iter().enumerate() {
+            block[(slot * 8)..(slot * 8 + 8)].copy_from_slice(&bucket_idx.to_be_bytes());
+        }
+        pager_write_block(pager, file_name, header.directory_pages[chunk_idx], block);
+    }
+}
+
+// Entries of the whole chain along with the blocks it is made of.
+fn hash_read_bucket(
+    pager: &Pager,
+    file_name: &String,
+    bucket_idx: u64
+) -> Result<(u8, Vec<(Tuple, TupleAddress)>, Vec<u64>), ExecutionError> {
+    let mut entries: Vec<(Tuple, TupleAddress)> = Vec::new();
+    let mut blocks: Vec<u64> = Vec::new();
+    let mut local_depth = 0;
+    let mut block_idx = bucket_idx;
+
+    while block_idx != 0 {
+        let mut bucket = hash_bucket_deserialize(&pager_read_block(pager, file_name, block_idx)?);
+
+        if blocks.len() == 0 {
+            local_depth = bucket.local_depth;
+        }
+        blocks.push(block_idx);
+        entries.append(&mut bucket.entries);
+        block_idx = bucket.next;
+    }
+
+    return Ok((local_depth, entries, blocks));
+}
+
+// Packs the entries on the blocks of the chain, taking new blocks when they
+// don't fit anymore. Blocks left over at the end of the chain are dropped.
+fn hash_write_bucket(
+    pager: &mut Pager,
+    file_name: &String,
+    header: &mut HashIndexHeader,
+    blocks: &Vec<u64>,
+    local_depth: u8,
+    entries: Vec<(Tuple, TupleAddress)>
+) {
+    let mut chunks: Vec<Vec<(Tuple, TupleAddress)>> = vec![Vec::new()];
+    let mut chunk_size = BUCKET_HEADER_SIZE;
+
+    for entry in entries.into_iter() {
+        let entry_size = hash_entry_size(&entry);
+
+        if chunk_size + entry_size > BLOCK_SIZE && chunks.last().unwrap().len() > 0 {
+            chunks.push(Vec::new());
+            chunk_size = BUCKET_HEADER_SIZE;
+        }
+
+        chunk_size += entry_size;
+        chunks.last_mut().unwrap().push(entry);
+    }
+
+    let mut chain: Vec<u64> = Vec::new();
+    for idx in 0..chunks.len() {
+        if idx < blocks.len() {
+            chain.push(blocks[idx]);
+        } else {
+            header.page_count += 1;
+            chain.push(header.page_count);
+        }
+    }
+
+    for (idx, chunk) in chunks.into_iter().enumerate() {
+        let next = if idx + 1 < chain.len() { chain[idx + 1] } else { 0 };
+        let bucket = HashBucket { local_depth, next, entries: chunk };
+        pager_write_block(pager, file_name, chain[idx], hash_bucket_serialize(&bucket));
+    }
+}
+
+fn hash_directory_slot(hash: u64, global_depth: u32) -> usize {
+    return (hash & ((1u64 << global_depth) - 1)) as usize;
+}
+
+// Starts a new index with a single empty bucket, replacing the previous one.
+// Blocks of the old index past the new ones are left unreferenced.
+pub fn hash_index_create(pager: &mut Pager, file_name: &String) {
+    pager_discard(pager, file_name);
+
+    let mut header = HashIndexHeader { global_depth: 0, page_count: 2, directory_pages: vec![1] };
+    hash_write_bucket(pager, file_name, &mut header, &vec![2], 0, Vec::new());
+    hash_write_directory(pager, file_name, &mut header, &vec![2]);
+    hash_write_header(pager, file_name, &header);
+}
+
+pub fn hash_index_insert(pager: &mut Pager, file_name: &String, key: &Tuple, address: &TupleAddress) -> Result<(), ExecutionError> {
+    let mut header = hash_read_header(pager, file_name)?;
+    let mut directory = hash_read_directory(pager, file_name, &header)?;
+
+    let hash = index_key_hash(key);
+    let bucket_idx = directory[hash_directory_slot(hash, header.global_depth)];
+    let (local_depth, mut entries, blocks) = hash_read_bucket(pager, file_name, bucket_idx)?;
+    entries.push((key.clone(), *address));
+
+    let same_hash = entries.iter().all(|(item, _)| index_key_hash(item) == hash);
+    let directory_full = local_depth as u32 == header.global_depth
+        && header.global_depth == HASH_INDEX_MAX_GLOBAL_DEPTH;
+
+    if hash_entries_fit(&entries) || same_hash || directory_full {
+        hash_write_bucket(pager, file_name, &mut header, &blocks, local_depth, entries);
+        hash_write_header(pager, file_name, &header);
+        return Ok(());
+    }
+
+    if local_depth as u32 == header.global_depth {
+        let copy = directory.clone();
+        directory.extend(copy);
+        header.global_depth += 1;
+    }
+
+    let bit = 1u64 << local_depth;
+    let (moved, kept): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .partition(|(item, _)| index_key_hash(item) & bit != 0);
+
+    header.page_count += 1;
+    let new_bucket_idx = header.page_count;
+
+    for (slot, target) in directory.iter_mut().enumerate() {
+        if *target == bucket_idx && (slot as u64) & bit != 0 {
+            *target = new_bucket_idx;
+        }
+    }
+
+    hash_write_bucket(pager, file_name, &mut header, &blocks, local_depth + 1, kept);
+    hash_write_bucket(pager, file_name, &mut header, &vec![new_bucket_idx], local_depth + 1, moved);
+    hash_write_directory(pager, file_name, &mut header, &directory);
+    hash_write_header(pager, file_name, &header);
+    return Ok(());
+}
+
+// Removes the entry of one row. Returns false when it is not on the index.
+pub fn hash_index_delete(pager: &mut Pager, file_name: &String, key: &Tuple, address: &TupleAddress) -> Result<bool, ExecutionError> {
+    let mut header = hash_read_header(pager, file_name)?;
+    let directory = hash_read_directory(pager, file_name, &header)?;
+
+    let bucket_idx = directory[hash_directory_slot(index_key_hash(key), header.global_depth)];
+    let (local_depth, mut entries, blocks) = hash_read_bucket(pager, file_name, bucket_idx)?;
+
+    let position = entries.iter().position(|(item, item_address)| {
+        index_key_compare(item, key) == Ordering::Equal && item_address == address
+    });
+
+    match position {
+        Some(position) => {
+            entries.remove(position);
+            hash_write_bucket(pager, file_name, &mut header, &blocks, local_depth, entries);
+            hash_write_header(pager, file_name, &header);
+            return Ok(true);
+        },
+        None => return Ok(false)
+    }
+}
+
+// Only whole keys can be looked up, hashing a prefix gives another bucket.
+pub fn hash_index_search(pager: &Pager, file_name: &String, key: &Tuple) -> Result<Vec<TupleAddress>, ExecutionError> {
+    let header = hash_read_header(pager, file_name)?;
+    let directory = hash_read_directory(pager, file_name, &header)?;
+
+    let bucket_idx = directory[hash_directory_slot(index_key_hash(key), header.global_depth)];
+    let (_, entries, _) = hash_read_bucket(pager, file_name, bucket_idx)?;
+
+    return Ok(entries
+        .into_iter()
+        .filter(|(item, _)| item.len() == key.len() && index_key_compare(item, key) == Ordering::Equal)
+        .map(|(_, address)| address)
+        .collect());
+}
+
+// Every entry of the index, in no particular order.
+pub fn hash_index_entries(pager: &Pager, file_name: &String) -> Result<Vec<(Tuple, TupleAddress)>, ExecutionError> {
+    let header = hash_read_header(pager, file_name)?;
+    let mut directory = hash_read_directory(pager, file_name, &header)?;
+    directory.sort();
+    directory.dedup();
+
+    let mut entries: Vec<(Tuple, TupleAddress)> = Vec::new();
+    for bucket_idx in directory.into_iter() {
+        let (_, mut bucket_entries, _) = hash_read_bucket(pager, file_name, bucket_idx)?;
+        entries.append(&mut bucket_entries);
+    }
+
+    return Ok(entries);
+}
+
+pub fn hash_index_global_depth(pager: &Pager, file_name: &String) -> Result<u32, ExecutionError> {
+    return Ok(hash_read_header(pager, file_name)?.global_depth);
+}
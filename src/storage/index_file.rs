@@ -0,0 +1,77 @@
+use crate::storage::Pager;
+use crate::storage::Tuple;
+use crate::storage::TupleAddress;
+use crate::storage::btree_create;
+use crate::storage::btree_insert;
+use crate::storage::btree_delete;
+use crate::storage::btree_search;
+use crate::storage::btree_entries;
+use crate::storage::hash_index_create;
+use crate::storage::hash_index_insert;
+use crate::storage::hash_index_delete;
+use crate::storage::hash_index_search;
+use crate::storage::hash_index_entries;
+
+use crate::utils::ExecutionError;
+
+// Index files are B+trees unless the index was created USING HASH.
+pub fn index_file_is_hash(index_type: &String) -> bool {
+    return index_type == "hash";
+}
+
+pub fn index_file_create(pager: &mut Pager, file_name: &String, index_type: &String) {
+    if index_file_is_hash(index_type) {
+        hash_index_create(pager, file_name);
+    } else {
+        btree_create(pager, file_name);
+    }
+}
+
+pub fn index_file_insert(
+    pager: &mut Pager,
+    file_name: &String,
+    index_type: &String,
+    key: &Tuple,
+    address: &TupleAddress
+) -> Result<(), ExecutionError> {
+    if index_file_is_hash(index_type) {
+        return hash_index_insert(pager, file_name, key, address);
+    }
+    return btree_insert(pager, file_name, key, address);
+}
+
+pub fn index_file_delete(
+    pager: &mut Pager,
+    file_name: &String,
+    index_type: &String,
+    key: &Tuple,
+    address: &TupleAddress
+) -> Result<bool, ExecutionError> {
+    if index_file_is_hash(index_type) {
+        return hash_index_delete(pager, file_name, key, address);
+    }
+    return btree_delete(pager, file_name, key, address);
+}
+
+pub fn index_file_search(
+    pager: &Pager,
+    file_name: &String,
+    index_type: &String,
+    key: &Tuple
+) -> Result<Vec<TupleAddress>, ExecutionError> {
+    if index_file_is_hash(index_type) {
+        return hash_index_search(pager, file_name, key);
+    }
+    return btree_search(pager, file_name, key);
+}
+
+pub fn index_file_entries(
+    pager: &Pager,
+    file_name: &String,
+    index_type: &String
+) -> Result<Vec<(Tuple, TupleAddress)>, ExecutionError> {
+    if index_file_is_hash(index_type) {
+        return hash_index_entries(pager, file_name);
+    }
+    return btree_entries(pager, file_name);
+}
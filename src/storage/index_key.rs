@@ -0,0 +1,170 @@
+use std::cmp::Ordering;
+
+use ordered_float::OrderedFloat;
+
+use crate::storage::Data;
+use crate::storage::Tuple;
+use crate::storage::tuple_serialize;
+use crate::storage::decimal_pow10;
+use crate::storage::temporal_to_timestamp;
+use crate::storage::temporal_interval_micros;
+use crate::storage::json_cast;
+use crate::storage::json_normalize;
+use crate::storage::json_to_bytes;
+use crate::storage::INDEX_MAX_KEY_SIZE;
+
+use crate::utils::fnv1a;
+
+fn index_integer_key(key: &Data) -> Option<i128> {
+    return match *key {
+        Data::UnsignedTinyint(value) => Some(value as i128),
+        Data::UnsignedSmallint(value) => Some(value as i128),
+        Data::UnsignedInt(value) => Some(value as i128),
+        Data::UnsignedBigint(value) => Some(value as i128),
+        Data::SignedTinyint(value) => Some(value as i128),
+        Data::SignedSmallint(value) => Some(value as i128),
+        Data::SignedInt(value) => Some(value as i128),
+        Data::SignedBigint(value) => Some(value as i128),
+        _ => None
+    };
+}
+
+// Floats holding a whole number are keyed as that integer, so they hash like
+// the integers they compare equal to.
+fn index_float_key(key: &Data) -> Option<f64> {
+    if key.is_float() {
+        return key.to_float();
+    }
+    return None;
+}
+
+fn index_whole_float_key(value: f64) -> Option<i128> {
+    if value.fract() == 0.0 && value.abs() < 1e38 {
+        return Some(value as i128);
+    }
+    return None;
+}
+
+// Decimals without a fraction are keyed as their integer, the others as the
+// double they compare equal to against floats.
+fn index_decimal_key(key: &Data) -> Option<Result<i128, f64>> {
+    if let Data::Decimal(value, _, scale) = *key {
+        if value % decimal_pow10(scale) == 0 {
+            return Some(Ok(value / decimal_pow10(scale)));
+        }
+        return Some(Err(key.to_float().unwrap()));
+    }
+    return None;
+}
+
+fn index_string_key(key: &Data) -> Option<&String> {
+    return match key {
+        Data::Varchar(value) | Data::Text(value) => Some(value),
+        _ => None
+    };
+}
+
+// Index keys are tuples holding the indexed columns in the order they were
+// declared, a single column index has keys of one value.
+pub fn index_key_serialize(key: &Tuple) -> Vec<u8> {
+    return tuple_serialize(key);
+}
+
+pub fn index_key_size(key: &Tuple) -> usize {
+    return index_key_serialize(key).len();
+}
+
+pub fn index_key_fits(key: &Tuple) -> bool {
+    return index_key_size(key) <= INDEX_MAX_KEY_SIZE;
+}
+
+pub fn index_key_from_tuple(tuple: &Tuple, positions: &Vec<usize>) -> Tuple {
+    return positions.iter().map(|position| tuple.get(*position).unwrap().clone()).collect();
+}
+
+// How a key is shown on errors: the value alone or the values in parentheses.
+pub fn index_key_format(key: &Tuple) -> String {
+    let values: Vec<String> = key.iter().map(|value| value.to_string()).collect();
+
+    if values.len() == 1 {
+        return values[0].clone();
+    }
+    return format!("({})", values.join(", "));
+}
+
+// Integers compare by value whatever their width, floats against any number
+// compare as doubles, and VARCHAR and TEXT compare as strings, so a literal
+// finds the rows no matter how the column stored it. Decimals and anything
+// else fall back to the Ord of Data (NULL goes first), which never panics
+// on values of different variants.
+pub fn index_value_compare(a: &Data, b: &Data) -> Ordering {
+    if let (Some(a), Some(b)) = (index_integer_key(a), index_integer_key(b)) {
+        return a.cmp(&b);
+    }
+    if index_float_key(a).is_some() || index_float_key(b).is_some() {
+        if let (Some(a), Some(b)) = (a.to_float(), b.to_float()) {
+            return OrderedFloat(a).cmp(&OrderedFloat(b));
+        }
+    }
+    if let (Some(a), Some(b)) = (index_string_key(a), index_string_key(b)) {
+        return a.cmp(b);
+    }
+    return a.cmp(b);
+}
+
+// Keys compare value by value. A key that is a prefix of the other compares
+// equal to it, which lets a bound on the leading columns match every key
+// starting with them.
+pub fn index_key_compare(a: &Tuple, b: &Tuple) -> Ordering {
+    for (a_value, b_value) in a.iter().zip(b.iter()) {
+        let ordering = index_value_compare(a_value, b_value);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    return Ordering::Equal;
+}
+
+// Keys that compare equal have the same hash.
+pub fn index_key_hash(key: &Tuple) -> u64 {
+    let mut bytes: Vec<u8> = Vec::new();
+
+    for value in key.iter() {
+        let mut float_key = index_float_key(value);
+        let mut integer_key = index_integer_key(value).or(float_key.and_then(index_whole_float_key));
+
+        match index_decimal_key(value) {
+            Some(Ok(value)) => integer_key = Some(value),
+            Some(Err(value)) => float_key = Some(value),
+            None => {}
+        }
+
+        if let Some(value) = integer_key {
+            bytes.push(b'i');
+            bytes.append(&mut value.to_be_bytes().to_vec());
+        } else if let Some(value) = float_key {
+            bytes.push(b'f');
+            bytes.append(&mut value.to_be_bytes().to_vec());
+        } else if let Some(value) = temporal_to_timestamp(value) {
+            // Dates hash as the timestamp of their midnight.
+            bytes.push(b't');
+            bytes.append(&mut value.to_be_bytes().to_vec());
+        } else if let Data::Interval(months, days, micros) = *value {
+            bytes.push(b'v');
+            bytes.append(&mut temporal_interval_micros(months, days, micros).to_be_bytes().to_vec());
+        } else if let (Data::Json(_) | Data::Jsonb(_), Some(json)) = (value, json_cast(value)) {
+            // JSON texts hash as the JSONB they compare as.
+            bytes.push(b'j');
+            bytes.append(&mut json_to_bytes(&json_normalize(json)));
+        } else if let Some(value) = index_string_key(value) {
+            bytes.push(b's');
+            bytes.append(&mut (value.len() as u64).to_be_bytes().to_vec());
+            bytes.append(&mut value.as_bytes().to_vec());
+        } else {
+            bytes.push(b'd');
+            bytes.append(&mut tuple_serialize(&vec![value.clone()]));
+        }
+    }
+
+    return fnv1a(&bytes);
+}
@@ -0,0 +1,587 @@
+use std::cmp::Ordering;
+
+use ordered_float::OrderedFloat;
+
+use crate::storage::Data;
+
+use crate::utils::v_u8_to_u32;
+
+// JSON columns keep the text they were given, JSONB columns the parsed
+// value, with the keys of each object sorted and without duplicates.
+// Numbers keep the digits they were written with and compare by value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonValue {
+    Null,
+    Boolean(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>)
+}
+
+// Deepest nesting of arrays and objects read from a JSON text.
+pub const JSON_MAX_DEPTH: usize = 256;
+
+fn skip_whitespace(bytes: &[u8], position: &mut usize) {
+    while *position < bytes.len() && matches!(bytes[*position], b' ' | b'\t' | b'\n' | b'\r') {
+        *position += 1;
+    }
+}
+
+fn parse_literal(bytes: &[u8], position: &mut usize, literal: &str, value: JsonValue) -> Option<JsonValue> {
+    if !bytes[*position..].starts_with(literal.as_bytes()) {
+        return None;
+    }
+    *position += literal.len();
+    return Some(value);
+}
+
+fn parse_digits(bytes: &[u8], position: &mut usize) -> usize {
+    let start = *position;
+    while *position < bytes.len() && bytes[*position].is_ascii_digit() {
+        *position += 1;
+    }
+    return *position - start;
+}
+
+fn parse_number(bytes: &[u8], position: &mut usize) -> Option<JsonValue> {
+    let start = *position;
+
+    if bytes[*position] == b'-' {
+        *position += 1;
+    }
+    let integer_start = *position;
+    if parse_digits(bytes, position) == 0 || (bytes[integer_start] == b'0' && *position - integer_start > 1) {
+        return None;
+    }
+    if bytes.get(*position) == Some(&b'.') {
+        *position += 1;
+        if parse_digits(bytes, position) == 0 {
+            return None;
+        }
+    }
+    if matches!(bytes.get(*position), Some(b'e' | b'E')) {
+        *position += 1;
+        if matches!(bytes.get(*position), Some(b'+' | b'-')) {
+            *position += 1;
+        }
+        if parse_digits(bytes, position) == 0 {
+            return None;
+        }
+    }
+
+    return Some(JsonValue::Number(String::from_utf8(bytes[start..*position].to_vec()).ok()?));
+}
+
+fn parse_hex4(bytes: &[u8], position: &mut usize) -> Option<u32> {
+    let digits = std::str::from_utf8(bytes.get(*position..(*position + 4))?).ok()?;
+    *position += 4;
+    return u32::from_str_radix(digits, 16).ok();
+}
+
+fn parse_string(bytes: &[u8], position: &mut usize) -> Option<String> {
+    let mut string: Vec<u8> = Vec::new();
+    *position += 1;
+
+    loop {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+
+        match byte {
+            b'"' => return String::from_utf8(string).ok(),
+            b'\\' => {
+                let escaped = *bytes.get(*position)?;
+                *position += 1;
+
+                let character = match escaped {
+                    b'"' => '"',
+                    b'\\' => '\\',
+                    b'/' => '/',
+                    b'b' => '\u{8}',
+                    b'f' => '\u{c}',
+                    b'n' => '\n',
+                    b'r' => '\r',
+                    b't' => '\t',
+                    b'u' => {
+                        let high = parse_hex4(bytes, position)?;
+                        if (0xd800..0xdc00).contains(&high) {
+                            if !bytes[*position..].starts_with(b"\\u") {
+                                return None;
+                            }
+                            *position += 2;
+                            let low = parse_hex4(bytes, position)?;
+                            if !(0xdc00..0xe000).contains(&low) {
+                                return None;
+                            }
+                            char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))?
+                        } else {
+                            char::from_u32(high)?
+                        }
+                    },
+                    _ => return None
+                };
+                let mut buffer = [0u8; 4];
+                string.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+            },
+            byte if byte < 0x20 => return None,
+            byte => string.push(byte)
+        }
+    }
+}
+
+fn parse_value(bytes: &[u8], position: &mut usize, depth: usize) -> Option<JsonValue> {
+    if depth > JSON_MAX_DEPTH {
+        return None;
+    }
+    skip_whitespace(bytes, position);
+
+    return match *bytes.get(*position)? {
+        b'n' => parse_literal(bytes, position, "null", JsonValue::Null),
+        b't' => parse_literal(bytes, position, "true", JsonValue::Boolean(true)),
+        b'f' => parse_literal(bytes, position, "false", JsonValue::Boolean(false)),
+        b'"' => Some(JsonValue::String(parse_string(bytes, position)?)),
+        b'-' | b'0'..=b'9' => parse_number(bytes, position),
+        b'[' => {
+            let mut items: Vec<JsonValue> = Vec::new();
+            *position += 1;
+            skip_whitespace(bytes, position);
+
+            if bytes.get(*position) == Some(&b']') {
+                *position += 1;
+                return Some(JsonValue::Array(items));
+            }
+            loop {
+                items.push(parse_value(bytes, position, depth + 1)?);
+                skip_whitespace(bytes, position);
+
+                match *bytes.get(*position)? {
+                    b',' => *position += 1,
+                    b']' => {
+                        *position += 1;
+                        return Some(JsonValue::Array(items));
+                    },
+                    _ => return None
+                }
+            }
+        },
+        b'{' => {
+            let mut pairs: Vec<(String, JsonValue)> = Vec::new();
+            *position += 1;
+            skip_whitespace(bytes, position);
+
+            if bytes.get(*position) == Some(&b'}') {
+                *position += 1;
+                return Some(JsonValue::Object(pairs));
+            }
+            loop {
+                skip_whitespace(bytes, position);
+                if bytes.get(*position) != Some(&b'"') {
+                    return None;
+                }
+                let key = parse_string(bytes, position)?;
+
+                skip_whitespace(bytes, position);
+                if bytes.get(*position) != Some(&b':') {
+                    return None;
+                }
+                *position += 1;
+                pairs.push((key, parse_value(bytes, position, depth + 1)?));
+                skip_whitespace(bytes, position);
+
+                match *bytes.get(*position)? {
+                    b',' => *position += 1,
+                    b'}' => {
+                        *position += 1;
+                        return Some(JsonValue::Object(pairs));
+                    },
+                    _ => return None
+                }
+            }
+        },
+        _ => None
+    };
+}
+
+// A whole JSON text, None when it is not valid.
+pub fn json_parse(value: &str) -> Option<JsonValue> {
+    let bytes = value.as_bytes();
+    let mut position = 0;
+
+    let json = parse_value(bytes, &mut position, 0)?;
+    skip_whitespace(bytes, &mut position);
+
+    if position != bytes.len() {
+        return None;
+    }
+    return Some(json);
+}
+
+// The value as JSONB keeps it: the last of the duplicated keys wins and the
+// keys go shorter first, then in byte order.
+pub fn json_normalize(value: JsonValue) -> JsonValue {
+    return match value {
+        JsonValue::Array(items) => JsonValue::Array(items.into_iter().map(json_normalize).collect()),
+        JsonValue::Object(pairs) => {
+            let mut normalized: Vec<(String, JsonValue)> = Vec::new();
+            for (key, value) in pairs.into_iter() {
+                normalized.retain(|(other_key, _)| *other_key != key);
+                normalized.push((key, json_normalize(value)));
+            }
+            normalized.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+            JsonValue::Object(normalized)
+        },
+        other => other
+    };
+}
+
+fn format_string(value: &str) -> String {
+    let mut string = String::from("\"");
+
+    for character in value.chars() {
+        match character {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            '\u{8}' => string.push_str("\\b"),
+            '\u{c}' => string.push_str("\\f"),
+            character if (character as u32) < 0x20 => string.push_str(&format!("\\u{:04x}", character as u32)),
+            character => string.push(character)
+        }
+    }
+    string.push('"');
+    return string;
+}
+
+// JSON text with a space after each comma and colon.
+pub fn json_format(value: &JsonValue) -> String {
+    return match value {
+        JsonValue::Null => String::from("null"),
+        JsonValue::Boolean(value) => format!("{}", value),
+        JsonValue::Number(value) => value.clone(),
+        JsonValue::String(value) => format_string(value),
+        JsonValue::Array(items) => {
+            let items: Vec<String> = items.iter().map(json_format).collect();
+            format!("[{}]", items.join(", "))
+        },
+        JsonValue::Object(pairs) => {
+            let pairs: Vec<String> = pairs
+                .iter()
+                .map(|(key, value)| format!("{}: {}", format_string(key), json_format(value)))
+                .collect();
+            format!("{{{}}}", pairs.join(", "))
+        }
+    };
+}
+
+fn append_sized(bytes: &mut Vec<u8>, value: &[u8]) {
+    bytes.append(&mut (value.len() as u32).to_be_bytes().to_vec());
+    bytes.extend_from_slice(value);
+}
+
+// JSONB on disk: a tag for each value, then numbers and strings with their
+// length, arrays with their count of items and objects with their count of
+// keys, each key before its value.
+pub fn json_to_bytes(value: &JsonValue) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::new();
+
+    match value {
+        JsonValue::Null => bytes.push(0),
+        JsonValue::Boolean(value) => bytes.push(1 + *value as u8),
+        JsonValue::Number(value) => {
+            bytes.push(3);
+            append_sized(&mut bytes, value.as_bytes());
+        },
+        JsonValue::String(value) => {
+            bytes.push(4);
+            append_sized(&mut bytes, value.as_bytes());
+        },
+        JsonValue::Array(items) => {
+            bytes.push(5);
+            bytes.append(&mut (items.len() as u32).to_be_bytes().to_vec());
+            for item in items.iter() {
+                bytes.append(&mut json_to_bytes(item));
+            }
+        },
+        JsonValue::Object(pairs) => {
+            bytes.push(6);
+            bytes.append(&mut (pairs.len() as u32).to_be_bytes().to_vec());
+            for (key, value) in pairs.iter() {
+                append_sized(&mut bytes, key.as_bytes());
+                bytes.append(&mut json_to_bytes(value));
+            }
+        }
+    }
+    return bytes;
+}
+
+fn read_sized(bytes: &Vec<u8>, position: &mut usize) -> String {
+    let size = v_u8_to_u32(bytes, *position) as usize;
+    let string = String::from_utf8(bytes[(*position + 4)..(*position + 4 + size)].to_vec()).unwrap();
+    *position += 4 + size;
+    return string;
+}
+
+// Reads the value written by json_to_bytes at the position, leaving the
+// position after it.
+pub fn json_from_bytes(bytes: &Vec<u8>, position: &mut usize) -> JsonValue {
+    let tag = bytes[*position];
+    *position += 1;
+
+    return match tag {
+        1 => JsonValue::Boolean(false),
+        2 => JsonValue::Boolean(true),
+        3 => JsonValue::Number(read_sized(bytes, position)),
+        4 => JsonValue::String(read_sized(bytes, position)),
+        5 => {
+            let count = v_u8_to_u32(bytes, *position) as usize;
+            *position += 4;
+            JsonValue::Array((0..count).map(|_| json_from_bytes(bytes, position)).collect())
+        },
+        6 => {
+            let count = v_u8_to_u32(bytes, *position) as usize;
+            *position += 4;
+            JsonValue::Object(
+                (0..count)
+                    .map(|_| {
+                        let key = read_sized(bytes, position);
+                        (key, json_from_bytes(bytes, position))
+                    })
+                    .collect()
+            )
+        },
+        _ => JsonValue::Null
+    };
+}
+
+// JSON values and strings holding one, None for anything else.
+pub fn json_cast(value: &Data) -> Option<JsonValue> {
+    return match value {
+        Data::Jsonb(value) => Some(value.clone()),
+        Data::Json(text) | Data::Varchar(text) | Data::Text(text) => json_parse(text),
+        _ => None
+    };
+}
+
+// Any value as JSON, the way to_jsonb converts it: numbers and booleans as
+// themselves, JSON values as they are and the rest as strings of their text.
+pub fn json_from_data(value: &Data) -> JsonValue {
+    return match value {
+        Data::Null | Data::Undefined => JsonValue::Null,
+        Data::Boolean(value) => JsonValue::Boolean(*value),
+        Data::Jsonb(value) => value.clone(),
+        Data::Json(text) => json_parse(text).map(json_normalize).unwrap_or(JsonValue::Null),
+        Data::Float32(value) if !value.0.is_finite() => JsonValue::String(value.to_string()),
+        Data::Float64(value) if !value.0.is_finite() => JsonValue::String(value.to_string()),
+        other if other.to_float().is_some() => JsonValue::Number(other.to_string()),
+        other => JsonValue::String(other.to_string())
+    };
+}
+
+// Values taken out of a JSONB stay JSONB, those of a JSON become JSON.
+fn json_wrap(source: &Data, value: JsonValue) -> Data {
+    if matches!(source, Data::Jsonb(_)) {
+        return Data::Jsonb(value);
+    }
+    return Data::Json(json_format(&value));
+}
+
+// Strings as their text, JSON null as NULL and the rest as JSON text.
+fn json_text(value: JsonValue) -> Data {
+    return match value {
+        JsonValue::Null => Data::Null,
+        JsonValue::String(value) => Data::Text(value),
+        other => Data::Text(json_format(&other))
+    };
+}
+
+// The item of an array at an index, counting from the end when negative,
+// or the value of a key in an object.
+fn json_step(value: &JsonValue, step: &Data) -> Option<JsonValue> {
+    return match (value, step) {
+        (JsonValue::Array(items), step) if step.to_integer().is_some() => {
+            let index = step.to_integer().unwrap();
+            let index = if index < 0 { items.len() as i128 + index } else { index };
+            items.get(usize::try_from(index).ok()?).cloned()
+        },
+        (JsonValue::Object(pairs), Data::Varchar(key) | Data::Text(key)) => {
+            pairs.iter().rev().find(|(other_key, _)| other_key == key).map(|(_, value)| value.clone())
+        },
+        _ => None
+    };
+}
+
+// Steps of a path written as a text array, such as '{items,0,name}'. Any
+// other text or integer is a path of one step, as json_extract_path gives
+// them.
+fn json_path_steps(path: &Data) -> Option<Vec<Data>> {
+    let path = match path {
+        Data::Varchar(path) | Data::Text(path) => path.trim(),
+        other if other.to_integer().is_some() => return Some(vec![other.clone()]),
+        _ => return None
+    };
+    let Some(inner) = path.strip_prefix('{').and_then(|path| path.strip_suffix('}')) else {
+        return Some(vec![Data::Varchar(path.to_string())]);
+    };
+    if inner.trim().len() == 0 {
+        return Some(Vec::new());
+    }
+
+    return Some(
+        inner
+            .split(',')
+            .map(|step| {
+                let step = step.trim();
+                let step = step.strip_prefix('"').and_then(|step| step.strip_suffix('"')).unwrap_or(step);
+                Data::Varchar(step.to_string())
+            })
+            .collect()
+    );
+}
+
+fn json_follow(value: JsonValue, steps: &Vec<Data>) -> Option<JsonValue> {
+    let mut value = value;
+
+    for step in steps.iter() {
+        value = match (&value, step) {
+            (JsonValue::Array(_), Data::Varchar(index)) => json_step(&value, &Data::from_integer(index.parse::<i128>().ok()?)?)?,
+            _ => json_step(&value, step)?
+        };
+    }
+    return Some(value);
+}
+
+// json -> key, NULL when the key or the index is not there.
+pub fn json_get(value: &Data, step: &Data) -> Data {
+    return match json_cast(value).and_then(|json| json_step(&json, step)) {
+        Some(json) => json_wrap(value, json),
+        None => Data::Null
+    };
+}
+
+// json ->> key, the same value as text.
+pub fn json_get_text(value: &Data, step: &Data) -> Data {
+    return match json_cast(value).and_then(|json| json_step(&json, step)) {
+        Some(json) => json_text(json),
+        None => Data::Null
+    };
+}
+
+// json #> path.
+pub fn json_get_path(value: &Data, path: &Data) -> Data {
+    let steps = json_path_steps(path);
+    return match json_cast(value).zip(steps).and_then(|(json, steps)| json_follow(json, &steps)) {
+        Some(json) => json_wrap(value, json),
+        None => Data::Null
+    };
+}
+
+// json #>> path.
+pub fn json_get_path_text(value: &Data, path: &Data) -> Data {
+    let steps = json_path_steps(path);
+    return match json_cast(value).zip(steps).and_then(|(json, steps)| json_follow(json, &steps)) {
+        Some(json) => json_text(json),
+        None => Data::Null
+    };
+}
+
+fn json_value_contains(a: &JsonValue, b: &JsonValue) -> bool {
+    return match (a, b) {
+        (JsonValue::Object(a_pairs), JsonValue::Object(b_pairs)) => b_pairs.iter().all(|(key, b_value)| {
+            a_pairs.iter().any(|(other_key, a_value)| other_key == key && json_value_contains(a_value, b_value))
+        }),
+        (JsonValue::Array(a_items), JsonValue::Array(b_items)) => {
+            b_items.iter().all(|b_item| a_items.iter().any(|a_item| json_value_contains(a_item, b_item)))
+        },
+        (a, b) => json_value_compare(a, b) == Ordering::Equal
+    };
+}
+
+// a @> b: every key of b is in a holding a value that contains its value,
+// every item of b is contained in some item of a, and other values are
+// equal. An array also contains the scalars among its items.
+pub fn json_contains(a: &Data, b: &Data) -> Data {
+    let (Some(a), Some(b)) = (json_cast(a), json_cast(b)) else { return Data::Null };
+
+    if let (JsonValue::Array(items), JsonValue::Null | JsonValue::Boolean(_) | JsonValue::Number(_) | JsonValue::String(_)) = (&a, &b) {
+        return Data::Boolean(items.iter().any(|item| json_value_compare(item, &b) == Ordering::Equal));
+    }
+    return Data::Boolean(json_value_contains(&a, &b));
+}
+
+pub fn json_array_length(value: &Data) -> Data {
+    return match json_cast(value) {
+        Some(JsonValue::Array(items)) => Data::from_integer(items.len() as i128).unwrap_or(Data::Null),
+        _ => Data::Null
+    };
+}
+
+pub fn json_typeof(value: &Data) -> Data {
+    let name = match json_cast(value) {
+        Some(JsonValue::Null) => "null",
+        Some(JsonValue::Boolean(_)) => "boolean",
+        Some(JsonValue::Number(_)) => "number",
+        Some(JsonValue::String(_)) => "string",
+        Some(JsonValue::Array(_)) => "array",
+        Some(JsonValue::Object(_)) => "object",
+        None => return Data::Null
+    };
+    return Data::Varchar(name.to_string());
+}
+
+fn json_rank(value: &JsonValue) -> u8 {
+    return match value {
+        JsonValue::Null => 0,
+        JsonValue::String(_) => 1,
+        JsonValue::Number(_) => 2,
+        JsonValue::Boolean(_) => 3,
+        JsonValue::Array(_) => 4,
+        JsonValue::Object(_) => 5
+    };
+}
+
+fn json_number(value: &String) -> OrderedFloat<f64> {
+    return OrderedFloat(value.parse::<f64>().unwrap_or(0.0));
+}
+
+// The order of JSONB: objects, then arrays, booleans, numbers, strings and
+// null. Arrays and objects with fewer items go first, the others compare
+// item by item, objects key by key.
+pub fn json_value_compare(a: &JsonValue, b: &JsonValue) -> Ordering {
+    return match (a, b) {
+        (JsonValue::Boolean(a), JsonValue::Boolean(b)) => a.cmp(b),
+        (JsonValue::Number(a), JsonValue::Number(b)) => json_number(a).cmp(&json_number(b)),
+        (JsonValue::String(a), JsonValue::String(b)) => a.cmp(b),
+        (JsonValue::Array(a), JsonValue::Array(b)) => a.len().cmp(&b.len()).then_with(|| {
+            a.iter()
+                .zip(b.iter())
+                .map(|(a, b)| json_value_compare(a, b))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        }),
+        (JsonValue::Object(_), JsonValue::Object(_)) => {
+            let (JsonValue::Object(a), JsonValue::Object(b)) = (json_normalize(a.clone()), json_normalize(b.clone())) else {
+                return Ordering::Equal;
+            };
+            a.len().cmp(&b.len()).then_with(|| {
+                a.iter()
+                    .zip(b.iter())
+                    .map(|((a_key, a_value), (b_key, b_value))| {
+                        a_key.cmp(b_key).then_with(|| json_value_compare(a_value, b_value))
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
+        },
+        (a, b) => json_rank(a).cmp(&json_rank(b))
+    };
+}
+
+// JSON values compare as JSONB does, strings against them are read as JSON.
+pub fn json_compare(a: &Data, b: &Data) -> Option<Ordering> {
+    if !matches!(a, Data::Json(_) | Data::Jsonb(_)) && !matches!(b, Data::Json(_) | Data::Jsonb(_)) {
+        return None;
+    }
+    return Some(json_value_compare(&json_cast(a)?, &json_cast(b)?));
+}
@@ -1,26 +1,141 @@
 pub mod tuple;
+pub mod decimal;
+pub mod temporal;
+pub mod bytes;
+pub mod uuid;
+pub mod json;
 
 pub mod header;
 pub mod pager;
+pub mod buffer_pool;
 pub mod page;
+pub mod free_space_map;
+pub mod index_key;
+pub mod btree;
+pub mod hash_index;
+pub mod index_file;
 pub mod result_set;
+pub mod sort;
 
 pub mod expression;
+pub mod aggregate;
 
 pub mod os_interface;
+pub mod wal;
 
 pub mod format_database_name;
 pub mod format_table_name;
+pub mod format_wal_name;
+pub mod format_fsm_name;
+pub mod format_index_name;
+pub mod format_sort_name;
 
 pub use self::format_database_name::format_database_name;
 pub use self::format_table_name::format_table_name;
+pub use self::format_wal_name::format_wal_name;
+pub use self::format_fsm_name::format_fsm_name;
+pub use self::format_index_name::format_index_name;
+pub use self::format_sort_name::format_sort_name;
 
 pub use self::result_set::ResultSet;
 pub use self::result_set::ResultSetType;
 
+pub use self::sort::SortKey;
+pub use self::sort::sort_compare;
+pub use self::sort::sort_tuples;
+
 pub use self::expression::Expression;
+pub use self::expression::Expression0Type;
 pub use self::expression::Expression1Type;
 pub use self::expression::Expression2Type;
+pub use self::expression::column_name_matches;
+
+pub use self::aggregate::Aggregate;
+pub use self::aggregate::AggregateType;
+pub use self::aggregate::AggregateState;
+pub use self::aggregate::aggregate_state_new;
+pub use self::aggregate::aggregate_argument_count;
+pub use self::aggregate::aggregate_update;
+pub use self::aggregate::aggregate_result;
+
+pub use self::decimal::DECIMAL_MAX_PRECISION;
+pub use self::decimal::DECIMAL_DIV_EXTRA_SCALE;
+pub use self::decimal::decimal_pow10;
+pub use self::decimal::decimal_digits;
+pub use self::decimal::decimal_rescale;
+pub use self::decimal::decimal_parse;
+pub use self::decimal::decimal_format;
+pub use self::decimal::decimal_to_float;
+pub use self::decimal::decimal_compare;
+pub use self::decimal::decimal_add;
+pub use self::decimal::decimal_sub;
+pub use self::decimal::decimal_mul;
+pub use self::decimal::decimal_div;
+pub use self::decimal::decimal_to_bytes;
+pub use self::decimal::decimal_from_bytes;
+
+pub use self::temporal::MICROS_PER_SECOND;
+pub use self::temporal::MICROS_PER_MINUTE;
+pub use self::temporal::MICROS_PER_HOUR;
+pub use self::temporal::MICROS_PER_DAY;
+pub use self::temporal::DAYS_PER_MONTH;
+pub use self::temporal::temporal_is_leap_year;
+pub use self::temporal::temporal_days_in_month;
+pub use self::temporal::temporal_days_from_civil;
+pub use self::temporal::temporal_civil_from_days;
+pub use self::temporal::temporal_now;
+pub use self::temporal::temporal_parse_date;
+pub use self::temporal::temporal_parse_time;
+pub use self::temporal::temporal_parse_timestamp;
+pub use self::temporal::temporal_parse_interval;
+pub use self::temporal::temporal_format_date;
+pub use self::temporal::temporal_format_time;
+pub use self::temporal::temporal_format_timestamp;
+pub use self::temporal::temporal_format_interval;
+pub use self::temporal::temporal_interval_micros;
+pub use self::temporal::temporal_add_interval;
+pub use self::temporal::temporal_is_temporal;
+pub use self::temporal::temporal_to_timestamp;
+pub use self::temporal::temporal_cast;
+pub use self::temporal::temporal_compare;
+pub use self::temporal::temporal_add;
+pub use self::temporal::temporal_sub;
+pub use self::temporal::temporal_mul;
+pub use self::temporal::temporal_is_extract_field;
+pub use self::temporal::temporal_is_trunc_field;
+pub use self::temporal::temporal_extract;
+pub use self::temporal::temporal_trunc;
+
+pub use self::bytes::BYTES_HEX_PREFIX;
+pub use self::bytes::bytes_parse_hex;
+pub use self::bytes::bytes_format_hex;
+pub use self::bytes::bytes_cast;
+pub use self::bytes::bytes_compare;
+
+pub use self::uuid::uuid_parse;
+pub use self::uuid::uuid_format;
+pub use self::uuid::uuid_generate;
+pub use self::uuid::uuid_cast;
+pub use self::uuid::uuid_compare;
+
+pub use self::json::JsonValue;
+pub use self::json::JSON_MAX_DEPTH;
+pub use self::json::json_parse;
+pub use self::json::json_normalize;
+pub use self::json::json_format;
+pub use self::json::json_to_bytes;
+pub use self::json::json_from_bytes;
+pub use self::json::json_cast;
+pub use self::json::json_get;
+pub use self::json::json_get_text;
+pub use self::json::json_get_path;
+pub use self::json::json_get_path_text;
+pub use self::json::json_contains;
+pub use self::json::json_array_length;
+pub use self::json::json_typeof;
+pub use self::json::json_from_data;
+pub use self::json::json_value_compare;
+pub use self::json::json_compare;
 
 pub use self::tuple::Tuple;
 pub use self::tuple::Data;
@@ -36,8 +151,25 @@ pub use self::tuple::get_tuple_table;
 pub use self::tuple::get_tuple_sequence;
 pub use self::tuple::get_tuple_sequence_without_id;
 pub use self::tuple::get_tuple_index;
+pub use self::tuple::get_tuple_foreign_key;
+pub use self::tuple::get_tuple_check_constraint;
 
 pub use self::page::Page;
+pub use self::page::Slot;
+pub use self::page::OverflowPointer;
+pub use self::page::OverflowChunk;
+pub use self::page::OVERFLOW_CHUNK_SIZE;
+pub use self::page::MAX_INLINE_TUPLE_SIZE;
+pub use self::page::page_new_overflow;
+pub use self::page::TupleAddress;
+pub use self::page::slot_size;
+pub use self::page::slot_reserved_size;
+pub use self::page::page_reserved_left;
+pub use self::page::page_fits;
+pub use self::page::page_insert_slot;
+pub use self::page::page_read_slot;
+pub use self::page::page_update_slot;
+pub use self::page::page_delete_slot;
 pub use self::page::page_new;
 pub use self::page::page_insert_tuples;
 pub use self::page::page_update_tuples;
@@ -50,17 +182,100 @@ pub use self::page::page_serialize;
 pub use self::page::page_deserialize;
 pub use self::page::page_display;
 
+pub use self::free_space_map::FreeSpaceMap;
+pub use self::free_space_map::FSM_CATEGORY_SIZE;
+pub use self::free_space_map::fsm_new;
+pub use self::free_space_map::fsm_category;
+pub use self::free_space_map::fsm_set;
+pub use self::free_space_map::fsm_get;
+pub use self::free_space_map::fsm_find;
+pub use self::free_space_map::fsm_serialize;
+pub use self::free_space_map::fsm_deserialize;
+
+pub use self::index_key::index_key_serialize;
+pub use self::index_key::index_key_size;
+pub use self::index_key::index_key_fits;
+pub use self::index_key::index_key_from_tuple;
+pub use self::index_key::index_key_format;
+pub use self::index_key::index_value_compare;
+pub use self::index_key::index_key_compare;
+pub use self::index_key::index_key_hash;
+
+pub use self::btree::INDEX_MAX_KEY_SIZE;
+pub use self::btree::BTreeHeader;
+pub use self::btree::BTreeNode;
+pub use self::btree::BTreeEntry;
+pub use self::btree::btree_node_size;
+pub use self::btree::btree_node_serialize;
+pub use self::btree::btree_node_deserialize;
+pub use self::btree::btree_create;
+pub use self::btree::btree_insert;
+pub use self::btree::btree_delete;
+pub use self::btree::btree_search;
+pub use self::btree::btree_range;
+pub use self::btree::btree_entries;
+pub use self::btree::btree_height;
+
+pub use self::hash_index::HASH_INDEX_MAX_GLOBAL_DEPTH;
+pub use self::hash_index::HashIndexHeader;
+pub use self::hash_index::HashBucket;
+pub use self::hash_index::hash_bucket_serialize;
+pub use self::hash_index::hash_bucket_deserialize;
+pub use self::hash_index::hash_index_create;
+pub use self::hash_index::hash_index_insert;
+pub use self::hash_index::hash_index_delete;
+pub use self::hash_index::hash_index_search;
+pub use self::hash_index::hash_index_entries;
+pub use self::hash_index::hash_index_global_depth;
+
+pub use self::index_file::index_file_is_hash;
+pub use self::index_file::index_file_create;
+pub use self::index_file::index_file_insert;
+pub use self::index_file::index_file_delete;
+pub use self::index_file::index_file_search;
+pub use self::index_file::index_file_entries;
+
 pub use self::header::Header;
 pub use self::header::header_new;
 pub use self::header::header_serialize;
 pub use self::header::header_deserialize;
 
+pub use self::buffer_pool::Frame;
+pub use self::buffer_pool::BufferPool;
+pub use self::buffer_pool::BufferPoolStats;
+pub use self::buffer_pool::buffer_pool_new;
+pub use self::buffer_pool::buffer_pool_fetch_page;
+pub use self::buffer_pool::buffer_pool_new_page;
+pub use self::buffer_pool::buffer_pool_unpin_page;
+pub use self::buffer_pool::buffer_pool_contains;
+pub use self::buffer_pool::buffer_pool_dirty_pages;
+pub use self::buffer_pool::buffer_pool_dirty_keys;
+pub use self::buffer_pool::buffer_pool_mark_clean;
+pub use self::buffer_pool::buffer_pool_shrink;
+pub use self::buffer_pool::buffer_pool_discard_pages;
+pub use self::buffer_pool::buffer_pool_stats;
+
 pub use self::pager::Pager;
 pub use self::pager::pager_new;
+pub use self::pager::pager_new_with_capacity;
+pub use self::pager::pager_discard;
+pub use self::pager::pager_vacuum;
+pub use self::pager::pager_stats;
 pub use self::pager::pager_insert_tuples;
 pub use self::pager::pager_update_tuples;
 pub use self::pager::pager_read_tuples;
+pub use self::pager::pager_read_tuples_with_address;
+pub use self::pager::pager_read_tuple;
+pub use self::pager::pager_update_tuple;
+pub use self::pager::pager_delete_tuple;
 pub use self::pager::pager_flush_page;
+pub use self::pager::pager_flush_blocks;
+pub use self::pager::pager_flush_all;
+pub use self::pager::pager_rollback;
+pub use self::pager::pager_read_block;
+pub use self::pager::pager_write_block;
+pub use self::pager::pager_write_ahead_log;
+pub use self::pager::pager_write_pages;
 pub use self::pager::pager_get_next_rowid;
 
 pub use self::os_interface::{ BLOCK_SIZE };
@@ -72,3 +287,16 @@ pub use self::os_interface::destroy_file;
 pub use self::os_interface::path_exists;
 pub use self::os_interface::create_folder;
 pub use self::os_interface::create_folder_if_not_exists;
+pub use self::os_interface::append_data;
+pub use self::os_interface::read_file;
+pub use self::os_interface::write_file;
+pub use self::os_interface::rename_file;
+pub use self::os_interface::file_size;
+pub use self::os_interface::truncate_file;
+pub use self::os_interface::sync_file;
+
+pub use self::wal::WalRecord;
+pub use self::wal::wal_append_records;
+pub use self::wal::wal_read_transactions;
+pub use self::wal::wal_checkpoint;
+pub use self::wal::wal_recover;
@@ -3,10 +3,14 @@ use std::path::Path;
 use std::fs::create_dir;
 use std::fs::remove_dir_all;
 use std::fs::remove_file;
+use std::fs::rename;
+use std::fs::metadata;
 use std::fs::OpenOptions;
 use std::io::{Read, Seek, SeekFrom, Write};
+use std::io::ErrorKind;
 
 use crate::utils::Logger;
+use crate::utils::ExecutionError;
 
 pub const BLOCK_SIZE: usize = 4096;
 
@@ -43,6 +47,18 @@ pub fn destroy_file(file_name: &String) {
     let _ = remove_file(file_name);
 }
 
+pub fn rename_file(from_name: &String, to_name: &String) {
+    Logger::debug(format!("rename file {} to {}", from_name, to_name).leak());
+    rename(from_name, to_name).expect("failed to rename file");
+}
+
+pub fn file_size(file_name: &String) -> u64 {
+    return match metadata(file_name) {
+        Ok(data) => data.len(),
+        Err(_) => 0
+    };
+}
+
 pub fn write_data(file_name: &String, pos: u64, data: &[u8; BLOCK_SIZE]) {
     Logger::debug(format!("write data {} on position {}", file_name, pos).leak());
     let mut file = OpenOptions::new()
@@ -74,3 +90,59 @@ pub fn read_data(file_name: &String, pos: u64) -> [u8; BLOCK_SIZE] {
         }
     }
 }
+
+pub fn append_data(file_name: &String, data: &Vec<u8>) {
+    Logger::debug(format!("append {} bytes on {}", data.len(), file_name).leak());
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .unwrap();
+
+    file.write_all(data).expect("buffer overflow");
+}
+
+// A missing file reads as empty, any other failure is returned.
+pub fn read_file(file_name: &String) -> Result<Vec<u8>, ExecutionError> {
+    Logger::debug(format!("read file {}", file_name).leak());
+    let mut buffer = Vec::new();
+
+    let mut file = match OpenOptions::new().read(true).open(file_name) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(buffer),
+        Err(err) => return Err(ExecutionError::IoError(file_name.clone(), err.to_string()))
+    };
+
+    match file.read_to_end(&mut buffer) {
+        Ok(_) => Ok(buffer),
+        Err(err) => Err(ExecutionError::IoError(file_name.clone(), err.to_string()))
+    }
+}
+
+pub fn write_file(file_name: &String, data: &Vec<u8>) -> Result<(), ExecutionError> {
+    Logger::debug(format!("write {} bytes on {}", data.len(), file_name).leak());
+    let mut file = match OpenOptions::new().create(true).write(true).truncate(true).open(file_name) {
+        Ok(file) => file,
+        Err(err) => return Err(ExecutionError::IoError(file_name.clone(), err.to_string()))
+    };
+
+    match file.write_all(data) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(ExecutionError::IoError(file_name.clone(), err.to_string()))
+    }
+}
+
+pub fn truncate_file(file_name: &String, size: u64) {
+    Logger::debug(format!("truncate file {} to {} bytes", file_name, size).leak());
+    if let Ok(file) = OpenOptions::new().write(true).open(file_name) {
+        let _ = file.set_len(size);
+        let _ = file.sync_all();
+    }
+}
+
+pub fn sync_file(file_name: &String) {
+    Logger::debug(format!("sync file {}", file_name).leak());
+    if let Ok(file) = OpenOptions::new().write(true).open(file_name) {
+        let _ = file.sync_all();
+    }
+}
@@ -7,65 +7,261 @@ use crate::storage::tuple_serialize;
 use crate::storage::tuple_deserialize;
 
 use crate::utils::v_u8_to_u16;
+use crate::utils::v_u8_to_u32;
+use crate::utils::v_u8_to_u64;
 use crate::utils::v_u8_to_vec_u8;
 
+/*
+ * Slotted page. The first two bytes hold the slot count, followed by one u16
+ * entry per slot; records are packed from the end of the block backwards and
+ * the length of a record is the distance to the record of the previous slot.
+ *
+ * The two high bits of a slot entry tell what the record is, the low bits are
+ * the record offset:
+ *   00 tuple      a live row stored in its home slot
+ *   01 tombstone  a deleted row, empty record, the slot number stays reserved
+ *   10 forward    the row grew and lives elsewhere: [page u64][slot u16]
+ *   11 moved      the body of a forwarded row, only reachable through its
+ *                 forward slot
+ *
+ * Rows too big for a page live in a chain of overflow pages. Their tuple (or
+ * moved) record then only holds [total size u32][first overflow page u64] and
+ * the entry has the SLOT_OVERFLOW bit set.
+ *
+ * Overflow pages start with 0xFFFF instead of a slot count, followed by
+ * [next page u64][chunk size u16][chunk]. The last page of a chain points to
+ * page 0.
+ */
+
+pub const SLOT_KIND_MASK: u16 = 0xC000;
+pub const SLOT_OVERFLOW: u16 = 0x2000;
+pub const SLOT_OFFSET_MASK: u16 = 0x1FFF;
+
+pub const SLOT_TUPLE: u16 = 0x0000;
+pub const SLOT_TOMBSTONE: u16 = 0x4000;
+pub const SLOT_FORWARD: u16 = 0x8000;
+pub const SLOT_MOVED: u16 = 0xC000;
+const SLOT_MOVED_OVERFLOW: u16 = SLOT_MOVED | SLOT_OVERFLOW;
+
+const FORWARD_RECORD_SIZE: usize = 10;
+const OVERFLOW_RECORD_SIZE: usize = 12;
+
+const OVERFLOW_PAGE_MARK: u16 = 0xFFFF;
+const OVERFLOW_PAGE_HEADER_SIZE: usize = 12;
+
+// Biggest chunk of a row that one overflow page carries.
+pub const OVERFLOW_CHUNK_SIZE: usize = BLOCK_SIZE - OVERFLOW_PAGE_HEADER_SIZE;
+
+// Biggest serialized row kept inline, the one that fills an empty page.
+pub const MAX_INLINE_TUPLE_SIZE: usize = BLOCK_SIZE - 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TupleAddress {
+    pub page: u64,
+    pub slot: u16
+}
+
+impl TupleAddress {
+    pub fn new(page: u64, slot: u16) -> Self {
+        TupleAddress { page, slot }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowPointer {
+    pub size: u32,
+    pub page: u64
+}
+
+impl OverflowPointer {
+    pub fn new(size: u32, page: u64) -> Self {
+        OverflowPointer { size, page }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverflowChunk {
+    pub next_page: u64,
+    pub data: Vec<u8>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Slot {
+    Tuple(Tuple),
+    Tombstone,
+    Forward(TupleAddress),
+    Moved(Tuple),
+    Overflow(OverflowPointer),
+    MovedOverflow(OverflowPointer)
+}
+
 #[derive(Debug)]
 pub struct Page {
-    pub tuples: Vec<Tuple>,
+    pub slots: Vec<Slot>,
+    pub overflow: Option<OverflowChunk>
 }
 
 pub fn page_new() -> Page {
-    Page { tuples: Vec::new() }
+    Page { slots: Vec::new(), overflow: None }
+}
+
+pub fn page_new_overflow(next_page: u64, data: Vec<u8>) -> Page {
+    Page { slots: Vec::new(), overflow: Some(OverflowChunk { next_page, data }) }
+}
+
+fn slot_serialize(slot: &Slot) -> Vec<u8> {
+    match slot {
+        Slot::Tuple(tuple) | Slot::Moved(tuple) => tuple_serialize(tuple),
+        Slot::Tombstone => Vec::new(),
+        Slot::Forward(address) => {
+            let mut buffer: Vec<u8> = Vec::new();
+            buffer.append(&mut address.page.to_be_bytes().to_vec());
+            buffer.append(&mut address.slot.to_be_bytes().to_vec());
+            buffer
+        },
+        Slot::Overflow(pointer) | Slot::MovedOverflow(pointer) => {
+            let mut buffer: Vec<u8> = Vec::new();
+            buffer.append(&mut pointer.size.to_be_bytes().to_vec());
+            buffer.append(&mut pointer.page.to_be_bytes().to_vec());
+            buffer
+        }
+    }
+}
+
+fn slot_kind(slot: &Slot) -> u16 {
+    match slot {
+        Slot::Tuple(_) => SLOT_TUPLE,
+        Slot::Tombstone => SLOT_TOMBSTONE,
+        Slot::Forward(_) => SLOT_FORWARD,
+        Slot::Moved(_) => SLOT_MOVED,
+        Slot::Overflow(_) => SLOT_OVERFLOW,
+        Slot::MovedOverflow(_) => SLOT_MOVED_OVERFLOW
+    }
+}
+
+pub fn slot_size(slot: &Slot) -> usize {
+    match slot {
+        Slot::Tuple(tuple) | Slot::Moved(tuple) => tuple_serialize(tuple).len(),
+        Slot::Tombstone => 0,
+        Slot::Forward(_) => FORWARD_RECORD_SIZE,
+        Slot::Overflow(_) | Slot::MovedOverflow(_) => OVERFLOW_RECORD_SIZE
+    }
 }
 
 pub fn page_amount_left(page: &Page) -> u16 {
+    if page.overflow.is_some() {
+        return 0;
+    }
+
     let mut block_pointer = BLOCK_SIZE;
 
-    for (_, tuple) in page.tuples.iter().enumerate() {
-        let raw_tuple = tuple_serialize(tuple);
-        block_pointer -= raw_tuple.len();
+    for slot in page.slots.iter() {
+        block_pointer -= slot_size(slot);
     }
 
-    if block_pointer < 2*(page.tuples.len() + 1) {
+    if block_pointer < 2*(page.slots.len() + 1) {
         return 0;
     };
-    return (block_pointer - 2*(page.tuples.len() + 1)) as u16;
+    return (block_pointer - 2*(page.slots.len() + 1)) as u16;
 }
 
-fn page_add_tuple(page: &mut Page, tuple: &Tuple) {
-    page.tuples.push(tuple.clone());
+// Room a slot holds on to. A live tuple always keeps enough room to be turned
+// into a forward record, so a growing row can be moved without ever changing
+// its address.
+pub fn slot_reserved_size(slot: &Slot) -> usize {
+    match slot {
+        Slot::Tuple(_) | Slot::Overflow(_) => usize::max(slot_size(slot), FORWARD_RECORD_SIZE),
+        _ => slot_size(slot)
+    }
+}
+
+pub fn page_reserved_left(page: &Page) -> usize {
+    if page.overflow.is_some() {
+        return 0;
+    }
+
+    let reserved = page.slots.iter().map(|slot| slot_reserved_size(slot) + 2).sum::<usize>() + 2;
+
+    if reserved > BLOCK_SIZE {
+        return 0;
+    }
+    return BLOCK_SIZE - reserved;
+}
+
+// Tells if the slot fits the page as a new slot.
+pub fn page_fits(page: &Page, slot: &Slot) -> bool {
+    return page_reserved_left(page) >= slot_reserved_size(slot) + 2;
+}
+
+// Appends the slot and returns its number. Tombstones are never reused, an
+// address that pointed at a deleted row must not resolve to another one, only
+// VACUUM gives their numbers back.
+pub fn page_insert_slot(page: &mut Page, slot: Slot) -> u16 {
+    page.slots.push(slot);
+    return (page.slots.len() - 1) as u16;
 }
 
 pub fn page_insert_tuples(page: &mut Page, tuples: &mut Vec<Tuple>) {
     for tuple in tuples {
-        page_add_tuple(page, tuple);
+        page_insert_slot(page, Slot::Tuple(tuple.clone()));
     }
 }
 
 pub fn page_update_tuples(page: &mut Page, tuples: &mut Vec<Tuple>) {
-    page.tuples = tuples.clone();
+    page.slots = tuples.iter().map(|tuple| Slot::Tuple(tuple.clone())).collect();
+}
+
+pub fn page_read_slot(page: &Page, slot: u16) -> Option<&Slot> {
+    return page.slots.get(slot as usize);
+}
+
+// Replaces the record kept on the slot. Returns false, leaving the page
+// untouched, when the new record does not fit on the page.
+pub fn page_update_slot(page: &mut Page, slot: u16, new_slot: Slot) -> bool {
+    let Some(old_slot) = page.slots.get(slot as usize) else { return false };
+
+    let available = page_reserved_left(page) + slot_reserved_size(old_slot);
+    if slot_reserved_size(&new_slot) > available {
+        return false;
+    }
+
+    page.slots[slot as usize] = new_slot;
+    return true;
+}
+
+pub fn page_delete_slot(page: &mut Page, slot: u16) {
+    if let Some(item) = page.slots.get_mut(slot as usize) {
+        *item = Slot::Tombstone;
+    }
 }
 
 pub fn page_read_tuple(page: &Page, tuple_index: u16) -> Tuple {
-    return page.tuples.get(tuple_index as usize).unwrap().clone();
+    match page.slots.get(tuple_index as usize).unwrap() {
+        Slot::Tuple(tuple) | Slot::Moved(tuple) => tuple.clone(),
+        _ => Tuple::new()
+    }
 }
 
+// Rows whose home slot is on this page and that were not moved away.
 pub fn page_read_tuples(page: &Page) -> Vec<Tuple> {
     let mut tuples = Vec::new();
 
-    for tuple_index in 0..page.tuples.len() {
-        tuples.push(page_read_tuple(page, tuple_index as u16));
+    for slot in page.slots.iter() {
+        if let Slot::Tuple(tuple) = slot {
+            tuples.push(tuple.clone());
+        }
     }
 
     return tuples;
 }
 
 pub fn page_display(page: &Page) {
-    let tuple_count = page.tuples.len();
+    let tuples = page_read_tuples(page);
+    let tuple_count = tuples.len();
 
     print!("Page [{}, (", tuple_count);
 
-    for (idx, tuple) in page_read_tuples(page).iter().enumerate() {
+    for (idx, tuple) in tuples.iter().enumerate() {
         tuple_display(tuple);
 
         if idx != tuple_count - 1 {
@@ -78,42 +274,74 @@ pub fn page_display(page: &Page) {
 pub fn page_serialize(page: &Page) -> [u8; BLOCK_SIZE] {
     let mut raw_buffer: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
 
+    if let Some(chunk) = &page.overflow {
+        raw_buffer[0..2].copy_from_slice(&OVERFLOW_PAGE_MARK.to_be_bytes());
+        raw_buffer[2..10].copy_from_slice(&chunk.next_page.to_be_bytes());
+        raw_buffer[10..12].copy_from_slice(&(chunk.data.len() as u16).to_be_bytes());
+        raw_buffer[OVERFLOW_PAGE_HEADER_SIZE..(OVERFLOW_PAGE_HEADER_SIZE + chunk.data.len())]
+            .copy_from_slice(&chunk.data);
+        return raw_buffer;
+    }
+
     let mut block_pointer = BLOCK_SIZE;
 
-    for (tidx, tuple) in page.tuples.iter().enumerate() {
-        let raw_tuple = tuple_serialize(tuple);
-        block_pointer -= raw_tuple.len();
+    for (sidx, slot) in page.slots.iter().enumerate() {
+        let raw_record = slot_serialize(slot);
+        block_pointer -= raw_record.len();
 
-        raw_buffer[2*(tidx + 1)] = (block_pointer >> 8) as u8;
-        raw_buffer[2*(tidx + 1) + 1] = (block_pointer % 256) as u8;
+        let entry = (block_pointer as u16) | slot_kind(slot);
+        raw_buffer[2*(sidx + 1)] = (entry >> 8) as u8;
+        raw_buffer[2*(sidx + 1) + 1] = (entry % 256) as u8;
 
-        for (cidx, cell) in raw_tuple.iter().enumerate() {
+        for (cidx, cell) in raw_record.iter().enumerate() {
             raw_buffer[block_pointer + cidx] = *cell;
         }
     }
 
-    raw_buffer[0] = (page.tuples.len() >> 8) as u8;
-    raw_buffer[1] = (page.tuples.len() % 255) as u8;
+    raw_buffer[0] = (page.slots.len() >> 8) as u8;
+    raw_buffer[1] = (page.slots.len() % 256) as u8;
     raw_buffer
 }
 
+fn overflow_pointer_deserialize(raw_record: &Vec<u8>) -> OverflowPointer {
+    return OverflowPointer::new(v_u8_to_u32(raw_record, 0), v_u8_to_u64(raw_record, 4));
+}
+
 pub fn page_deserialize(raw_page: [u8; BLOCK_SIZE]) -> Page {
-    let tuple_count = v_u8_to_u16(&raw_page, 0) as usize;
-    let mut tuples: Vec<Tuple> = Vec::new();
+    let slot_count = v_u8_to_u16(&raw_page, 0) as usize;
+    let mut slots: Vec<Slot> = Vec::new();
 
-    for tidx in 0..tuple_count {
-        let block_pointer = v_u8_to_u16(&raw_page, 2*(tidx + 1)) as usize;
-        let next_pointer = if tidx != 0 {
-            v_u8_to_u16(&raw_page, 2*(tidx)) as usize
+    if slot_count == OVERFLOW_PAGE_MARK as usize {
+        let chunk_size = v_u8_to_u16(&raw_page, 10) as usize;
+        let data = v_u8_to_vec_u8(&raw_page, OVERFLOW_PAGE_HEADER_SIZE, chunk_size);
+
+        return page_new_overflow(v_u8_to_u64(&raw_page, 2), data);
+    }
+
+    for sidx in 0..slot_count {
+        let entry = v_u8_to_u16(&raw_page, 2*(sidx + 1));
+        let block_pointer = (entry & SLOT_OFFSET_MASK) as usize;
+        let next_pointer = if sidx != 0 {
+            (v_u8_to_u16(&raw_page, 2*(sidx)) & SLOT_OFFSET_MASK) as usize
         } else {
             BLOCK_SIZE
         };
 
         let size = next_pointer - block_pointer;
-        let raw_tuple: Vec<u8> = v_u8_to_vec_u8(&raw_page, block_pointer,  size);
+        let raw_record: Vec<u8> = v_u8_to_vec_u8(&raw_page, block_pointer,  size);
 
-        tuples.push(tuple_deserialize(&raw_tuple));
+        let slot = match entry & (SLOT_KIND_MASK | SLOT_OVERFLOW) {
+            SLOT_TOMBSTONE => Slot::Tombstone,
+            SLOT_FORWARD => Slot::Forward(
+                TupleAddress::new(v_u8_to_u64(&raw_record, 0), v_u8_to_u16(&raw_record, 8))
+            ),
+            SLOT_MOVED => Slot::Moved(tuple_deserialize(&raw_record)),
+            SLOT_OVERFLOW => Slot::Overflow(overflow_pointer_deserialize(&raw_record)),
+            SLOT_MOVED_OVERFLOW => Slot::MovedOverflow(overflow_pointer_deserialize(&raw_record)),
+            _ => Slot::Tuple(tuple_deserialize(&raw_record))
+        };
+        slots.push(slot);
     }
 
-    return Page { tuples };
+    return Page { slots, overflow: None };
 }
@@ -1,92 +1,187 @@
 use std::collections::HashMap;
+use std::collections::BTreeMap;
 
+use crate::config::Config;
+
+use crate::storage::BLOCK_SIZE;
 use crate::storage::Tuple;
 use crate::storage::Header;
 use crate::storage::header_new;
 use crate::storage::header_serialize;
 use crate::storage::header_deserialize;
 
-use crate::storage::Page;
-use crate::storage::page_insert_tuples;
-use crate::storage::page_update_tuples;
-use crate::storage::page_read_tuples;
+use crate::storage::Slot;
+use crate::storage::OverflowPointer;
+use crate::storage::OVERFLOW_CHUNK_SIZE;
+use crate::storage::MAX_INLINE_TUPLE_SIZE;
+use crate::storage::tuple_serialize;
+use crate::storage::tuple_deserialize;
+use crate::storage::TupleAddress;
 use crate::storage::page_new;
-use crate::storage::page_amount_left;
-use crate::storage::page_serialize;
-use crate::storage::page_deserialize;
-use crate::storage::tuple_size;
-
+use crate::storage::page_new_overflow;
+use crate::storage::page_insert_slot;
+use crate::storage::page_read_slot;
+use crate::storage::page_update_slot;
+use crate::storage::page_delete_slot;
+use crate::storage::page_fits;
+use crate::storage::page_reserved_left;
+use crate::storage::slot_reserved_size;
+
+use crate::storage::FreeSpaceMap;
+use crate::storage::fsm_new;
+use crate::storage::fsm_set;
+use crate::storage::fsm_find;
+use crate::storage::fsm_serialize;
+use crate::storage::fsm_deserialize;
+use crate::storage::format_fsm_name;
+
+use crate::storage::BufferPool;
+use crate::storage::BufferPoolStats;
+use crate::storage::buffer_pool_fetch_page;
+use crate::storage::buffer_pool_new_page;
+use crate::storage::buffer_pool_unpin_page;
+use crate::storage::buffer_pool_dirty_pages;
+use crate::storage::buffer_pool_dirty_keys;
+use crate::storage::buffer_pool_mark_clean;
+use crate::storage::buffer_pool_shrink;
+use crate::storage::buffer_pool_discard_pages;
+use crate::storage::buffer_pool_stats;
 
 use crate::storage::write_data;
 use crate::storage::read_data;
 use crate::storage::path_exists;
+use crate::storage::sync_file;
+use crate::storage::read_file;
+use crate::storage::write_file;
+use crate::storage::destroy_file;
+use crate::storage::rename_file;
+use crate::storage::file_size;
+
+use crate::storage::WalRecord;
+use crate::storage::wal_append_records;
+use crate::storage::wal_checkpoint;
 
 use crate::utils::Logger;
+use crate::utils::ExecutionError;
+
+#[derive(Debug)]
+pub struct Pager {
+    pub headers: HashMap<String, Header>,
+    pub free_space: HashMap<String, FreeSpaceMap>,
+    pub pool: BufferPool,
+    pub blocks: HashMap<String, BTreeMap<u64, [u8; BLOCK_SIZE]>>
+}
 
-pub type PagerItem = HashMap<usize, Page>;
+impl Pager {
+    pub fn new() -> Self {
+        Self::new_with_capacity(Config::buffer_pool_size())
+    }
 
-pub fn pager_new_pager_item() -> HashMap<usize, Page> {
-    return HashMap::new();
+    pub fn new_with_capacity(capacity: usize) -> Self {
+        Self {
+            headers: HashMap::new(),
+            free_space: HashMap::new(),
+            pool: BufferPool::new(capacity),
+            blocks: HashMap::new()
+        }
+    }
 }
 
-pub fn pager_insert_pager_item_tuples(pager_item: &mut PagerItem, header: &mut Header, tuples: &mut Vec<Tuple>) {
-    let page_count = header.page_count;
+pub fn pager_new() -> Pager {
+    return Pager::new()
+}
 
-    if page_count == 0 {
-       let mut page = page_new();
+pub fn pager_new_with_capacity(capacity: usize) -> Pager {
+    return Pager::new_with_capacity(capacity)
+}
+
+fn pager_load_header(pager: &mut Pager, page_key: &String) {
+    if pager.headers.contains_key(page_key) {
+        return;
+    }
 
-       page_insert_tuples(&mut page, tuples);
-       pager_item.insert(1, page);
-       header.page_count = 1;
+    if path_exists(page_key) {
+        pager.headers.insert(page_key.clone(), header_deserialize(&read_data(page_key, 0)));
     } else {
-        let mut set_new_page = false;
-        let tuple_data_size = tuples.iter().map(|item| tuple_size(item) as u64).sum::<u64>();
-
-        pager_item.entry(page_count as usize).and_modify(|_| {}).or_insert(page_new());
-
-        pager_item.entry(page_count as usize)
-            .and_modify(|page| {
-                if page_amount_left(page) as u64 > tuple_data_size {
-                    page_insert_tuples(page, tuples);
-                } else {
-                    set_new_page = true;
-                } 
-            })
-            .or_insert(page_new());
-
-        if set_new_page == true {
-            let mut page = page_new();
-
-            page_insert_tuples(&mut page, tuples);
-            pager_item.insert(page_count as usize + 1, page);
-            header.page_count += 1;
+        pager.headers.insert(page_key.clone(), header_new());
+    }
+
+    pager_load_free_space(pager, page_key);
+}
+
+// Reads the free space map sidecar, rebuilding the entries of the pages it
+// does not know about (old tables, or a crash between flushes).
+fn pager_load_free_space(pager: &mut Pager, page_key: &String) {
+    let page_count = pager.headers.get(page_key).unwrap().page_count;
+    let mut fsm = match read_file(&format_fsm_name(page_key)) {
+        Ok(buffer) => fsm_deserialize(&buffer),
+        Err(err) => {
+            Logger::warn(format!("rebuilding free space map of {}: {:?}", page_key, err).leak());
+            fsm_new()
         }
+    };
+
+    fsm.pages.truncate(page_count as usize);
+    let known_pages = fsm.pages.len() as u64;
+    pager.free_space.insert(page_key.clone(), fsm);
+
+    for page_idx in (known_pages + 1)..(page_count + 1) {
+        let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, page_idx as usize);
+        pager_note_free_space(pager, page_key, page_idx, frame_idx);
+        buffer_pool_unpin_page(&mut pager.pool, frame_idx, false);
     }
 }
 
-pub fn pager_update_pager_item_tuples(pager_item: &mut PagerItem, tuples: &mut Vec<Tuple>) {
-    let pager_item_len = pager_item.len();
-    pager_item.entry(pager_item_len).and_modify(|_| {}).or_insert(page_new());
+fn pager_note_free_space(pager: &mut Pager, page_key: &String, page_idx: u64, frame_idx: usize) {
+    let free_bytes = page_reserved_left(&pager.pool.frames[frame_idx].page);
+    let fsm = pager.free_space.entry(page_key.clone()).or_insert(fsm_new());
 
-    pager_item.entry(pager_item_len)
-        .and_modify(|page| {
-            page_update_tuples(page, tuples);
-        })
-        .or_insert(page_new());
+    fsm_set(fsm, page_idx, free_bytes);
 }
 
-pub fn pager_read_pager_item_tuples(pager_item: &mut PagerItem, page_key: &String, header: &mut Header) -> Vec<Tuple> {
-    let mut tuples: Vec<Tuple> = Vec::new();
-    let page_count = header.page_count;
+pub fn pager_get_next_rowid(pager: &mut Pager, page_key: &String) -> u64 {
+    Logger::debug(format!("search page {} on pager", page_key).leak());
+    pager_load_header(pager, page_key);
 
-    if page_count != 0 {
-        for page_idx in 1..(page_count + 1) {
-            if let Some(page) = pager_item.get(&(page_idx as usize)) {
-                tuples.append(&mut page_read_tuples(page))
-            } else {
-                let buffer: Page = page_deserialize(read_data(page_key, page_idx as u64));
-                tuples.append(&mut page_read_tuples(&buffer));
-                pager_item.insert(page_idx as usize, buffer);
+    let header = pager.headers.get_mut(page_key).unwrap();
+    let next_rowid = header.next_rowid;
+    header.next_rowid += 1;
+
+    return next_rowid;
+}
+
+pub fn pager_read_tuples(pager: &mut Pager, page_key: &String) -> Vec<Tuple> {
+    return pager_read_tuples_with_address(pager, page_key)
+        .into_iter()
+        .map(|(_, tuple)| tuple)
+        .collect();
+}
+
+// Every live row of the file along with the address of its home slot. Rows
+// that were forwarded are read from where they live now but keep reporting
+// their home address, so the address of a row never changes.
+pub fn pager_read_tuples_with_address(pager: &mut Pager, page_key: &String) -> Vec<(TupleAddress, Tuple)> {
+    Logger::debug(format!("search page {} on pager", page_key).leak());
+    pager_load_header(pager, page_key);
+
+    let mut tuples = Vec::new();
+    let page_count = pager.headers.get(page_key).unwrap().page_count;
+
+    for page_idx in 1..(page_count + 1) {
+        let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, page_idx as usize);
+        let slots = pager.pool.frames[frame_idx].page.slots.clone();
+        buffer_pool_unpin_page(&mut pager.pool, frame_idx, false);
+
+        for (slot_idx, slot) in slots.into_iter().enumerate() {
+            let address = TupleAddress::new(page_idx, slot_idx as u16);
+            let record = match slot {
+                Slot::Tuple(_) | Slot::Overflow(_) => Some(slot),
+                Slot::Forward(target) => pager_read_slot(pager, page_key, &target),
+                _ => None
+            };
+
+            if let Some(tuple) = record.and_then(|record| pager_read_record(pager, page_key, record)) {
+                tuples.push((address, tuple));
             }
         }
     }
@@ -94,111 +189,440 @@ pub fn pager_read_pager_item_tuples(pager_item: &mut PagerItem, page_key: &Strin
     return tuples;
 }
 
-#[derive(Debug)]
-pub struct Pager { 
-    pub headers: HashMap<String, Header>,
-    pub pages: HashMap<String, PagerItem>
+// The row whose home slot is the address, used when an index points at it.
+pub fn pager_read_tuple(pager: &mut Pager, page_key: &String, address: &TupleAddress) -> Option<Tuple> {
+    pager_load_header(pager, page_key);
+
+    let record = match pager_read_slot(pager, page_key, address) {
+        Some(slot @ (Slot::Tuple(_) | Slot::Overflow(_))) => Some(slot),
+        Some(Slot::Forward(target)) => pager_read_slot(pager, page_key, &target),
+        _ => None
+    };
+
+    return record.and_then(|record| pager_read_record(pager, page_key, record));
 }
 
-impl Pager {
-    pub fn new() -> Self {
-        Self { headers: HashMap::new(), pages: HashMap::new() }
+fn pager_read_slot(pager: &mut Pager, page_key: &String, address: &TupleAddress) -> Option<Slot> {
+    let page_count = pager.headers.get(page_key).unwrap().page_count;
+    if address.page == 0 || address.page > page_count {
+        return None;
     }
-}
 
-pub fn pager_new() -> Pager {
-    return Pager::new()
+    let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, address.page as usize);
+    let slot = page_read_slot(&pager.pool.frames[frame_idx].page, address.slot).cloned();
+    buffer_pool_unpin_page(&mut pager.pool, frame_idx, false);
+
+    return slot;
 }
 
-pub fn pager_get_next_rowid(pager: &mut Pager, page_key: &String) -> u64 {
-    Logger::debug(format!("search page {} on pager", page_key).leak());
-    let mut next_rowid = 0;
+// The row a record holds, reassembling it from its overflow pages if needed.
+fn pager_read_record(pager: &mut Pager, page_key: &String, record: Slot) -> Option<Tuple> {
+    match record {
+        Slot::Tuple(tuple) | Slot::Moved(tuple) => Some(tuple),
+        Slot::Overflow(pointer) | Slot::MovedOverflow(pointer) => {
+            Some(tuple_deserialize(&pager_read_overflow(pager, page_key, &pointer)))
+        },
+        _ => None
+    }
+}
 
-    if path_exists(page_key) {
-        if let None = pager.headers.get(page_key) {
-            pager.headers.insert(page_key.clone(), header_deserialize(&read_data(page_key, 0)));
+fn pager_read_overflow(pager: &mut Pager, page_key: &String, pointer: &OverflowPointer) -> Vec<u8> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut page_idx = pointer.page;
+
+    while page_idx != 0 && buffer.len() < pointer.size as usize {
+        let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, page_idx as usize);
+        match &pager.pool.frames[frame_idx].page.overflow {
+            Some(chunk) => {
+                buffer.extend_from_slice(&chunk.data);
+                page_idx = chunk.next_page;
+            },
+            None => {
+                Logger::warn(format!("broken overflow chain on page {} of {}", page_idx, page_key).leak());
+                page_idx = 0;
+            }
         }
+        buffer_pool_unpin_page(&mut pager.pool, frame_idx, false);
     }
 
-    pager.headers.entry(page_key.clone()).and_modify(|_| {}).or_insert(header_new());
+    return buffer;
+}
 
-    pager.headers.entry(page_key.clone())
-        .and_modify(|header| {
-            next_rowid = header.next_rowid;
-            header.next_rowid += 1;
-        })
-        .or_insert(header_new());
+// Appends the bytes to the end of the file as a chain of overflow pages.
+fn pager_write_overflow(pager: &mut Pager, page_key: &String, bytes: &Vec<u8>) -> OverflowPointer {
+    let first_page = pager.headers.get(page_key).unwrap().page_count + 1;
+    let chunks: Vec<&[u8]> = bytes.chunks(OVERFLOW_CHUNK_SIZE).collect();
 
-    return next_rowid;
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let page_idx = first_page + idx as u64;
+        let next_page = if idx + 1 == chunks.len() { 0 } else { page_idx + 1 };
+
+        let frame_idx = buffer_pool_new_page(&mut pager.pool, page_key, page_idx as usize);
+        pager.pool.frames[frame_idx].page = page_new_overflow(next_page, chunk.to_vec());
+        pager_note_free_space(pager, page_key, page_idx, frame_idx);
+        buffer_pool_unpin_page(&mut pager.pool, frame_idx, true);
+    }
+
+    pager.headers.get_mut(page_key).unwrap().page_count = first_page + chunks.len() as u64 - 1;
+
+    return OverflowPointer::new(bytes.len() as u32, first_page);
 }
 
-pub fn pager_read_tuples(pager: &mut Pager, page_key: &String) -> Vec<Tuple> {
-    Logger::debug(format!("search page {} on pager", page_key).leak());
-    let mut tuples = Vec::new();
+// Turns the overflow pages of the record back into empty pages.
+fn pager_free_overflow(pager: &mut Pager, page_key: &String, record: &Slot) {
+    let (Slot::Overflow(pointer) | Slot::MovedOverflow(pointer)) = record else { return };
+    let mut page_idx = pointer.page;
 
-    if path_exists(page_key) {
-        if let None = pager.headers.get(page_key) {
-            pager.headers.insert(page_key.clone(), header_deserialize(&read_data(page_key, 0)));
+    while page_idx != 0 {
+        let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, page_idx as usize);
+        let Some(chunk) = &pager.pool.frames[frame_idx].page.overflow else {
+            buffer_pool_unpin_page(&mut pager.pool, frame_idx, false);
+            break;
+        };
+        let next_page = chunk.next_page;
+
+        pager.pool.frames[frame_idx].page = page_new();
+        pager_note_free_space(pager, page_key, page_idx, frame_idx);
+        buffer_pool_unpin_page(&mut pager.pool, frame_idx, true);
+
+        page_idx = next_page;
+    }
+}
+
+// The record that stores the row on its home slot: the row itself, or a
+// pointer to its overflow pages when it does not fit a page.
+fn pager_make_record(pager: &mut Pager, page_key: &String, tuple: Tuple) -> Slot {
+    let bytes = tuple_serialize(&tuple);
+
+    if bytes.len() > MAX_INLINE_TUPLE_SIZE {
+        return Slot::Overflow(pager_write_overflow(pager, page_key, &bytes));
+    }
+    return Slot::Tuple(tuple);
+}
+
+fn slot_as_moved(record: Slot) -> Slot {
+    match record {
+        Slot::Tuple(tuple) => Slot::Moved(tuple),
+        Slot::Overflow(pointer) => Slot::MovedOverflow(pointer),
+        other => other
+    }
+}
+
+fn pager_write_slot(pager: &mut Pager, page_key: &String, address: &TupleAddress, slot: Slot) -> bool {
+    let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, address.page as usize);
+    let updated = page_update_slot(&mut pager.pool.frames[frame_idx].page, address.slot, slot);
+    pager_note_free_space(pager, page_key, address.page, frame_idx);
+    buffer_pool_unpin_page(&mut pager.pool, frame_idx, updated);
+
+    return updated;
+}
+
+fn pager_free_slot(pager: &mut Pager, page_key: &String, address: &TupleAddress) {
+    let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, address.page as usize);
+    page_delete_slot(&mut pager.pool.frames[frame_idx].page, address.slot);
+    pager_note_free_space(pager, page_key, address.page, frame_idx);
+    buffer_pool_unpin_page(&mut pager.pool, frame_idx, true);
+}
+
+// Stores the record on a page that the free space map says has room for it,
+// otherwise on a new page at the end of the file.
+fn pager_place_slot(pager: &mut Pager, page_key: &String, slot: Slot) -> TupleAddress {
+    let needed = slot_reserved_size(&slot) + 2;
+
+    while let Some(page_idx) = fsm_find(pager.free_space.get(page_key).unwrap(), needed) {
+        let frame_idx = buffer_pool_fetch_page(&mut pager.pool, page_key, page_idx as usize);
+        let page = &mut pager.pool.frames[frame_idx].page;
+
+        if page_fits(page, &slot) {
+            let slot_idx = page_insert_slot(page, slot);
+            pager_note_free_space(pager, page_key, page_idx, frame_idx);
+            buffer_pool_unpin_page(&mut pager.pool, frame_idx, true);
+            return TupleAddress::new(page_idx, slot_idx);
         }
+        pager_note_free_space(pager, page_key, page_idx, frame_idx);
+        buffer_pool_unpin_page(&mut pager.pool, frame_idx, false);
     }
 
-    pager.headers.entry(page_key.clone()).and_modify(|_| {}).or_insert(header_new());
+    let page_idx = pager.headers.get(page_key).unwrap().page_count + 1;
+    let frame_idx = buffer_pool_new_page(&mut pager.pool, page_key, page_idx as usize);
+    let slot_idx = page_insert_slot(&mut pager.pool.frames[frame_idx].page, slot);
+    pager_note_free_space(pager, page_key, page_idx, frame_idx);
+    buffer_pool_unpin_page(&mut pager.pool, frame_idx, true);
 
-    pager.headers.entry(page_key.clone())
-        .and_modify(|header| {
-            pager.pages.entry(page_key.clone()).and_modify(|_| {}).or_insert(pager_new_pager_item());
+    pager.headers.get_mut(page_key).unwrap().page_count = page_idx;
 
-            pager.pages.entry(page_key.clone())
-                .and_modify(|pager_item| {
-                    tuples.append(&mut pager_read_pager_item_tuples(pager_item, page_key, header));
-                })
-                .or_insert(pager_new_pager_item());
-        })
-        .or_insert(header_new());
+    return TupleAddress::new(page_idx, slot_idx);
+}
 
-    return tuples;
+pub fn pager_insert_tuples(pager: &mut Pager, page_key: &String, tuples: &mut Vec<Tuple>) -> Vec<TupleAddress> {
+    pager_load_header(pager, page_key);
+
+    let mut addresses = Vec::new();
+    for tuple in tuples.iter() {
+        let record = pager_make_record(pager, page_key, tuple.clone());
+        addresses.push(pager_place_slot(pager, page_key, record));
+    }
+
+    return addresses;
 }
 
-pub fn pager_insert_tuples(pager: &mut Pager, page_key: &String, tuples: &mut Vec<Tuple>) {
-    pager.headers.entry(page_key.clone()).and_modify(|_| {}).or_insert(header_new());
+// Rewrites the row in its home slot when it still fits there. A row that grew
+// too much is moved to another page and its home slot becomes a forward, so
+// the row keeps its address. Returns false when there is no row on the address.
+pub fn pager_update_tuple(pager: &mut Pager, page_key: &String, address: &TupleAddress, tuple: Tuple) -> bool {
+    pager_load_header(pager, page_key);
+
+    match pager_read_slot(pager, page_key, address) {
+        Some(old_record @ (Slot::Tuple(_) | Slot::Overflow(_))) => {
+            pager_free_overflow(pager, page_key, &old_record);
+            let record = pager_make_record(pager, page_key, tuple);
 
-    pager.headers.entry(page_key.clone())
-        .and_modify(|header| {
-            pager.pages.entry(page_key.clone()).and_modify(|_| {}).or_insert(pager_new_pager_item());
+            if pager_write_slot(pager, page_key, address, record.clone()) {
+                return true;
+            }
+
+            let target = pager_place_slot(pager, page_key, slot_as_moved(record));
+            return pager_write_slot(pager, page_key, address, Slot::Forward(target));
+        },
+        Some(Slot::Forward(target)) => {
+            if let Some(old_record) = pager_read_slot(pager, page_key, &target) {
+                pager_free_overflow(pager, page_key, &old_record);
+            }
+            let record = slot_as_moved(pager_make_record(pager, page_key, tuple));
+
+            if pager_write_slot(pager, page_key, &target, record.clone()) {
+                return true;
+            }
+
+            pager_free_slot(pager, page_key, &target);
+            let new_target = pager_place_slot(pager, page_key, record);
+            return pager_write_slot(pager, page_key, address, Slot::Forward(new_target));
+        },
+        _ => return false
+    }
+}
 
-            pager.pages.entry(page_key.clone())
-                .and_modify(|pager_item| {
-                    pager_insert_pager_item_tuples(pager_item, header, tuples)
-                })
-                .or_insert(pager_new_pager_item());
-        })
-        .or_insert(header_new());
+// Leaves a tombstone on the home slot (and on the moved body, if any) and
+// releases the overflow pages of the row.
+pub fn pager_delete_tuple(pager: &mut Pager, page_key: &String, address: &TupleAddress) -> bool {
+    pager_load_header(pager, page_key);
+
+    match pager_read_slot(pager, page_key, address) {
+        Some(record @ (Slot::Tuple(_) | Slot::Overflow(_))) => {
+            pager_free_overflow(pager, page_key, &record);
+            pager_free_slot(pager, page_key, address);
+            return true;
+        },
+        Some(Slot::Forward(target)) => {
+            if let Some(record) = pager_read_slot(pager, page_key, &target) {
+                pager_free_overflow(pager, page_key, &record);
+            }
+            pager_free_slot(pager, page_key, &target);
+            pager_free_slot(pager, page_key, address);
+            return true;
+        },
+        _ => return false
+    }
 }
 
+// Replaces the whole content of the file with the given tuples.
 pub fn pager_update_tuples(pager: &mut Pager, page_key: &String, tuples: &mut Vec<Tuple>) {
-    pager.headers.entry(page_key.clone()).and_modify(|_| {}).or_insert(header_new());
+    pager_load_header(pager, page_key);
+    pager.headers.get_mut(page_key).unwrap().page_count = 0;
+    pager.free_space.insert(page_key.clone(), fsm_new());
 
-    pager.headers.entry(page_key.clone())
-        .and_modify(|_header| {
-            pager.pages.entry(page_key.clone())
-                .and_modify(|pager_item| {
-                    pager_update_pager_item_tuples(pager_item, tuples)
-                })
-                .or_insert(pager_new_pager_item());
-        })
-        .or_insert(header_new());
+    for tuple in tuples.iter() {
+        pager_insert_tuples(pager, page_key, &mut vec![tuple.clone()]);
+    }
 }
 
-pub fn pager_flush_page(pager: &mut Pager, page_key: &String) {
-    Logger::debug(format!("FLUSH {}", page_key).leak());
+// Rewrites the file densely into a temporary file and swaps it in with a
+// rename, so a crash leaves either the old file or the new one. Rows get new
+// addresses, anything pointing at them has to be rebuilt afterwards. Returns
+// the amount of bytes reclaimed.
+pub fn pager_vacuum(pager: &mut Pager, page_key: &String) -> u64 {
+    pager_load_header(pager, page_key);
+    pager_flush_page(pager, page_key);
+
+    let old_size = file_size(page_key);
+    let vacuum_key = format!("{}.vacuum", page_key);
+
+    let mut tuples = pager_read_tuples(pager, page_key);
+    let old_header = pager.headers.get(page_key).unwrap();
+    let header = Header {
+        page_count: 0,
+        next_rowid: old_header.next_rowid,
+        tuple_order: old_header.tuple_order.clone()
+    };
+
+    pager_discard(pager, &vacuum_key);
+    destroy_file(&vacuum_key);
+    pager.headers.insert(vacuum_key.clone(), header);
+    pager.free_space.insert(vacuum_key.clone(), fsm_new());
+
+    pager_insert_tuples(pager, &vacuum_key, &mut tuples);
+    pager_flush_page(pager, &vacuum_key);
+
+    rename_file(&vacuum_key, page_key);
+    rename_file(&format_fsm_name(&vacuum_key), &format_fsm_name(page_key));
+    pager_discard(pager, &vacuum_key);
+    pager_discard(pager, page_key);
+
+    let new_size = file_size(page_key);
+    Logger::debug(format!("VACUUM {} from {} to {} bytes", page_key, old_size, new_size).leak());
+
+    return old_size.saturating_sub(new_size);
+}
+
+// Drops every cached information about the file, used when it is destroyed or
+// recreated by someone else.
+pub fn pager_discard(pager: &mut Pager, page_key: &String) {
+    pager.headers.remove(page_key);
+    pager.free_space.remove(page_key);
+    buffer_pool_discard_pages(&mut pager.pool, page_key);
+    pager.blocks.remove(page_key);
+}
+
+// Raw blocks of the files that are not made of slotted pages, the index
+// files. Written blocks are kept on the pager and only reach the file with
+// the next flush, on the same write-ahead log transaction as the pages of
+// the statement that changed them.
+pub fn pager_read_block(pager: &Pager, file_name: &String, block_idx: u64) -> Result<[u8; BLOCK_SIZE], ExecutionError> {
+    if let Some(block) = pager.blocks.get(file_name).and_then(|blocks| blocks.get(&block_idx)) {
+        return Ok(*block);
+    }
+
+    if file_size(file_name) < (block_idx + 1) * BLOCK_SIZE as u64 {
+        return Err(ExecutionError::IoError(
+            file_name.clone(),
+            format!("block {} is past the end of the file", block_idx)
+        ));
+    }
+    return Ok(read_data(file_name, block_idx));
+}
+
+pub fn pager_write_block(pager: &mut Pager, file_name: &String, block_idx: u64, block: [u8; BLOCK_SIZE]) {
+    pager.blocks.entry(file_name.clone()).or_default().insert(block_idx, block);
+}
+
+pub fn pager_stats(pager: &Pager) -> BufferPoolStats {
+    return buffer_pool_stats(&pager.pool);
+}
+
+fn pager_get_page_records(pager: &Pager, page_key: &String) -> Vec<WalRecord> {
+    let mut records: Vec<WalRecord> = Vec::new();
+
+    if let Some(header) = &pager.headers.get(page_key) {
+        records.push(WalRecord::new(page_key.clone(), 0, header_serialize(header)));
+    }
+    for (idx, data) in buffer_pool_dirty_pages(&pager.pool, page_key) {
+        records.push(WalRecord::new(page_key.clone(), idx as u64, data));
+    }
+    records.append(&mut pager_get_block_records(pager));
+
+    return records;
+}
+
+fn pager_get_block_records(pager: &Pager) -> Vec<WalRecord> {
+    let mut records: Vec<WalRecord> = Vec::new();
+
+    for (file_name, blocks) in pager.blocks.iter() {
+        for (block_idx, data) in blocks.iter() {
+            records.push(WalRecord::new(file_name.clone(), *block_idx, *data));
+        }
+    }
+
+    return records;
+}
+
+fn pager_write_blocks(pager: &mut Pager) {
+    for (file_name, blocks) in pager.blocks.drain() {
+        for (block_idx, data) in blocks.iter() {
+            write_data(&file_name, *block_idx, data);
+        }
+        sync_file(&file_name);
+    }
+}
+
+pub fn pager_write_ahead_log(pager: &mut Pager, page_key: &String) {
+    Logger::debug(format!("WAL {}", page_key).leak());
+    wal_append_records(&pager_get_page_records(pager, page_key));
+}
+
+pub fn pager_write_pages(pager: &mut Pager, page_key: &String) {
     if let Some(header) = &pager.headers.get(page_key) {
         write_data(page_key, 0, &header_serialize(header));
     }
-    if let Some(pager_item) = &pager.pages.get(page_key) {
-        for (idx, page) in *pager_item {
-            write_data(page_key, *idx as u64, &page_serialize(page));
+    for (idx, data) in buffer_pool_dirty_pages(&pager.pool, page_key) {
+        write_data(page_key, idx as u64, &data);
+    }
+    sync_file(page_key);
+    pager_write_blocks(pager);
+    if let Some(fsm) = pager.free_space.get(page_key) {
+        if let Err(err) = write_file(&format_fsm_name(page_key), &fsm_serialize(fsm)) {
+            panic!("failed to write the free space map: {:?}", err);
         }
     }
+    buffer_pool_mark_clean(&mut pager.pool, page_key);
+    buffer_pool_shrink(&mut pager.pool);
+}
+
+pub fn pager_flush_page(pager: &mut Pager, page_key: &String) {
+    Logger::debug(format!("FLUSH {}", page_key).leak());
+    pager_write_ahead_log(pager, page_key);
+    pager_write_pages(pager, page_key);
+    wal_checkpoint();
+}
+
+// Commits the blocks written by the index files alone, for the statements
+// that change an index without touching a table, like CREATE INDEX.
+pub fn pager_flush_blocks(pager: &mut Pager) {
+    let records = pager_get_block_records(pager);
+    if records.len() == 0 {
+        return;
+    }
+
+    Logger::debug("FLUSH index blocks");
+    wal_append_records(&records);
+    pager_write_blocks(pager);
+    wal_checkpoint();
 }
 
+// Commits every changed page and block on one write-ahead log transaction,
+// for the statements that change more than one table, like the referential
+// actions of a DELETE.
+pub fn pager_flush_all(pager: &mut Pager) {
+    let page_keys = buffer_pool_dirty_keys(&pager.pool);
 
+    let mut records: Vec<WalRecord> = Vec::new();
+    for page_key in page_keys.iter() {
+        if let Some(header) = &pager.headers.get(page_key) {
+            records.push(WalRecord::new(page_key.clone(), 0, header_serialize(header)));
+        }
+        for (idx, data) in buffer_pool_dirty_pages(&pager.pool, page_key) {
+            records.push(WalRecord::new(page_key.clone(), idx as u64, data));
+        }
+    }
+    records.append(&mut pager_get_block_records(pager));
+    if records.len() == 0 {
+        return;
+    }
+
+    Logger::debug("FLUSH all");
+    wal_append_records(&records);
+    for page_key in page_keys.iter() {
+        pager_write_pages(pager, page_key);
+    }
+    pager_write_blocks(pager);
+    wal_checkpoint();
+}
+
+// Forgets whatever was changed since the last flush, the files are read
+// again as they were committed. Dirty pages are never written before their
+// flush, so the files still hold the state before the failed statement.
+pub fn pager_rollback(pager: &mut Pager) {
+    for page_key in buffer_pool_dirty_keys(&pager.pool).iter() {
+        pager_discard(pager, page_key);
+    }
+    pager.blocks.clear();
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 use std::vec::Vec;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 use crate::machine::Column;
 
@@ -7,6 +9,15 @@ use crate::storage::Expression;
 use crate::storage::Tuple;
 use crate::storage::Data;
 use crate::storage::tuple_new;
+use crate::storage::SortKey;
+use crate::storage::sort_tuples;
+use crate::storage::Aggregate;
+use crate::storage::AggregateState;
+use crate::storage::aggregate_state_new;
+use crate::storage::aggregate_update;
+use crate::storage::aggregate_result;
+use crate::storage::index_key_hash;
+use crate::storage::index_key_compare;
 
 use crate::utils::ExecutionError;
 
@@ -27,6 +38,9 @@ pub struct ResultSet {
  *   - selection
  *   - projection
  *   - cartesian product
+ *   - sort
+ *   - aggregation
+ *   - distinct
  *   - union
  *   - diff 
  * 
@@ -60,6 +74,11 @@ impl ResultSet {
         }
     }
 
+    // Names the expressions evaluated over the set reach its columns by.
+    pub fn column_names(&self) -> Vec<String> {
+        return self.columns.iter().map(|column| column.qualified_name()).collect();
+    }
+
     fn get_column_position(&self, column_name: &String) -> Option<usize> {
         return self.columns.iter().position(|elem| elem.check_column_name(column_name) )
     }
@@ -141,6 +160,24 @@ impl ResultSet {
         return Ok(new_set);
     }
 
+    // Projection that computes its columns, each one the result of its
+    // expression over the tuples of the set.
+    pub fn extended_projection(&self, columns: Vec<Column>, expressions: &Vec<Expression>) -> Result<ResultSet, ExecutionError> {
+        let column_names = self.column_names();
+        let mut new_set: ResultSet = ResultSet::new_select(columns, vec![]);
+
+        for tuple in &self.tuples {
+            let new_tuple: Result<Tuple, ExecutionError> = expressions
+                .iter()
+                .map(|expression| expression.result(tuple, &column_names))
+                .collect();
+
+            new_set.tuples.push(new_tuple?);
+        }
+
+        return Ok(new_set);
+    }
+
     pub fn selection(&self, condition: Expression) -> Result<ResultSet, ExecutionError> {
         let mut columns: Vec<Column> = Vec::new();
         let mut tuples: Vec<Tuple> = Vec::new();
@@ -149,10 +186,10 @@ impl ResultSet {
             columns.push(column.clone());
         }
 
-        let column_names = self.columns.iter().map(|e| e.name.clone()).collect();
+        let column_names = self.column_names();
 
         for tuple in &self.tuples {
-            if condition.result(tuple, &column_names).is_true() {
+            if condition.result(tuple, &column_names)?.is_true() {
                 tuples.push(tuple.clone());
             }
         }
@@ -160,6 +197,150 @@ impl ResultSet {
         Ok(ResultSet::new_select(columns, tuples))
     }
 
+    // Rows ordered by the keys, evaluated over the tuples of the set. Sorts
+    // bigger than the memory budget spill to temporary files.
+    pub fn sort(&self, keys: &Vec<SortKey>, memory_budget: usize) -> Result<ResultSet, ExecutionError> {
+        let column_names = self.column_names();
+
+        // The rows end at the first key that fails, its error is returned
+        // once the sort has cleaned up.
+        let mut key_error: Option<ExecutionError> = None;
+        let rows = self.tuples.iter().map_while(|tuple| {
+            let values: Result<Tuple, ExecutionError> = keys
+                .iter()
+                .map(|key| key.expression.result(tuple, &column_names))
+                .collect();
+            match values {
+                Ok(values) => Some((values, tuple.clone())),
+                Err(error) => {
+                    key_error = Some(error);
+                    None
+                }
+            }
+        });
+
+        let sorted = sort_tuples(keys, rows, memory_budget);
+        if let Some(error) = key_error {
+            return Err(error);
+        }
+        return match sorted {
+            Ok(tuples) => Ok(ResultSet::new_select(self.columns.clone(), tuples)),
+            Err(error) => Err(error)
+        };
+    }
+
+    // Groups the tuples by the values of the group expressions, NULLs falling
+    // in the same group, and computes the aggregates of each group. The new
+    // set holds the first tuple of each group followed by one column for
+    // each aggregate. Without group expressions the whole set is one group,
+    // even when it is empty.
+    pub fn aggregation(&self, group_by: &Vec<Expression>, aggregates: &Vec<&Aggregate>) -> Result<ResultSet, ExecutionError> {
+        let column_names = self.column_names();
+
+        let mut group_keys: Vec<Tuple> = Vec::new();
+        let mut groups: Vec<(Tuple, Vec<AggregateState>)> = Vec::new();
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+
+        for tuple in &self.tuples {
+            let key: Tuple = group_by
+                .iter()
+                .map(|expression| expression.result(tuple, &column_names))
+                .collect::<Result<Tuple, ExecutionError>>()?;
+
+            let bucket = buckets.entry(index_key_hash(&key)).or_default();
+            let position = match bucket.iter().find(|idx| index_key_compare(&group_keys[**idx], &key) == Ordering::Equal) {
+                Some(idx) => *idx,
+                None => {
+                    bucket.push(groups.len());
+                    group_keys.push(key);
+                    groups.push((tuple.clone(), aggregates.iter().map(|_| aggregate_state_new()).collect()));
+                    groups.len() - 1
+                }
+            };
+
+            for (aggregate, state) in aggregates.iter().zip(groups[position].1.iter_mut()) {
+                let arguments: Vec<Data> = aggregate.arguments
+                    .iter()
+                    .map(|expression| expression.result(tuple, &column_names))
+                    .collect::<Result<Vec<Data>, ExecutionError>>()?;
+                aggregate_update(aggregate, state, arguments)?;
+            }
+        }
+
+        if group_by.is_empty() && groups.is_empty() {
+            let tuple: Tuple = self.columns.iter().map(|_| Data::Null).collect();
+            groups.push((tuple, aggregates.iter().map(|_| aggregate_state_new()).collect()));
+        }
+
+        let mut columns: Vec<Column> = self.columns.clone();
+        for aggregate in aggregates {
+            columns.push(
+                Column::new(
+                    0u64,
+                    String::from(""),
+                    String::from(""),
+                    aggregate.name.clone(),
+                    Data::Undefined,
+                    false,
+                    false,
+                    false,
+                    String::from("")
+                )
+            );
+        }
+
+        let mut tuples: Vec<Tuple> = Vec::new();
+        for (mut tuple, states) in groups.into_iter() {
+            for (aggregate, state) in aggregates.iter().zip(states.iter()) {
+                tuple.push(aggregate_result(aggregate, state)?);
+            }
+            tuples.push(tuple);
+        }
+
+        Ok(ResultSet::new_select(columns, tuples))
+    }
+
+    // Keeps the first tuple of each set of equal tuples, NULLs being equal
+    // among themselves.
+    pub fn distinct(&self) -> ResultSet {
+        return self.distinct_by(self.tuples.clone());
+    }
+
+    // Keeps the first tuple of each set of tuples with equal values of the
+    // expressions.
+    pub fn distinct_on(&self, expressions: &Vec<Expression>) -> Result<ResultSet, ExecutionError> {
+        let column_names = self.column_names();
+        let mut keys: Vec<Tuple> = Vec::new();
+        for tuple in &self.tuples {
+            let key: Result<Tuple, ExecutionError> = expressions
+                .iter()
+                .map(|expression| expression.result(tuple, &column_names))
+                .collect();
+            keys.push(key?);
+        }
+
+        return Ok(self.distinct_by(keys));
+    }
+
+    // Keeps the first tuple of each set of tuples with equal keys, one key
+    // for each tuple.
+    fn distinct_by(&self, keys: Vec<Tuple>) -> ResultSet {
+        let mut buckets: HashMap<u64, Vec<Tuple>> = HashMap::new();
+        let mut tuples: Vec<Tuple> = Vec::new();
+
+        for (tuple, key) in self.tuples.iter().zip(keys.into_iter()) {
+            let bucket = buckets.entry(index_key_hash(&key)).or_default();
+
+            if bucket.iter().any(|other| index_key_compare(other, &key) == Ordering::Equal) {
+                continue;
+            }
+            bucket.push(key);
+            tuples.push(tuple.clone());
+        }
+
+        ResultSet::new_select(self.columns.clone(), tuples)
+    }
+
     pub fn limit(&self, size: usize) -> ResultSet {
         let mut columns: Vec<Column> = Vec::new();
         let mut tuples: Vec<Tuple> = Vec::new();
@@ -291,7 +472,7 @@ impl fmt::Display for ResultSet {
                 for (cell_index, column) in self.columns.iter().enumerate() {
                     let _ = write!(f, " {}", column.alias);
 
-                    let adjust_column_size = column_length.get(cell_index as usize).unwrap() - (column.name.len() as u64);
+                    let adjust_column_size = column_length.get(cell_index as usize).unwrap() - (column.alias.len() as u64);
                     print_complete_cell(f, adjust_column_size);
                     let _ = write!(f, " |");
                 }
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering as AtomicOrdering;
+
+use crate::storage::Data;
+use crate::storage::Expression;
+use crate::storage::Tuple;
+use crate::storage::tuple_size;
+use crate::storage::tuple_serialize;
+use crate::storage::tuple_deserialize;
+use crate::storage::format_sort_name;
+use crate::storage::write_file;
+use crate::storage::destroy_file;
+
+use crate::utils::ExecutionError;
+use crate::utils::Logger;
+
+// Rows are sorted in memory while they fit the memory budget. Beyond it they
+// are sorted in runs of the budget size, each one kept on a temporary file,
+// and the runs are merged back. Each row goes with the values of its keys,
+// so the keys are evaluated only once.
+
+// Count of the sorts that spilled to files, names their runs.
+static SORT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug)]
+pub struct SortKey {
+    pub expression: Expression,
+    pub descending: bool,
+    pub nulls_first: bool
+}
+
+impl SortKey {
+    // NULLs go last on ascending keys and first on descending ones unless
+    // told otherwise, as in PostgreSQL.
+    pub fn new(expression: Expression, descending: bool, nulls_first: Option<bool>) -> Self {
+        SortKey {
+            expression,
+            descending,
+            nulls_first: nulls_first.unwrap_or(descending)
+        }
+    }
+}
+
+// Orders two rows by the values of their keys, the first key where they
+// differ decides.
+pub fn sort_compare(keys: &Vec<SortKey>, a: &Tuple, b: &Tuple) -> Ordering {
+    for (idx, key) in keys.iter().enumerate() {
+        let ordering = match (&a[idx], &b[idx]) {
+            (Data::Null, Data::Null) => Ordering::Equal,
+            (Data::Null, _) if key.nulls_first => Ordering::Less,
+            (Data::Null, _) => Ordering::Greater,
+            (_, Data::Null) if key.nulls_first => Ordering::Greater,
+            (_, Data::Null) => Ordering::Less,
+            (value_a, value_b) if key.descending => value_b.cmp(value_a),
+            (value_a, value_b) => value_a.cmp(value_b)
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    return Ordering::Equal;
+}
+
+// Sorts the rows, given as the values of their keys and their tuple, and
+// returns the tuples. Rows with equal keys keep the order they came in.
+pub fn sort_tuples<I>(keys: &Vec<SortKey>, rows: I, memory_budget: usize) -> Result<Vec<Tuple>, ExecutionError>
+where
+    I: Iterator<Item = (Tuple, Tuple)>
+{
+    let mut run: Vec<(Tuple, Tuple)> = Vec::new();
+    let mut run_size: usize = 0;
+    let mut run_names: Vec<String> = Vec::new();
+    let mut sort_id: Option<u64> = None;
+
+    for row in rows {
+        run_size += tuple_size(&row.0) + tuple_size(&row.1);
+        run.push(row);
+
+        if run_size > memory_budget {
+            let sort_id = *sort_id.get_or_insert_with(|| SORT_COUNTER.fetch_add(1, AtomicOrdering::Relaxed));
+            if let Err(error) = sort_write_run(keys, &mut run, sort_id, &mut run_names) {
+                return Err(error);
+            }
+            run_size = 0;
+        }
+    }
+
+    run.sort_by(|a, b| sort_compare(keys, &a.0, &b.0));
+
+    if let Some(sort_id) = sort_id {
+        if run.len() > 0 {
+            if let Err(error) = sort_write_run(keys, &mut run, sort_id, &mut run_names) {
+                return Err(error);
+            }
+        }
+
+        // The runs go away whether the merge worked or not.
+        let tuples = sort_merge_runs(keys, &run_names);
+        for run_name in run_names.iter() {
+            destroy_file(run_name);
+        }
+        return tuples;
+    }
+
+    return Ok(run.into_iter().map(|(_, tuple)| tuple).collect());
+}
+
+// Sorts the run and moves it to a file, each row as a u32 length and the
+// row serialized as one tuple, its key values first. When the file can not
+// be written every run of the sort goes away.
+fn sort_write_run(
+    keys: &Vec<SortKey>,
+    run: &mut Vec<(Tuple, Tuple)>,
+    sort_id: u64,
+    run_names: &mut Vec<String>
+) -> Result<(), ExecutionError> {
+    let run_name = format_sort_name(sort_id, run_names.len());
+    Logger::debug(format!("sort spills {} rows to {}", run.len(), run_name).leak());
+
+    run.sort_by(|a, b| sort_compare(keys, &a.0, &b.0));
+
+    let mut buffer: Vec<u8> = Vec::new();
+    for (mut values, mut tuple) in run.drain(..) {
+        values.append(&mut tuple);
+        let mut row = tuple_serialize(&values);
+        buffer.append(&mut (row.len() as u32).to_be_bytes().to_vec());
+        buffer.append(&mut row);
+    }
+
+    run_names.push(run_name);
+    if let Err(error) = write_file(run_names.last().unwrap(), &buffer) {
+        for run_name in run_names.iter() {
+            destroy_file(run_name);
+        }
+        return Err(error);
+    }
+    return Ok(());
+}
+
+// Next row of the run, None once it is over.
+fn sort_read_row(reader: &mut BufReader<File>, run_name: &String, key_count: usize) -> Result<Option<(Tuple, Tuple)>, ExecutionError> {
+    let mut length = [0u8; 4];
+    match reader.read_exact(&mut length) {
+        Ok(_) => {},
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(ExecutionError::IoError(run_name.clone(), error.to_string()))
+    }
+
+    let mut buffer = vec![0u8; u32::from_be_bytes(length) as usize];
+    if let Err(error) = reader.read_exact(&mut buffer) {
+        return Err(ExecutionError::IoError(run_name.clone(), error.to_string()));
+    }
+
+    let mut values = tuple_deserialize(&buffer);
+    let tuple = values.split_off(key_count);
+    return Ok(Some((values, tuple)));
+}
+
+// Merges the sorted runs reading one row of each at a time. On equal keys
+// the earlier run goes first, which keeps the sort stable.
+fn sort_merge_runs(keys: &Vec<SortKey>, run_names: &Vec<String>) -> Result<Vec<Tuple>, ExecutionError> {
+    let mut readers: Vec<BufReader<File>> = Vec::new();
+    let mut heads: Vec<Option<(Tuple, Tuple)>> = Vec::new();
+
+    for run_name in run_names.iter() {
+        let mut reader = match File::open(run_name) {
+            Ok(file) => BufReader::new(file),
+            Err(error) => return Err(ExecutionError::IoError(run_name.clone(), error.to_string()))
+        };
+        match sort_read_row(&mut reader, run_name, keys.len()) {
+            Ok(head) => heads.push(head),
+            Err(error) => return Err(error)
+        }
+        readers.push(reader);
+    }
+
+    let mut tuples: Vec<Tuple> = Vec::new();
+    loop {
+        let mut next: Option<usize> = None;
+        for (idx, head) in heads.iter().enumerate() {
+            if let Some((values, _)) = head {
+                let is_smaller = match next {
+                    Some(next_idx) => {
+                        let (next_values, _) = heads[next_idx].as_ref().unwrap();
+                        sort_compare(keys, values, next_values) == Ordering::Less
+                    },
+                    None => true
+                };
+                if is_smaller {
+                    next = Some(idx);
+                }
+            }
+        }
+
+        match next {
+            Some(idx) => {
+                let (_, tuple) = heads[idx].take().unwrap();
+                tuples.push(tuple);
+                heads[idx] = match sort_read_row(&mut readers[idx], &run_names[idx], keys.len()) {
+                    Ok(head) => head,
+                    Err(error) => return Err(error)
+                };
+            },
+            None => return Ok(tuples)
+        }
+    }
+}
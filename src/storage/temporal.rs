@@ -0,0 +1,571 @@
+use std::cmp::Ordering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::storage::Data;
+use crate::storage::DECIMAL_MAX_PRECISION;
+
+// Dates are days since 1970-01-01, times microseconds since midnight and
+// timestamps microseconds since 1970-01-01 00:00:00. Timestamps with time
+// zone hold that count in UTC. Intervals keep months, days and microseconds
+// apart, since neither months nor days have a fixed length.
+pub const MICROS_PER_SECOND: i64 = 1_000_000;
+pub const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+pub const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+pub const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+// Length of a month when intervals are compared.
+pub const DAYS_PER_MONTH: i64 = 30;
+
+pub fn temporal_is_leap_year(year: i64) -> bool {
+    return (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+}
+
+pub fn temporal_days_in_month(year: i64, month: u32) -> u32 {
+    return match month {
+        2 if temporal_is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    };
+}
+
+pub fn temporal_days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    return era * 146097 + day_of_era - 719468;
+}
+
+pub fn temporal_civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    return (year, month, day);
+}
+
+pub fn temporal_now() -> i64 {
+    return SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros() as i64;
+}
+
+fn parse_number(value: &str) -> Option<i64> {
+    if value.len() == 0 || !value.chars().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    return value.parse::<i64>().ok();
+}
+
+// Up to six digits after the point, as microseconds.
+fn parse_fraction(value: &str) -> Option<i64> {
+    let digits: String = value.chars().take(6).collect();
+    let micros = parse_number(&format!("{:0<6}", digits))?;
+
+    if !value.chars().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    return Some(micros);
+}
+
+// Dates as YYYY-MM-DD.
+pub fn temporal_parse_date(value: &str) -> Option<i32> {
+    let parts: Vec<&str> = value.trim().split('-').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let year = parse_number(parts[0])?;
+    let month = parse_number(parts[1])? as u32;
+    let day = parse_number(parts[2])? as u32;
+
+    if month < 1 || month > 12 || day < 1 || day > temporal_days_in_month(year, month) {
+        return None;
+    }
+    return i32::try_from(temporal_days_from_civil(year, month, day)).ok();
+}
+
+// Times as HH:MM, HH:MM:SS or HH:MM:SS.ffffff.
+pub fn temporal_parse_time(value: &str) -> Option<i64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.len() != 2 && parts.len() != 3 {
+        return None;
+    }
+
+    let hours = parse_number(parts[0])?;
+    let minutes = parse_number(parts[1])?;
+    let (seconds, micros) = match parts.get(2) {
+        Some(seconds) => match seconds.split_once('.') {
+            Some((seconds, fraction)) => (parse_number(seconds)?, parse_fraction(fraction)?),
+            None => (parse_number(seconds)?, 0)
+        },
+        None => (0, 0)
+    };
+
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    return Some(hours * MICROS_PER_HOUR + minutes * MICROS_PER_MINUTE + seconds * MICROS_PER_SECOND + micros);
+}
+
+// Offsets as Z, +HH, +HH:MM or +HHMM.
+fn parse_offset(value: &str) -> Option<i64> {
+    if value == "Z" || value == "z" {
+        return Some(0);
+    }
+
+    let sign = match value.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None
+    };
+    let digits = value[1..].replace(':', "");
+    let (hours, minutes) = match digits.len() {
+        2 => (parse_number(&digits)?, 0),
+        4 => (parse_number(&digits[0..2])?, parse_number(&digits[2..4])?),
+        _ => return None
+    };
+
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    return Some(sign * (hours * MICROS_PER_HOUR + minutes * MICROS_PER_MINUTE));
+}
+
+// ISO-8601 timestamps: a date, then a time after a T or a space and at last
+// an optional offset. Gives the local time and the offset when there is one.
+pub fn temporal_parse_timestamp(value: &str) -> Option<(i64, Option<i64>)> {
+    let value = value.trim();
+    let (date, rest) = match value.find(|separator| separator == 'T' || separator == ' ') {
+        Some(position) => (&value[..position], value[(position + 1)..].trim()),
+        None => (value, "")
+    };
+
+    let days = temporal_parse_date(date)? as i64;
+    let (time, offset) = match rest.find(|sign| sign == '+' || sign == '-' || sign == 'Z' || sign == 'z') {
+        Some(position) => (rest[..position].trim(), Some(parse_offset(rest[position..].trim())?)),
+        None => (rest, None)
+    };
+    let micros = if time.len() == 0 { 0 } else { temporal_parse_time(time)? };
+
+    return Some((days.checked_mul(MICROS_PER_DAY)?.checked_add(micros)?, offset));
+}
+
+// Intervals as a list of quantities and units, such as 1 year 2 months or
+// 3 days 04:05:06, negated by a trailing ago.
+pub fn temporal_parse_interval(value: &str) -> Option<(i32, i32, i64)> {
+    let mut months: i64 = 0;
+    let mut days: i64 = 0;
+    let mut micros: i64 = 0;
+    let mut tokens = value.trim().trim_start_matches('@').split_whitespace();
+    let mut parsed = false;
+    let mut ago = false;
+
+    while let Some(token) = tokens.next() {
+        let (negative, quantity) = match token.strip_prefix('-') {
+            Some(quantity) => (true, quantity),
+            None => (false, token.strip_prefix('+').unwrap_or(token))
+        };
+        let sign = if negative { -1 } else { 1 };
+
+        if token.eq_ignore_ascii_case("ago") && parsed {
+            ago = true;
+            continue;
+        }
+        if quantity.contains(':') {
+            micros += sign * temporal_parse_time(quantity)?;
+            parsed = true;
+            continue;
+        }
+
+        let (whole, fraction) = match quantity.split_once('.') {
+            Some((whole, fraction)) => (parse_number(whole)?, parse_fraction(fraction)?),
+            None => (parse_number(quantity)?, 0)
+        };
+        let unit = tokens.next()?.to_lowercase();
+        let unit = match unit.as_str() {
+            "ms" | "msec" | "msecs" => "millisecond",
+            "us" | "usec" | "usecs" => "microsecond",
+            other => other.trim_end_matches('s')
+        };
+
+        match unit {
+            "year" | "y" if fraction == 0 => months = months.checked_add(sign * whole.checked_mul(12)?)?,
+            "mon" | "month" if fraction == 0 => months = months.checked_add(sign * whole)?,
+            "week" | "w" if fraction == 0 => days = days.checked_add(sign * whole.checked_mul(7)?)?,
+            "day" | "d" if fraction == 0 => days = days.checked_add(sign * whole)?,
+            "hour" | "h" if fraction == 0 => micros = micros.checked_add(sign * whole.checked_mul(MICROS_PER_HOUR)?)?,
+            "minute" | "min" | "m" if fraction == 0 => {
+                micros = micros.checked_add(sign * whole.checked_mul(MICROS_PER_MINUTE)?)?
+            },
+            "second" | "sec" | "" => {
+                micros = micros.checked_add(sign * (whole.checked_mul(MICROS_PER_SECOND)? + fraction))?
+            },
+            "millisecond" if fraction == 0 => micros = micros.checked_add(sign * whole.checked_mul(1000)?)?,
+            "microsecond" if fraction == 0 => micros = micros.checked_add(sign * whole)?,
+            _ => return None
+        }
+        parsed = true;
+    }
+
+    if !parsed {
+        return None;
+    }
+    if ago {
+        months = -months;
+        days = -days;
+        micros = -micros;
+    }
+    return Some((i32::try_from(months).ok()?, i32::try_from(days).ok()?, micros));
+}
+
+pub fn temporal_format_date(days: i32) -> String {
+    let (year, month, day) = temporal_civil_from_days(days as i64);
+    return format!("{:04}-{:02}-{:02}", year, month, day);
+}
+
+// Fractions of a second are only shown when there are some.
+pub fn temporal_format_time(micros: i64) -> String {
+    let seconds = micros / MICROS_PER_SECOND;
+    let time = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if micros % MICROS_PER_SECOND == 0 {
+        return time;
+    }
+    let fraction = format!("{:06}", micros % MICROS_PER_SECOND);
+    return format!("{}.{}", time, fraction.trim_end_matches('0'));
+}
+
+pub fn temporal_format_timestamp(micros: i64) -> String {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    return format!(
+        "{} {}",
+        temporal_format_date(days as i32),
+        temporal_format_time(micros.rem_euclid(MICROS_PER_DAY))
+    );
+}
+
+pub fn temporal_format_interval(months: i32, days: i32, micros: i64) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let plural = |value: i64| if value.abs() == 1 { "" } else { "s" };
+
+    if months / 12 != 0 {
+        parts.push(format!("{} year{}", months / 12, plural((months / 12) as i64)));
+    }
+    if months % 12 != 0 {
+        parts.push(format!("{} mon{}", months % 12, plural((months % 12) as i64)));
+    }
+    if days != 0 {
+        parts.push(format!("{} day{}", days, plural(days as i64)));
+    }
+    if micros != 0 || parts.len() == 0 {
+        let sign = if micros < 0 { "-" } else { "" };
+        parts.push(format!("{}{}", sign, temporal_format_time(micros.abs())));
+    }
+    return parts.join(" ");
+}
+
+// Length of an interval taking months of 30 days, to compare intervals.
+pub fn temporal_interval_micros(months: i32, days: i32, micros: i64) -> i128 {
+    let days = months as i128 * DAYS_PER_MONTH as i128 + days as i128;
+    return days * MICROS_PER_DAY as i128 + micros as i128;
+}
+
+// Moves a timestamp by an interval: first the months, keeping the day
+// inside the month it lands on, then the days and the time.
+pub fn temporal_add_interval(timestamp: i64, months: i32, days: i32, micros: i64) -> Option<i64> {
+    let (year, month, day) = temporal_civil_from_days(timestamp.div_euclid(MICROS_PER_DAY));
+    let total_months = year * 12 + month as i64 - 1 + months as i64;
+    let new_year = total_months.div_euclid(12);
+    let new_month = (total_months.rem_euclid(12) + 1) as u32;
+    let new_day = day.min(temporal_days_in_month(new_year, new_month));
+
+    let new_days = temporal_days_from_civil(new_year, new_month, new_day) + days as i64;
+    return new_days
+        .checked_mul(MICROS_PER_DAY)?
+        .checked_add(timestamp.rem_euclid(MICROS_PER_DAY))?
+        .checked_add(micros);
+}
+
+pub fn temporal_is_temporal(value: &Data) -> bool {
+    return matches!(
+        value,
+        Data::Date(_) | Data::Time(_) | Data::Timestamp(_) | Data::TimestampTz(_) | Data::Interval(_, _, _)
+    );
+}
+
+// Dates and timestamps as microseconds since 1970, dates at midnight.
+pub fn temporal_to_timestamp(value: &Data) -> Option<i64> {
+    return match *value {
+        Data::Date(days) => Some(days as i64 * MICROS_PER_DAY),
+        Data::Timestamp(micros) | Data::TimestampTz(micros) => Some(micros),
+        _ => None
+    };
+}
+
+// Converts a value to the temporal type of `kind`: strings are parsed,
+// timestamps give their date or their time of day and dates become
+// timestamps at midnight. Plain timestamps ignore the offset of a string.
+pub fn temporal_cast(kind: &Data, value: &Data) -> Option<Data> {
+    if let Data::Varchar(string) | Data::Text(string) = value {
+        return match kind {
+            Data::Date(_) => match temporal_parse_date(string) {
+                Some(days) => Some(Data::Date(days)),
+                None => temporal_cast(kind, &Data::Timestamp(temporal_parse_timestamp(string)?.0))
+            },
+            Data::Time(_) => match temporal_parse_time(string) {
+                Some(micros) => Some(Data::Time(micros)),
+                None => temporal_cast(kind, &Data::Timestamp(temporal_parse_timestamp(string)?.0))
+            },
+            Data::Timestamp(_) => Some(Data::Timestamp(temporal_parse_timestamp(string)?.0)),
+            Data::TimestampTz(_) => {
+                let (micros, offset) = temporal_parse_timestamp(string)?;
+                Some(Data::TimestampTz(micros.checked_sub(offset.unwrap_or(0))?))
+            },
+            Data::Interval(_, _, _) => {
+                let (months, days, micros) = temporal_parse_interval(string)?;
+                Some(Data::Interval(months, days, micros))
+            },
+            _ => None
+        };
+    }
+
+    return match (kind, value) {
+        (Data::Date(_), Data::Date(_)) => Some(value.clone()),
+        (Data::Date(_), Data::Timestamp(micros) | Data::TimestampTz(micros)) => {
+            Some(Data::Date(i32::try_from(micros.div_euclid(MICROS_PER_DAY)).ok()?))
+        },
+        (Data::Time(_), Data::Time(_)) => Some(value.clone()),
+        (Data::Time(_), Data::Timestamp(micros) | Data::TimestampTz(micros)) => {
+            Some(Data::Time(micros.rem_euclid(MICROS_PER_DAY)))
+        },
+        (Data::Timestamp(_), _) => Some(Data::Timestamp(temporal_to_timestamp(value)?)),
+        (Data::TimestampTz(_), _) => Some(Data::TimestampTz(temporal_to_timestamp(value)?)),
+        (Data::Interval(_, _, _), Data::Interval(_, _, _)) => Some(value.clone()),
+        _ => None
+    };
+}
+
+// Dates and timestamps compare on the same time line, times and intervals
+// among themselves, and strings as the temporal type they are compared to.
+pub fn temporal_compare(a: &Data, b: &Data) -> Option<Ordering> {
+    return match (a, b) {
+        (Data::Time(a), Data::Time(b)) => Some(a.cmp(b)),
+        (Data::Interval(a_months, a_days, a_micros), Data::Interval(b_months, b_days, b_micros)) => {
+            Some(
+                temporal_interval_micros(*a_months, *a_days, *a_micros)
+                    .cmp(&temporal_interval_micros(*b_months, *b_days, *b_micros))
+            )
+        },
+        (a, b) if temporal_to_timestamp(a).is_some() && temporal_to_timestamp(b).is_some() => {
+            Some(temporal_to_timestamp(a).unwrap().cmp(&temporal_to_timestamp(b).unwrap()))
+        },
+        (a, Data::Varchar(_) | Data::Text(_)) if temporal_is_temporal(a) => temporal_compare(a, &temporal_cast(a, b)?),
+        (Data::Varchar(_) | Data::Text(_), b) if temporal_is_temporal(b) => temporal_compare(&temporal_cast(b, a)?, b),
+        _ => None
+    };
+}
+
+fn interval_shift(value: &Data, months: i32, days: i32, micros: i64) -> Option<Data> {
+    return match *value {
+        Data::Date(_) | Data::Timestamp(_) => {
+            Some(Data::Timestamp(temporal_add_interval(temporal_to_timestamp(value)?, months, days, micros)?))
+        },
+        Data::TimestampTz(timestamp) => Some(Data::TimestampTz(temporal_add_interval(timestamp, months, days, micros)?)),
+        Data::Time(time) => Some(Data::Time((time as i128 + micros as i128).rem_euclid(MICROS_PER_DAY as i128) as i64)),
+        Data::Interval(a_months, a_days, a_micros) => {
+            Some(Data::Interval(a_months.checked_add(months)?, a_days.checked_add(days)?, a_micros.checked_add(micros)?))
+        },
+        _ => None
+    };
+}
+
+// date + integer moves by days, date + time makes a timestamp and intervals
+// move any temporal value. None when the operands don't go together.
+pub fn temporal_add(a: &Data, b: &Data) -> Option<Data> {
+    return match (a, b) {
+        (value, Data::Interval(months, days, micros)) => interval_shift(value, *months, *days, *micros),
+        (Data::Interval(months, days, micros), value) if !matches!(value, Data::Interval(_, _, _)) => {
+            interval_shift(value, *months, *days, *micros)
+        },
+        (Data::Date(days), other) | (other, Data::Date(days)) if other.to_integer().is_some() => {
+            Some(Data::Date(i32::try_from(*days as i128 + other.to_integer().unwrap()).ok()?))
+        },
+        (Data::Date(days), Data::Time(micros)) | (Data::Time(micros), Data::Date(days)) => {
+            Some(Data::Timestamp((*days as i64).checked_mul(MICROS_PER_DAY)?.checked_add(*micros)?))
+        },
+        _ => None
+    };
+}
+
+// date - date gives days, timestamp - timestamp and time - time give
+// intervals, anything else is an addition of the negated operand.
+pub fn temporal_sub(a: &Data, b: &Data) -> Option<Data> {
+    return match (a, b) {
+        (Data::Date(a), Data::Date(b)) => Data::from_integer(*a as i128 - *b as i128),
+        (Data::Time(a), Data::Time(b)) => Some(Data::Interval(0, 0, a - b)),
+        (Data::Date(_) | Data::Timestamp(_) | Data::TimestampTz(_), Data::Timestamp(_) | Data::TimestampTz(_)) |
+        (Data::Timestamp(_) | Data::TimestampTz(_), Data::Date(_)) => {
+            let micros = temporal_to_timestamp(a)?.checked_sub(temporal_to_timestamp(b)?)?;
+            Some(Data::Interval(0, (micros / MICROS_PER_DAY) as i32, micros % MICROS_PER_DAY))
+        },
+        (value, Data::Interval(months, days, micros)) => {
+            interval_shift(value, months.checked_neg()?, days.checked_neg()?, micros.checked_neg()?)
+        },
+        (Data::Date(days), other) if other.to_integer().is_some() => {
+            Some(Data::Date(i32::try_from(*days as i128 - other.to_integer().unwrap()).ok()?))
+        },
+        _ => None
+    };
+}
+
+// Intervals scale by integers, each of their parts on its own.
+pub fn temporal_mul(a: &Data, b: &Data) -> Option<Data> {
+    return match (a, b) {
+        (Data::Interval(months, days, micros), other) | (other, Data::Interval(months, days, micros))
+            if other.to_integer().is_some() => {
+            let factor = other.to_integer().unwrap();
+            Some(Data::Interval(
+                i32::try_from(*months as i128 * factor).ok()?,
+                i32::try_from(*days as i128 * factor).ok()?,
+                i64::try_from(*micros as i128 * factor).ok()?
+            ))
+        },
+        _ => None
+    };
+}
+
+pub fn temporal_is_extract_field(field: &str) -> bool {
+    return matches!(
+        field,
+        "millennium" | "century" | "decade" | "year" | "quarter" | "month" | "week" | "day" | "dow" |
+        "isodow" | "doy" | "hour" | "minute" | "second" | "milliseconds" | "microseconds" | "epoch"
+    );
+}
+
+pub fn temporal_is_trunc_field(field: &str) -> bool {
+    return matches!(
+        field,
+        "millennium" | "century" | "decade" | "year" | "quarter" | "month" | "week" | "day" |
+        "hour" | "minute" | "second" | "milliseconds" | "microseconds"
+    );
+}
+
+// Microseconds read as seconds with a scale of 6 or milliseconds with 3.
+fn micros_as_decimal(micros: i128, scale: u8) -> Data {
+    return Data::Decimal(micros, DECIMAL_MAX_PRECISION, scale);
+}
+
+fn extract_from_date(field: &str, days: i64) -> Option<Data> {
+    let (year, month, day) = temporal_civil_from_days(days);
+    let dow = (days + 4).rem_euclid(7);
+    let isodow = if dow == 0 { 7 } else { dow };
+
+    let value = match field {
+        "millennium" => if year > 0 { (year - 1) / 1000 + 1 } else { year / 1000 - 1 },
+        "century" => if year > 0 { (year - 1) / 100 + 1 } else { year / 100 - 1 },
+        "decade" => year.div_euclid(10),
+        "year" => year,
+        "quarter" => (month as i64 - 1) / 3 + 1,
+        "month" => month as i64,
+        "week" => {
+            let thursday = days - isodow + 4;
+            let (iso_year, _, _) = temporal_civil_from_days(thursday);
+            (thursday - temporal_days_from_civil(iso_year, 1, 1)) / 7 + 1
+        },
+        "day" => day as i64,
+        "dow" => dow,
+        "isodow" => isodow,
+        "doy" => days - temporal_days_from_civil(year, 1, 1) + 1,
+        _ => return None
+    };
+    return Data::from_integer(value as i128);
+}
+
+fn extract_from_time(field: &str, micros: i64) -> Option<Data> {
+    let micros_of_minute = (micros % MICROS_PER_MINUTE) as i128;
+
+    return match field {
+        "hour" => Data::from_integer((micros / MICROS_PER_HOUR) as i128),
+        "minute" => Data::from_integer((micros / MICROS_PER_MINUTE % 60) as i128),
+        "second" => Some(micros_as_decimal(micros_of_minute, 6)),
+        "milliseconds" => Some(micros_as_decimal(micros_of_minute, 3)),
+        "microseconds" => Data::from_integer(micros_of_minute),
+        _ => None
+    };
+}
+
+// EXTRACT gives integers, but seconds, milliseconds and epochs keep their
+// fractions as decimals. NULL for fields the value doesn't have.
+pub fn temporal_extract(field: &String, value: &Data) -> Data {
+    let extracted = match *value {
+        Data::Date(days) if field == "epoch" => Data::from_integer(days as i128 * 86400),
+        Data::Date(days) => extract_from_date(field, days as i64),
+        Data::Time(micros) if field == "epoch" => Some(micros_as_decimal(micros as i128, 6)),
+        Data::Time(micros) => extract_from_time(field, micros),
+        Data::Timestamp(micros) | Data::TimestampTz(micros) => match field.as_str() {
+            "epoch" => Some(micros_as_decimal(micros as i128, 6)),
+            "hour" | "minute" | "second" | "milliseconds" | "microseconds" => {
+                extract_from_time(field, micros.rem_euclid(MICROS_PER_DAY))
+            },
+            _ => extract_from_date(field, micros.div_euclid(MICROS_PER_DAY))
+        },
+        Data::Interval(months, days, micros) => match field.as_str() {
+            "epoch" => Some(micros_as_decimal(temporal_interval_micros(months, days, micros), 6)),
+            "year" => Data::from_integer((months / 12) as i128),
+            "month" => Data::from_integer((months % 12) as i128),
+            "day" => Data::from_integer(days as i128),
+            _ => extract_from_time(field, micros)
+        },
+        _ => None
+    };
+    return extracted.unwrap_or(Data::Null);
+}
+
+fn trunc_days(field: &str, days: i64) -> i64 {
+    let (year, month, _) = temporal_civil_from_days(days);
+    let year_start = |year: i64| temporal_days_from_civil(year, 1, 1);
+
+    return match field {
+        "millennium" => year_start((year - 1).div_euclid(1000) * 1000 + 1),
+        "century" => year_start((year - 1).div_euclid(100) * 100 + 1),
+        "decade" => year_start(year.div_euclid(10) * 10),
+        "year" => year_start(year),
+        "quarter" => temporal_days_from_civil(year, (month - 1) / 3 * 3 + 1, 1),
+        "month" => temporal_days_from_civil(year, month, 1),
+        "week" => days - (days + 3).rem_euclid(7),
+        _ => days
+    };
+}
+
+fn trunc_timestamp(field: &str, micros: i64) -> i64 {
+    let time = micros.rem_euclid(MICROS_PER_DAY);
+    let unit = match field {
+        "hour" => MICROS_PER_HOUR,
+        "minute" => MICROS_PER_MINUTE,
+        "second" => MICROS_PER_SECOND,
+        "milliseconds" => 1000,
+        "microseconds" => 1,
+        _ => return trunc_days(field, micros.div_euclid(MICROS_PER_DAY)) * MICROS_PER_DAY
+    };
+    return micros - time + time / unit * unit;
+}
+
+// DATE_TRUNC drops everything below the field, weeks start on Monday. Dates
+// give timestamps at midnight, timestamps with time zone truncate in UTC.
+pub fn temporal_trunc(field: &String, value: &Data) -> Data {
+    return match *value {
+        Data::Date(days) => Data::Timestamp(trunc_days(field, days as i64) * MICROS_PER_DAY),
+        Data::Timestamp(micros) => Data::Timestamp(trunc_timestamp(field, micros)),
+        Data::TimestampTz(micros) => Data::TimestampTz(trunc_timestamp(field, micros)),
+        _ => Data::Null
+    };
+}
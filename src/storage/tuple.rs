@@ -1,20 +1,56 @@
 use std::fmt;
 use std::mem;
 use std::ops;
-//use std::cmp::Ordering;
+use std::cmp::Ordering;
 use std::string::ToString;
 
+use ordered_float::OrderedFloat;
+
 use crate::utils::vec_u8_to_u16;
 use crate::utils::vec_u8_to_u32;
 use crate::utils::vec_u8_to_u64;
+use crate::utils::vec_u8_to_u128;
 use crate::utils::vec_u8_to_i16;
 use crate::utils::vec_u8_to_i32;
 use crate::utils::vec_u8_to_i64;
+use crate::utils::vec_u8_to_f32;
+use crate::utils::vec_u8_to_f64;
 use crate::utils::vec_u8_to_string;
 use crate::utils::vec_u8_to_text;
+use crate::utils::vec_u8_to_bytes;
+use crate::utils::ExecutionError;
+
+use crate::storage::DECIMAL_MAX_PRECISION;
+use crate::storage::decimal_format;
+use crate::storage::decimal_to_float;
+use crate::storage::decimal_compare;
+use crate::storage::decimal_add;
+use crate::storage::decimal_sub;
+use crate::storage::decimal_mul;
+use crate::storage::decimal_div;
+use crate::storage::decimal_to_bytes;
+use crate::storage::decimal_from_bytes;
+use crate::storage::temporal_is_temporal;
+use crate::storage::temporal_format_date;
+use crate::storage::temporal_format_time;
+use crate::storage::temporal_format_timestamp;
+use crate::storage::temporal_format_interval;
+use crate::storage::temporal_compare;
+use crate::storage::temporal_add;
+use crate::storage::temporal_sub;
+use crate::storage::temporal_mul;
+use crate::storage::bytes_format_hex;
+use crate::storage::bytes_compare;
+use crate::storage::uuid_format;
+use crate::storage::uuid_compare;
+use crate::storage::JsonValue;
+use crate::storage::json_format;
+use crate::storage::json_to_bytes;
+use crate::storage::json_from_bytes;
+use crate::storage::json_compare;
 
 // Should be save in one byte
-#[derive(Debug,Eq,Clone, Ord, PartialOrd)]
+#[derive(Debug,Eq,Clone)]
 pub enum Data {
     Undefined,
     Null,
@@ -28,7 +64,22 @@ pub enum Data {
     SignedInt(i32),
     SignedBigint(i64),
     Varchar(String),
-    Text(String)
+    Text(String),
+    Float32(OrderedFloat<f32>),
+    Float64(OrderedFloat<f64>),
+    // Every digit of the value, the precision and the scale.
+    Decimal(i128, u8, u8),
+    Date(i32),
+    Time(i64),
+    Timestamp(i64),
+    TimestampTz(i64),
+    // Months, days and microseconds.
+    Interval(i32, i32, i64),
+    Bytes(Vec<u8>),
+    Uuid(u128),
+    // The text as it was given.
+    Json(String),
+    Jsonb(JsonValue)
 }
 
 impl Data {
@@ -44,6 +95,9 @@ impl Data {
             (Data::SignedBigint(a), Data::SignedBigint(b)) => Data::Boolean(*a != 0 && *b != 0),
             (Data::SignedInt(a), Data::SignedInt(b)) => Data::Boolean(*a != 0 && *b != 0),
             (Data::Boolean(a), Data::Boolean(b)) => Data::Boolean(*a && *b),
+            (a, b) if a.to_float().is_some() && b.to_float().is_some() => {
+                Data::Boolean(a.to_float().unwrap() != 0.0 && b.to_float().unwrap() != 0.0)
+            },
             (Data::Null, Data::Null) => Data::Boolean(true),
             (_, Data::Null) => Data::Boolean(false),
             (Data::Null, _) => Data::Boolean(false),
@@ -67,6 +121,9 @@ impl Data {
             (Data::SignedBigint(a), Data::SignedBigint(b)) => Data::Boolean(*a != 0 || *b != 0),
             (Data::SignedInt(a), Data::SignedInt(b)) => Data::Boolean(*a != 0 || *b != 0),
             (Data::Boolean(a), Data::Boolean(b)) => Data::Boolean(*a || *b),
+            (a, b) if a.to_float().is_some() && b.to_float().is_some() => {
+                Data::Boolean(a.to_float().unwrap() != 0.0 || b.to_float().unwrap() != 0.0)
+            },
             (Data::Null, Data::Null) => Data::Boolean(true),
             (_, Data::Null) => Data::Boolean(true),
             (Data::Null, _) => Data::Boolean(true),
@@ -83,6 +140,76 @@ impl Data {
         return Data::Boolean(true) == *self;
     }
 
+    // Value of any of the integer types, None for everything else.
+    pub fn to_integer(&self) -> Option<i128> {
+        return match *self {
+            Data::UnsignedTinyint(value) => Some(value as i128),
+            Data::UnsignedSmallint(value) => Some(value as i128),
+            Data::UnsignedInt(value) => Some(value as i128),
+            Data::UnsignedBigint(value) => Some(value as i128),
+            Data::SignedTinyint(value) => Some(value as i128),
+            Data::SignedSmallint(value) => Some(value as i128),
+            Data::SignedInt(value) => Some(value as i128),
+            Data::SignedBigint(value) => Some(value as i128),
+            _ => None
+        };
+    }
+
+    pub fn is_float(&self) -> bool {
+        return matches!(self, Data::Float32(_) | Data::Float64(_));
+    }
+
+    pub fn is_temporal(&self) -> bool {
+        return temporal_is_temporal(self);
+    }
+
+    pub fn is_decimal(&self) -> bool {
+        return matches!(self, Data::Decimal(_, _, _));
+    }
+
+    // Digits and scale of decimals and integers, None for everything else.
+    pub fn to_decimal(&self) -> Option<(i128, u8)> {
+        return match *self {
+            Data::Decimal(value, _, scale) => Some((value, scale)),
+            _ => self.to_integer().map(|value| (value, 0))
+        };
+    }
+
+    // Value of any of the numeric types as a double, None for everything else.
+    pub fn to_float(&self) -> Option<f64> {
+        return match *self {
+            Data::Float32(value) => Some(value.0 as f64),
+            Data::Float64(value) => Some(value.0),
+            Data::Decimal(value, _, scale) => Some(decimal_to_float(value, scale)),
+            _ => self.to_integer().map(|value| value as f64)
+        };
+    }
+
+    // Results of integer arithmetic, unsigned unless they are negative. None
+    // when they fit neither.
+    pub fn from_integer(value: i128) -> Option<Data> {
+        if let Ok(value) = u64::try_from(value) {
+            return Some(Data::UnsignedBigint(value));
+        }
+        return i64::try_from(value).ok().map(Data::SignedBigint);
+    }
+
+    // Results of decimal arithmetic, NULL when they do not fit.
+    pub fn from_decimal(value: Option<(i128, u8)>) -> Data {
+        return match value {
+            Some((value, scale)) => Data::Decimal(value, DECIMAL_MAX_PRECISION, scale),
+            None => Data::Null
+        };
+    }
+
+    // Results of arithmetic on floats, single precision only when both sides are.
+    pub fn from_float(a: &Data, b: &Data, value: f64) -> Data {
+        if let (Data::Float32(_), Data::Float32(_)) = (a, b) {
+            return Data::Float32(OrderedFloat(value as f32));
+        }
+        return Data::Float64(OrderedFloat(value));
+    }
+
     pub fn type_of_children(&self) -> u8 {
         match *self {
             Data::Null => 1,
@@ -97,7 +224,19 @@ impl Data {
             Data::SignedInt(_) => 10,
             Data::SignedBigint(_) => 11,
             Data::Varchar(_) => 12,
-            Data::Text(_) => 13
+            Data::Text(_) => 13,
+            Data::Float32(_) => 14,
+            Data::Float64(_) => 15,
+            Data::Decimal(_, _, _) => 16,
+            Data::Date(_) => 17,
+            Data::Time(_) => 18,
+            Data::Timestamp(_) => 19,
+            Data::TimestampTz(_) => 20,
+            Data::Interval(_, _, _) => 21,
+            Data::Bytes(_) => 22,
+            Data::Uuid(_) => 23,
+            Data::Json(_) => 24,
+            Data::Jsonb(_) => 25
         }
     }
 
@@ -114,6 +253,18 @@ impl Data {
             Data::SignedBigint(_) => 8,
             Data::Varchar(ref s) => s.len() * mem::size_of::<u8>(),
             Data::Text(ref s) => s.len() * mem::size_of::<u8>(),
+            Data::Float32(_) => 4,
+            Data::Float64(_) => 8,
+            Data::Decimal(value, _, _) => 2 + decimal_to_bytes(value).len(),
+            Data::Date(_) => 4,
+            Data::Time(_) => 8,
+            Data::Timestamp(_) => 8,
+            Data::TimestampTz(_) => 8,
+            Data::Interval(_, _, _) => 16,
+            Data::Bytes(ref bytes) => bytes.len(),
+            Data::Uuid(_) => 16,
+            Data::Json(ref s) => s.len(),
+            Data::Jsonb(ref value) => json_to_bytes(value).len(),
             Data::Null => 0,
             Data::Undefined => 0
         }
@@ -132,6 +283,24 @@ impl Data {
             Data::SignedSmallint(i) => i.to_be_bytes().to_vec(),
             Data::SignedInt(i) => i.to_be_bytes().to_vec(),
             Data::SignedBigint(i) => i.to_be_bytes().to_vec(),
+            Data::Float32(i) => i.0.to_be_bytes().to_vec(),
+            Data::Float64(i) => i.0.to_be_bytes().to_vec(),
+            Data::Decimal(value, precision, scale) => {
+                let mut vecs = vec![precision, scale];
+                vecs.append(&mut decimal_to_bytes(value));
+                vecs
+            },
+            Data::Date(i) => i.to_be_bytes().to_vec(),
+            Data::Time(i) => i.to_be_bytes().to_vec(),
+            Data::Timestamp(i) => i.to_be_bytes().to_vec(),
+            Data::TimestampTz(i) => i.to_be_bytes().to_vec(),
+            Data::Uuid(i) => i.to_be_bytes().to_vec(),
+            Data::Interval(months, days, micros) => {
+                let mut vecs = months.to_be_bytes().to_vec();
+                vecs.append(&mut days.to_be_bytes().to_vec());
+                vecs.append(&mut micros.to_be_bytes().to_vec());
+                vecs
+            },
             Data::Varchar(ref s) => {
                 let mut vecs = Vec::new();
                 vecs.append(&mut (s.len() as u16).to_be_bytes().to_vec());
@@ -144,6 +313,25 @@ impl Data {
                 vecs.append(&mut s.as_bytes().to_vec());
                 vecs
             },
+            Data::Bytes(ref bytes) => {
+                let mut vecs = Vec::new();
+                vecs.append(&mut (bytes.len() as u32).to_be_bytes().to_vec());
+                vecs.append(&mut bytes.clone());
+                vecs
+            },
+            Data::Json(ref s) => {
+                let mut vecs = Vec::new();
+                vecs.append(&mut (s.len() as u32).to_be_bytes().to_vec());
+                vecs.append(&mut s.as_bytes().to_vec());
+                vecs
+            },
+            Data::Jsonb(ref value) => {
+                let mut bytes = json_to_bytes(value);
+                let mut vecs = Vec::new();
+                vecs.append(&mut (bytes.len() as u32).to_be_bytes().to_vec());
+                vecs.append(&mut bytes);
+                vecs
+            },
         }
     }
 
@@ -162,6 +350,18 @@ impl Data {
             Data::SignedBigint(i) => format!("{}", i),
             Data::Varchar(ref s) => format!("{}", s),
             Data::Text(ref s) => format!("{}", s),
+            Data::Float32(i) => format!("{}", i),
+            Data::Float64(i) => format!("{}", i),
+            Data::Decimal(value, _, scale) => decimal_format(value, scale),
+            Data::Date(days) => temporal_format_date(days),
+            Data::Time(micros) => temporal_format_time(micros),
+            Data::Timestamp(micros) => temporal_format_timestamp(micros),
+            Data::TimestampTz(micros) => format!("{}+00", temporal_format_timestamp(micros)),
+            Data::Interval(months, days, micros) => temporal_format_interval(months, days, micros),
+            Data::Bytes(ref bytes) => bytes_format_hex(bytes),
+            Data::Uuid(value) => uuid_format(value),
+            Data::Json(ref s) => format!("{}", s),
+            Data::Jsonb(ref value) => json_format(value),
         }
     }
 }
@@ -172,91 +372,158 @@ impl fmt::Display for Data {
     }
 }
 
+// Equal values are those Ord puts in the same place, so values that cannot
+// be compared, as a string that is not a UUID against a UUID, differ.
 impl PartialEq for Data {
     fn eq(&self, other: &Self) -> bool {
-        return match (self, other) {
-            (Data::SignedBigint(a), Data::UnsignedBigint(b)) => *a == (*b as i64),
-            (Data::SignedInt(a), Data::UnsignedInt(b)) => *a == (*b as i32),
-            (Data::SignedSmallint(a), Data::UnsignedSmallint(b)) => *a == (*b as i16),
-            (Data::SignedTinyint(a), Data::UnsignedTinyint(b)) => *a == (*b as i8),
-            (Data::UnsignedBigint(a), Data::UnsignedBigint(b)) => *a == *b,
-            (Data::UnsignedSmallint(a), Data::UnsignedSmallint(b)) =>  *a == *b,
-            (Data::UnsignedInt(a), Data::UnsignedInt(b)) =>  *a == *b,
-            (Data::UnsignedTinyint(a), Data::UnsignedTinyint(b)) =>  *a == *b,
-            (Data::SignedBigint(a), Data::SignedBigint(b)) =>  *a == *b,
-            (Data::SignedSmallint(a), Data::SignedSmallint(b)) => *a == *b,
-            (Data::SignedInt(a), Data::SignedInt(b)) => *a == *b,
-            (Data::SignedTinyint(a), Data::SignedTinyint(b)) => *a == *b,
-            (Data::Boolean(a), Data::Boolean(b)) => *a == *b,
-            (Data::Varchar(a), Data::Varchar(b)) => *a == *b,
-            (Data::Text(a), Data::Text(b)) =>  *a == *b,
-            (Data::Null, Data::Null) => true,
-            (Data::Undefined, Data::Undefined) => true,
-            (Data::Text(a), Data::Varchar(b)) =>  *a == *b,
-            (Data::Varchar(a), Data::Text(b)) =>  *a == *b,
-            (Data::Null, _) => false,
-            (_, Data::Null) => false,
-            (Data::Undefined, _) => false,
-            (_, Data::Undefined) => false,
-            other => panic!("Not implemented equal {:?}", other) 
-        }
+        return self.cmp(other) == Ordering::Equal;
     }
 }
 
-impl ops::Add<Data> for Data {
-    type Output = Data;
+// Integers compare by value whatever their size and sign, as do strings of
+// both types. A float against any number compares as a double, a decimal
+// against a decimal or an integer compares exactly. Temporal values compare
+// on their time line, strings against them are read as their type. Binary
+// values compare byte by byte, UUIDs by their bits and JSON values as JSONB
+// orders them. Values of different kinds keep the order of the variants.
+impl Ord for Data {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let (Some(a), Some(b)) = (self.to_integer(), other.to_integer()) {
+            return a.cmp(&b);
+        }
+        if (self.is_float() || other.is_float()) && self.to_float().is_some() && other.to_float().is_some() {
+            return OrderedFloat(self.to_float().unwrap()).cmp(&OrderedFloat(other.to_float().unwrap()));
+        }
+        if (self.is_decimal() || other.is_decimal()) && self.to_decimal().is_some() && other.to_decimal().is_some() {
+            return decimal_compare(self.to_decimal().unwrap(), other.to_decimal().unwrap());
+        }
+        if let Some(ordering) = temporal_compare(self, other) {
+            return ordering;
+        }
+        if let Some(ordering) = bytes_compare(self, other) {
+            return ordering;
+        }
+        if let Some(ordering) = uuid_compare(self, other) {
+            return ordering;
+        }
+        if let Some(ordering) = json_compare(self, other) {
+            return ordering;
+        }
 
-    fn add(self, other: Data) -> Data {
         return match (self, other) {
-            (Data::UnsignedTinyint(a), Data::UnsignedTinyint(b)) => Data::UnsignedTinyint(a + b),
-            (Data::UnsignedSmallint(a), Data::UnsignedSmallint(b)) => Data::UnsignedSmallint(a + b),
-            (Data::UnsignedInt(a), Data::UnsignedInt(b)) => Data::UnsignedInt(a + b),
-            (Data::UnsignedBigint(a), Data::UnsignedBigint(b)) => Data::UnsignedBigint(a + b),
-            (Data::SignedTinyint(a), Data::SignedTinyint(b)) => Data::SignedTinyint(a + b),
-            (Data::SignedSmallint(a), Data::SignedSmallint(b)) => Data::SignedSmallint(a + b),
-            (Data::SignedInt(a), Data::SignedInt(b)) => Data::SignedInt(a + b),
-            (Data::SignedBigint(a), Data::SignedBigint(b)) => Data::SignedBigint(a + b),
-            (Data::Boolean(a), Data::Boolean(b)) => Data::Boolean(a && b),
-            (Data::Varchar(a), Data::Varchar(b)) => Data::Varchar(format!("{}{}", a, b)),
-            (Data::Text(a), Data::Text(b)) => Data::Text(format!("{}{}", a, b)),
-            other => panic!("Not implemented plus {:?}", other) 
-        }
+            (Data::Varchar(a) | Data::Text(a), Data::Varchar(b) | Data::Text(b)) => a.cmp(b),
+            (Data::Boolean(a), Data::Boolean(b)) => a.cmp(b),
+            _ => self.type_of_children().cmp(&other.type_of_children())
+        };
     }
 }
 
-impl ops::Sub<Data> for Data {
-    type Output = Data;
-
-    fn sub(self, other: Data) -> Data {
-        return match (self, other) {
-            (Data::UnsignedBigint(a), Data::UnsignedBigint(b)) => Data::UnsignedBigint(a - b),
-            _ => panic!("Not implemented") 
-        }
+impl PartialOrd for Data {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
     }
 }
 
-impl ops::Mul<Data> for Data {
-    type Output = Data;
+// Arithmetic between values. Integers of the same type keep their type,
+// other integers are computed wide and take the smallest of BIGINT UNSIGNED
+// and BIGINT that holds the result. Results that do not fit their type are
+// an overflow, operands without the operator are a type error.
+impl Data {
+    pub fn checked_add(self, other: Data) -> Result<Data, ExecutionError> {
+        let result = match (&self, &other) {
+            (Data::Null, _) | (_, Data::Null) => Some(Data::Null),
+            (Data::UnsignedTinyint(a), Data::UnsignedTinyint(b)) => a.checked_add(*b).map(Data::UnsignedTinyint),
+            (Data::UnsignedSmallint(a), Data::UnsignedSmallint(b)) => a.checked_add(*b).map(Data::UnsignedSmallint),
+            (Data::UnsignedInt(a), Data::UnsignedInt(b)) => a.checked_add(*b).map(Data::UnsignedInt),
+            (Data::UnsignedBigint(a), Data::UnsignedBigint(b)) => a.checked_add(*b).map(Data::UnsignedBigint),
+            (Data::SignedTinyint(a), Data::SignedTinyint(b)) => a.checked_add(*b).map(Data::SignedTinyint),
+            (Data::SignedSmallint(a), Data::SignedSmallint(b)) => a.checked_add(*b).map(Data::SignedSmallint),
+            (Data::SignedInt(a), Data::SignedInt(b)) => a.checked_add(*b).map(Data::SignedInt),
+            (Data::SignedBigint(a), Data::SignedBigint(b)) => a.checked_add(*b).map(Data::SignedBigint),
+            (Data::Boolean(a), Data::Boolean(b)) => Some(Data::Boolean(*a && *b)),
+            (Data::Varchar(a), Data::Varchar(b)) => Some(Data::Varchar(format!("{}{}", a, b))),
+            (Data::Text(a), Data::Text(b)) => Some(Data::Text(format!("{}{}", a, b))),
+            (Data::Bytes(a), Data::Bytes(b)) => Some(Data::Bytes([a.as_slice(), b.as_slice()].concat())),
+            (a, b) if a.to_integer().is_some() && b.to_integer().is_some() => {
+                a.to_integer().unwrap().checked_add(b.to_integer().unwrap()).and_then(Data::from_integer)
+            },
+            (a, b) if (a.is_float() || b.is_float()) && a.to_float().is_some() && b.to_float().is_some() => {
+                Some(Data::from_float(a, b, a.to_float().unwrap() + b.to_float().unwrap()))
+            },
+            (a, b) if (a.is_decimal() || b.is_decimal()) && a.to_decimal().is_some() && b.to_decimal().is_some() => {
+                Some(Data::from_decimal(decimal_add(a.to_decimal().unwrap(), b.to_decimal().unwrap())))
+            },
+            (a, b) if temporal_add(a, b).is_some() => temporal_add(a, b),
+            _ => return Err(arithmetic_type_error("+", &self, &other))
+        };
+        return result.ok_or_else(|| arithmetic_overflow("+", &self, &other));
+    }
 
-    fn mul(self, other: Data) -> Data {
-        return match (self, other) {
-            (Data::UnsignedBigint(a), Data::UnsignedBigint(b)) => Data::UnsignedBigint(a * b),
-            _ => panic!("Not implemented") 
-        }
+    pub fn checked_sub(self, other: Data) -> Result<Data, ExecutionError> {
+        let result = match (&self, &other) {
+            (Data::Null, _) | (_, Data::Null) => Some(Data::Null),
+            (a, b) if a.to_integer().is_some() && b.to_integer().is_some() => {
+                a.to_integer().unwrap().checked_sub(b.to_integer().unwrap()).and_then(Data::from_integer)
+            },
+            (a, b) if (a.is_float() || b.is_float()) && a.to_float().is_some() && b.to_float().is_some() => {
+                Some(Data::from_float(a, b, a.to_float().unwrap() - b.to_float().unwrap()))
+            },
+            (a, b) if (a.is_decimal() || b.is_decimal()) && a.to_decimal().is_some() && b.to_decimal().is_some() => {
+                Some(Data::from_decimal(decimal_sub(a.to_decimal().unwrap(), b.to_decimal().unwrap())))
+            },
+            (a, b) if temporal_sub(a, b).is_some() => temporal_sub(a, b),
+            _ => return Err(arithmetic_type_error("-", &self, &other))
+        };
+        return result.ok_or_else(|| arithmetic_overflow("-", &self, &other));
     }
-}
 
-impl ops::Div<Data> for Data {
-    type Output = Data;
+    pub fn checked_mul(self, other: Data) -> Result<Data, ExecutionError> {
+        let result = match (&self, &other) {
+            (Data::Null, _) | (_, Data::Null) => Some(Data::Null),
+            (a, b) if a.to_integer().is_some() && b.to_integer().is_some() => {
+                a.to_integer().unwrap().checked_mul(b.to_integer().unwrap()).and_then(Data::from_integer)
+            },
+            (a, b) if (a.is_float() || b.is_float()) && a.to_float().is_some() && b.to_float().is_some() => {
+                Some(Data::from_float(a, b, a.to_float().unwrap() * b.to_float().unwrap()))
+            },
+            (a, b) if (a.is_decimal() || b.is_decimal()) && a.to_decimal().is_some() && b.to_decimal().is_some() => {
+                Some(Data::from_decimal(decimal_mul(a.to_decimal().unwrap(), b.to_decimal().unwrap())))
+            },
+            (a, b) if temporal_mul(a, b).is_some() => temporal_mul(a, b),
+            _ => return Err(arithmetic_type_error("*", &self, &other))
+        };
+        return result.ok_or_else(|| arithmetic_overflow("*", &self, &other));
+    }
 
-    fn div(self, other: Data) -> Data {
-        return match (self, other) {
-            (Data::UnsignedBigint(a), Data::UnsignedBigint(b)) => Data::UnsignedBigint(a / b),
-            _ => panic!("Not implemented") 
-        }
+    // Division by zero gives NULL.
+    pub fn checked_div(self, other: Data) -> Result<Data, ExecutionError> {
+        let result = match (&self, &other) {
+            (Data::Null, _) | (_, Data::Null) => Some(Data::Null),
+            (a, b) if b.to_integer() == Some(0) && a.to_integer().is_some() => Some(Data::Null),
+            (a, b) if b.to_float() == Some(0.0) && a.to_float().is_some() => Some(Data::Null),
+            (a, b) if a.to_integer().is_some() && b.to_integer().is_some() => {
+                a.to_integer().unwrap().checked_div(b.to_integer().unwrap()).and_then(Data::from_integer)
+            },
+            (a, b) if (a.is_float() || b.is_float()) && a.to_float().is_some() && b.to_float().is_some() => {
+                Some(Data::from_float(a, b, a.to_float().unwrap() / b.to_float().unwrap()))
+            },
+            (a, b) if (a.is_decimal() || b.is_decimal()) && a.to_decimal().is_some() && b.to_decimal().is_some() => {
+                Some(Data::from_decimal(decimal_div(a.to_decimal().unwrap(), b.to_decimal().unwrap())))
+            },
+            _ => return Err(arithmetic_type_error("/", &self, &other))
+        };
+        return result.ok_or_else(|| arithmetic_overflow("/", &self, &other));
     }
 }
 
+fn arithmetic_overflow(operator: &str, a: &Data, b: &Data) -> ExecutionError {
+    return ExecutionError::NumericOverflow(format!("{} {} {}", a, operator, b));
+}
+
+fn arithmetic_type_error(operator: &str, a: &Data, b: &Data) -> ExecutionError {
+    return ExecutionError::OperatorTypeNotMatch(String::from(operator), a.to_string(), b.to_string());
+}
+
 impl ops::Not for Data {
     type Output = Data;
 
@@ -273,6 +540,14 @@ impl ops::Not for Data {
             Data::Boolean(a) => Data::Boolean(!a),
             Data::Varchar(a) => Data::Boolean(a == ""),
             Data::Text(a) => Data::Boolean(a == ""),
+            Data::Float32(a) => Data::Boolean(a.0 == 0.0),
+            Data::Float64(a) => Data::Boolean(a.0 == 0.0),
+            Data::Decimal(a, _, _) => Data::Boolean(a == 0),
+            Data::Date(_) | Data::Time(_) | Data::Timestamp(_) | Data::TimestampTz(_) => Data::Boolean(false),
+            Data::Interval(months, days, micros) => Data::Boolean(months == 0 && days == 0 && micros == 0),
+            Data::Bytes(a) => Data::Boolean(a.len() == 0),
+            Data::Uuid(_) => Data::Boolean(false),
+            Data::Json(_) | Data::Jsonb(_) => Data::Boolean(false),
             Data::Null => Data::Boolean(true),
             Data::Undefined => Data::Boolean(true)
         }
@@ -295,6 +570,18 @@ impl ops::Neg for Data {
             Data::Boolean(a) => Data::Boolean(!a),
             Data::Varchar(a) => Data::Varchar(a),
             Data::Text(a) => Data::Text(a),
+            Data::Float32(a) => Data::Float32(-a),
+            Data::Float64(a) => Data::Float64(-a),
+            Data::Decimal(a, precision, scale) => Data::Decimal(-a, precision, scale),
+            Data::Interval(months, days, micros) => Data::Interval(-months, -days, -micros),
+            Data::Date(a) => Data::Date(a),
+            Data::Time(a) => Data::Time(a),
+            Data::Timestamp(a) => Data::Timestamp(a),
+            Data::TimestampTz(a) => Data::TimestampTz(a),
+            Data::Bytes(a) => Data::Bytes(a),
+            Data::Uuid(a) => Data::Uuid(a),
+            Data::Json(a) => Data::Json(a),
+            Data::Jsonb(a) => Data::Jsonb(a),
             Data::Null => Data::Boolean(true),
             Data::Undefined => Data::Boolean(true)
         }
@@ -397,6 +684,65 @@ pub fn tuple_deserialize(buffer: &Vec<u8> ) -> Tuple {
                     tuple.push(Data::Text(string));
                     value_position += string_size;
                 },
+                14 => {
+                    tuple.push(Data::Float32(OrderedFloat(vec_u8_to_f32(buffer, value_position))));
+                    value_position += 4;
+                },
+                15 => {
+                    tuple.push(Data::Float64(OrderedFloat(vec_u8_to_f64(buffer, value_position))));
+                    value_position += 8;
+                },
+                16 => {
+                    let precision = buffer[value_position];
+                    let scale = buffer[value_position + 1];
+                    let value = decimal_from_bytes(buffer, value_position + 2);
+                    tuple.push(Data::Decimal(value, precision, scale));
+                    value_position += 3 + buffer[value_position + 2] as usize;
+                },
+                17 => {
+                    tuple.push(Data::Date(vec_u8_to_i32(buffer, value_position)));
+                    value_position += 4;
+                },
+                18 => {
+                    tuple.push(Data::Time(vec_u8_to_i64(buffer, value_position)));
+                    value_position += 8;
+                },
+                19 => {
+                    tuple.push(Data::Timestamp(vec_u8_to_i64(buffer, value_position)));
+                    value_position += 8;
+                },
+                20 => {
+                    tuple.push(Data::TimestampTz(vec_u8_to_i64(buffer, value_position)));
+                    value_position += 8;
+                },
+                21 => {
+                    let months = vec_u8_to_i32(buffer, value_position);
+                    let days = vec_u8_to_i32(buffer, value_position + 4);
+                    let micros = vec_u8_to_i64(buffer, value_position + 8);
+                    tuple.push(Data::Interval(months, days, micros));
+                    value_position += 16;
+                },
+                22 => {
+                    let bytes = vec_u8_to_bytes(buffer, value_position);
+                    let bytes_size = bytes.len() + 4;
+                    tuple.push(Data::Bytes(bytes));
+                    value_position += bytes_size;
+                },
+                23 => {
+                    tuple.push(Data::Uuid(vec_u8_to_u128(buffer, value_position)));
+                    value_position += 16;
+                },
+                24 => {
+                    let string = vec_u8_to_text(buffer, value_position);
+                    let string_size = string.len() + 4;
+                    tuple.push(Data::Json(string));
+                    value_position += string_size;
+                },
+                25 => {
+                    let mut json_position = value_position + 4;
+                    tuple.push(Data::Jsonb(json_from_bytes(buffer, &mut json_position)));
+                    value_position = json_position;
+                },
                 _ => {}
             }
         }
@@ -516,12 +862,36 @@ pub fn get_tuple_sequence_without_id(
     return tuple;
 }
 
+pub fn get_tuple_foreign_key(
+    db_name: &String,
+    tbl_name: &String,
+    col_name: &String,
+    name: &String,
+    ref_tbl_name: &String,
+    ref_col_name: &String,
+    on_delete: &String,
+    on_update: &String
+) -> Tuple {
+    let mut tuple: Tuple = tuple_new();
+    tuple.push(Data::Varchar(db_name.clone()));
+    tuple.push(Data::Varchar(tbl_name.clone()));
+    tuple.push(Data::Varchar(col_name.clone()));
+    tuple.push(Data::Varchar(name.clone()));
+    tuple.push(Data::Varchar(ref_tbl_name.clone()));
+    tuple.push(Data::Varchar(ref_col_name.clone()));
+    tuple.push(Data::Varchar(on_delete.clone()));
+    tuple.push(Data::Varchar(on_update.clone()));
+    return tuple;
+}
+
 pub fn get_tuple_index(
     db_name: &String,
     tbl_name: &String,
     col_name: &String,
     name: &String,
-    itype: &String
+    itype: &String,
+    unique: bool,
+    primary_key: bool
 ) -> Tuple {
     let mut tuple: Tuple = tuple_new();
     tuple.push(Data::Varchar(db_name.clone()));
@@ -529,7 +899,23 @@ pub fn get_tuple_index(
     tuple.push(Data::Varchar(col_name.clone()));
     tuple.push(Data::Varchar(name.clone()));
     tuple.push(Data::Varchar(itype.clone()));
+    tuple.push(Data::Boolean(unique));
+    tuple.push(Data::Boolean(primary_key));
     return tuple;
 }
 
 
+
+pub fn get_tuple_check_constraint(
+    db_name: &String,
+    tbl_name: &String,
+    name: &String,
+    expression: &String
+) -> Tuple {
+    let mut tuple: Tuple = tuple_new();
+    tuple.push(Data::Varchar(db_name.clone()));
+    tuple.push(Data::Varchar(tbl_name.clone()));
+    tuple.push(Data::Varchar(name.clone()));
+    tuple.push(Data::Varchar(expression.clone()));
+    return tuple;
+}
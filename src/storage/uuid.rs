@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::collections::hash_map::RandomState;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering as AtomicOrdering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::storage::Data;
+
+// UUIDs are kept as the 128 bits they stand for, so they order the way
+// their canonical text does.
+
+// Count of the UUIDs generated, mixed into the next one.
+static UUID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// The canonical form, 8-4-4-4-12 hex digits, also taken without hyphens
+// and between braces. Digits are read in any case.
+pub fn uuid_parse(value: &str) -> Option<u128> {
+    let value = value.trim();
+    let value = match value.strip_prefix('{') {
+        Some(inner) => inner.strip_suffix('}')?,
+        None => value
+    };
+
+    let digits: String = if value.len() == 36 {
+        let groups: Vec<&str> = value.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+        if lengths != vec![8, 4, 4, 4, 12] {
+            return None;
+        }
+        groups.concat()
+    } else {
+        value.to_string()
+    };
+
+    if digits.len() != 32 || !digits.chars().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+    return u128::from_str_radix(&digits, 16).ok();
+}
+
+pub fn uuid_format(value: u128) -> String {
+    let digits = format!("{:032x}", value);
+    return format!(
+        "{}-{}-{}-{}-{}",
+        &digits[0..8],
+        &digits[8..12],
+        &digits[12..16],
+        &digits[16..20],
+        &digits[20..32]
+    );
+}
+
+// Random UUIDs, version 4. The bits come from the randomly keyed hasher of
+// the standard library, fed the clock and a counter so that no two calls
+// hash the same input.
+pub fn uuid_generate() -> u128 {
+    let mut random: u128 = 0;
+
+    for _ in 0..2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(UUID_COUNTER.fetch_add(1, AtomicOrdering::Relaxed));
+        hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos());
+        random = (random << 64) | hasher.finish() as u128;
+    }
+
+    let version = 0x4u128 << 76;
+    let variant = 0x2u128 << 62;
+    return (random & !(0xfu128 << 76) & !(0x3u128 << 62)) | version | variant;
+}
+
+// Strings become UUIDs when they hold one.
+pub fn uuid_cast(value: &Data) -> Option<u128> {
+    return match value {
+        Data::Uuid(value) => Some(*value),
+        Data::Varchar(string) | Data::Text(string) => uuid_parse(string),
+        _ => None
+    };
+}
+
+// UUIDs compare by their bits, strings against them are read as UUIDs.
+pub fn uuid_compare(a: &Data, b: &Data) -> Option<Ordering> {
+    if !matches!(a, Data::Uuid(_)) && !matches!(b, Data::Uuid(_)) {
+        return None;
+    }
+    return Some(uuid_cast(a)?.cmp(&uuid_cast(b)?));
+}
@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use crate::storage::BLOCK_SIZE;
+use crate::storage::format_wal_name;
+use crate::storage::append_data;
+use crate::storage::read_file;
+use crate::storage::write_data;
+use crate::storage::sync_file;
+use crate::storage::truncate_file;
+
+use crate::utils::crc32;
+use crate::utils::v_u8_to_u16;
+use crate::utils::v_u8_to_u32;
+use crate::utils::v_u8_to_u64;
+use crate::utils::Logger;
+use crate::utils::ExecutionError;
+
+/*
+ * The write-ahead log keeps full images of the blocks that a flush is going
+ * to overwrite. A flush is only allowed to touch the data files after its
+ * records and the commit record were appended and synced, so on startup the
+ * log either has a complete transaction (replay it) or a torn tail (discard).
+ *
+ * Page record:   [1][name_len u16][name][position u64][block][crc32 u32]
+ * Commit record: [2][record_count u32][crc32 u32]
+ */
+
+pub const WAL_PAGE_RECORD: u8 = 1;
+pub const WAL_COMMIT_RECORD: u8 = 2;
+
+#[derive(Debug, Clone)]
+pub struct WalRecord {
+    pub file_name: String,
+    pub position: u64,
+    pub data: [u8; BLOCK_SIZE]
+}
+
+impl WalRecord {
+    pub fn new(file_name: String, position: u64, data: [u8; BLOCK_SIZE]) -> Self {
+        WalRecord { file_name, position, data }
+    }
+}
+
+fn wal_serialize_record(record: &WalRecord) -> Vec<u8> {
+    let mut buffer: Vec<u8> = Vec::new();
+
+    buffer.push(WAL_PAGE_RECORD);
+    buffer.append(&mut (record.file_name.len() as u16).to_be_bytes().to_vec());
+    buffer.append(&mut record.file_name.as_bytes().to_vec());
+    buffer.append(&mut record.position.to_be_bytes().to_vec());
+    buffer.extend_from_slice(&record.data);
+
+    let checksum = crc32(&buffer);
+    buffer.append(&mut checksum.to_be_bytes().to_vec());
+    buffer
+}
+
+fn wal_serialize_commit(record_count: u32) -> Vec<u8> {
+    let mut buffer: Vec<u8> = Vec::new();
+
+    buffer.push(WAL_COMMIT_RECORD);
+    buffer.append(&mut record_count.to_be_bytes().to_vec());
+
+    let checksum = crc32(&buffer);
+    buffer.append(&mut checksum.to_be_bytes().to_vec());
+    buffer
+}
+
+pub fn wal_append_records(records: &Vec<WalRecord>) {
+    let wal_name = format_wal_name();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    for record in records {
+        buffer.append(&mut wal_serialize_record(record));
+    }
+    buffer.append(&mut wal_serialize_commit(records.len() as u32));
+
+    append_data(&wal_name, &buffer);
+    sync_file(&wal_name);
+}
+
+pub fn wal_checkpoint() {
+    truncate_file(&format_wal_name(), 0);
+}
+
+// Returns only the committed transactions. Parsing stops at the first record
+// that is truncated or whose checksum does not match, since everything after a
+// torn write is garbage. A log that can not be read is an error, not an
+// empty log.
+pub fn wal_read_transactions() -> Result<Vec<Vec<WalRecord>>, ExecutionError> {
+    let buffer = read_file(&format_wal_name())?;
+    let mut transactions: Vec<Vec<WalRecord>> = Vec::new();
+    let mut pending: Vec<WalRecord> = Vec::new();
+    let mut pos: usize = 0;
+
+    while pos < buffer.len() {
+        let start = pos;
+        match buffer[pos] {
+            WAL_PAGE_RECORD => {
+                if pos + 3 > buffer.len() {
+                    break;
+                }
+                let name_len = v_u8_to_u16(&buffer, pos + 1) as usize;
+                let record_len = 1 + 2 + name_len + 8 + BLOCK_SIZE;
+                if start + record_len + 4 > buffer.len() {
+                    break;
+                }
+                if crc32(&buffer[start..(start + record_len)]) != v_u8_to_u32(&buffer, start + record_len) {
+                    break;
+                }
+
+                let name_bytes = buffer[(pos + 3)..(pos + 3 + name_len)].to_vec();
+                let Ok(file_name) = String::from_utf8(name_bytes) else { break };
+                let position = v_u8_to_u64(&buffer, pos + 3 + name_len);
+                let mut data = [0u8; BLOCK_SIZE];
+                let data_start = pos + 3 + name_len + 8;
+                data.copy_from_slice(&buffer[data_start..(data_start + BLOCK_SIZE)]);
+
+                pending.push(WalRecord::new(file_name, position, data));
+                pos = start + record_len + 4;
+            },
+            WAL_COMMIT_RECORD => {
+                if start + 9 > buffer.len() {
+                    break;
+                }
+                if crc32(&buffer[start..(start + 5)]) != v_u8_to_u32(&buffer, start + 5) {
+                    break;
+                }
+                let record_count = v_u8_to_u32(&buffer, start + 1) as usize;
+                if record_count != pending.len() {
+                    break;
+                }
+
+                transactions.push(pending);
+                pending = Vec::new();
+                pos = start + 9;
+            },
+            _ => break
+        }
+    }
+
+    if pending.len() > 0 {
+        Logger::warn(format!("discarding {} incomplete wal records", pending.len()).leak());
+    }
+
+    return Ok(transactions);
+}
+
+pub fn wal_recover() -> Result<usize, ExecutionError> {
+    let transactions = wal_read_transactions()?;
+    let mut files: Vec<String> = Vec::new();
+
+    for transaction in transactions.iter() {
+        for record in transaction {
+            let folder_exists = match Path::new(&record.file_name).parent() {
+                Some(folder) => folder.exists(),
+                None => false
+            };
+            if folder_exists == false {
+                continue;
+            }
+
+            write_data(&record.file_name, record.position, &record.data);
+            if files.contains(&record.file_name) == false {
+                files.push(record.file_name.clone());
+            }
+        }
+    }
+
+    for file_name in files.iter() {
+        sync_file(file_name);
+    }
+
+    if transactions.len() > 0 {
+        Logger::info(format!("recovered {} wal transactions", transactions.len()).leak());
+    }
+
+    wal_checkpoint();
+
+    return Ok(transactions.len());
+}
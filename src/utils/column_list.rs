@@ -0,0 +1,30 @@
+// Composite keys keep their columns on a single catalog field, in the order of
+// the key. Commas separate them, backslashes escape commas and backslashes
+// inside the names, so a list of one plain name is the name itself.
+pub fn join_column_names(column_names: &Vec<String>) -> String {
+    return column_names
+        .iter()
+        .map(|column_name| column_name.replace('\\', "\\\\").replace(',', "\\,"))
+        .collect::<Vec<String>>()
+        .join(",");
+}
+
+pub fn split_column_names(column_list: &str) -> Vec<String> {
+    let mut column_names: Vec<String> = Vec::new();
+    let mut column_name = String::new();
+    let mut chars = column_list.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    column_name.push(escaped);
+                }
+            },
+            ',' => column_names.push(std::mem::take(&mut column_name)),
+            _ => column_name.push(c)
+        }
+    }
+    column_names.push(column_name);
+    return column_names;
+}
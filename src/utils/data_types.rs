@@ -18,6 +18,12 @@ pub fn vec_u8_to_u64(bytes: &Vec<u8>, pos: usize) -> u64 {
     ])
 }
 
+pub fn vec_u8_to_u128(bytes: &Vec<u8>, pos: usize) -> u128 {
+    let mut value = [0u8; 16];
+    value.copy_from_slice(&bytes[pos..(pos + 16)]);
+    u128::from_be_bytes(value)
+}
+
 pub fn vec_u8_to_i16(bytes: &Vec<u8>, pos: usize) -> i16 {
     i16::from_be_bytes([bytes[pos], bytes[pos + 1]])
 }
@@ -33,6 +39,17 @@ pub fn vec_u8_to_i64(bytes: &Vec<u8>, pos: usize) -> i64 {
     ])
 }
 
+pub fn vec_u8_to_f32(bytes: &Vec<u8>, pos: usize) -> f32 {
+    f32::from_be_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]])
+}
+
+pub fn vec_u8_to_f64(bytes: &Vec<u8>, pos: usize) -> f64 {
+    f64::from_be_bytes([
+        bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3], 
+        bytes[pos+4], bytes[pos + 5], bytes[pos + 6], bytes[pos + 7]
+    ])
+}
+
 pub fn vec_u8_to_string(bytes: &Vec<u8>, pos: usize) -> String {
     // Read first 2 bytes as u16 length (little-endian)
     let size = u16::from_be_bytes([bytes[pos], bytes[pos + 1]]) as usize;
@@ -56,6 +73,16 @@ pub fn vec_u8_to_text(bytes: &Vec<u8>, pos: usize) -> String {
     string
 }
 
+pub fn vec_u8_to_bytes(bytes: &Vec<u8>, pos: usize) -> Vec<u8> {
+    // Read first 4 bytes as u32 length
+    let size = u32::from_be_bytes([
+        bytes[pos], bytes[pos + 1],
+        bytes[pos + 2], bytes[pos + 3]
+    ]) as usize;
+
+    bytes[(pos + 4)..(pos + 4 + size)].to_vec()
+}
+
 pub fn v_u8_to_u16(bytes: &[u8], pos: usize) -> u16 {
     u16::from_be_bytes([bytes[pos], bytes[pos + 1]])
 }
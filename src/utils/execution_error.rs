@@ -23,6 +23,15 @@ pub enum ExecutionError {
 
     IndexNotExists(String),
     IndexExists(String),
+    IndexKeyTooLarge(String),
+    IndexTypeNotSupported(String),
+    IndexCorrupted(String),
+    UniqueViolation(String, String),
+
+    ForeignKeyViolation(String, String),
+    ReferencedKeyNotUnique(String, String),
+    TableReferenced(String, String),
+    CheckViolation(String),
 
     ViewNotExists(String),
     ViewExists(String),
@@ -30,12 +39,18 @@ pub enum ExecutionError {
     ColumnNotExists(String),
     ColumnCantBeNull(String, String, String),
     ColumnTypeNotMatch(String, String, String),
+    ColumnNotGrouped(String),
+    AggregateTypeNotMatch(String, String),
+    OperatorTypeNotMatch(String, String, String),
+    NumericOverflow(String),
 
     WrongTupleSize(usize, usize),
     TupleNotExists(usize),
     PositionNotExists(usize),
     FailedUpdateTuples,
 
+    IoError(String, String),
+
     NotImplementedYet
 }
 
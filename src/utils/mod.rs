@@ -1,20 +1,32 @@
 pub mod logger;
 pub mod execution_error;
 pub mod data_types;
+pub mod checksum;
+pub mod column_list;
 
 pub use self::execution_error::ExecutionError;
 pub use self::execution_error::QueryError;
 
 pub use self::logger::Logger;
 
+pub use self::checksum::crc32;
+pub use self::checksum::fnv1a;
+
+pub use self::column_list::join_column_names;
+pub use self::column_list::split_column_names;
+
 pub use self::data_types::vec_u8_to_u16;
 pub use self::data_types::vec_u8_to_u32;
 pub use self::data_types::vec_u8_to_u64;
+pub use self::data_types::vec_u8_to_u128;
 pub use self::data_types::vec_u8_to_i16;
 pub use self::data_types::vec_u8_to_i32;
 pub use self::data_types::vec_u8_to_i64;
+pub use self::data_types::vec_u8_to_f32;
+pub use self::data_types::vec_u8_to_f64;
 pub use self::data_types::vec_u8_to_string;
 pub use self::data_types::vec_u8_to_text;
+pub use self::data_types::vec_u8_to_bytes;
 
 pub use self::data_types::v_u8_to_u16;
 pub use self::data_types::v_u8_to_u32;
@@ -55,7 +55,7 @@ pub fn test_in_one_column_without_primary_key() {
     let result_set = insert_row(&mut machine, &table, &columns, &columns, &mut vec![tuple], false);
     assert!(matches!(result_set, Ok(_result_set)));
 
-    let result_set = product_cartesian(&mut machine, vec![table]);
+    let result_set = product_cartesian(&mut machine, vec![table]).unwrap();
 
     assert_eq!(result_set.tuples.len(), 1);
     assert_eq!(result_set.tuples[0].len(), 2);
@@ -124,7 +124,7 @@ pub fn test_in_one_column_with_primary_key() {
     let result_set = insert_row(&mut machine, &table, &table_columns, &insert_columns, &mut vec![tuple], false);
     assert!(matches!(result_set, Ok(_result_set)));
 
-    let result_set = product_cartesian(&mut machine, vec![table]);
+    let result_set = product_cartesian(&mut machine, vec![table]).unwrap();
 
     assert_eq!(result_set.tuples.len(), 1);
     assert_eq!(result_set.column_count(), 2);
@@ -0,0 +1,176 @@
+use rusticodb::machine::Machine;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::utils::ExecutionError;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+use rusticodb::storage::Tuple;
+use rusticodb::storage::ResultSet;
+use rusticodb::storage::JsonValue;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_scores(machine: &mut Machine) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let result_set = parse_command(
+        machine,
+        "CREATE TABLE scores (id BIGINT PRIMARY KEY, name VARCHAR, team VARCHAR, score BIGINT)"
+    );
+    assert!(matches!(result_set, Ok(_)));
+
+    let result_set = parse_command(
+        machine,
+        "INSERT scores(id, name, team, score) VALUES \
+        (1, 'ana', 'red', 30), (2, 'bruno', 'blue', NULL), (3, 'carla', 'red', 10), \
+        (4, 'davi', 'blue', 20), (5, 'eva', NULL, 20), (6, 'fabio', 'red', 30)"
+    );
+    assert!(matches!(result_set, Ok(_)));
+}
+
+fn get_tuples(result_set: Result<Vec<ResultSet>, ExecutionError>) -> Vec<Tuple> {
+    return result_set.unwrap().remove(0).tuples;
+}
+
+fn varchar(value: &str) -> Data {
+    return Data::Varchar(String::from(value));
+}
+
+#[test]
+pub fn test_aggregates_without_group_by() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+    setup_scores(&mut machine);
+
+    let tuples = get_tuples(parse_command(
+        &mut machine,
+        "SELECT COUNT(*), COUNT(score), COUNT(DISTINCT score), SUM(score), MIN(score), MAX(name) FROM scores"
+    ));
+    assert_eq!(
+        tuples,
+        vec![vec![
+            Data::UnsignedBigint(6),
+            Data::UnsignedBigint(5),
+            Data::UnsignedBigint(3),
+            Data::UnsignedBigint(110),
+            Data::SignedBigint(10),
+            varchar("fabio")
+        ]]
+    );
+
+    let tuples = get_tuples(parse_command(&mut machine, "SELECT COUNT(*), SUM(score) FROM scores WHERE id > 10"));
+    assert_eq!(tuples, vec![vec![Data::UnsignedBigint(0), Data::Null]]);
+}
+
+#[test]
+pub fn test_group_by_with_null_group() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+    setup_scores(&mut machine);
+
+    let result_set = parse_command(
+        &mut machine,
+        "SELECT team, COUNT(*) AS players, SUM(score) AS total FROM scores GROUP BY team ORDER BY team NULLS FIRST"
+    );
+    let rs = &result_set.as_ref().unwrap()[0];
+    assert_eq!(rs.columns[1].alias, String::from("players"));
+    assert_eq!(
+        rs.tuples,
+        vec![
+            vec![Data::Null, Data::UnsignedBigint(1), Data::UnsignedBigint(20)],
+            vec![varchar("blue"), Data::UnsignedBigint(2), Data::UnsignedBigint(20)],
+            vec![varchar("red"), Data::UnsignedBigint(3), Data::UnsignedBigint(70)]
+        ]
+    );
+}
+
+#[test]
+pub fn test_group_by_expressions_positions_and_aliases() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+    setup_scores(&mut machine);
+
+    let expected = vec![
+        vec![Data::Null, Data::UnsignedBigint(1)],
+        vec![Data::UnsignedBigint(1), Data::UnsignedBigint(1)],
+        vec![Data::UnsignedBigint(2), Data::UnsignedBigint(2)],
+        vec![Data::UnsignedBigint(3), Data::UnsignedBigint(2)]
+    ];
+
+    let tuples = get_tuples(parse_command(
+        &mut machine,
+        "SELECT score / 10, COUNT(*) FROM scores GROUP BY score / 10 ORDER BY 1 NULLS FIRST"
+    ));
+    assert_eq!(tuples, expected);
+
+    let tuples = get_tuples(parse_command(
+        &mut machine,
+        "SELECT score / 10 AS tens, COUNT(*) FROM scores GROUP BY tens ORDER BY tens NULLS FIRST"
+    ));
+    assert_eq!(tuples, expected);
+
+    let tuples = get_tuples(parse_command(
+        &mut machine,
+        "SELECT score / 10, COUNT(*) FROM scores GROUP BY 1 ORDER BY COUNT(*) DESC, 1 LIMIT 2"
+    ));
+    assert_eq!(tuples, vec![expected[2].clone(), expected[3].clone()]);
+}
+
+#[test]
+pub fn test_having_filters_groups() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+    setup_scores(&mut machine);
+
+    let tuples = get_tuples(parse_command(
+        &mut machine,
+        "SELECT team, AVG(score) FROM scores GROUP BY team HAVING COUNT(*) > 1 AND MAX(score) > 20"
+    ));
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][0], varchar("red"));
+    assert_eq!(tuples[0][1].to_string(), String::from("23.333333"));
+
+    let tuples = get_tuples(parse_command(&mut machine, "SELECT COUNT(*) FROM scores HAVING SUM(score) > 1000"));
+    assert!(tuples.is_empty());
+}
+
+#[test]
+pub fn test_string_and_array_aggregates() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+    setup_scores(&mut machine);
+
+    let tuples = get_tuples(parse_command(
+        &mut machine,
+        "SELECT team, STRING_AGG(name, ';'), ARRAY_AGG(score) FROM scores WHERE team = 'blue' GROUP BY team"
+    ));
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][1], Data::Text(String::from("bruno;davi")));
+    assert_eq!(
+        tuples[0][2],
+        Data::Jsonb(JsonValue::Array(vec![JsonValue::Null, JsonValue::Number(String::from("20"))]))
+    );
+}
+
+#[test]
+pub fn test_grouping_errors() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+    setup_scores(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT name, COUNT(*) FROM scores GROUP BY team");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnNotGrouped(ref name)) if name == "name"));
+
+    let result_set = parse_command(&mut machine, "SELECT team FROM scores WHERE COUNT(*) > 1 GROUP BY team");
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+
+    let result_set = parse_command(&mut machine, "SELECT SUM(COUNT(*)) FROM scores");
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+
+    let result_set = parse_command(&mut machine, "SELECT SUM(name) FROM scores");
+    assert!(matches!(result_set, Err(ExecutionError::AggregateTypeNotMatch(_, _))));
+}
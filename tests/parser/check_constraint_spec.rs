@@ -0,0 +1,134 @@
+use rstest::rstest;
+
+use rusticodb::machine::Machine;
+use rusticodb::machine::Table;
+use rusticodb::machine::Attribution;
+use rusticodb::machine::get_columns;
+use rusticodb::machine::get_check_constraints;
+use rusticodb::machine::check_table_exists;
+use rusticodb::machine::update_row;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::utils::ExecutionError;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::Expression2Type;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_products(machine: &mut Machine) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let result_set = parse_command(
+        machine,
+        "CREATE TABLE products (id BIGINT PRIMARY KEY, price BIGINT CHECK (price > 0), \
+         discount BIGINT, CONSTRAINT valid_discount CHECK (discount <= price))"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+#[test]
+pub fn test_create_table_registers_check_constraints() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_products(&mut machine);
+
+    let check_constraints = get_check_constraints(&mut machine, &String::from("database1"));
+    assert_eq!(check_constraints.len(), 2);
+    assert_eq!(check_constraints[0].name, "products_price_check");
+    assert_eq!(check_constraints[0].expression, "price > 0");
+    assert_eq!(check_constraints[1].name, "valid_discount");
+    assert_eq!(check_constraints[1].expression, "discount <= price");
+}
+
+#[rstest]
+#[case("INSERT products(id, price, discount) VALUES (1, 0, 0)", "products_price_check")]
+#[case("INSERT products(id, price, discount) VALUES (1, 10, 20)", "valid_discount")]
+#[case("INSERT products(id, price, discount) VALUES (1, 10, 5), (2, 10, 11)", "valid_discount")]
+pub fn test_insert_violating_check(#[case] insert: &str, #[case] constraint: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_products(&mut machine);
+
+    let result_set = parse_command(&mut machine, insert);
+    assert!(matches!(result_set, Err(ExecutionError::CheckViolation(ref name)) if name == constraint));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM products");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
+}
+
+#[test]
+pub fn test_insert_satisfying_check() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_products(&mut machine);
+
+    let result_set = parse_command(&mut machine, "INSERT products(id, price, discount) VALUES (1, 10, 10), (2, 5, NULL)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM products");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 2);
+}
+
+#[test]
+pub fn test_update_violating_check() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_products(&mut machine);
+
+    let _ = parse_command(&mut machine, "INSERT products(id, price, discount) VALUES (1, 10, 5)");
+
+    let table = Table::new(String::from("database1"), String::from("products"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "discount").unwrap();
+    let condition = Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("id"))),
+        Box::new(Expression::Const(Data::UnsignedBigint(1)))
+    );
+
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::UnsignedBigint(15)))];
+    let result_set = update_row(&mut machine, &table, &attributions, condition);
+    assert!(matches!(result_set, Err(ExecutionError::CheckViolation(ref name)) if name == "valid_discount"));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM products");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples[0][2], Data::UnsignedBigint(5));
+}
+
+#[test]
+pub fn test_check_with_unknown_column() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(&mut machine, "CREATE TABLE products (id BIGINT PRIMARY KEY, CHECK (price > 0))");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnNotExists(ref column)) if column == "price"));
+
+    let table = Table::new(String::from("database1"), String::from("products"));
+    assert!(!check_table_exists(&mut machine, &table));
+}
+
+#[test]
+pub fn test_drop_table_drops_its_check_constraints() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_products(&mut machine);
+
+    let result_set = parse_command(&mut machine, "DROP TABLE products");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    assert_eq!(get_check_constraints(&mut machine, &String::from("database1")).len(), 0);
+}
@@ -2,11 +2,27 @@ use rusticodb::machine::Machine;
 use rusticodb::machine::Table;
 use rusticodb::machine::check_database_exists;
 use rusticodb::machine::check_table_exists;
+use rusticodb::machine::get_columns;
+use rusticodb::machine::update_row;
+use rusticodb::machine::Attribution;
+use rusticodb::machine::get_indexes;
+use rusticodb::machine::insert_tuples;
 
+use rusticodb::config::SysDb;
 use rusticodb::parser::parse_command;
 use rusticodb::setup::setup_system;
 use rusticodb::storage::Pager;
 use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::Expression2Type;
+use rusticodb::storage::path_exists;
+use rusticodb::storage::format_index_name;
+use rusticodb::storage::destroy_file;
+use rusticodb::storage::btree_search;
+use rusticodb::storage::btree_entries;
+use rusticodb::storage::hash_index_search;
+use rusticodb::storage::hash_index_entries;
+use rusticodb::utils::ExecutionError;
 
 use crate::test_utils::create_tmp_test_folder;
 
@@ -39,7 +55,7 @@ pub fn test_index_creation_if_not_exists() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
 
     assert_eq!(result_set.as_ref().unwrap().get(0).unwrap().line_count(), 1);
     assert_eq!(
@@ -93,7 +109,7 @@ pub fn test_index_creation_if_exists() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
 
     assert_eq!(result_set.as_ref().unwrap().get(0).unwrap().line_count(), 1);
     assert_eq!(
@@ -143,7 +159,7 @@ pub fn test_index_creation() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
 
     assert_eq!(result_set.as_ref().unwrap().get(0).unwrap().line_count(), 1);
     assert_eq!(
@@ -167,3 +183,437 @@ pub fn test_index_creation() {
         Data::Varchar(String::from("btree"))
     );
 }
+
+fn index_keys(pager: &Pager, index_key: &String) -> Vec<Data> {
+    return btree_entries(pager, index_key).unwrap().into_iter().map(|(mut key, _)| key.remove(0)).collect();
+}
+
+#[test]
+pub fn test_index_is_built_and_maintained_on_insert_and_delete() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, attr1 INT)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, attr1) VALUES (1, 30), (2, 10)");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX index1 ON table1(attr1)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert!(path_exists(&index_key));
+    assert_eq!(index_keys(&machine.pager, &index_key), vec![Data::UnsignedBigint(10), Data::UnsignedBigint(30)]);
+
+    let _ = parse_command(&mut machine, "INSERT table1(id, attr1) VALUES (3, 20), (4, 10)");
+    assert_eq!(
+        index_keys(&machine.pager, &index_key),
+        vec![Data::UnsignedBigint(10), Data::UnsignedBigint(10), Data::UnsignedBigint(20), Data::UnsignedBigint(30)]
+    );
+
+    let _ = parse_command(&mut machine, "DELETE FROM table1 WHERE id = 2");
+    assert_eq!(index_keys(&machine.pager, &index_key), vec![Data::UnsignedBigint(10), Data::UnsignedBigint(20), Data::UnsignedBigint(30)]);
+    assert_eq!(btree_search(&machine.pager, &index_key, &vec![Data::UnsignedBigint(10)]).unwrap().len(), 1);
+}
+
+#[test]
+pub fn test_index_is_maintained_on_update() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name1 VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (1, 'fabiano'), (2, 'martins')");
+    let _ = parse_command(&mut machine, "CREATE INDEX index1 ON table1(name1)");
+
+    let table = Table::new(String::from("database1"), String::from("table1"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "name1").unwrap();
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::Varchar(String::from("rustico"))))];
+    let condition = Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("id"))),
+        Box::new(Expression::Const(Data::UnsignedBigint(2)))
+    );
+
+    let result_set = update_row(&mut machine, &table, &attributions, condition);
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert_eq!(
+        index_keys(&machine.pager, &index_key),
+        vec![Data::Varchar(String::from("fabiano")), Data::Varchar(String::from("rustico"))]
+    );
+    assert_eq!(btree_search(&machine.pager, &index_key, &vec![Data::Varchar(String::from("martins"))]).unwrap().len(), 0);
+}
+
+#[test]
+pub fn test_index_survives_restart() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (attr1 INT, name1 VARCHAR)");
+    let _ = parse_command(&mut machine, "CREATE INDEX index1 ON table1(attr1)");
+
+    for idx in 0..500 {
+        let _ = parse_command(&mut machine, &format!("INSERT table1(attr1, name1) VALUES ({}, 'row')", idx % 50));
+    }
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "INSERT table1(attr1, name1) VALUES (7, 'after restart')");
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert_eq!(btree_entries(&machine.pager, &index_key).unwrap().len(), 501);
+    assert_eq!(btree_search(&machine.pager, &index_key, &vec![Data::UnsignedBigint(7)]).unwrap().len(), 11);
+}
+
+#[test]
+pub fn test_index_on_unknown_column() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, attr1 INT)");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX index1 ON table1(attr2)");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnNotExists(ref _column))));
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert!(!path_exists(&index_key));
+}
+
+#[test]
+pub fn test_hash_index_creation() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name1 VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (1, 'fabiano'), (2, 'martins')");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX index1 ON table1 USING HASH (name1)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert_eq!(hash_index_search(&machine.pager, &index_key, &vec![Data::Varchar(String::from("martins"))]).unwrap().len(), 1);
+
+    let _ = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (3, 'martins')");
+    assert_eq!(hash_index_search(&machine.pager, &index_key, &vec![Data::Varchar(String::from("martins"))]).unwrap().len(), 2);
+
+    let _ = parse_command(&mut machine, "DELETE FROM table1 WHERE id = 2");
+    assert_eq!(hash_index_search(&machine.pager, &index_key, &vec![Data::Varchar(String::from("martins"))]).unwrap().len(), 1);
+    assert_eq!(hash_index_entries(&machine.pager, &index_key).unwrap().len(), 2);
+
+    let _ = parse_command(&mut machine, "USE rusticodb");
+    let result_set = parse_command(&mut machine, "SELECT * FROM indexes WHERE name = 'index1'");
+    assert_eq!(
+        result_set.as_ref().unwrap().get(0).unwrap().get_value(0, &String::from("type")).unwrap(),
+        Data::Varchar(String::from("hash"))
+    );
+}
+
+#[test]
+pub fn test_hash_index_survives_restart() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (attr1 INT, name1 VARCHAR)");
+    let _ = parse_command(&mut machine, "CREATE INDEX index1 ON table1 USING HASH (attr1)");
+
+    for idx in 0..500 {
+        let _ = parse_command(&mut machine, &format!("INSERT table1(attr1, name1) VALUES ({}, 'row')", idx % 50));
+    }
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "INSERT table1(attr1, name1) VALUES (7, 'after restart')");
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert_eq!(hash_index_entries(&machine.pager, &index_key).unwrap().len(), 501);
+    assert_eq!(hash_index_search(&machine.pager, &index_key, &vec![Data::UnsignedBigint(7)]).unwrap().len(), 11);
+}
+
+#[test]
+pub fn test_index_with_unsupported_type() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, attr1 INT)");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX index1 ON table1 USING GIST (attr1)");
+    assert!(matches!(result_set, Err(ExecutionError::IndexTypeNotSupported(ref _type))));
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert!(!path_exists(&index_key));
+}
+
+#[test]
+pub fn test_index_of_old_catalog_is_not_unique() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    // Rows written before the `unique` column was appended to the catalog.
+    let mut tuples = vec![
+        vec![
+            Data::UnsignedBigint(100),
+            Data::Varchar(String::from("database1")),
+            Data::Varchar(String::from("table1")),
+            Data::Varchar(String::from("attr1")),
+            Data::Varchar(String::from("index1")),
+            Data::Varchar(String::from("btree"))
+        ]
+    ];
+    insert_tuples(&mut machine, &SysDb::table_indexes(), &mut tuples);
+
+    let indexes = get_indexes(&mut machine, &String::from("database1"));
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(indexes[0].name, "index1");
+    assert!(!indexes[0].unique);
+}
+
+#[test]
+pub fn test_composite_index_creation() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, tenant_id INT, created INT)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, tenant_id, created) VALUES (1, 3, 20), (2, 3, 10), (3, 1, 30)");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX ON table1(tenant_id, created)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let _ = parse_command(&mut machine, "INSERT table1(id, tenant_id, created) VALUES (4, 3, 15)");
+
+    let index_key = format_index_name(
+        &String::from("database1"),
+        &String::from("table1"),
+        &String::from("index_table1_tenant_id_created")
+    );
+    let keys: Vec<Vec<Data>> = btree_entries(&machine.pager, &index_key).unwrap().into_iter().map(|(key, _)| key).collect();
+    assert_eq!(
+        keys,
+        vec![
+            vec![Data::UnsignedBigint(1), Data::UnsignedBigint(30)],
+            vec![Data::UnsignedBigint(3), Data::UnsignedBigint(10)],
+            vec![Data::UnsignedBigint(3), Data::UnsignedBigint(15)],
+            vec![Data::UnsignedBigint(3), Data::UnsignedBigint(20)]
+        ]
+    );
+    assert_eq!(btree_search(&machine.pager, &index_key, &vec![Data::UnsignedBigint(3)]).unwrap().len(), 3);
+
+    let _ = parse_command(&mut machine, "USE rusticodb");
+    let result_set = parse_command(&mut machine, "SELECT * FROM indexes WHERE name = 'index_table1_tenant_id_created'");
+    assert_eq!(
+        result_set.as_ref().unwrap().get(0).unwrap().get_value(0, &String::from("column_name")).unwrap(),
+        Data::Varchar(String::from("tenant_id,created"))
+    );
+}
+
+#[test]
+pub fn test_composite_index_on_quoted_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, \"tenant,id\" INT, created INT)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, created) VALUES (1, 20)");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX index1 ON table1(\"tenant,id\", created)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let _ = parse_command(&mut machine, "USE rusticodb");
+    let result_set = parse_command(&mut machine, "SELECT * FROM indexes WHERE name = 'index1'");
+    assert_eq!(
+        result_set.as_ref().unwrap().get(0).unwrap().get_value(0, &String::from("column_name")).unwrap(),
+        Data::Varchar(String::from("tenant\\,id,created"))
+    );
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert_eq!(btree_entries(&machine.pager, &index_key).unwrap().len(), 1);
+}
+
+#[test]
+pub fn test_composite_index_on_unknown_column() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, attr1 INT)");
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX index1 ON table1(attr1, attr2)");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnNotExists(ref column)) if column == "attr2"));
+}
+
+fn id_equals(id: u64) -> Expression {
+    return Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("id"))),
+        Box::new(Expression::Const(Data::UnsignedBigint(id)))
+    );
+}
+
+#[test]
+pub fn test_update_keeps_keys_unique() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name1 VARCHAR UNIQUE)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (1, 'fabiano'), (2, 'martins')");
+
+    let table = Table::new(String::from("database1"), String::from("table1"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "name1").unwrap();
+
+    let attributions = vec![Attribution::new(column.clone(), Expression::Const(Data::Varchar(String::from("fabiano"))))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(2));
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::UniqueViolation(ref name, ref value)) if name == "table1_name1_key" && value == "fabiano"
+    ));
+
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::Varchar(String::from("martins"))))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(2));
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("table1_name1_key"));
+    assert_eq!(btree_entries(&machine.pager, &index_key).unwrap().len(), 2);
+}
+
+#[test]
+pub fn test_index_without_its_file_is_an_error() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name1 VARCHAR UNIQUE)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (1, 'fabiano')");
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("table1_name1_key"));
+    destroy_file(&index_key);
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE id = 1");
+    assert!(matches!(result_set, Err(ExecutionError::IndexCorrupted(ref name)) if *name == index_key));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (2, 'martins')");
+    assert!(matches!(result_set, Err(ExecutionError::IndexCorrupted(ref name)) if *name == index_key));
+
+    // The catalog tables have no index files and keep working.
+    let result_set = parse_command(&mut machine, "CREATE TABLE table2 (id BIGINT PRIMARY KEY)");
+    assert!(matches!(result_set, Ok(_)));
+}
+
+#[test]
+pub fn test_rows_are_not_kept_when_an_index_can_not_take_them() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (name1 VARCHAR, score BIGINT)");
+    let _ = parse_command(&mut machine, "CREATE INDEX table1_score ON table1 (score)");
+    let _ = parse_command(&mut machine, "INSERT table1(name1, score) VALUES ('fabiano', 10)");
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("table1_score"));
+    std::fs::write(&index_key, b"corrupted").unwrap();
+
+    let result_set = parse_command(&mut machine, "INSERT table1(name1, score) VALUES ('martins', 20)");
+    assert!(matches!(result_set, Err(_)));
+
+    let tuples = &parse_command(&mut machine, "SELECT name1, score FROM table1").unwrap()[0].tuples;
+    assert_eq!(*tuples, vec![vec![Data::Varchar(String::from("fabiano")), Data::SignedBigint(10)]]);
+
+    let table = Table::new(String::from("database1"), String::from("table1"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "score").unwrap();
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::SignedBigint(30)))];
+    let result_set = update_row(&mut machine, &table, &attributions, Expression::Const(Data::Boolean(true)));
+    assert!(matches!(result_set, Err(_)));
+
+    let tuples = &parse_command(&mut machine, "SELECT name1, score FROM table1").unwrap()[0].tuples;
+    assert_eq!(*tuples, vec![vec![Data::Varchar(String::from("fabiano")), Data::SignedBigint(10)]]);
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let tuples = &parse_command(&mut machine, "SELECT name1, score FROM table1").unwrap()[0].tuples;
+    assert_eq!(*tuples, vec![vec![Data::Varchar(String::from("fabiano")), Data::SignedBigint(10)]]);
+}
@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use rstest::rstest;
+
 use rusticodb::config::Config;
 
 use rusticodb::machine::Machine;
@@ -1464,3 +1466,48 @@ pub fn test_with_two_columns_and_one_is_a_primary_key_and_second_has_default_val
         Data::Varchar(String::from("0"))
     );
 }
+
+#[rstest]
+#[case("attr1 INT DEFAULT 'abc'")]
+#[case("attr1 TINYINT DEFAULT 300")]
+#[case("attr1 INT DEFAULT 1.5")]
+#[case("attr1 DECIMAL(3,1) DEFAULT 1234")]
+#[case("attr1 UUID DEFAULT 'abc'")]
+pub fn test_default_value_that_does_not_fit_the_column(#[case] column: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_create = parse_command(&mut machine, &format!("CREATE TABLE table1(id BIGINT PRIMARY KEY, {})", column));
+
+    assert!(matches!(result_create, Err(ExecutionError::ColumnTypeNotMatch(_, _, ref name)) if name == "attr1"));
+
+    let table = Table::new(String::from("database1"), String::from("table1"));
+    assert!(!check_table_exists(&mut machine, &table));
+}
+
+#[rstest]
+#[case("attr1 INT DEFAULT id + 1")]
+#[case("attr1 INT DEFAULT (SELECT 1)")]
+pub fn test_default_value_that_is_not_constant(#[case] column: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_create = parse_command(&mut machine, &format!("CREATE TABLE table1(id BIGINT PRIMARY KEY, {})", column));
+
+    assert!(matches!(result_create, Err(ExecutionError::ParserError(_))));
+
+    let table = Table::new(String::from("database1"), String::from("table1"));
+    assert!(!check_table_exists(&mut machine, &table));
+}
@@ -5,6 +5,7 @@ use rusticodb::machine::Machine;
 use rusticodb::parser::parse_command;
 use rusticodb::setup::setup_system;
 use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
 
 use crate::test_utils::create_tmp_test_folder;
 
@@ -38,3 +39,26 @@ pub fn test_with_two_columns_and_one_is_a_primary_key() {
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
     assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 9);
 }
+
+#[test]
+pub fn test_delete_on_table_without_primary_key() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(name1 VARCHAR, name2 VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(name1, name2) VALUES ('fabiano', 'martins')");
+    let _ = parse_command(&mut machine, "INSERT table1(name1, name2) VALUES ('rustico', 'db')");
+
+    let result_set_delete = parse_command(&mut machine, "DELETE FROM table1 WHERE name1 = 'fabiano'");
+    assert!(matches!(result_set_delete, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples[0][0], Data::Varchar(String::from("rustico")));
+}
@@ -6,6 +6,8 @@ use rusticodb::machine::check_table_exists;
 use rusticodb::parser::parse_command;
 use rusticodb::setup::setup_system;
 use rusticodb::storage::Pager;
+use rusticodb::storage::path_exists;
+use rusticodb::storage::format_index_name;
 use rusticodb::utils::ExecutionError;
 
 use crate::test_utils::create_tmp_test_folder;
@@ -69,7 +71,7 @@ pub fn test_index_error_if_not_exists() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
 }
 
 #[test]
@@ -100,5 +102,28 @@ pub fn test_index_drop_with_if_exists_statement() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
+}
+
+#[test]
+pub fn test_index_drop_removes_the_index_file() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, attr1 INT)");
+    let _ = parse_command(&mut machine, "CREATE INDEX index1 ON table1(attr1)");
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    assert!(path_exists(&index_key));
+
+    let result_set_drop = parse_command(&mut machine, "DROP INDEX index1");
+
+    assert!(matches!(result_set_drop, Ok(ref _result_set)));
+    assert!(!path_exists(&index_key));
 }
@@ -69,7 +69,7 @@ pub fn test_sequence_error_if_not_exists() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
 }
 
 #[test]
@@ -100,5 +100,5 @@ pub fn test_sequence_drop_with_if_exists_statement() {
 
     assert!(matches!(result_set, Ok(ref _result_set)));
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
-    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 6);
+    assert_eq!(result_set.as_ref().unwrap()[0].column_count(), 8);
 }
@@ -0,0 +1,180 @@
+use rusticodb::machine::Machine;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+use rusticodb::utils::ExecutionError;
+
+use rstest::rstest;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_indexed_table(machine: &mut Machine) {
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let _ = parse_command(machine, "CREATE TABLE table1(id BIGINT PRIMARY KEY, attr1 INT, name1 VARCHAR)");
+
+    for idx in 1..=100 {
+        let _ = parse_command(machine, &format!("INSERT table1(id, attr1, name1) VALUES ({}, {}, \'row {}\')", idx, idx % 20, idx));
+    }
+
+    let _ = parse_command(machine, "CREATE INDEX index_attr1 ON table1(attr1)");
+    let _ = parse_command(machine, "CREATE INDEX index_name1 ON table1(name1)");
+}
+
+fn get_plan(machine: &mut Machine, sql: &str) -> Vec<Data> {
+    let result_set = parse_command(machine, &format!("EXPLAIN {}", sql));
+
+    return result_set.unwrap()[0].tuples.iter().map(|tuple| tuple[0].clone()).collect();
+}
+
+#[rstest]
+#[case("SELECT * FROM table1", "Seq Scan on table1")]
+#[case("SELECT * FROM table1 WHERE id = 3", "Index Scan using table1_pkey on table1 (id = 3)")]
+#[case("SELECT * FROM table1 WHERE attr1 = 3 OR attr1 = 4", "Seq Scan on table1")]
+#[case("SELECT * FROM table1 WHERE attr1 = 5", "Index Scan using index_attr1 on table1 (attr1 = 5)")]
+#[case("SELECT * FROM table1 WHERE 5 = attr1", "Index Scan using index_attr1 on table1 (attr1 = 5)")]
+#[case("SELECT * FROM table1 WHERE attr1 > 3", "Index Scan using index_attr1 on table1 (attr1 > 3)")]
+#[case("SELECT * FROM table1 WHERE 3 > attr1", "Index Scan using index_attr1 on table1 (attr1 < 3)")]
+#[case("SELECT * FROM table1 WHERE attr1 >= 3 AND attr1 < 10", "Index Scan using index_attr1 on table1 (attr1 >= 3 AND attr1 < 10)")]
+#[case("SELECT * FROM table1 WHERE attr1 >= 3 AND attr1 > 3 AND attr1 <= 12", "Index Scan using index_attr1 on table1 (attr1 > 3 AND attr1 <= 12)")]
+#[case("SELECT * FROM table1 WHERE attr1 BETWEEN 3 AND 5", "Index Scan using index_attr1 on table1 (attr1 >= 3 AND attr1 <= 5)")]
+#[case("SELECT * FROM table1 WHERE attr1 > 3 AND name1 = 'row 7'", "Index Scan using index_name1 on table1 (name1 = 'row 7')")]
+pub fn test_explain_access_path(#[case] sql: &str, #[case] plan: &str) {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_indexed_table(&mut machine);
+
+    assert_eq!(get_plan(&mut machine, sql), vec![Data::Varchar(String::from(plan))]);
+}
+
+#[test]
+pub fn test_explain_join_uses_full_scans() {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_indexed_table(&mut machine);
+    let _ = parse_command(&mut machine, "CREATE TABLE table2(id BIGINT PRIMARY KEY, attr2 INT)");
+
+    assert_eq!(
+        get_plan(&mut machine, "SELECT * FROM table1, table2 WHERE attr1 = 5"),
+        vec![Data::Varchar(String::from("Seq Scan on table1")), Data::Varchar(String::from("Seq Scan on table2"))]
+    );
+}
+
+#[test]
+pub fn test_explain_table_that_not_exists() {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_indexed_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "EXPLAIN SELECT * FROM table3 WHERE attr1 = 5");
+    assert!(matches!(result_set, Err(ExecutionError::TableNotExists(ref _table))));
+}
+
+#[rstest]
+#[case("SELECT * FROM table1 WHERE attr1 = 5", 5)]
+#[case("SELECT * FROM table1 WHERE attr1 > 17", 10)]
+#[case("SELECT * FROM table1 WHERE attr1 <= 1", 10)]
+#[case("SELECT * FROM table1 WHERE attr1 BETWEEN 3 AND 5", 15)]
+#[case("SELECT * FROM table1 WHERE attr1 NOT BETWEEN 3 AND 5", 85)]
+#[case("SELECT * FROM table1 WHERE attr1 > 10 AND attr1 < 5", 0)]
+#[case("SELECT * FROM table1 WHERE attr1 = 5 AND id > 50", 2)]
+#[case("SELECT * FROM table1 WHERE name1 = 'row 42'", 1)]
+pub fn test_index_scan_returns_the_matching_rows(#[case] sql: &str, #[case] amount: usize) {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_indexed_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, sql);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), amount);
+}
+
+#[test]
+pub fn test_index_scan_after_delete() {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_indexed_table(&mut machine);
+
+    let _ = parse_command(&mut machine, "DELETE FROM table1 WHERE attr1 = 5");
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE attr1 = 5");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE attr1 BETWEEN 4 AND 6");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 10);
+}
+
+#[rstest]
+#[case("SELECT * FROM table2 WHERE code = 3", "Hash Scan using index_code on table2 (code = 3)")]
+#[case("SELECT * FROM table2 WHERE code > 3", "Seq Scan on table2")]
+#[case("SELECT * FROM table2 WHERE code BETWEEN 3 AND 5", "Seq Scan on table2")]
+#[case("SELECT * FROM table2 WHERE code = 3 AND attr2 > 2", "Hash Scan using index_code on table2 (code = 3)")]
+pub fn test_hash_index_is_only_used_for_equalities(#[case] sql: &str, #[case] plan: &str) {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_indexed_table(&mut machine);
+    let _ = parse_command(&mut machine, "CREATE TABLE table2(code INT, attr2 INT)");
+
+    for idx in 1..=100 {
+        let _ = parse_command(&mut machine, &format!("INSERT table2(code, attr2) VALUES ({}, {})", idx, idx % 20));
+    }
+
+    let _ = parse_command(&mut machine, "CREATE INDEX index_code ON table2 USING HASH (code)");
+    let _ = parse_command(&mut machine, "CREATE INDEX index_attr2 ON table2(attr2)");
+
+    assert_eq!(get_plan(&mut machine, sql), vec![Data::Varchar(String::from(plan))]);
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table2 WHERE code = 42");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+}
+
+fn setup_composite_index(machine: &mut Machine) {
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let _ = parse_command(machine, "CREATE TABLE table1(id BIGINT PRIMARY KEY, tenant_id INT, created INT)");
+
+    for idx in 1..=100 {
+        let _ = parse_command(machine, &format!("INSERT table1(id, tenant_id, created) VALUES ({}, {}, {})", idx, idx % 5, idx));
+    }
+
+    let _ = parse_command(machine, "CREATE INDEX index_tenant ON table1(tenant_id, created)");
+}
+
+#[rstest]
+#[case("SELECT * FROM table1 WHERE tenant_id = 3", "Index Scan using index_tenant on table1 (tenant_id = 3)", 20)]
+#[case("SELECT * FROM table1 WHERE created = 3", "Seq Scan on table1", 1)]
+#[case("SELECT * FROM table1 WHERE tenant_id = 3 AND created = 8", "Index Scan using index_tenant on table1 (tenant_id = 3 AND created = 8)", 1)]
+#[case("SELECT * FROM table1 WHERE tenant_id = 3 AND created > 50", "Index Scan using index_tenant on table1 (tenant_id = 3 AND created > 50)", 10)]
+#[case("SELECT * FROM table1 WHERE created <= 50 AND tenant_id = 3", "Index Scan using index_tenant on table1 (tenant_id = 3 AND created <= 50)", 10)]
+#[case("SELECT * FROM table1 WHERE tenant_id >= 3 AND created = 8", "Index Scan using index_tenant on table1 (tenant_id >= 3)", 1)]
+pub fn test_composite_index_prefix_match(#[case] sql: &str, #[case] plan: &str, #[case] amount: usize) {
+    let mut machine = Machine::new(Pager::new());
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    setup_composite_index(&mut machine);
+
+    assert_eq!(get_plan(&mut machine, sql), vec![Data::Varchar(String::from(plan))]);
+
+    let result_set = parse_command(&mut machine, sql);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), amount);
+}
@@ -0,0 +1,462 @@
+use rstest::rstest;
+
+use rusticodb::machine::Machine;
+use rusticodb::machine::Table;
+use rusticodb::machine::Attribution;
+use rusticodb::machine::get_columns;
+use rusticodb::machine::get_foreign_keys;
+use rusticodb::machine::check_table_exists;
+use rusticodb::machine::update_row;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::utils::ExecutionError;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::Expression2Type;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_books(machine: &mut Machine, actions: &str) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let _ = parse_command(machine, "CREATE TABLE authors (id BIGINT PRIMARY KEY, name VARCHAR)");
+    let result_set = parse_command(
+        machine,
+        format!("CREATE TABLE books (id BIGINT PRIMARY KEY, author_id BIGINT DEFAULT 2 REFERENCES authors(id) {})", actions).as_str()
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let _ = parse_command(machine, "INSERT authors(id, name) VALUES (1, 'fabiano'), (2, 'martins')");
+    let result_set = parse_command(machine, "INSERT books(id, author_id) VALUES (1, 1), (2, 1), (3, 2)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+fn id_equals(id: u64) -> Expression {
+    return Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("id"))),
+        Box::new(Expression::Const(Data::UnsignedBigint(id)))
+    );
+}
+
+#[test]
+pub fn test_create_table_registers_foreign_key() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "ON DELETE CASCADE");
+
+    let foreign_keys = get_foreign_keys(&mut machine, &String::from("database1"));
+    assert_eq!(foreign_keys.len(), 1);
+    assert_eq!(foreign_keys[0].name, "books_author_id_fkey");
+    assert_eq!(foreign_keys[0].table_name, "books");
+    assert_eq!(foreign_keys[0].column_name, "author_id");
+    assert_eq!(foreign_keys[0].referenced_table, "authors");
+    assert_eq!(foreign_keys[0].referenced_column, "id");
+    assert_eq!(foreign_keys[0].on_delete, "CASCADE");
+    assert_eq!(foreign_keys[0].on_update, "NO ACTION");
+}
+
+#[test]
+pub fn test_insert_without_referenced_row() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "");
+
+    let result_set = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (4, 5)");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ForeignKeyViolation(ref name, ref value)) if name == "books_author_id_fkey" && value == "5"
+    ));
+
+    let result_set = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (4, NULL)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM books");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 4);
+}
+
+#[rstest]
+#[case("")]
+#[case("ON DELETE NO ACTION")]
+#[case("ON DELETE RESTRICT")]
+pub fn test_delete_referenced_row_is_restricted(#[case] actions: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, actions);
+
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ForeignKeyViolation(ref name, ref value)) if name == "books_author_id_fkey" && value == "1"
+    ));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM authors");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 2);
+
+    let _ = parse_command(&mut machine, "DELETE FROM books WHERE author_id = 1");
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+#[test]
+pub fn test_delete_cascade() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "ON DELETE CASCADE");
+
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM books");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples[0][0], Data::UnsignedBigint(3));
+}
+
+#[test]
+pub fn test_delete_set_null() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "ON DELETE SET NULL");
+
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM books");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 3);
+    assert_eq!(tuples.iter().filter(|tuple| matches!(tuple[1], Data::Null)).count(), 2);
+}
+
+#[test]
+pub fn test_delete_set_default() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "ON DELETE SET DEFAULT");
+
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM books WHERE author_id = 2");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 3);
+}
+
+#[test]
+pub fn test_failed_set_null_keeps_the_parent_row() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE authors (id BIGINT PRIMARY KEY, name VARCHAR)");
+    let _ = parse_command(
+        &mut machine,
+        "CREATE TABLE books (id BIGINT PRIMARY KEY, author_id BIGINT NOT NULL REFERENCES authors(id) ON DELETE SET NULL)"
+    );
+    let _ = parse_command(&mut machine, "INSERT authors(id, name) VALUES (1, 'fabiano'), (2, 'martins')");
+    let _ = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (1, 1), (2, 2)");
+
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnCantBeNull(_, _, ref column)) if column == "author_id"));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM authors WHERE id = 1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM authors WHERE id = 1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+    let result_set = parse_command(&mut machine, "SELECT * FROM books WHERE author_id = 1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+}
+
+#[test]
+pub fn test_failed_cascade_keeps_the_parent_key() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE authors (id BIGINT PRIMARY KEY, name VARCHAR)");
+    let _ = parse_command(
+        &mut machine,
+        "CREATE TABLE books (id BIGINT PRIMARY KEY, author_id BIGINT CHECK (author_id < 10) REFERENCES authors(id) ON UPDATE CASCADE)"
+    );
+    let _ = parse_command(&mut machine, "INSERT authors(id, name) VALUES (1, 'fabiano'), (2, 'martins')");
+    let _ = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (1, 1), (2, 2)");
+
+    let table = Table::new(String::from("database1"), String::from("authors"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "id").unwrap();
+
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::UnsignedBigint(20)))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(result_set, Err(ExecutionError::CheckViolation(_))));
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM authors WHERE id = 20");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 0);
+    let result_set = parse_command(&mut machine, "SELECT * FROM authors WHERE id = 1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+    let result_set = parse_command(&mut machine, "SELECT * FROM books WHERE author_id = 1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+}
+
+#[test]
+pub fn test_update_cascade() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "ON UPDATE CASCADE");
+
+    let table = Table::new(String::from("database1"), String::from("authors"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "id").unwrap();
+
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::UnsignedBigint(10)))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM books WHERE author_id = 10");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 2);
+}
+
+#[test]
+pub fn test_update_referenced_key_is_restricted() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "ON DELETE CASCADE");
+
+    let table = Table::new(String::from("database1"), String::from("authors"));
+    let columns = get_columns(&mut machine, &table);
+    let id_column = columns.iter().find(|column| column.name == "id").unwrap().clone();
+    let name_column = columns.iter().find(|column| column.name == "name").unwrap().clone();
+
+    let attributions = vec![Attribution::new(id_column, Expression::Const(Data::UnsignedBigint(10)))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ForeignKeyViolation(ref name, ref value)) if name == "books_author_id_fkey" && value == "1"
+    ));
+
+    let attributions = vec![Attribution::new(name_column, Expression::Const(Data::Varchar(String::from("rustico"))))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+#[test]
+pub fn test_update_to_missing_referenced_row() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "");
+
+    let table = Table::new(String::from("database1"), String::from("books"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "author_id").unwrap();
+
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::UnsignedBigint(9)))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ForeignKeyViolation(ref name, ref value)) if name == "books_author_id_fkey" && value == "9"
+    ));
+}
+
+#[test]
+pub fn test_table_constraint_with_composite_key() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (a BIGINT, b BIGINT, PRIMARY KEY (a, b))");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE table2 (id BIGINT PRIMARY KEY, a BIGINT, b BIGINT, \
+         CONSTRAINT table2_fk FOREIGN KEY (a, b) REFERENCES table1(a, b) ON DELETE CASCADE)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let foreign_keys = get_foreign_keys(&mut machine, &String::from("database1"));
+    assert_eq!(foreign_keys[0].column_name, "a,b");
+    assert_eq!(foreign_keys[0].referenced_column, "a,b");
+
+    let _ = parse_command(&mut machine, "INSERT table1(a, b) VALUES (1, 2), (1, 3)");
+    let result_set = parse_command(&mut machine, "INSERT table2(id, a, b) VALUES (1, 1, 2), (2, 1, 3)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "INSERT table2(id, a, b) VALUES (3, 2, 1)");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ForeignKeyViolation(ref name, ref value)) if name == "table2_fk" && value == "(2, 1)"
+    ));
+
+    let _ = parse_command(&mut machine, "DELETE FROM table1 WHERE b = 3");
+    let result_set = parse_command(&mut machine, "SELECT * FROM table2");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+}
+
+#[test]
+pub fn test_table_constraint_with_quoted_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (\"a,b\" BIGINT, c BIGINT, PRIMARY KEY (\"a,b\", c))");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE table2 (id BIGINT PRIMARY KEY, \"x\\y\" BIGINT, c BIGINT, \
+         FOREIGN KEY (\"x\\y\", c) REFERENCES table1(\"a,b\", c))"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let foreign_keys = get_foreign_keys(&mut machine, &String::from("database1"));
+    assert_eq!(foreign_keys[0].column_name, "x\\\\y,c");
+    assert_eq!(foreign_keys[0].column_names(), vec![String::from("x\\y"), String::from("c")]);
+    assert_eq!(foreign_keys[0].referenced_column, "a\\,b,c");
+    assert_eq!(foreign_keys[0].referenced_column_names(), vec![String::from("a,b"), String::from("c")]);
+}
+
+#[test]
+pub fn test_self_reference_cascades_down_the_tree() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE nodes (id BIGINT PRIMARY KEY, parent_id BIGINT REFERENCES nodes(id) ON DELETE CASCADE)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "INSERT nodes(id, parent_id) VALUES (1, NULL), (2, 1), (3, 2), (4, NULL)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let _ = parse_command(&mut machine, "DELETE FROM nodes WHERE id = 1");
+    let result_set = parse_command(&mut machine, "SELECT * FROM nodes");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples[0][0], Data::UnsignedBigint(4));
+}
+
+#[test]
+pub fn test_referenced_columns_must_be_unique() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE authors (id BIGINT PRIMARY KEY, name VARCHAR)");
+    let result_set = parse_command(&mut machine, "CREATE TABLE books (id BIGINT PRIMARY KEY, author VARCHAR REFERENCES authors(name))");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ReferencedKeyNotUnique(ref table, ref columns)) if table == "authors" && columns == "name"
+    ));
+
+    let table = Table::new(String::from("database1"), String::from("books"));
+    assert!(!check_table_exists(&mut machine, &table));
+    assert_eq!(get_foreign_keys(&mut machine, &String::from("database1")).len(), 0);
+
+    let result_set = parse_command(&mut machine, "CREATE TABLE books (id BIGINT PRIMARY KEY, author_id BIGINT REFERENCES authors)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    assert_eq!(get_foreign_keys(&mut machine, &String::from("database1"))[0].referenced_column, "id");
+}
+
+#[test]
+pub fn test_reference_to_a_named_primary_key_constraint() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE authors (id BIGINT, name VARCHAR, CONSTRAINT authors_key PRIMARY KEY (id))");
+    let result_set = parse_command(&mut machine, "CREATE TABLE books (id BIGINT PRIMARY KEY, author_id BIGINT REFERENCES authors)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    assert_eq!(get_foreign_keys(&mut machine, &String::from("database1"))[0].referenced_column, "id");
+
+    let _ = parse_command(&mut machine, "INSERT authors(id, name) VALUES (1, 'Machado')");
+    let result_set = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (1, 1)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (2, 2)");
+    assert!(matches!(result_set, Err(ExecutionError::ForeignKeyViolation(_, _))));
+}
+
+#[test]
+pub fn test_drop_referenced_table() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "");
+
+    let result_set = parse_command(&mut machine, "DROP TABLE authors");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::TableReferenced(ref table, ref name)) if table == "authors" && name == "books_author_id_fkey"
+    ));
+
+    let result_set = parse_command(&mut machine, "DROP TABLE authors CASCADE");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    assert_eq!(get_foreign_keys(&mut machine, &String::from("database1")).len(), 0);
+
+    let result_set = parse_command(&mut machine, "INSERT books(id, author_id) VALUES (4, 5)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+#[test]
+pub fn test_drop_referencing_table() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_books(&mut machine, "");
+
+    let result_set = parse_command(&mut machine, "DROP TABLE books");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    assert_eq!(get_foreign_keys(&mut machine, &String::from("database1")).len(), 0);
+
+    let result_set = parse_command(&mut machine, "DELETE FROM authors WHERE id = 1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
@@ -1,4 +1,9 @@
+use rstest::rstest;
+use ordered_float::OrderedFloat;
+
 use rusticodb::machine::Machine;
+use rusticodb::machine::ColumnType;
+use rusticodb::machine::map_column_type;
 use rusticodb::parser::parse_command;
 use rusticodb::setup::setup_system;
 use rusticodb::utils::ExecutionError;
@@ -543,3 +548,539 @@ pub fn test_in_two_columns_one_with_default_signed_tinyint_value() {
         Data::Varchar(String::from("martins"))
     );
 }
+
+#[rstest]
+#[case(5000)]
+#[case(200 * 1024)]
+#[case(700 * 1024)]
+pub fn test_in_large_text_value(#[case] size: usize) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let large_text: String = (0..size).map(|idx| (b'a' + (idx % 26) as u8) as char).collect();
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(id BIGINT PRIMARY KEY, name1 VARCHAR, body TEXT)");
+    let _ = parse_command(&mut machine, "INSERT table1(name1, body) VALUES (\'small\', \'small body\')");
+    let result_set = parse_command(&mut machine, &format!("INSERT table1(name1, body) VALUES (\'large\', \'{}\')", large_text));
+
+    assert!(matches!(result_set, Ok(_result_set)));
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set_select = parse_command(&mut machine, "SELECT * FROM table1");
+    let rs = result_set_select.unwrap();
+
+    assert_eq!(rs[0].tuples.len(), 2);
+    assert_eq!(
+        rs.get(0).unwrap().get_value(0, &String::from("body")).unwrap(),
+        Data::Text(String::from("small body"))
+    );
+    assert_eq!(
+        rs.get(0).unwrap().get_value(1, &String::from("body")).unwrap(),
+        Data::Text(large_text)
+    );
+}
+
+#[test]
+pub fn test_duplicated_primary_key_is_rejected() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(id BIGINT PRIMARY KEY, name1 VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (1, 'fabiano')");
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (1, 'martins')");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::UniqueViolation(ref name, ref value)) if name == "table1_pkey" && value == "1"
+    ));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, name1) VALUES (2, 'martins'), (2, 'rustico')");
+    assert!(matches!(result_set, Err(ExecutionError::UniqueViolation(_, _))));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    assert_eq!(result_set.unwrap()[0].tuples.len(), 1);
+}
+
+#[rstest]
+#[case("INSERT table1(tenant_id, code) VALUES (1, 3)", true)]
+#[case("INSERT table1(tenant_id, code) VALUES (2, 2)", true)]
+#[case("INSERT table1(tenant_id, code) VALUES (1, 2)", false)]
+pub fn test_multi_column_unique_constraint(#[case] sql: &str, #[case] accepted: bool) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(tenant_id INT, code INT, UNIQUE (tenant_id, code))");
+    let _ = parse_command(&mut machine, "INSERT table1(tenant_id, code) VALUES (1, 2)");
+
+    let result_set = parse_command(&mut machine, sql);
+    if accepted {
+        assert!(matches!(result_set, Ok(_)));
+    } else {
+        assert!(matches!(
+            result_set,
+            Err(ExecutionError::UniqueViolation(ref name, ref value)) if name == "table1_tenant_id_code_key" && value == "(1, 2)"
+        ));
+    }
+}
+
+#[test]
+pub fn test_unique_index_on_duplicated_rows() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(name1 VARCHAR, name2 VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(name1, name2) VALUES ('fabiano', 'a'), ('martins', 'b'), ('fabiano', 'c')");
+
+    let result_set = parse_command(&mut machine, "CREATE UNIQUE INDEX index1 ON table1(name1)");
+    assert!(matches!(result_set, Err(ExecutionError::UniqueViolation(_, ref value)) if value == "fabiano"));
+
+    let result_set = parse_command(&mut machine, "CREATE UNIQUE INDEX index2 ON table1(name2)");
+    assert!(matches!(result_set, Ok(_)));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(name1, name2) VALUES ('rustico', 'b')");
+    assert!(matches!(result_set, Err(ExecutionError::UniqueViolation(ref name, _)) if name == "index2"));
+}
+
+fn setup_defaults(machine: &mut Machine) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let result_set = parse_command(
+        machine,
+        "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name1 VARCHAR DEFAULT 'none', \
+         amount BIGINT DEFAULT 2 * 5, active BOOLEAN DEFAULT true, delta INT DEFAULT -(2 + 3))"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+#[rstest]
+#[case("INSERT table1(id) VALUES (1)")]
+#[case("INSERT table1(id, name1, amount) VALUES (1, DEFAULT, DEFAULT)")]
+#[case("INSERT table1 VALUES (1, DEFAULT, DEFAULT, DEFAULT, DEFAULT)")]
+pub fn test_omitted_columns_take_their_default(#[case] insert: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_defaults(&mut machine);
+
+    let result_set = parse_command(&mut machine, insert);
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0].len(), 5);
+    assert_eq!(tuples[0][0], Data::UnsignedBigint(1));
+    assert_eq!(tuples[0][1], Data::Varchar(String::from("none")));
+    assert_eq!(tuples[0][2], Data::SignedBigint(10));
+    assert_eq!(tuples[0][3], Data::UnsignedTinyint(1));
+    assert_eq!(tuples[0][4], Data::SignedInt(-5));
+}
+
+#[test]
+pub fn test_values_without_column_list_keep_their_order() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_defaults(&mut machine);
+
+    let result_set = parse_command(&mut machine, "INSERT table1 VALUES (1, 'fabiano', 3)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples[0][1], Data::Varchar(String::from("fabiano")));
+    assert_eq!(tuples[0][2], Data::UnsignedBigint(3));
+    assert_eq!(tuples[0][4], Data::SignedInt(-5));
+}
+
+#[test]
+pub fn test_insert_default_values() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (name1 VARCHAR DEFAULT 'none', name2 VARCHAR)");
+    let _ = parse_command(&mut machine, "CREATE TABLE table2 (name1 VARCHAR NOT NULL)");
+
+    let result_set = parse_command(&mut machine, "INSERT INTO table1 DEFAULT VALUES");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][0], Data::Varchar(String::from("none")));
+    assert!(matches!(tuples[0][1], Data::Null));
+
+    let result_set = parse_command(&mut machine, "INSERT INTO table2 DEFAULT VALUES");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnCantBeNull(_, _, ref column)) if column == "name1"));
+}
+
+fn setup_typed_table(machine: &mut Machine) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let result_set = parse_command(
+        machine,
+        "CREATE TABLE table1 (id BIGINT PRIMARY KEY, small TINYINT, usmall TINYINT UNSIGNED, \
+         body TEXT, active BOOLEAN, amount INT)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
+
+#[test]
+pub fn test_values_take_the_column_type() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_typed_table(&mut machine);
+
+    let result_set = parse_command(
+        &mut machine,
+        "INSERT table1(id, small, usmall, body, active, amount) VALUES (1, -5, '7', 'rustico', true, 42)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    let tuple = &result_set.as_ref().unwrap()[0].tuples[0];
+    assert!(matches!(tuple[0], Data::UnsignedBigint(1)));
+    assert!(matches!(tuple[1], Data::SignedTinyint(-5)));
+    assert!(matches!(tuple[2], Data::UnsignedTinyint(7)));
+    assert!(matches!(tuple[3], Data::Text(ref body) if body == "rustico"));
+    assert!(matches!(tuple[4], Data::UnsignedTinyint(1)));
+    assert!(matches!(tuple[5], Data::SignedInt(42)));
+}
+
+#[rstest]
+#[case("INSERT table1(id, small) VALUES (1, 128)", "small")]
+#[case("INSERT table1(id, small) VALUES (1, -129)", "small")]
+#[case("INSERT table1(id, usmall) VALUES (1, -1)", "usmall")]
+#[case("INSERT table1(id, amount) VALUES (1, 'forty')", "amount")]
+#[case("INSERT table1(id, amount) VALUES (1, 4294967296)", "amount")]
+pub fn test_values_out_of_the_column_type(#[case] insert: &str, #[case] column_name: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_typed_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, insert);
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ColumnTypeNotMatch(_, ref table, ref column)) if table == "table1" && column == column_name
+    ));
+}
+
+#[test]
+pub fn test_values_in_float_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE table1 (id BIGINT PRIMARY KEY, price REAL, ratio DOUBLE PRECISION, total FLOAT DEFAULT 0.5)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, price, ratio) VALUES (1, 2.5, 3)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    let result_set = parse_command(&mut machine, "INSERT table1(id, price, ratio) VALUES (2, '0.75', -1.25)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE ratio > 0");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][1], Data::Float32(OrderedFloat(2.5)));
+    assert_eq!(tuples[0][2], Data::Float64(OrderedFloat(3.0)));
+    assert_eq!(tuples[0][3], Data::Float64(OrderedFloat(0.5)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE price < 1");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][1], Data::Float32(OrderedFloat(0.75)));
+    assert_eq!(tuples[0][2], Data::Float64(OrderedFloat(-1.25)));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, ratio) VALUES (2.5, 1)");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "id"
+    ));
+}
+
+#[test]
+pub fn test_values_in_decimal_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE table1 (id BIGINT PRIMARY KEY, price DECIMAL(6,2), amount NUMERIC(4) DEFAULT 10)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, price) VALUES (1, 0.1 + 0.2)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    let result_set = parse_command(&mut machine, "INSERT table1(id, price, amount) VALUES (2, '19.999', 7.5)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE price = 0.3");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert!(matches!(tuples[0][1], Data::Decimal(30, 6, 2)));
+    assert!(matches!(tuples[0][2], Data::Decimal(10, 4, 0)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE price >= 20");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][1].to_string(), "20.00");
+    assert_eq!(tuples[0][2].to_string(), "8");
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, price) VALUES (3, 10000)");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "price"
+    ));
+}
+
+#[rstest]
+#[case("CREATE TABLE table1 (price DECIMAL(0))")]
+#[case("CREATE TABLE table1 (price DECIMAL(39,2))")]
+#[case("CREATE TABLE table1 (price NUMERIC(4,5))")]
+pub fn test_decimal_columns_out_of_range(#[case] create_table: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set = parse_command(&mut machine, create_table);
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+}
+
+#[rstest]
+#[case("CREATE TABLE table1 (price DECIMAL(300,2))")]
+#[case("CREATE TABLE table1 (price NUMERIC(300))")]
+pub fn test_decimal_precision_over_a_byte(#[case] create_table: &str) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set = parse_command(&mut machine, create_table);
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+}
+
+#[rstest]
+#[case("DECIMAL(6,2)", ColumnType::Decimal(0, 6, 2))]
+#[case("DECIMAL(10)", ColumnType::Decimal(0, 10, 0))]
+#[case("DECIMAL", ColumnType::Decimal(0, 38, 0))]
+#[case("NUMERIC", ColumnType::Decimal(0, 38, 0))]
+#[case("NUMERIC(4, 1)", ColumnType::Decimal(0, 4, 1))]
+#[case("DECIMAL(300,2)", ColumnType::Undefined)]
+#[case("DECIMAL(4,5)", ColumnType::Undefined)]
+#[case("DECIMAL(abc)", ColumnType::Undefined)]
+#[case("DECIMAL(6,2", ColumnType::Undefined)]
+pub fn test_decimal_type_names_of_the_catalog(#[case] name: &str, #[case] column_type: ColumnType) {
+    assert_eq!(map_column_type(String::from(name)), column_type);
+}
+
+#[test]
+pub fn test_values_in_bytea_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE table1 (id BIGINT PRIMARY KEY, payload BYTEA, digest BLOB DEFAULT X'00ff')"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, payload) VALUES (1, X'DEADBEEF')");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    let result_set = parse_command(&mut machine, "INSERT table1(id, payload) VALUES (2, '\\x0102')");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    let result_set = parse_command(&mut machine, "INSERT table1(id, payload) VALUES (3, 'ab')");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE id = 1");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples[0][1], Data::Bytes(vec![0xde, 0xad, 0xbe, 0xef]));
+    assert_eq!(tuples[0][1].to_string(), "\\xdeadbeef");
+    assert_eq!(tuples[0][2], Data::Bytes(vec![0x00, 0xff]));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE payload = '\\x0102'");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][0], Data::UnsignedBigint(2));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE payload < X'6200'");
+    let ids: Vec<Data> = result_set.unwrap()[0].tuples.iter().map(|tuple| tuple[0].clone()).collect();
+    assert_eq!(ids, vec![Data::UnsignedBigint(2), Data::UnsignedBigint(3)]);
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE payload = 5 OR payload = '\\x0'");
+    assert_eq!(result_set.unwrap()[0].tuples.len(), 0);
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, payload) VALUES (4, '\\x0')");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "payload"
+    ));
+}
+
+#[rstest]
+#[case(5000)]
+#[case(200 * 1024)]
+pub fn test_in_large_bytea_value(#[case] size: usize) {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let large_bytes: Vec<u8> = (0..size).map(|idx| (idx % 251) as u8).collect();
+    let large_hex: String = large_bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(id BIGINT PRIMARY KEY, payload BYTEA)");
+    let result_set = parse_command(&mut machine, &format!("INSERT table1(payload) VALUES (X'{}')", large_hex));
+
+    assert!(matches!(result_set, Ok(_result_set)));
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set_select = parse_command(&mut machine, "SELECT * FROM table1");
+    let rs = result_set_select.unwrap();
+
+    assert_eq!(rs[0].tuples.len(), 1);
+    assert_eq!(rs.get(0).unwrap().get_value(0, &String::from("payload")).unwrap(), Data::Bytes(large_bytes));
+}
+
+#[test]
+pub fn test_values_in_uuid_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE table1 (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), name VARCHAR, parent UUID)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(
+        &mut machine,
+        "INSERT table1(id, name) VALUES ('a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', 'root')"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    let result_set = parse_command(
+        &mut machine,
+        "INSERT table1(name, parent) VALUES ('first', 'A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11')"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    let result_set = parse_command(&mut machine, "INSERT table1(id, name) VALUES (gen_random_uuid(), 'second')");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 3);
+    assert!(tuples.iter().all(|tuple| matches!(tuple[0], Data::Uuid(_))));
+    assert_ne!(tuples[1][0], tuples[2][0]);
+
+    let result_set = parse_command(
+        &mut machine,
+        "SELECT * FROM table1 WHERE parent = 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'"
+    );
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][1], Data::Varchar(String::from("first")));
+    assert_eq!(tuples[0][2].to_string(), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE id = 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'");
+    let tuples = &result_set.as_ref().unwrap()[0].tuples;
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0][1], Data::Varchar(String::from("root")));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE id = 'not-an-uuid'");
+    assert_eq!(result_set.unwrap()[0].tuples.len(), 0);
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE parent = 'not-an-uuid' OR parent = 5");
+    assert_eq!(result_set.unwrap()[0].tuples.len(), 0);
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, name) VALUES ('not-an-uuid', 'third')");
+    assert!(matches!(
+        result_set,
+        Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "id"
+    ));
+}
@@ -0,0 +1,142 @@
+use rusticodb::machine::Machine;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::utils::ExecutionError;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_events(machine: &mut Machine) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let result_set = parse_command(
+        machine,
+        "CREATE TABLE events (id BIGINT PRIMARY KEY, payload JSONB, raw JSON DEFAULT '{}')"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    for insert in [
+        "INSERT events(id, payload, raw) VALUES (1, '{\"kind\": \"click\", \"tags\": [\"a\", \"b\"], \"user\": {\"id\": 7}}', '{\"b\":1,  \"a\":2}')",
+        "INSERT events(id, payload) VALUES (2, '{\"kind\": \"view\", \"tags\": [], \"user\": {\"id\": 8}}')",
+        "INSERT events(id, payload) VALUES (3, JSONB '{\"kind\": \"click\", \"tags\": [\"c\"], \"user\": null}')"
+    ] {
+        let result_set = parse_command(machine, insert);
+        assert!(matches!(result_set, Ok(ref _result_set)));
+    }
+}
+
+fn select_ids(machine: &mut Machine, sql: &str) -> Vec<Data> {
+    let result_set = parse_command(machine, sql);
+    return result_set.unwrap()[0].tuples.iter().map(|tuple| tuple[0].clone()).collect();
+}
+
+#[test]
+pub fn test_json_values_are_stored_with_their_type() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM events WHERE id = 1");
+    let tuple = &result_set.as_ref().unwrap()[0].tuples[0];
+    assert_eq!(tuple[1].to_string(), "{\"kind\": \"click\", \"tags\": [\"a\", \"b\"], \"user\": {\"id\": 7}}");
+    assert_eq!(tuple[2], Data::Json(String::from("{\"b\":1,  \"a\":2}")));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM events WHERE id = 2");
+    let tuple = &result_set.as_ref().unwrap()[0].tuples[0];
+    assert_eq!(tuple[2], Data::Json(String::from("{}")));
+}
+
+#[test]
+pub fn test_json_operators_in_where() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE payload ->> 'kind' = 'click'"),
+        vec![Data::UnsignedBigint(1), Data::UnsignedBigint(3)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE payload -> 'user' -> 'id' = '8'"),
+        vec![Data::UnsignedBigint(2)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE payload #>> '{tags,0}' = 'c'"),
+        vec![Data::UnsignedBigint(3)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE payload @> '{\"tags\": [\"b\"]}'"),
+        vec![Data::UnsignedBigint(1)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE raw -> 'a' = '2'"),
+        vec![Data::UnsignedBigint(1)]
+    );
+}
+
+#[test]
+pub fn test_json_functions_in_where() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE jsonb_array_length(payload -> 'tags') = 0"),
+        vec![Data::UnsignedBigint(2)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE json_extract_path_text(payload, 'user', 'id') = '7'"),
+        vec![Data::UnsignedBigint(1)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE jsonb_extract_path(payload, 'tags', '1') = '\"b\"'"),
+        vec![Data::UnsignedBigint(1)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE payload = '{\"user\": null, \"tags\": [\"c\"], \"kind\": \"click\"}'::jsonb"),
+        vec![Data::UnsignedBigint(3)]
+    );
+}
+
+#[test]
+pub fn test_invalid_json_values_are_rejected() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let result_set = parse_command(&mut machine, "INSERT events(id, payload) VALUES (4, '{\"kind\": }')");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "payload"));
+
+    let result_set = parse_command(&mut machine, "INSERT events(id, raw) VALUES (4, 'not json')");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "raw"));
+
+    let result_set = parse_command(&mut machine, "DELETE FROM events WHERE payload = JSONB '{'");
+    assert!(matches!(result_set, Err(_)));
+}
+
+#[test]
+pub fn test_hash_index_on_json_finds_the_rows_a_full_scan_finds() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let sql = "SELECT * FROM events WHERE raw = '{\"a\": 2, \"b\": 1}'";
+    let scanned = select_ids(&mut machine, sql);
+    assert_eq!(scanned, vec![Data::UnsignedBigint(1)]);
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX events_raw ON events USING HASH (raw)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    assert_eq!(select_ids(&mut machine, sql), scanned);
+    assert_eq!(select_ids(&mut machine, "SELECT * FROM events WHERE raw = '{}'"), vec![Data::UnsignedBigint(2), Data::UnsignedBigint(3)]);
+}
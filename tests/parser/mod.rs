@@ -17,9 +17,18 @@ pub mod drop_index_spec;
 pub mod create_view_spec;
 
 pub mod query_spec;
+pub mod aggregate_spec;
 
 pub mod insert_spec;
 
 pub mod update_spec;
 
 pub mod delete_spec;
+
+pub mod foreign_key_spec;
+pub mod check_constraint_spec;
+pub mod temporal_spec;
+pub mod json_spec;
+
+pub mod vacuum_spec;
+pub mod explain_spec;
@@ -5,6 +5,7 @@ use rusticodb::parser::parse_command;
 use rusticodb::setup::setup_system;
 use rusticodb::storage::Pager;
 use rusticodb::storage::Data;
+use rusticodb::storage::ResultSet;
 
 use crate::test_utils::create_tmp_test_folder;
 
@@ -195,7 +196,7 @@ pub fn test_select_with_one_tables() {
     let result_set = parse_command(&mut machine, "SELECT * FROM columns b");
 
     assert!(matches!(result_set, Ok(ref _result_set)));
-    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 28);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 44);
     assert_eq!(result_set.as_ref().unwrap()[0].columns.len(), 9);
 }
 
@@ -212,7 +213,7 @@ pub fn test_select_with_two_tables() {
     let result_set = parse_command(&mut machine, "SELECT * FROM columns a, columns b");
 
     assert!(matches!(result_set, Ok(ref _result_set)));
-    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 784);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 1936);
     assert_eq!(result_set.as_ref().unwrap()[0].columns.len(), 18);
 }
 
@@ -228,7 +229,7 @@ pub fn test_select_with_three_tables() {
     let _ = parse_command(&mut machine, "USE rusticodb;");
     let result_set = parse_command(&mut machine, "SELECT * FROM columns a, columns b, columns c");
 
-    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 21952);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 85184);
     assert_eq!(result_set.as_ref().unwrap()[0].columns.len(), 27);
     //assert_eq!(result_set.unwrap()[0].tuples[0].len(), 27);
 }
@@ -246,7 +247,7 @@ pub fn test_select_with_all_and_more_one_attr() {
     let result_set = parse_command(&mut machine, "SELECT *, name FROM columns");
 
     assert!(matches!(result_set, Ok(ref _result_set)));
-    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 28);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 44);
     assert_eq!(result_set.unwrap()[0].column_count(), 10);
 }
 
@@ -298,7 +299,7 @@ pub fn test_select_with_all_where_with_not_equal() {
 
 
     assert!(matches!(result_set, Ok(ref _result_set)));
-    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 23);
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 39);
     assert_eq!(result_set.unwrap()[0].column_count(), 9);
 }
 
@@ -340,3 +341,249 @@ pub fn test_select_with_all_where_with_or_conditions() {
     assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 7);
     assert_eq!(result_set.unwrap()[0].column_count(), 9);
 }
+
+#[test]
+pub fn test_select_filters_before_projection_and_limit() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, name) VALUES (1, 'one'), (2, 'two'), (3, 'three'), (4, 'four')");
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 WHERE id = 3 LIMIT 1");
+    let rs = &result_set.as_ref().unwrap()[0];
+    assert_eq!(rs.line_count(), 1);
+    assert_eq!(rs.column_count(), 1);
+    assert_eq!(rs.get_value(0, &String::from("name")).unwrap(), Data::Varchar(String::from("three")));
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 WHERE id > 1 LIMIT 2 OFFSET 1");
+    let rs = &result_set.as_ref().unwrap()[0];
+    assert_eq!(rs.line_count(), 2);
+    assert_eq!(rs.get_value(0, &String::from("name")).unwrap(), Data::Varchar(String::from("three")));
+    assert_eq!(rs.get_value(1, &String::from("name")).unwrap(), Data::Varchar(String::from("four")));
+}
+
+#[test]
+pub fn test_select_with_computed_columns_and_aliases() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, price BIGINT)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, price) VALUES (1, 10), (2, 20)");
+
+    let result_set = parse_command(&mut machine, "SELECT id, price * 2 AS doubled, t.price + 1 FROM table1 t WHERE id = 2");
+    let rs = &result_set.as_ref().unwrap()[0];
+    assert_eq!(rs.line_count(), 1);
+    assert_eq!(rs.column_count(), 3);
+    assert_eq!(rs.columns[1].alias, String::from("doubled"));
+    assert_eq!(rs.columns[2].alias, String::from("t.price + 1"));
+    assert_eq!(rs.tuples[0][1], Data::UnsignedBigint(40));
+    assert_eq!(rs.tuples[0][2], Data::UnsignedBigint(21));
+}
+
+#[test]
+pub fn test_select_arithmetic_that_overflows_is_an_error() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, price BIGINT, name VARCHAR)");
+    let _ = parse_command(&mut machine, "INSERT table1(id, price, name) VALUES (1, 10, 'ana'), (2, 20, 'bruno')");
+
+    let result_set = parse_command(&mut machine, "SELECT price * 18446744073709551615 FROM table1");
+    assert!(matches!(result_set, Err(ExecutionError::NumericOverflow(_))));
+
+    let result_set = parse_command(&mut machine, "SELECT id FROM table1 WHERE price - 9223372036854775807 - 9223372036854775807 < 0");
+    assert!(matches!(result_set, Err(ExecutionError::NumericOverflow(_))));
+
+    let result_set = parse_command(&mut machine, "SELECT name * 2 FROM table1");
+    assert!(matches!(result_set, Err(ExecutionError::OperatorTypeNotMatch(_, _, _))));
+
+    let result_set = parse_command(&mut machine, "INSERT table1(id, price) VALUES (3, 9223372036854775807 * 4)");
+    assert!(matches!(result_set, Err(ExecutionError::NumericOverflow(_))));
+
+    let result_set = parse_command(&mut machine, "SELECT id FROM table1");
+    assert_eq!(result_set.unwrap()[0].line_count(), 2);
+}
+
+#[test]
+pub fn test_select_qualified_columns_of_two_tables() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "USE rusticodb;");
+    let result_set = parse_command(
+        &mut machine,
+        "SELECT a.table_name, b.table_name FROM columns a, columns b WHERE a.table_name = 'databases' and b.table_name = 'tables'"
+    );
+    let rs = &result_set.as_ref().unwrap()[0];
+    assert_eq!(rs.line_count(), 10);
+    assert_eq!(rs.column_count(), 2);
+    assert!(rs.tuples.iter().all(|tuple| tuple[0] == Data::Varchar(String::from("databases"))));
+    assert!(rs.tuples.iter().all(|tuple| tuple[1] == Data::Varchar(String::from("tables"))));
+}
+
+fn create_order_by_table(machine: &mut Machine) {
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let _ = parse_command(machine, "CREATE TABLE table1 (id BIGINT PRIMARY KEY, name VARCHAR, team VARCHAR, score BIGINT)");
+    let _ = parse_command(
+        machine,
+        "INSERT table1(id, name, team, score) VALUES \
+        (1, 'ana', 'red', 30), (2, 'bruno', 'blue', NULL), (3, 'carla', 'red', 10), \
+        (4, 'davi', 'blue', 20), (5, 'eva', NULL, 20)"
+    );
+}
+
+fn get_names(result_set: &Result<Vec<ResultSet>, ExecutionError>, column_name: &str) -> Vec<Data> {
+    let rs = &result_set.as_ref().unwrap()[0];
+    return (0..rs.line_count()).map(|idx| rs.get_value(idx, &String::from(column_name)).unwrap()).collect();
+}
+
+fn varchars(values: Vec<&str>) -> Vec<Data> {
+    return values.into_iter().map(|value| Data::Varchar(String::from(value))).collect();
+}
+
+#[test]
+pub fn test_select_order_by_asc_and_desc() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    create_order_by_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 ORDER BY score");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["carla", "davi", "eva", "ana", "bruno"]));
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 ORDER BY score DESC");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["bruno", "ana", "davi", "eva", "carla"]));
+}
+
+#[test]
+pub fn test_select_order_by_nulls_first_and_last() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    create_order_by_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 ORDER BY score ASC NULLS FIRST");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["bruno", "carla", "davi", "eva", "ana"]));
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 ORDER BY score DESC NULLS LAST");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["ana", "davi", "eva", "carla", "bruno"]));
+}
+
+#[test]
+pub fn test_select_order_by_many_keys() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    create_order_by_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT name, team FROM table1 ORDER BY team DESC, name DESC");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["eva", "carla", "ana", "davi", "bruno"]));
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 ORDER BY score, id DESC LIMIT 2 OFFSET 1");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["eva", "davi"]));
+}
+
+#[test]
+pub fn test_select_order_by_aliases_positions_and_expressions() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    create_order_by_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT name, 100 - id AS rank FROM table1 ORDER BY rank");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["eva", "davi", "carla", "bruno", "ana"]));
+
+    let result_set = parse_command(&mut machine, "SELECT id, name FROM table1 ORDER BY 2 DESC");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["eva", "davi", "carla", "bruno", "ana"]));
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 WHERE id < 4 ORDER BY id * 2 DESC");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["carla", "bruno", "ana"]));
+
+    let result_set = parse_command(&mut machine, "SELECT name FROM table1 ORDER BY 3");
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+}
+
+#[test]
+pub fn test_select_distinct_rows() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    create_order_by_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT DISTINCT team FROM table1 ORDER BY team NULLS FIRST");
+    assert_eq!(
+        get_names(&result_set, "team"),
+        vec![Data::Null, Data::Varchar(String::from("blue")), Data::Varchar(String::from("red"))]
+    );
+
+    let result_set = parse_command(&mut machine, "SELECT DISTINCT score FROM table1");
+    assert_eq!(result_set.as_ref().unwrap()[0].line_count(), 4);
+
+    let result_set = parse_command(&mut machine, "SELECT DISTINCT team, score FROM table1 LIMIT 10");
+    assert_eq!(result_set.as_ref().unwrap()[0].line_count(), 5);
+
+    let result_set = parse_command(&mut machine, "SELECT DISTINCT name FROM table1 ORDER BY score");
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+}
+
+#[test]
+pub fn test_select_distinct_on_keeps_first_row_of_each_key() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    create_order_by_table(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT DISTINCT ON (team) name FROM table1");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["ana", "bruno", "eva"]));
+
+    let result_set = parse_command(
+        &mut machine,
+        "SELECT DISTINCT ON (team) name, score FROM table1 ORDER BY team, score DESC NULLS LAST"
+    );
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["davi", "ana", "eva"]));
+
+    let result_set = parse_command(&mut machine, "SELECT DISTINCT ON (2) name, score FROM table1 ORDER BY 2, id DESC");
+    assert_eq!(get_names(&result_set, "name"), varchars(vec!["carla", "eva", "ana", "bruno"]));
+}
@@ -0,0 +1,145 @@
+use rusticodb::machine::Machine;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::utils::ExecutionError;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_events(machine: &mut Machine) {
+    create_tmp_test_folder();
+
+    setup_system(machine);
+
+    let _ = parse_command(machine, "CREATE DATABASE database1");
+    let _ = parse_command(machine, "USE database1");
+    let result_set = parse_command(
+        machine,
+        "CREATE TABLE events (id BIGINT PRIMARY KEY, day DATE, starts_at TIMESTAMP, \
+         created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(), opens TIME)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    for insert in [
+        "INSERT events(id, day, starts_at, opens) VALUES (1, '2024-01-15', '2024-01-15 09:30:00', '09:00')",
+        "INSERT events(id, day, starts_at, opens) VALUES (2, '2024-02-29', '2024-02-29T18:00:00', '18:00:00')",
+        "INSERT events(id, day, starts_at, opens) VALUES (3, DATE '2024-03-01', '2024-03-01 08:00', '08:00')"
+    ] {
+        let result_set = parse_command(machine, insert);
+        assert!(matches!(result_set, Ok(ref _result_set)));
+    }
+}
+
+fn select_ids(machine: &mut Machine, sql: &str) -> Vec<Data> {
+    let result_set = parse_command(machine, sql);
+    return result_set.unwrap()[0].tuples.iter().map(|tuple| tuple[0].clone()).collect();
+}
+
+#[test]
+pub fn test_temporal_values_are_stored_with_their_type() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM events WHERE id = 2");
+    let tuple = &result_set.as_ref().unwrap()[0].tuples[0];
+    assert_eq!(tuple[1].to_string(), "2024-02-29");
+    assert_eq!(tuple[2].to_string(), "2024-02-29 18:00:00");
+    assert!(matches!(tuple[3], Data::TimestampTz(_)));
+    assert_eq!(tuple[4].to_string(), "18:00:00");
+}
+
+#[test]
+pub fn test_temporal_columns_compare_with_string_literals() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE day >= '2024-02-01'"),
+        vec![Data::UnsignedBigint(2), Data::UnsignedBigint(3)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE starts_at < '2024-02-29 18:00'"),
+        vec![Data::UnsignedBigint(1)]
+    );
+    assert_eq!(select_ids(&mut machine, "SELECT * FROM events WHERE opens = '08:00:00'"), vec![Data::UnsignedBigint(3)]);
+}
+
+#[test]
+pub fn test_interval_arithmetic_and_temporal_functions() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE starts_at + INTERVAL '1 month' >= '2024-03-29'"),
+        vec![Data::UnsignedBigint(2), Data::UnsignedBigint(3)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE day - INTERVAL '2' DAY = '2024-02-27'"),
+        vec![Data::UnsignedBigint(2)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE EXTRACT(MONTH FROM day) = 2"),
+        vec![Data::UnsignedBigint(2)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE DATE_TRUNC('month', starts_at) = '2024-03-01'"),
+        vec![Data::UnsignedBigint(3)]
+    );
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events WHERE created_at <= NOW() AND day < CURRENT_DATE"),
+        vec![Data::UnsignedBigint(1), Data::UnsignedBigint(2), Data::UnsignedBigint(3)]
+    );
+}
+
+#[test]
+pub fn test_index_on_a_date_column_finds_string_literals() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let result_set = parse_command(&mut machine, "CREATE INDEX events_day ON events USING HASH (day)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    assert_eq!(select_ids(&mut machine, "SELECT * FROM events WHERE day = '2024-01-15'"), vec![Data::UnsignedBigint(1)]);
+}
+
+#[test]
+pub fn test_delete_with_temporal_condition() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let result_set = parse_command(&mut machine, "DELETE FROM events WHERE day < DATE '2024-02-01'");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    assert_eq!(
+        select_ids(&mut machine, "SELECT * FROM events"),
+        vec![Data::UnsignedBigint(2), Data::UnsignedBigint(3)]
+    );
+}
+
+#[test]
+pub fn test_invalid_temporal_values_are_rejected() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    setup_events(&mut machine);
+
+    let result_set = parse_command(&mut machine, "INSERT events(id, day) VALUES (4, '2024-02-30')");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "day"));
+
+    let result_set = parse_command(&mut machine, "INSERT events(id, opens) VALUES (4, 900)");
+    assert!(matches!(result_set, Err(ExecutionError::ColumnTypeNotMatch(_, _, ref column)) if column == "opens"));
+
+    let result_set = parse_command(&mut machine, "DELETE FROM events WHERE day > INTERVAL 'soon'");
+    assert!(matches!(result_set, Err(_)));
+}
@@ -1,9 +1,16 @@
 
 use rusticodb::machine::Machine;
+use rusticodb::machine::Table;
+use rusticodb::machine::Attribution;
+use rusticodb::machine::get_columns;
+use rusticodb::machine::update_row;
 use rusticodb::parser::parse_command;
 use rusticodb::setup::setup_system;
 use rusticodb::storage::Pager;
-// use rusticodb::utils::ExecutionError;
+use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::Expression2Type;
+use rusticodb::utils::ExecutionError;
 
 use crate::test_utils::create_tmp_test_folder;
 
@@ -32,3 +39,44 @@ pub fn test_in_two_rows_varchar_and_update() {
     assert_eq!(result_set_select.as_ref().unwrap()[0].tuples.len(), 2);
     assert_eq!(result_set_select.unwrap()[0].column_count(), 3);
 }
+
+fn id_equals(id: u64) -> Expression {
+    return Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from("id"))),
+        Box::new(Expression::Const(Data::UnsignedBigint(id)))
+    );
+}
+
+#[test]
+pub fn test_updated_values_take_the_column_type() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE table1(id BIGINT PRIMARY KEY, small TINYINT NOT NULL)");
+    let _ = parse_command(&mut machine, "INSERT table1 VALUES (1, 1)");
+
+    let table = Table::new(String::from("database1"), String::from("table1"));
+    let column = get_columns(&mut machine, &table).into_iter().find(|column| column.name == "small").unwrap();
+
+    let attributions = vec![Attribution::new(column.clone(), Expression::Const(Data::Varchar(String::from("12"))))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    assert!(matches!(result_set.as_ref().unwrap()[0].tuples[0][1], Data::SignedTinyint(12)));
+
+    let attributions = vec![Attribution::new(column.clone(), Expression::Const(Data::UnsignedBigint(1000)))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(result_set, Err(ExecutionError::ColumnTypeNotMatch(_, _, ref name)) if name == "small"));
+
+    let attributions = vec![Attribution::new(column, Expression::Const(Data::Null))];
+    let result_set = update_row(&mut machine, &table, &attributions, id_equals(1));
+    assert!(matches!(result_set, Err(ExecutionError::ColumnCantBeNull(_, _, ref name)) if name == "small"));
+}
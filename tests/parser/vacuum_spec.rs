@@ -0,0 +1,169 @@
+use rusticodb::config::Config;
+use rusticodb::machine::Machine;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::storage::Pager;
+use rusticodb::storage::file_size;
+use rusticodb::storage::Data;
+use rusticodb::storage::format_index_name;
+use rusticodb::storage::btree_search;
+use rusticodb::storage::btree_entries;
+use rusticodb::utils::ExecutionError;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_churned_table(machine: &mut Machine, table_name: &str) {
+    let _ = parse_command(machine, &format!("CREATE TABLE {}(id BIGINT PRIMARY KEY, name1 VARCHAR, name2 VARCHAR)", table_name));
+
+    for idx in 0..300 {
+        let status = if idx % 10 == 0 { "keep" } else { "drop" };
+        let _ = parse_command(machine, &format!("INSERT {}(name1, name2) VALUES (\'row {}\', \'{}\')", table_name, idx, status));
+    }
+
+    let _ = parse_command(machine, &format!("DELETE FROM {} WHERE name2 = \'drop\'", table_name));
+}
+
+#[test]
+pub fn test_vacuum_table_shrinks_the_file() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    setup_churned_table(&mut machine, "table1");
+
+    let table_filename = format!("{}/database1/table1.db", Config::data_folder());
+    let size_before = file_size(&table_filename);
+
+    let result_set = parse_command(&mut machine, "VACUUM table1");
+
+    let size_after = file_size(&table_filename);
+    assert!(size_after < size_before);
+    assert_eq!(
+        result_set.unwrap()[0].message,
+        format!("VACUUM {} bytes reclaimed", size_before - size_after)
+    );
+
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 30);
+
+    let _ = parse_command(&mut machine, "INSERT table1(name1, name2) VALUES (\'after vacuum\', \'keep\')");
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1 WHERE name2 = \'keep\'");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 31);
+}
+
+#[test]
+pub fn test_vacuum_current_database() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    setup_churned_table(&mut machine, "table1");
+    setup_churned_table(&mut machine, "table2");
+
+    let table1_filename = format!("{}/database1/table1.db", Config::data_folder());
+    let table2_filename = format!("{}/database1/table2.db", Config::data_folder());
+    let size_before = file_size(&table1_filename) + file_size(&table2_filename);
+
+    let result_set = parse_command(&mut machine, "VACUUM; SELECT * FROM table2");
+
+    let size_after = file_size(&table1_filename) + file_size(&table2_filename);
+    assert!(matches!(result_set, Ok(ref _result_set)));
+    assert_eq!(
+        result_set.as_ref().unwrap()[0].message,
+        format!("VACUUM {} bytes reclaimed", size_before - size_after)
+    );
+    assert_eq!(result_set.as_ref().unwrap()[1].tuples.len(), 30);
+
+    let result_set = parse_command(&mut machine, "VACUUM DATABASE database1");
+    assert_eq!(result_set.unwrap()[0].message, String::from("VACUUM 0 bytes reclaimed"));
+}
+
+#[test]
+pub fn test_vacuum_table_with_database_name() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    setup_churned_table(&mut machine, "table1");
+    let _ = parse_command(&mut machine, "USE rusticodb");
+
+    let result_set = parse_command(&mut machine, "VACUUM database1.table1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let _ = parse_command(&mut machine, "USE database1");
+    let result_set = parse_command(&mut machine, "SELECT * FROM table1");
+    assert_eq!(result_set.as_ref().unwrap()[0].tuples.len(), 30);
+}
+
+#[test]
+pub fn test_vacuum_table_that_not_exists() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+
+    let result_set = parse_command(&mut machine, "VACUUM table1");
+    assert!(matches!(result_set, Err(ExecutionError::TableNotExists(_))));
+}
+
+#[test]
+pub fn test_vacuum_without_database() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let result_set = parse_command(&mut machine, "VACUUM");
+    assert!(matches!(result_set, Err(ExecutionError::DatabaseNotSetted)));
+
+    let result_set = parse_command(&mut machine, "VACUUM DATABASE");
+    assert!(matches!(result_set, Err(ExecutionError::ParserError(_))));
+}
+
+#[test]
+pub fn test_vacuum_table_rebuilds_its_indexes() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    setup_churned_table(&mut machine, "table1");
+    let _ = parse_command(&mut machine, "CREATE INDEX index1 ON table1(name1)");
+
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    let old_addresses = btree_search(&machine.pager, &index_key, &vec![Data::Varchar(String::from("row 290"))]).unwrap();
+
+    let result_set = parse_command(&mut machine, "VACUUM table1");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let new_addresses = btree_search(&machine.pager, &index_key, &vec![Data::Varchar(String::from("row 290"))]).unwrap();
+    assert_eq!(btree_entries(&machine.pager, &index_key).unwrap().len(), 30);
+    assert_eq!(new_addresses.len(), 1);
+    assert_ne!(new_addresses, old_addresses);
+}
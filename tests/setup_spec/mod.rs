@@ -0,0 +1 @@
+pub mod upgrade_base_spec;
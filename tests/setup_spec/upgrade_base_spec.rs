@@ -0,0 +1,124 @@
+use rusticodb::machine::Machine;
+use rusticodb::machine::Table;
+use rusticodb::machine::get_columns;
+use rusticodb::machine::get_indexes;
+use rusticodb::machine::read_tuples;
+use rusticodb::machine::insert_tuples;
+use rusticodb::machine::drop_tuples;
+use rusticodb::config::SysDb;
+use rusticodb::parser::parse_command;
+use rusticodb::setup::setup_system;
+use rusticodb::storage::Pager;
+use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::Expression2Type;
+use rusticodb::storage::destroy_file;
+use rusticodb::storage::format_table_name;
+use rusticodb::utils::ExecutionError;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn equals(column_name: &str, value: &String) -> Expression {
+    return Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::ColName(String::from(column_name))),
+        Box::new(Expression::Const(Data::Varchar(value.clone())))
+    );
+}
+
+fn drop_catalog_rows(machine: &mut Machine, table: &Table, condition: &Expression) {
+    let columns = get_columns(machine, table);
+    assert!(matches!(drop_tuples(machine, table, columns, condition), Ok(_)));
+}
+
+// Leaves the catalog as a version without foreign keys, check constraints
+// and the `unique` and `primary_key` columns of indexes wrote it.
+fn downgrade_base(machine: &mut Machine) {
+    for table in [SysDb::table_foreign_keys(), SysDb::table_check_constraints()] {
+        destroy_file(&format_table_name(&table.database_name, &table.name));
+        drop_catalog_rows(machine, &SysDb::table_tables(), &equals("name", &table.name));
+        drop_catalog_rows(machine, &SysDb::table_columns(), &equals("table_name", &table.name));
+        drop_catalog_rows(machine, &SysDb::table_sequences(), &equals("table_name", &table.name));
+        drop_catalog_rows(machine, &SysDb::table_indexes(), &equals("table_name", &table.name));
+    }
+
+    for column_name in ["unique", "primary_key"] {
+        let condition = Expression::Func2(
+            Expression2Type::And,
+            Box::new(equals("table_name", &SysDb::tblname_indexes())),
+            Box::new(equals("name", &String::from(column_name)))
+        );
+        drop_catalog_rows(machine, &SysDb::table_columns(), &condition);
+    }
+}
+
+#[test]
+pub fn test_old_catalogs_get_the_missing_tables_and_columns() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+    downgrade_base(&mut machine);
+    assert_eq!(get_columns(&mut machine, &SysDb::table_indexes()).len(), 6);
+
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+
+    assert_eq!(get_columns(&mut machine, &SysDb::table_indexes()).len(), 8);
+    assert_eq!(get_columns(&mut machine, &SysDb::table_foreign_keys()).len(), 9);
+    assert_eq!(get_columns(&mut machine, &SysDb::table_check_constraints()).len(), 5);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE authors (id BIGINT PRIMARY KEY)");
+    let result_set = parse_command(
+        &mut machine,
+        "CREATE TABLE books (id BIGINT PRIMARY KEY, pages BIGINT CHECK (pages > 0), author_id BIGINT REFERENCES authors)"
+    );
+    assert!(matches!(result_set, Ok(ref _result_set)));
+
+    let _ = parse_command(&mut machine, "INSERT authors(id) VALUES (1)");
+    let result_set = parse_command(&mut machine, "INSERT books(id, pages, author_id) VALUES (1, 10, 2)");
+    assert!(matches!(result_set, Err(ExecutionError::ForeignKeyViolation(_, _))));
+    let result_set = parse_command(&mut machine, "INSERT books(id, pages, author_id) VALUES (1, 0, 1)");
+    assert!(matches!(result_set, Err(ExecutionError::CheckViolation(_))));
+
+    // Later starts find nothing left to add.
+    let mut machine = Machine::new(Pager::new());
+    setup_system(&mut machine);
+
+    assert_eq!(get_columns(&mut machine, &SysDb::table_indexes()).len(), 8);
+    assert_eq!(get_columns(&mut machine, &SysDb::table_foreign_keys()).len(), 9);
+}
+
+#[test]
+pub fn test_old_primary_key_indexes_are_told_by_their_name() {
+    let pager = Pager::new();
+    let mut machine = Machine::new(pager);
+
+    create_tmp_test_folder();
+
+    setup_system(&mut machine);
+
+    let _ = parse_command(&mut machine, "CREATE DATABASE database1");
+    let _ = parse_command(&mut machine, "USE database1");
+    let _ = parse_command(&mut machine, "CREATE TABLE authors (id BIGINT PRIMARY KEY)");
+
+    // The row of the index as written before it had the `primary_key` column.
+    let mut tuples: Vec<Vec<Data>> = read_tuples(&mut machine, &SysDb::table_indexes())
+        .into_iter()
+        .filter(|tuple| tuple[4] == Data::Varchar(String::from("authors_pkey")))
+        .map(|tuple| tuple[..7].to_vec())
+        .collect();
+    drop_catalog_rows(&mut machine, &SysDb::table_indexes(), &equals("name", &String::from("authors_pkey")));
+    insert_tuples(&mut machine, &SysDb::table_indexes(), &mut tuples);
+
+    let indexes = get_indexes(&mut machine, &String::from("database1"));
+    assert_eq!(indexes.len(), 1);
+    assert!(indexes[0].primary_key);
+
+    let result_set = parse_command(&mut machine, "CREATE TABLE books (id BIGINT PRIMARY KEY, author_id BIGINT REFERENCES authors)");
+    assert!(matches!(result_set, Ok(ref _result_set)));
+}
@@ -0,0 +1,115 @@
+use rstest::rstest;
+
+use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::JsonValue;
+use rusticodb::storage::Aggregate;
+use rusticodb::storage::AggregateType;
+use rusticodb::storage::aggregate_state_new;
+use rusticodb::storage::aggregate_update;
+use rusticodb::storage::aggregate_result;
+use rusticodb::utils::ExecutionError;
+
+fn aggregate(aggregate_type: AggregateType, distinct: bool) -> Aggregate {
+    Aggregate {
+        aggregate_type,
+        distinct,
+        arguments: Vec::new(),
+        name: String::from("aggregate")
+    }
+}
+
+fn compute(aggregate: &Aggregate, values: Vec<Data>) -> Result<Data, ExecutionError> {
+    let mut state = aggregate_state_new();
+    for value in values {
+        aggregate_update(aggregate, &mut state, vec![value, Data::Varchar(String::from(", "))])?;
+    }
+    aggregate_result(aggregate, &state)
+}
+
+fn numbers() -> Vec<Data> {
+    vec![
+        Data::UnsignedTinyint(200),
+        Data::Null,
+        Data::UnsignedTinyint(100),
+        Data::UnsignedTinyint(200)
+    ]
+}
+
+#[rstest]
+#[case(AggregateType::CountAll, false, Data::UnsignedBigint(4))]
+#[case(AggregateType::Count, false, Data::UnsignedBigint(3))]
+#[case(AggregateType::Count, true, Data::UnsignedBigint(2))]
+#[case(AggregateType::Sum, false, Data::UnsignedBigint(500))]
+#[case(AggregateType::Sum, true, Data::UnsignedBigint(300))]
+#[case(AggregateType::Min, false, Data::UnsignedTinyint(100))]
+#[case(AggregateType::Max, false, Data::UnsignedTinyint(200))]
+#[case(AggregateType::StringAgg, false, Data::Text(String::from("200, 100, 200")))]
+#[case(AggregateType::StringAgg, true, Data::Text(String::from("200, 100")))]
+pub fn test_aggregate_over_numbers(#[case] aggregate_type: AggregateType, #[case] distinct: bool, #[case] expected: Data) {
+    assert_eq!(compute(&aggregate(aggregate_type, distinct), numbers()).unwrap(), expected);
+}
+
+#[rstest]
+#[case(AggregateType::CountAll, Data::UnsignedBigint(0))]
+#[case(AggregateType::Count, Data::UnsignedBigint(0))]
+#[case(AggregateType::Sum, Data::Null)]
+#[case(AggregateType::Avg, Data::Null)]
+#[case(AggregateType::Min, Data::Null)]
+#[case(AggregateType::Max, Data::Null)]
+#[case(AggregateType::StringAgg, Data::Null)]
+#[case(AggregateType::ArrayAgg, Data::Null)]
+pub fn test_aggregate_without_values(#[case] aggregate_type: AggregateType, #[case] expected: Data) {
+    assert_eq!(compute(&aggregate(aggregate_type, false), Vec::new()).unwrap(), expected);
+}
+
+#[test]
+pub fn test_aggregate_average() {
+    let average = compute(&aggregate(AggregateType::Avg, false), numbers()).unwrap();
+    assert!(matches!(average, Data::Decimal(_, _, _)));
+    assert_eq!(average.to_string(), String::from("166.666667"));
+
+    let average = compute(
+        &aggregate(AggregateType::Avg, false),
+        vec![Data::Float64(1.5.into()), Data::Float64(2.0.into())]
+    ).unwrap();
+    assert_eq!(average, Data::Float64(1.75.into()));
+}
+
+#[test]
+pub fn test_aggregate_array_keeps_nulls() {
+    let array = compute(&aggregate(AggregateType::ArrayAgg, false), numbers()).unwrap();
+    assert_eq!(
+        array,
+        Data::Jsonb(JsonValue::Array(vec![
+            JsonValue::Number(String::from("200")),
+            JsonValue::Null,
+            JsonValue::Number(String::from("100")),
+            JsonValue::Number(String::from("200"))
+        ]))
+    );
+}
+
+#[test]
+pub fn test_aggregate_sum_of_strings_fails() {
+    let result = compute(&aggregate(AggregateType::Sum, false), vec![Data::Varchar(String::from("a"))]);
+    assert!(matches!(result, Err(ExecutionError::AggregateTypeNotMatch(_, _))));
+}
+
+#[test]
+pub fn test_aggregate_sum_that_does_not_fit_fails() {
+    let values = vec![Data::UnsignedBigint(u64::MAX), Data::UnsignedTinyint(1)];
+    let result = compute(&aggregate(AggregateType::Sum, false), values);
+    assert!(matches!(result, Err(ExecutionError::NumericOverflow(_))));
+}
+
+#[test]
+pub fn test_aggregate_expression_reads_its_column() {
+    let expression = Expression::Aggregate(aggregate(AggregateType::CountAll, false));
+    let columns = vec![String::from("name"), String::from("aggregate")];
+    let tuple = vec![Data::Varchar(String::from("ana")), Data::UnsignedBigint(7)];
+
+    assert!(expression.has_aggregate());
+    assert!(expression.column_names().is_empty());
+    assert_eq!(expression.result(&tuple, &columns).unwrap(), Data::UnsignedBigint(7));
+}
@@ -0,0 +1,248 @@
+use std::ops::Bound;
+
+use rusticodb::storage::BTreeNode;
+use rusticodb::storage::TupleAddress;
+use rusticodb::storage::Pager;
+use rusticodb::storage::BLOCK_SIZE;
+use rusticodb::storage::pager_flush_blocks;
+use rusticodb::storage::file_size;
+use rusticodb::storage::truncate_file;
+use rusticodb::storage::write_data;
+use rusticodb::storage::Data;
+use rusticodb::storage::format_index_name;
+use rusticodb::storage::format_database_name;
+use rusticodb::storage::create_folder;
+use rusticodb::storage::btree_node_serialize;
+use rusticodb::storage::btree_node_deserialize;
+use rusticodb::storage::btree_create;
+use rusticodb::storage::btree_insert;
+use rusticodb::storage::btree_delete;
+use rusticodb::storage::btree_search;
+use rusticodb::storage::btree_range;
+use rusticodb::storage::btree_entries;
+use rusticodb::storage::btree_height;
+use rusticodb::storage::index_key_fits;
+
+use rusticodb::utils::ExecutionError;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_index(pager: &mut Pager) -> String {
+    let database1 = String::from("database1");
+
+    create_tmp_test_folder();
+    create_folder(&format_database_name(&database1));
+
+    let index_key = format_index_name(&database1, &String::from("table1"), &String::from("index1"));
+    btree_create(pager, &index_key);
+
+    return index_key;
+}
+
+#[test]
+pub fn test_node_serialize_and_deserialize() {
+    let node = BTreeNode::new_internal(
+        vec![
+            (vec![Data::UnsignedBigint(10)], TupleAddress::new(1, 2)),
+            (vec![Data::Varchar(String::from("key")), Data::Null], TupleAddress::new(3, 4))
+        ],
+        vec![5, 6, 7]
+    );
+
+    assert_eq!(btree_node_deserialize(&btree_node_serialize(&node)), node);
+}
+
+#[test]
+pub fn test_search_on_empty_tree() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::UnsignedBigint(1)]).unwrap(), vec![]);
+    assert_eq!(btree_entries(&pager, &index_key).unwrap().len(), 0);
+    assert_eq!(btree_height(&pager, &index_key).unwrap(), 1);
+}
+
+#[test]
+pub fn test_insert_splits_nodes_and_keeps_order() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..5000u64 {
+        let value = (idx * 7919) % 5000;
+        btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(value)], &TupleAddress::new(value + 1, 0)).unwrap();
+    }
+
+    let entries = btree_entries(&pager, &index_key).unwrap();
+    assert_eq!(entries.len(), 5000);
+    for (idx, (key, address)) in entries.iter().enumerate() {
+        assert_eq!(*key, vec![Data::UnsignedBigint(idx as u64)]);
+        assert_eq!(*address, TupleAddress::new(idx as u64 + 1, 0));
+    }
+
+    assert!(btree_height(&pager, &index_key).unwrap() > 1);
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::UnsignedBigint(4321)]).unwrap(), vec![TupleAddress::new(4322, 0)]);
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::UnsignedBigint(5000)]).unwrap(), vec![]);
+}
+
+#[test]
+pub fn test_repeated_keys_spanning_leaves() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..1000u64 {
+        btree_insert(&mut pager, &index_key, &vec![Data::Varchar(format!("key{}", idx % 3))], &TupleAddress::new(idx, 1)).unwrap();
+    }
+
+    let addresses = btree_search(&pager, &index_key, &vec![Data::Varchar(String::from("key1"))]).unwrap();
+    assert_eq!(addresses.len(), 333);
+    assert_eq!(addresses[0], TupleAddress::new(1, 1));
+    assert_eq!(addresses[332], TupleAddress::new(997, 1));
+}
+
+#[test]
+pub fn test_delete_only_removes_the_given_row() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..2000u64 {
+        btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx % 10)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    assert!(btree_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(3)], &TupleAddress::new(13, 0)).unwrap());
+    assert!(!btree_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(3)], &TupleAddress::new(13, 0)).unwrap());
+    assert!(!btree_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(4)], &TupleAddress::new(13, 0)).unwrap());
+
+    let addresses = btree_search(&pager, &index_key, &vec![Data::UnsignedBigint(3)]).unwrap();
+    assert_eq!(addresses.len(), 199);
+    assert!(!addresses.contains(&TupleAddress::new(13, 0)));
+
+    for idx in 0..2000u64 {
+        btree_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx % 10)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+    assert_eq!(btree_entries(&pager, &index_key).unwrap().len(), 0);
+
+    btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(3)], &TupleAddress::new(1, 0)).unwrap();
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::UnsignedBigint(3)]).unwrap(), vec![TupleAddress::new(1, 0)]);
+}
+
+#[test]
+pub fn test_null_keys_are_indexed_first() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(1)], &TupleAddress::new(1, 0)).unwrap();
+    btree_insert(&mut pager, &index_key, &vec![Data::Null], &TupleAddress::new(1, 1)).unwrap();
+
+    let entries = btree_entries(&pager, &index_key).unwrap();
+    assert_eq!(entries[0], (vec![Data::Null], TupleAddress::new(1, 1)));
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::Null]).unwrap(), vec![TupleAddress::new(1, 1)]);
+}
+
+#[test]
+pub fn test_large_keys_split_inside_a_block() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..200u64 {
+        let key = format!("{:04}{}", idx, "x".repeat(1000));
+        assert!(index_key_fits(&vec![Data::Varchar(key.clone())]));
+        btree_insert(&mut pager, &index_key, &vec![Data::Varchar(key)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    assert_eq!(btree_entries(&pager, &index_key).unwrap().len(), 200);
+    assert!(!index_key_fits(&vec![Data::Text("x".repeat(2000))]));
+}
+
+#[test]
+pub fn test_range_scan_with_bounds() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..3000u64 {
+        btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx / 3)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    let lower = vec![Data::UnsignedBigint(10)];
+    let upper = vec![Data::UnsignedBigint(20)];
+
+    assert_eq!(btree_range(&pager, &index_key, Bound::Included(&lower), Bound::Included(&upper)).unwrap().len(), 33);
+    assert_eq!(btree_range(&pager, &index_key, Bound::Excluded(&lower), Bound::Excluded(&upper)).unwrap().len(), 27);
+    assert_eq!(btree_range(&pager, &index_key, Bound::Unbounded, Bound::Excluded(&lower)).unwrap().len(), 30);
+    assert_eq!(btree_range(&pager, &index_key, Bound::Excluded(&upper), Bound::Unbounded).unwrap().len(), 2937);
+    assert_eq!(btree_range(&pager, &index_key, Bound::Included(&upper), Bound::Included(&lower)).unwrap().len(), 0);
+}
+
+#[test]
+pub fn test_integer_keys_compare_by_value() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    btree_insert(&mut pager, &index_key, &vec![Data::SignedInt(-5)], &TupleAddress::new(1, 0)).unwrap();
+    btree_insert(&mut pager, &index_key, &vec![Data::UnsignedTinyint(7)], &TupleAddress::new(1, 1)).unwrap();
+    btree_insert(&mut pager, &index_key, &vec![Data::Text(String::from("seven"))], &TupleAddress::new(1, 2)).unwrap();
+
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::UnsignedBigint(7)]).unwrap(), vec![TupleAddress::new(1, 1)]);
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::SignedBigint(-5)]).unwrap(), vec![TupleAddress::new(1, 0)]);
+    assert_eq!(btree_search(&pager, &index_key, &vec![Data::Varchar(String::from("seven"))]).unwrap(), vec![TupleAddress::new(1, 2)]);
+}
+
+#[test]
+pub fn test_composite_keys_match_by_prefix() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..3000u64 {
+        let key = vec![Data::UnsignedBigint(idx % 10), Data::Varchar(format!("{:04}", idx))];
+        btree_insert(&mut pager, &index_key, &key, &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    let entries = btree_entries(&pager, &index_key).unwrap();
+    assert_eq!(entries[0].0, vec![Data::UnsignedBigint(0), Data::Varchar(String::from("0000"))]);
+    assert_eq!(entries[1].0, vec![Data::UnsignedBigint(0), Data::Varchar(String::from("0010"))]);
+
+    let prefix = vec![Data::UnsignedBigint(3)];
+    assert_eq!(btree_search(&pager, &index_key, &prefix).unwrap().len(), 300);
+
+    let lower = vec![Data::UnsignedBigint(3), Data::Varchar(String::from("1000"))];
+    let addresses = btree_range(&pager, &index_key, Bound::Excluded(&lower), Bound::Included(&prefix)).unwrap();
+    assert_eq!(addresses.len(), 200);
+    assert_eq!(addresses[0], TupleAddress::new(1003, 0));
+
+    let upper = vec![Data::UnsignedBigint(5)];
+    assert_eq!(btree_range(&pager, &index_key, Bound::Included(&prefix), Bound::Excluded(&upper)).unwrap().len(), 600);
+}
+
+#[test]
+pub fn test_blocks_reach_the_file_on_flush() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..1000u64 {
+        btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    assert_eq!(file_size(&index_key), 0);
+    assert!(btree_entries(&Pager::new(), &index_key).is_err());
+
+    pager_flush_blocks(&mut pager);
+
+    assert_eq!(btree_entries(&Pager::new(), &index_key).unwrap().len(), 1000);
+}
+
+#[test]
+pub fn test_truncated_or_broken_file_is_an_error() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..1000u64 {
+        btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+    pager_flush_blocks(&mut pager);
+
+    truncate_file(&index_key, BLOCK_SIZE as u64 + 10);
+    let key = vec![Data::UnsignedBigint(1)];
+    assert!(matches!(btree_search(&Pager::new(), &index_key, &key), Err(ExecutionError::IoError(_, _))));
+
+    write_data(&index_key, 0, &[0u8; BLOCK_SIZE]);
+    assert!(matches!(btree_search(&Pager::new(), &index_key, &key), Err(ExecutionError::IndexCorrupted(_))));
+}
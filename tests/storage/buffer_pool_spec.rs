@@ -0,0 +1,171 @@
+use rusticodb::storage::format_table_name;
+use rusticodb::storage::format_database_name;
+use rusticodb::storage::Pager;
+use rusticodb::storage::tuple_new;
+use rusticodb::storage::pager_insert_tuples;
+use rusticodb::storage::pager_read_tuples;
+use rusticodb::storage::pager_flush_page;
+use rusticodb::storage::pager_stats;
+use rusticodb::storage::create_file;
+use rusticodb::storage::file_size;
+use rusticodb::storage::create_folder;
+use rusticodb::storage::buffer_pool_new;
+use rusticodb::storage::buffer_pool_fetch_page;
+use rusticodb::storage::buffer_pool_new_page;
+use rusticodb::storage::buffer_pool_unpin_page;
+use rusticodb::storage::buffer_pool_contains;
+use rusticodb::storage::buffer_pool_mark_clean;
+use rusticodb::storage::buffer_pool_stats;
+use rusticodb::storage::Data;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_table() -> String {
+    let database1 = String::from("database1");
+    let table1 = String::from("table1");
+    let page_key = format_table_name(&database1, &table1);
+
+    create_tmp_test_folder();
+
+    create_folder(&format_database_name(&database1));
+    create_file(&page_key);
+
+    return page_key;
+}
+
+fn insert_tuples(pager: &mut Pager, page_key: &String, amount: u64) {
+    for idx in 0..amount {
+        let mut tuple = tuple_new();
+        tuple.push(Data::UnsignedBigint(idx));
+        tuple.push(Data::Varchar(String::from("buffer pool tuple")));
+
+        pager_insert_tuples(pager, page_key, &mut vec![tuple]);
+    }
+}
+
+#[test]
+pub fn test_pool_never_holds_more_frames_than_capacity() {
+    let page_key = setup_table();
+    let mut pager = Pager::new_with_capacity(2);
+
+    insert_tuples(&mut pager, &page_key, 1000);
+    pager_flush_page(&mut pager, &page_key);
+
+    let stats = pager_stats(&pager);
+    assert_eq!(stats.capacity, 2);
+    assert_eq!(stats.resident, 2);
+    assert!(stats.evictions > 0);
+
+    let mut pager_new = Pager::new_with_capacity(2);
+    let _ = pager_read_tuples(&mut pager_new, &page_key);
+
+    let stats = pager_stats(&pager_new);
+    assert_eq!(stats.resident, 2);
+    assert!(stats.evictions > 0);
+}
+
+#[test]
+pub fn test_dirty_pages_are_not_written_before_flush() {
+    let page_key = setup_table();
+    let mut pager = Pager::new_with_capacity(2);
+
+    insert_tuples(&mut pager, &page_key, 1000);
+
+    assert!(pager_stats(&pager).resident > 2);
+    assert_eq!(pager_stats(&pager).evictions, 0);
+    assert_eq!(file_size(&page_key), 0);
+
+    pager_flush_page(&mut pager, &page_key);
+    assert_eq!(pager_stats(&pager).resident, 2);
+
+    let mut pager_new = Pager::new_with_capacity(3);
+    let tuples = pager_read_tuples(&mut pager_new, &page_key);
+
+    assert_eq!(tuples.len(), 1000);
+    assert_eq!(tuples[0], vec![Data::UnsignedBigint(0), Data::Varchar(String::from("buffer pool tuple"))]);
+    assert_eq!(tuples[999], vec![Data::UnsignedBigint(999), Data::Varchar(String::from("buffer pool tuple"))]);
+    assert_eq!(pager_stats(&pager_new).resident, 3);
+}
+
+#[test]
+pub fn test_scan_counts_hits_and_misses() {
+    let page_key = setup_table();
+    let mut pager = Pager::new_with_capacity(100);
+
+    insert_tuples(&mut pager, &page_key, 300);
+    pager_flush_page(&mut pager, &page_key);
+
+    let mut pager_new = Pager::new_with_capacity(100);
+    let _ = pager_read_tuples(&mut pager_new, &page_key);
+    let first_scan = pager_stats(&pager_new);
+
+    assert_eq!(first_scan.hits, 0);
+    assert!(first_scan.misses > 1);
+
+    let _ = pager_read_tuples(&mut pager_new, &page_key);
+    let second_scan = pager_stats(&pager_new);
+
+    assert_eq!(second_scan.misses, first_scan.misses);
+    assert_eq!(second_scan.hits, first_scan.misses);
+    assert_eq!(second_scan.evictions, 0);
+}
+
+#[test]
+pub fn test_least_recently_used_page_is_evicted() {
+    let page_key = setup_table();
+    let mut pool = buffer_pool_new(2);
+
+    let frame1 = buffer_pool_new_page(&mut pool, &page_key, 1);
+    buffer_pool_unpin_page(&mut pool, frame1, false);
+    let frame2 = buffer_pool_new_page(&mut pool, &page_key, 2);
+    buffer_pool_unpin_page(&mut pool, frame2, false);
+    buffer_pool_mark_clean(&mut pool, &page_key);
+
+    let frame1 = buffer_pool_fetch_page(&mut pool, &page_key, 1);
+    buffer_pool_unpin_page(&mut pool, frame1, false);
+
+    let frame3 = buffer_pool_new_page(&mut pool, &page_key, 3);
+    buffer_pool_unpin_page(&mut pool, frame3, false);
+
+    assert!(buffer_pool_contains(&pool, &page_key, 1));
+    assert!(!buffer_pool_contains(&pool, &page_key, 2));
+    assert!(buffer_pool_contains(&pool, &page_key, 3));
+    assert_eq!(buffer_pool_stats(&pool).evictions, 1);
+}
+
+#[test]
+pub fn test_pinned_page_is_not_evicted() {
+    let page_key = setup_table();
+    let mut pool = buffer_pool_new(2);
+
+    let _pinned = buffer_pool_new_page(&mut pool, &page_key, 1);
+    let frame2 = buffer_pool_new_page(&mut pool, &page_key, 2);
+    buffer_pool_unpin_page(&mut pool, frame2, false);
+    buffer_pool_mark_clean(&mut pool, &page_key);
+
+    let frame3 = buffer_pool_new_page(&mut pool, &page_key, 3);
+    buffer_pool_unpin_page(&mut pool, frame3, false);
+
+    assert!(buffer_pool_contains(&pool, &page_key, 1));
+    assert!(!buffer_pool_contains(&pool, &page_key, 2));
+    assert!(buffer_pool_contains(&pool, &page_key, 3));
+}
+
+#[test]
+pub fn test_dirty_page_is_not_evicted() {
+    let page_key = setup_table();
+    let mut pool = buffer_pool_new(2);
+
+    let frame1 = buffer_pool_new_page(&mut pool, &page_key, 1);
+    buffer_pool_unpin_page(&mut pool, frame1, true);
+    let frame2 = buffer_pool_new_page(&mut pool, &page_key, 2);
+    buffer_pool_unpin_page(&mut pool, frame2, true);
+
+    let frame3 = buffer_pool_new_page(&mut pool, &page_key, 3);
+    buffer_pool_unpin_page(&mut pool, frame3, true);
+
+    assert!(buffer_pool_contains(&pool, &page_key, 1));
+    assert!(buffer_pool_contains(&pool, &page_key, 2));
+    assert!(buffer_pool_contains(&pool, &page_key, 3));
+    assert_eq!(buffer_pool_stats(&pool).evictions, 0);
+}
@@ -0,0 +1,44 @@
+use std::cmp::Ordering;
+
+use rstest::rstest;
+
+use rusticodb::storage::Data;
+use rusticodb::storage::bytes_parse_hex;
+use rusticodb::storage::bytes_format_hex;
+use rusticodb::storage::bytes_cast;
+use rusticodb::storage::bytes_compare;
+
+#[rstest]
+#[case("deadBEEF", Some(vec![0xde, 0xad, 0xbe, 0xef]))]
+#[case("01 02", Some(vec![0x01, 0x02]))]
+#[case("", Some(vec![]))]
+#[case("abc", None)]
+#[case("zz", None)]
+pub fn test_bytes_parse_hex(#[case] value: &str, #[case] expected: Option<Vec<u8>>) {
+    assert_eq!(bytes_parse_hex(value), expected);
+}
+
+#[rstest]
+#[case(vec![0x00, 0x0a, 0xff], "\\x000aff")]
+#[case(vec![], "\\x")]
+pub fn test_bytes_format_hex(#[case] value: Vec<u8>, #[case] expected: &str) {
+    assert_eq!(bytes_format_hex(&value), expected);
+}
+
+#[rstest]
+#[case(Data::Varchar(String::from("\\x0aff")), Some(vec![0x0a, 0xff]))]
+#[case(Data::Text(String::from("ab")), Some(vec![0x61, 0x62]))]
+#[case(Data::Varchar(String::from("\\xfg")), None)]
+#[case(Data::UnsignedBigint(1), None)]
+pub fn test_bytes_cast(#[case] value: Data, #[case] expected: Option<Vec<u8>>) {
+    assert_eq!(bytes_cast(&value), expected);
+}
+
+#[rstest]
+#[case(Data::Bytes(vec![1, 2]), Data::Bytes(vec![1, 3]), Some(Ordering::Less))]
+#[case(Data::Bytes(vec![1, 2]), Data::Bytes(vec![1]), Some(Ordering::Greater))]
+#[case(Data::Bytes(vec![0xff]), Data::Varchar(String::from("\\xff")), Some(Ordering::Equal))]
+#[case(Data::Varchar(String::from("a")), Data::Varchar(String::from("b")), None)]
+pub fn test_bytes_compare(#[case] a: Data, #[case] b: Data, #[case] expected: Option<Ordering>) {
+    assert_eq!(bytes_compare(&a, &b), expected);
+}
@@ -0,0 +1,82 @@
+use rstest::rstest;
+
+use rusticodb::storage::Data;
+use rusticodb::storage::decimal_parse;
+use rusticodb::storage::decimal_format;
+use rusticodb::storage::decimal_rescale;
+use rusticodb::storage::decimal_to_bytes;
+use rusticodb::storage::decimal_from_bytes;
+
+#[rstest]
+#[case("12.50", Some((1250, 2)))]
+#[case("-0.05", Some((-5, 2)))]
+#[case(".25", Some((25, 2)))]
+#[case("+7", Some((7, 0)))]
+#[case("1e5", None)]
+#[case("1.2.3", None)]
+#[case("-", None)]
+pub fn test_decimal_parse(#[case] value: &str, #[case] expected: Option<(i128, u8)>) {
+    assert_eq!(decimal_parse(value), expected);
+}
+
+#[rstest]
+#[case(1250, 2, "12.50")]
+#[case(-5, 2, "-0.05")]
+#[case(42, 0, "42")]
+pub fn test_decimal_format(#[case] value: i128, #[case] scale: u8, #[case] expected: &str) {
+    assert_eq!(decimal_format(value, scale), expected);
+}
+
+#[rstest]
+#[case(12345, 3, 2, Some(1235))]
+#[case(-12345, 3, 2, Some(-1235))]
+#[case(12344, 3, 2, Some(1234))]
+#[case(15, 1, 0, Some(2))]
+#[case(5, 0, 2, Some(500))]
+#[case(i128::MAX, 0, 1, None)]
+pub fn test_decimal_rescale_rounds_half_away_from_zero(
+    #[case] value: i128,
+    #[case] scale: u8,
+    #[case] new_scale: u8,
+    #[case] expected: Option<i128>
+) {
+    assert_eq!(decimal_rescale(value, scale, new_scale), expected);
+}
+
+#[rstest]
+#[case(0)]
+#[case(127)]
+#[case(128)]
+#[case(-128)]
+#[case(-129)]
+#[case(i128::MAX)]
+#[case(i128::MIN)]
+pub fn test_decimal_bytes_round_trip(#[case] value: i128) {
+    let bytes = decimal_to_bytes(value);
+
+    assert_eq!(bytes[0] as usize, bytes.len() - 1);
+    assert_eq!(decimal_from_bytes(&bytes, 0), value);
+}
+
+#[test]
+pub fn test_decimal_arithmetic_is_exact() {
+    let a = Data::Decimal(10, 38, 1);
+    let b = Data::Decimal(20, 38, 1);
+
+    assert_eq!(a.clone().checked_add(b.clone()).unwrap().to_string(), "3.0");
+    assert_eq!(a.clone().checked_add(b.clone()).unwrap(), Data::Decimal(3, 38, 0));
+    assert_eq!(Data::Decimal(1999, 10, 2).checked_sub(Data::UnsignedBigint(20)).unwrap().to_string(), "-0.01");
+    assert_eq!(Data::Decimal(1999, 10, 2).checked_mul(Data::Decimal(3, 10, 1)).unwrap().to_string(), "5.997");
+    assert_eq!(Data::Decimal(10, 10, 0).checked_div(Data::UnsignedBigint(3)).unwrap().to_string(), "3.333333");
+    assert_eq!(Data::Decimal(2, 10, 0).checked_div(Data::Decimal(3, 10, 0)).unwrap().to_string(), "0.666667");
+    assert!(matches!(Data::Decimal(10, 10, 0).checked_div(Data::Decimal(0, 10, 2)), Ok(Data::Null)));
+}
+
+#[test]
+pub fn test_decimal_compares_with_other_numbers() {
+    assert!(Data::Decimal(250, 10, 2) == Data::Decimal(25, 10, 1));
+    assert!(Data::Decimal(300, 10, 2) == Data::SignedInt(3));
+    assert!(Data::Decimal(-1, 10, 2) < Data::UnsignedTinyint(0));
+    assert!(Data::Decimal(5, 10, 1) > Data::Decimal(49, 10, 2));
+    assert!(Data::Decimal(5, 10, 1) == Data::Float64(ordered_float::OrderedFloat(0.5)));
+}
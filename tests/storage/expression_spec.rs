@@ -1,3 +1,5 @@
+use ordered_float::OrderedFloat;
+
 use rusticodb::machine::Column;
 use rusticodb::machine::ColumnType;
 
@@ -32,7 +34,7 @@ pub fn test_if_expression_equal_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -62,7 +64,7 @@ pub fn test_if_expression_equal_operator_with_string() {
     let mut tuple = tuple_new();
     tuple.push(Data::Varchar(String::from("value1")));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -92,7 +94,7 @@ pub fn test_if_expression_equal_operator_with_null() {
     let mut tuple = tuple_new();
     tuple.push(Data::Null);
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -122,7 +124,7 @@ pub fn test_if_expression_not_equal_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -152,7 +154,7 @@ pub fn test_if_expression_not_equal_operator_with_string() {
     let mut tuple = tuple_new();
     tuple.push(Data::Varchar(String::from("value1")));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -182,7 +184,7 @@ pub fn test_if_expression_not_equal_operator_with_null() {
     let mut tuple = tuple_new();
     tuple.push(Data::Null);
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -220,7 +222,7 @@ pub fn test_if_expression_and_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -258,7 +260,7 @@ pub fn test_if_expression_or_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -288,7 +290,7 @@ pub fn test_if_expression_greather_or_equal_operator_with_equal_value() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -318,7 +320,7 @@ pub fn test_if_expression_less_or_equal_operator_with_equal_value() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -348,7 +350,7 @@ pub fn test_if_expression_greather_or_equal_operator_with_diff_value() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(100u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -378,7 +380,7 @@ pub fn test_if_expression_less_or_equal_operator_with_diff_value() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(1u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -408,7 +410,7 @@ pub fn test_if_expression_greather_than_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(20u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -438,7 +440,7 @@ pub fn test_if_expression_less_than_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(20u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -468,7 +470,7 @@ pub fn test_if_expression_add_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(20u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -498,7 +500,7 @@ pub fn test_if_expression_add_operator_inverse() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(20u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -528,7 +530,7 @@ pub fn test_if_expression_sub_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(200u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -558,7 +560,7 @@ pub fn test_if_expression_sub_operator_inverse() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(100u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -588,7 +590,7 @@ pub fn test_if_expression_mul_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(200u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -619,7 +621,7 @@ pub fn test_if_expression_mul_operator_inverse() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(200u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -649,7 +651,7 @@ pub fn test_if_expression_div_operator() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(200u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -680,7 +682,7 @@ pub fn test_if_expression_div_operator_inverse() {
     let mut tuple = tuple_new();
     tuple.push(Data::UnsignedBigint(200u64));
     
-    let cell = expression.result(&tuple, &columns);
+    let cell = expression.result(&tuple, &columns).unwrap();
 
     assert!(matches!(cell, Data::UnsignedBigint(_)));
 }
@@ -692,7 +694,7 @@ pub fn test_if_expression_not_operator_inverse() {
         Box::new(Expression::Const(Data::UnsignedBigint(1u64))),
     );
 
-    let cell = expression.result(&tuple_new(), &Vec::new());
+    let cell = expression.result(&tuple_new(), &Vec::new()).unwrap();
 
     assert!(matches!(cell, Data::Boolean(_)));
 }
@@ -704,7 +706,79 @@ pub fn test_if_expression_negate_operator_inverse() {
         Box::new(Expression::Const(Data::UnsignedBigint(1u64))),
     );
 
-    let cell = expression.result(&tuple_new(), &Vec::new());
+    let cell = expression.result(&tuple_new(), &Vec::new()).unwrap();
 
     assert!(matches!(cell, Data::SignedBigint(_)));
 }
+
+#[test]
+pub fn test_if_expression_compares_integers_of_any_type() {
+    let expression = Expression::Func2(
+        Expression2Type::LessThan,
+        Box::new(Expression::Const(Data::SignedTinyint(-3))),
+        Box::new(Expression::Const(Data::UnsignedBigint(2u64)))
+    );
+    assert!(matches!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Boolean(true)));
+
+    let expression = Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::Const(Data::UnsignedBigint(300u64))),
+        Box::new(Expression::Const(Data::SignedInt(300)))
+    );
+    assert!(matches!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Boolean(true)));
+}
+
+#[test]
+pub fn test_if_expression_sub_operator_goes_negative() {
+    let expression = Expression::Func2(
+        Expression2Type::Sub,
+        Box::new(Expression::Const(Data::SignedInt(2))),
+        Box::new(Expression::Const(Data::UnsignedBigint(5u64)))
+    );
+
+    let cell = expression.result(&tuple_new(), &Vec::new()).unwrap();
+
+    assert_eq!(cell, Data::SignedBigint(-3));
+}
+
+#[test]
+pub fn test_if_expression_promotes_integers_to_float() {
+    let expression = Expression::Func2(
+        Expression2Type::Add,
+        Box::new(Expression::Const(Data::SignedInt(2))),
+        Box::new(Expression::Const(Data::Float64(OrderedFloat(0.5))))
+    );
+    assert_eq!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Float64(OrderedFloat(2.5)));
+
+    let expression = Expression::Func2(
+        Expression2Type::Mul,
+        Box::new(Expression::Const(Data::Float32(OrderedFloat(1.5)))),
+        Box::new(Expression::Const(Data::Float32(OrderedFloat(2.0))))
+    );
+    assert!(matches!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Float32(value) if value.0 == 3.0));
+
+    let expression = Expression::Func2(
+        Expression2Type::Equal,
+        Box::new(Expression::Const(Data::UnsignedBigint(3u64))),
+        Box::new(Expression::Const(Data::Float64(OrderedFloat(3.0))))
+    );
+    assert!(matches!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Boolean(true)));
+
+    let expression = Expression::Func2(
+        Expression2Type::GreatherThan,
+        Box::new(Expression::Const(Data::Float32(OrderedFloat(-0.5)))),
+        Box::new(Expression::Const(Data::SignedTinyint(-1)))
+    );
+    assert!(matches!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Boolean(true)));
+}
+
+#[test]
+pub fn test_if_expression_float_div_by_zero_is_null() {
+    let expression = Expression::Func2(
+        Expression2Type::Div,
+        Box::new(Expression::Const(Data::Float64(OrderedFloat(1.0)))),
+        Box::new(Expression::Const(Data::UnsignedBigint(0u64)))
+    );
+
+    assert!(matches!(expression.result(&tuple_new(), &Vec::new()).unwrap(), Data::Null));
+}
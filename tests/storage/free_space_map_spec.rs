@@ -0,0 +1,50 @@
+use rusticodb::storage::fsm_new;
+use rusticodb::storage::fsm_set;
+use rusticodb::storage::fsm_get;
+use rusticodb::storage::fsm_find;
+use rusticodb::storage::fsm_category;
+use rusticodb::storage::fsm_serialize;
+use rusticodb::storage::fsm_deserialize;
+use rusticodb::storage::BLOCK_SIZE;
+
+#[test]
+pub fn test_category_is_rounded_down() {
+    assert_eq!(fsm_category(0), 0);
+    assert_eq!(fsm_category(15), 0);
+    assert_eq!(fsm_category(16), 1);
+    assert_eq!(fsm_category(BLOCK_SIZE), 255);
+}
+
+#[test]
+pub fn test_set_grows_the_map() {
+    let mut fsm = fsm_new();
+
+    fsm_set(&mut fsm, 3, 100);
+
+    assert_eq!(fsm.pages, vec![0, 0, 6]);
+    assert_eq!(fsm_get(&fsm, 3), 96);
+    assert_eq!(fsm_get(&fsm, 10), 0);
+}
+
+#[test]
+pub fn test_find_first_page_with_room() {
+    let mut fsm = fsm_new();
+
+    fsm_set(&mut fsm, 1, 10);
+    fsm_set(&mut fsm, 2, 200);
+    fsm_set(&mut fsm, 3, 4000);
+
+    assert_eq!(fsm_find(&fsm, 100), Some(2));
+    assert_eq!(fsm_find(&fsm, 1000), Some(3));
+    assert_eq!(fsm_find(&fsm, 4090), None);
+}
+
+#[test]
+pub fn test_serialize_round_trip() {
+    let mut fsm = fsm_new();
+
+    fsm_set(&mut fsm, 1, 1024);
+    fsm_set(&mut fsm, 2, 32);
+
+    assert_eq!(fsm_deserialize(&fsm_serialize(&fsm)), fsm);
+}
@@ -0,0 +1,154 @@
+use rusticodb::storage::HashBucket;
+use rusticodb::storage::TupleAddress;
+use rusticodb::storage::Pager;
+use rusticodb::storage::BLOCK_SIZE;
+use rusticodb::storage::pager_flush_blocks;
+use rusticodb::storage::truncate_file;
+use rusticodb::storage::write_data;
+use rusticodb::storage::Data;
+use rusticodb::storage::format_index_name;
+use rusticodb::storage::format_database_name;
+use rusticodb::storage::create_folder;
+use rusticodb::storage::hash_bucket_serialize;
+use rusticodb::storage::hash_bucket_deserialize;
+use rusticodb::storage::hash_index_create;
+use rusticodb::storage::hash_index_insert;
+use rusticodb::storage::hash_index_delete;
+use rusticodb::storage::hash_index_search;
+use rusticodb::storage::hash_index_entries;
+use rusticodb::storage::hash_index_global_depth;
+
+use rusticodb::utils::ExecutionError;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn setup_index(pager: &mut Pager) -> String {
+    let database1 = String::from("database1");
+
+    create_tmp_test_folder();
+    create_folder(&format_database_name(&database1));
+
+    let index_key = format_index_name(&database1, &String::from("table1"), &String::from("index1"));
+    hash_index_create(pager, &index_key);
+
+    return index_key;
+}
+
+#[test]
+pub fn test_bucket_serialize_and_deserialize() {
+    let bucket = HashBucket {
+        local_depth: 3,
+        next: 12,
+        entries: vec![
+            (vec![Data::UnsignedBigint(10)], TupleAddress::new(1, 2)),
+            (vec![Data::Varchar(String::from("key")), Data::Null], TupleAddress::new(3, 4))
+        ]
+    };
+
+    assert_eq!(hash_bucket_deserialize(&hash_bucket_serialize(&bucket)), bucket);
+}
+
+#[test]
+pub fn test_search_on_empty_index() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    assert_eq!(hash_index_search(&pager, &index_key, &vec![Data::UnsignedBigint(1)]).unwrap(), vec![]);
+    assert_eq!(hash_index_entries(&pager, &index_key).unwrap().len(), 0);
+    assert_eq!(hash_index_global_depth(&pager, &index_key).unwrap(), 0);
+}
+
+#[test]
+pub fn test_insert_splits_buckets() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..5000u64 {
+        hash_index_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    assert!(hash_index_global_depth(&pager, &index_key).unwrap() > 1);
+    assert_eq!(hash_index_entries(&pager, &index_key).unwrap().len(), 5000);
+
+    for idx in [0u64, 1, 777, 2500, 4999] {
+        assert_eq!(hash_index_search(&pager, &index_key, &vec![Data::UnsignedBigint(idx)]).unwrap(), vec![TupleAddress::new(idx, 0)]);
+    }
+    assert_eq!(hash_index_search(&pager, &index_key, &vec![Data::UnsignedBigint(5000)]).unwrap(), vec![]);
+}
+
+#[test]
+pub fn test_duplicated_keys_chain_on_one_bucket() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..1000u64 {
+        hash_index_insert(&mut pager, &index_key, &vec![Data::Varchar(String::from("same key"))], &TupleAddress::new(idx, 1)).unwrap();
+    }
+
+    assert_eq!(hash_index_global_depth(&pager, &index_key).unwrap(), 0);
+    assert_eq!(hash_index_search(&pager, &index_key, &vec![Data::Varchar(String::from("same key"))]).unwrap().len(), 1000);
+}
+
+#[test]
+pub fn test_delete_removes_only_the_given_address() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..1000u64 {
+        hash_index_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx % 10)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    assert!(hash_index_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(3)], &TupleAddress::new(13, 0)).unwrap());
+    assert!(!hash_index_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(3)], &TupleAddress::new(13, 0)).unwrap());
+    assert!(!hash_index_delete(&mut pager, &index_key, &vec![Data::UnsignedBigint(4)], &TupleAddress::new(13, 0)).unwrap());
+
+    let addresses = hash_index_search(&pager, &index_key, &vec![Data::UnsignedBigint(3)]).unwrap();
+    assert_eq!(addresses.len(), 99);
+    assert!(!addresses.contains(&TupleAddress::new(13, 0)));
+    assert_eq!(hash_index_entries(&pager, &index_key).unwrap().len(), 999);
+}
+
+#[test]
+pub fn test_integer_keys_hash_the_same_whatever_the_type() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    hash_index_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(42)], &TupleAddress::new(1, 0)).unwrap();
+    hash_index_insert(&mut pager, &index_key, &vec![Data::SignedInt(42)], &TupleAddress::new(2, 0)).unwrap();
+
+    assert_eq!(hash_index_search(&pager, &index_key, &vec![Data::SignedBigint(42)]).unwrap().len(), 2);
+}
+
+#[test]
+pub fn test_composite_keys_need_every_value() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..500u64 {
+        let key = vec![Data::UnsignedBigint(idx % 10), Data::Varchar(format!("name {}", idx % 7))];
+        hash_index_insert(&mut pager, &index_key, &key, &TupleAddress::new(idx, 0)).unwrap();
+    }
+
+    let key = vec![Data::UnsignedBigint(3), Data::Varchar(String::from("name 3"))];
+    assert_eq!(hash_index_search(&pager, &index_key, &key).unwrap().len(), 8);
+    assert_eq!(hash_index_search(&pager, &index_key, &vec![Data::UnsignedBigint(3)]).unwrap().len(), 0);
+}
+
+#[test]
+pub fn test_truncated_or_broken_file_is_an_error() {
+    let mut pager = Pager::new();
+    let index_key = setup_index(&mut pager);
+
+    for idx in 0..1000u64 {
+        hash_index_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx)], &TupleAddress::new(idx, 0)).unwrap();
+    }
+    pager_flush_blocks(&mut pager);
+    assert_eq!(hash_index_entries(&Pager::new(), &index_key).unwrap().len(), 1000);
+
+    truncate_file(&index_key, BLOCK_SIZE as u64 + 10);
+    let key = vec![Data::UnsignedBigint(1)];
+    assert!(matches!(hash_index_search(&Pager::new(), &index_key, &key), Err(ExecutionError::IoError(_, _))));
+
+    write_data(&index_key, 0, &[0xFFu8; BLOCK_SIZE]);
+    assert!(matches!(hash_index_search(&Pager::new(), &index_key, &key), Err(ExecutionError::IndexCorrupted(_))));
+}
@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+
+use rstest::rstest;
+
+use rusticodb::storage::Data;
+use rusticodb::storage::JsonValue;
+use rusticodb::storage::json_parse;
+use rusticodb::storage::json_normalize;
+use rusticodb::storage::json_format;
+use rusticodb::storage::json_to_bytes;
+use rusticodb::storage::json_from_bytes;
+use rusticodb::storage::json_get;
+use rusticodb::storage::json_get_text;
+use rusticodb::storage::json_get_path;
+use rusticodb::storage::json_contains;
+use rusticodb::storage::json_array_length;
+use rusticodb::storage::json_compare;
+
+fn jsonb(text: &str) -> Data {
+    return Data::Jsonb(json_normalize(json_parse(text).unwrap()));
+}
+
+#[rstest]
+#[case("null", Some(JsonValue::Null))]
+#[case(" true ", Some(JsonValue::Boolean(true)))]
+#[case("-1.5e3", Some(JsonValue::Number(String::from("-1.5e3"))))]
+#[case("\"a\\n\\u00e9\\ud83d\\ude00\"", Some(JsonValue::String(String::from("a\né😀"))))]
+#[case("[1, [], {}]", Some(JsonValue::Array(vec![
+    JsonValue::Number(String::from("1")),
+    JsonValue::Array(vec![]),
+    JsonValue::Object(vec![])
+])))]
+#[case("{\"a\": 1,}", None)]
+#[case("[1 2]", None)]
+#[case("01", None)]
+#[case("\"tab\tinside\"", None)]
+#[case("{\"a\": 1} x", None)]
+pub fn test_json_parse(#[case] text: &str, #[case] expected: Option<JsonValue>) {
+    assert_eq!(json_parse(text), expected);
+}
+
+#[test]
+pub fn test_json_normalize_sorts_keys_and_keeps_the_last_duplicate() {
+    let json = json_normalize(json_parse("{\"bb\": 1, \"a\": {\"d\": 2, \"c\": 3}, \"bb\": 4}").unwrap());
+    assert_eq!(json_format(&json), "{\"a\": {\"c\": 3, \"d\": 2}, \"bb\": 4}");
+}
+
+#[test]
+pub fn test_json_bytes_round_trip() {
+    let json = json_parse("{\"a\": [1, \"two\", null, true, false], \"b\": {\"c\": 2.5}}").unwrap();
+    let bytes = json_to_bytes(&json);
+    let mut position = 0;
+
+    assert_eq!(json_from_bytes(&bytes, &mut position), json);
+    assert_eq!(position, bytes.len());
+}
+
+#[rstest]
+#[case(jsonb("{\"a\": {\"b\": 1}}"), Data::Varchar(String::from("a")), jsonb("{\"b\": 1}"))]
+#[case(jsonb("[1, 2, 3]"), Data::SignedBigint(-1), jsonb("3"))]
+#[case(jsonb("[1, 2, 3]"), Data::UnsignedBigint(5), Data::Null)]
+#[case(jsonb("{\"a\": 1}"), Data::Varchar(String::from("b")), Data::Null)]
+#[case(Data::Json(String::from("{\"a\":[1,2]}")), Data::Varchar(String::from("a")), Data::Json(String::from("[1, 2]")))]
+pub fn test_json_get(#[case] value: Data, #[case] step: Data, #[case] expected: Data) {
+    let result = json_get(&value, &step);
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+pub fn test_json_get_text_and_path() {
+    let value = jsonb("{\"a\": {\"b\": [\"x\", \"y\"]}, \"n\": null}");
+
+    assert_eq!(json_get_text(&value, &Data::Varchar(String::from("n"))), Data::Null);
+    assert_eq!(
+        json_get_path(&value, &Data::Varchar(String::from("{a,b,1}"))),
+        jsonb("\"y\"")
+    );
+    assert_eq!(json_get_path(&value, &Data::Varchar(String::from("{a,c}"))), Data::Null);
+}
+
+#[rstest]
+#[case("{\"a\": 1, \"b\": [1, 2]}", "{\"b\": [2]}", true)]
+#[case("{\"a\": 1, \"b\": [1, 2]}", "{\"a\": 2}", false)]
+#[case("[1, 2, [3]]", "[[3], 1]", true)]
+#[case("[\"a\", \"b\"]", "\"a\"", true)]
+#[case("{\"a\": {\"b\": 1, \"c\": 2}}", "{\"a\": {\"c\": 2}}", true)]
+pub fn test_json_contains(#[case] a: &str, #[case] b: &str, #[case] expected: bool) {
+    assert_eq!(json_contains(&jsonb(a), &jsonb(b)), Data::Boolean(expected));
+}
+
+#[test]
+pub fn test_json_array_length() {
+    assert_eq!(json_array_length(&jsonb("[1, [2, 3]]")), Data::UnsignedBigint(2));
+    assert_eq!(json_array_length(&jsonb("{}")), Data::Null);
+}
+
+#[rstest]
+#[case(jsonb("{\"a\": 1}"), Data::Varchar(String::from("{ \"a\" : 1.0 }")), Some(Ordering::Equal))]
+#[case(jsonb("[1]"), jsonb("{}"), Some(Ordering::Less))]
+#[case(jsonb("2"), jsonb("10"), Some(Ordering::Less))]
+#[case(jsonb("\"a\""), jsonb("1"), Some(Ordering::Less))]
+#[case(Data::Varchar(String::from("a")), Data::Varchar(String::from("a")), None)]
+pub fn test_json_compare(#[case] a: Data, #[case] b: Data, #[case] expected: Option<Ordering>) {
+    assert_eq!(json_compare(&a, &b), expected);
+}
@@ -1,10 +1,23 @@
 pub mod header_spec;
 
 pub mod pager_spec;
+pub mod buffer_pool_spec;
 pub mod page_spec;
+pub mod free_space_map_spec;
+pub mod btree_spec;
+pub mod hash_index_spec;
 pub mod tuple_spec;
+pub mod decimal_spec;
+pub mod temporal_spec;
+pub mod bytes_spec;
+pub mod uuid_spec;
+pub mod json_spec;
 pub mod expression_spec;
+pub mod aggregate_spec;
 
 pub mod result_set_spec;
+pub mod sort_spec;
 
 pub mod os_interface_spec;
+
+pub mod wal_spec;
@@ -3,6 +3,18 @@ use rusticodb::storage::page_new;
 use rusticodb::storage::page_amount_left;
 use rusticodb::storage::page_insert_tuples;
 use rusticodb::storage::page_serialize;
+use rusticodb::storage::page_deserialize;
+use rusticodb::storage::page_read_tuples;
+use rusticodb::storage::page_read_slot;
+use rusticodb::storage::page_insert_slot;
+use rusticodb::storage::page_update_slot;
+use rusticodb::storage::page_delete_slot;
+use rusticodb::storage::Slot;
+use rusticodb::storage::TupleAddress;
+use rusticodb::storage::OverflowPointer;
+use rusticodb::storage::OverflowChunk;
+use rusticodb::storage::OVERFLOW_CHUNK_SIZE;
+use rusticodb::storage::page_new_overflow;
 use rusticodb::storage::Data;
 use rusticodb::storage::BLOCK_SIZE;
 use rusticodb::storage::tuple_new;
@@ -199,3 +211,106 @@ pub fn test_insert_two_tuples_with_string() {
     assert_eq!(page_serialize(&page), raw_buffer);
 }
 
+
+#[test]
+pub fn test_deleted_slot_keeps_its_number() {
+    let mut tuples: Vec<Tuple> = Vec::new();
+    for value in 1..4 {
+        let mut tuple = tuple_new();
+        tuple.push(Data::UnsignedTinyint(value));
+        tuples.push(tuple);
+    }
+
+    let mut page = page_new();
+    page_insert_tuples(&mut page, &mut tuples);
+    page_delete_slot(&mut page, 1);
+
+    let raw_buffer = page_serialize(&page);
+    assert_eq!(raw_buffer[1], 3);
+    assert_eq!(raw_buffer[4] & 0xC0, 0x40);
+
+    let page = page_deserialize(raw_buffer);
+    assert_eq!(page_read_slot(&page, 1), Some(&Slot::Tombstone));
+    assert_eq!(page_read_tuples(&page), vec![tuples[0].clone(), tuples[2].clone()]);
+}
+
+#[test]
+pub fn test_insert_does_not_reuse_tombstones() {
+    let mut tuples: Vec<Tuple> = Vec::new();
+    for value in 1..4 {
+        let mut tuple = tuple_new();
+        tuple.push(Data::UnsignedTinyint(value));
+        tuples.push(tuple);
+    }
+
+    let mut page = page_new();
+    page_insert_tuples(&mut page, &mut tuples);
+    page_delete_slot(&mut page, 1);
+
+    assert_eq!(page_insert_slot(&mut page, Slot::Tuple(tuples[1].clone())), 3);
+    assert_eq!(page_read_slot(&page, 1), Some(&Slot::Tombstone));
+}
+
+#[test]
+pub fn test_forward_and_moved_slots_survive_serialization() {
+    let mut tuple = tuple_new();
+    tuple.push(Data::Varchar(String::from("moved row")));
+
+    let mut page = page_new();
+    page_insert_slot(&mut page, Slot::Forward(TupleAddress::new(7, 300)));
+    page_insert_slot(&mut page, Slot::Moved(tuple.clone()));
+
+    let page = page_deserialize(page_serialize(&page));
+
+    assert_eq!(page_read_slot(&page, 0), Some(&Slot::Forward(TupleAddress::new(7, 300))));
+    assert_eq!(page_read_slot(&page, 1), Some(&Slot::Moved(tuple)));
+    assert_eq!(page_read_tuples(&page).len(), 0);
+}
+
+#[test]
+pub fn test_update_slot_that_does_not_fit() {
+    let mut small = tuple_new();
+    small.push(Data::UnsignedTinyint(1));
+
+    let mut big = tuple_new();
+    big.push(Data::Text("a".repeat(BLOCK_SIZE)));
+
+    let mut page = page_new();
+    page_insert_tuples(&mut page, &mut vec![small.clone()]);
+
+    assert!(!page_update_slot(&mut page, 0, Slot::Tuple(big)));
+    assert_eq!(page_read_slot(&page, 0), Some(&Slot::Tuple(small)));
+
+    let mut bigger = tuple_new();
+    bigger.push(Data::Text("a".repeat(100)));
+
+    assert!(page_update_slot(&mut page, 0, Slot::Tuple(bigger.clone())));
+    assert_eq!(page_read_tuples(&page), vec![bigger]);
+}
+
+#[test]
+pub fn test_overflow_page_survives_serialization() {
+    let data: Vec<u8> = (0..OVERFLOW_CHUNK_SIZE).map(|idx| (idx % 251) as u8).collect();
+    let page = page_new_overflow(42, data.clone());
+
+    let raw_buffer = page_serialize(&page);
+    assert_eq!(raw_buffer[0], 0xFF);
+    assert_eq!(raw_buffer[1], 0xFF);
+
+    let page = page_deserialize(raw_buffer);
+    assert_eq!(page.overflow, Some(OverflowChunk { next_page: 42, data }));
+    assert_eq!(page.slots.len(), 0);
+    assert_eq!(page_amount_left(&page), 0);
+}
+
+#[test]
+pub fn test_overflow_slots_survive_serialization() {
+    let mut page = page_new();
+    page_insert_slot(&mut page, Slot::Overflow(OverflowPointer::new(300000, 5)));
+    page_insert_slot(&mut page, Slot::MovedOverflow(OverflowPointer::new(9000, 80)));
+
+    let page = page_deserialize(page_serialize(&page));
+
+    assert_eq!(page_read_slot(&page, 0), Some(&Slot::Overflow(OverflowPointer::new(300000, 5))));
+    assert_eq!(page_read_slot(&page, 1), Some(&Slot::MovedOverflow(OverflowPointer::new(9000, 80))));
+}
@@ -4,10 +4,19 @@ use rusticodb::storage::Pager;
 use rusticodb::storage::tuple_new;
 use rusticodb::storage::pager_insert_tuples;
 use rusticodb::storage::pager_read_tuples;
+use rusticodb::storage::pager_read_tuples_with_address;
+use rusticodb::storage::pager_update_tuple;
+use rusticodb::storage::pager_delete_tuple;
+use rusticodb::storage::Tuple;
+use rusticodb::storage::read_file;
+use rusticodb::storage::destroy_file;
+use rusticodb::storage::format_fsm_name;
+use rusticodb::storage::fsm_deserialize;
 use rusticodb::storage::pager_flush_page;
 use rusticodb::storage::create_file;
 use rusticodb::storage::create_folder;
 use rusticodb::storage::Data;
+use rusticodb::storage::buffer_pool_contains;
 
 use crate::test_utils::create_tmp_test_folder;
 
@@ -29,8 +38,7 @@ pub fn test_write_data_100_tuples() {
     }
 
     assert!(matches!(pager.headers.get(&page_key), Some(_hash_page)));
-    assert!(matches!(pager.pages.get(&page_key), Some(_hash_page)));
-    assert!(matches!(pager.pages.get(&page_key).unwrap().get(&1), Some(_page)));
+    assert!(buffer_pool_contains(&pager.pool, &page_key, 1));
 }
 
 #[test]
@@ -58,9 +66,8 @@ pub fn test_read_data_metadata_file() {
 
     assert_eq!(tuples.len(), 100);
     assert!(matches!(pager.headers.get(&page_key), Some(_hash_page)));
-    assert!(matches!(pager.pages.get(&page_key), Some(_hash_page)));
-    assert!(matches!(pager.pages.get(&page_key).unwrap().get(&1), Some(_page)));
-    assert!(matches!(pager.pages.get(&page_key).unwrap().get(&2), Some(_page)));
+    assert!(buffer_pool_contains(&pager.pool, &page_key, 1));
+    assert!(buffer_pool_contains(&pager.pool, &page_key, 2));
 }
 
 #[test]
@@ -96,7 +103,237 @@ pub fn test_read_data_from_new_pager() {
 
     assert_eq!(tuples.len(), 100);
     assert!(matches!(pager.headers.get(&page_key), Some(_hash_page)));
-    assert!(matches!(pager.pages.get(&page_key), Some(_hash_page)));
-    assert!(matches!(pager.pages.get(&page_key).unwrap().get(&1), Some(_page)));
-    assert!(matches!(pager.pages.get(&page_key).unwrap().get(&2), Some(_page)));
+    assert!(buffer_pool_contains(&pager.pool, &page_key, 1));
+    assert!(buffer_pool_contains(&pager.pool, &page_key, 2));
+}
+
+fn setup_table() -> String {
+    let database1 = String::from("database1");
+    let table1 = String::from("table1");
+    let page_key = format_table_name(&database1, &table1);
+
+    create_tmp_test_folder();
+
+    create_folder(&format_database_name(&database1));
+    create_file(&page_key);
+
+    return page_key;
+}
+
+fn row(id: u64, name: &str) -> Tuple {
+    let mut tuple = tuple_new();
+    tuple.push(Data::UnsignedBigint(id));
+    tuple.push(Data::Varchar(String::from(name)));
+    return tuple;
+}
+
+#[test]
+pub fn test_update_tuple_in_place_keeps_address() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut vec![row(1, "one"), row(2, "two")]);
+
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[0], row(1, "uno")));
+    pager_flush_page(&mut pager, &page_key);
+
+    let mut pager_new = Pager::new();
+    let tuples = pager_read_tuples_with_address(&mut pager_new, &page_key);
+
+    assert_eq!(tuples, vec![(addresses[0], row(1, "uno")), (addresses[1], row(2, "two"))]);
+    assert_eq!(pager_new.headers.get(&page_key).unwrap().page_count, 1);
+}
+
+#[test]
+pub fn test_growing_tuple_is_forwarded_and_keeps_address() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let mut tuples: Vec<Tuple> = (0..100).map(|idx| row(idx, "small row")).collect();
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+    assert_eq!(addresses[99].page, 1);
+
+    let grown = row(10, &"x".repeat(3000));
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[10], grown.clone()));
+
+    let grown_again = row(10, &"y".repeat(3500));
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[10], grown_again.clone()));
+    pager_flush_page(&mut pager, &page_key);
+
+    let mut pager_new = Pager::new();
+    let tuples = pager_read_tuples_with_address(&mut pager_new, &page_key);
+
+    assert_eq!(tuples.len(), 100);
+    assert_eq!(tuples[10], (addresses[10], grown_again));
+    assert_eq!(tuples[11], (addresses[11], row(11, "small row")));
+}
+
+#[test]
+pub fn test_delete_tuple_leaves_other_addresses_untouched() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let mut tuples: Vec<Tuple> = (0..500).map(|idx| row(idx, "row to delete")).collect();
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+
+    for address in addresses.iter().step_by(2) {
+        assert!(pager_delete_tuple(&mut pager, &page_key, address));
+    }
+    assert!(!pager_delete_tuple(&mut pager, &page_key, &addresses[0]));
+    pager_flush_page(&mut pager, &page_key);
+
+    let mut pager_new = Pager::new();
+    let tuples = pager_read_tuples_with_address(&mut pager_new, &page_key);
+
+    assert_eq!(tuples.len(), 250);
+    assert_eq!(tuples[0], (addresses[1], row(1, "row to delete")));
+    assert_eq!(tuples[249], (addresses[499], row(499, "row to delete")));
+}
+
+#[test]
+pub fn test_delete_forwarded_tuple() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let mut tuples: Vec<Tuple> = (0..100).map(|idx| row(idx, "small row")).collect();
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[5], row(5, &"x".repeat(3000))));
+    assert!(pager_delete_tuple(&mut pager, &page_key, &addresses[5]));
+    assert!(!pager_update_tuple(&mut pager, &page_key, &addresses[5], row(5, "again")));
+
+    let tuples = pager_read_tuples(&mut pager, &page_key);
+
+    assert_eq!(tuples.len(), 99);
+    assert!(tuples.iter().all(|tuple| tuple[0] != Data::UnsignedBigint(5)));
+}
+
+#[test]
+pub fn test_inserts_reuse_space_freed_by_deletes() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    for round in 0..10 {
+        let mut tuples: Vec<Tuple> = (0..500).map(|idx| row(idx, "churned row")).collect();
+        let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+        pager_flush_page(&mut pager, &page_key);
+
+        if round != 9 {
+            for address in addresses.iter() {
+                pager_delete_tuple(&mut pager, &page_key, address);
+            }
+            pager_flush_page(&mut pager, &page_key);
+        }
+    }
+
+    let mut pager_new = Pager::new();
+    let tuples = pager_read_tuples(&mut pager_new, &page_key);
+
+    assert_eq!(tuples.len(), 500);
+    assert_eq!(pager_new.headers.get(&page_key).unwrap().page_count, 6);
+}
+
+#[test]
+pub fn test_free_space_map_is_persisted() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let mut tuples: Vec<Tuple> = (0..500).map(|idx| row(idx, "persisted row")).collect();
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+    pager_delete_tuple(&mut pager, &page_key, &addresses[3]);
+    pager_flush_page(&mut pager, &page_key);
+
+    let fsm = fsm_deserialize(&read_file(&format_fsm_name(&page_key)).unwrap());
+    assert_eq!(&fsm, pager.free_space.get(&page_key).unwrap());
+
+    let mut pager_new = Pager::new();
+    let new_addresses = pager_insert_tuples(&mut pager_new, &page_key, &mut vec![row(500, "persisted row")]);
+
+    assert_eq!(new_addresses[0].page, addresses[3].page);
+    assert!(new_addresses[0].slot > addresses[3].slot);
+}
+
+#[test]
+pub fn test_free_space_map_is_rebuilt_when_missing() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let mut tuples: Vec<Tuple> = (0..500).map(|idx| row(idx, "rebuilt row")).collect();
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+    pager_delete_tuple(&mut pager, &page_key, &addresses[7]);
+    pager_flush_page(&mut pager, &page_key);
+
+    destroy_file(&format_fsm_name(&page_key));
+
+    let mut pager_new = Pager::new();
+    let new_addresses = pager_insert_tuples(&mut pager_new, &page_key, &mut vec![row(500, "rebuilt row")]);
+
+    assert_eq!(new_addresses[0].page, addresses[7].page);
+    assert!(new_addresses[0].slot > addresses[7].slot);
+}
+
+fn large_row(id: u64, size: usize) -> Tuple {
+    let mut tuple = tuple_new();
+    tuple.push(Data::UnsignedBigint(id));
+    tuple.push(Data::Text((0..size).map(|idx| (b'a' + (idx % 26) as u8) as char).collect()));
+    return tuple;
+}
+
+#[test]
+pub fn test_large_tuple_is_stored_on_overflow_pages() {
+    let page_key = setup_table();
+    let mut pager = Pager::new_with_capacity(8);
+
+    let addresses = pager_insert_tuples(
+        &mut pager,
+        &page_key,
+        &mut vec![row(1, "before"), large_row(2, 300 * 1024), row(3, "after")]
+    );
+    pager_flush_page(&mut pager, &page_key);
+
+    assert_eq!(addresses[0].page, addresses[2].page);
+
+    let mut pager_new = Pager::new_with_capacity(8);
+    let tuples = pager_read_tuples(&mut pager_new, &page_key);
+
+    assert_eq!(tuples, vec![row(1, "before"), large_row(2, 300 * 1024), row(3, "after")]);
+    assert!(pager_new.headers.get(&page_key).unwrap().page_count > 75);
+}
+
+#[test]
+pub fn test_overflow_pages_are_reused_after_update_and_delete() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut vec![large_row(1, 100 * 1024)]);
+    let page_count = pager.headers.get(&page_key).unwrap().page_count;
+
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[0], large_row(1, 50 * 1024)));
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[0], row(1, "small again")));
+
+    let mut tuples: Vec<Tuple> = (0..1000).map(|idx| row(idx, "fills freed pages")).collect();
+    pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+
+    assert!(pager.headers.get(&page_key).unwrap().page_count <= page_count + 13);
+
+    assert!(pager_delete_tuple(&mut pager, &page_key, &addresses[0]));
+    assert_eq!(pager_read_tuples(&mut pager, &page_key).len(), 1000);
+}
+
+#[test]
+pub fn test_small_tuple_grows_into_overflow_pages() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    let mut tuples: Vec<Tuple> = (0..100).map(|idx| row(idx, "small row")).collect();
+    let addresses = pager_insert_tuples(&mut pager, &page_key, &mut tuples);
+
+    assert!(pager_update_tuple(&mut pager, &page_key, &addresses[50], large_row(50, 20000)));
+    pager_flush_page(&mut pager, &page_key);
+
+    let mut pager_new = Pager::new();
+    let tuples = pager_read_tuples_with_address(&mut pager_new, &page_key);
+
+    assert_eq!(tuples.len(), 100);
+    assert_eq!(tuples[50], (addresses[50], large_row(50, 20000)));
 }
@@ -7,6 +7,9 @@ use rusticodb::storage::Expression2Type;
 use rusticodb::storage::Tuple;
 use rusticodb::storage::tuple_new;
 use rusticodb::storage::ResultSet;
+use rusticodb::storage::SortKey;
+use rusticodb::storage::Aggregate;
+use rusticodb::storage::AggregateType;
 
 use rusticodb::utils::ExecutionError;
 
@@ -582,6 +585,245 @@ pub fn test_projection_in_two_columns() {
     // assert!(matches!(new_set_result, Ok(_new_set)));
 }
 
+#[test]
+pub fn test_extended_projection_computing_columns() {
+    let columns: Vec<Column> = vec![
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("products"),
+            String::from("price"),
+            ColumnType::UnsignedBigint(0),
+            false,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("products"),
+            String::from("quantity"),
+            ColumnType::UnsignedBigint(0),
+            false,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+
+    let tuples: Vec<Tuple> = vec![
+        vec![Data::UnsignedBigint(10), Data::UnsignedBigint(3)],
+        vec![Data::UnsignedBigint(7), Data::UnsignedBigint(2)]
+    ];
+
+    let projection_columns: Vec<Column> = vec![
+        Column::new(
+            0u64,
+            String::from(""),
+            String::from(""),
+            String::from("total"),
+            ColumnType::Undefined,
+            false,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("products"),
+            String::from("quantity"),
+            ColumnType::UnsignedBigint(0),
+            false,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+    let expressions: Vec<Expression> = vec![
+        Expression::Func2(
+            Expression2Type::Mul,
+            Box::new(Expression::ColName(String::from("price"))),
+            Box::new(Expression::ColName(String::from("products.quantity")))
+        ),
+        Expression::ColName(String::from("quantity"))
+    ];
+
+    let result_set = ResultSet::new_select(columns, tuples);
+    let new_set = result_set.extended_projection(projection_columns, &expressions).unwrap();
+
+    assert_eq!(new_set.line_count(), 2);
+    assert_eq!(new_set.column_count(), 2);
+    assert_eq!(new_set.get_value(0, &String::from("total")).unwrap(), Data::UnsignedBigint(30));
+    assert_eq!(new_set.get_value(1, &String::from("total")).unwrap(), Data::UnsignedBigint(14));
+    assert_eq!(new_set.get_value(1, &String::from("quantity")).unwrap(), Data::UnsignedBigint(2));
+}
+
+#[test]
+pub fn test_sort_by_two_keys_of_result_set() {
+    let columns: Vec<Column> = vec![
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("people"),
+            String::from("name"),
+            ColumnType::Varchar("".to_string()),
+            false,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("people"),
+            String::from("age"),
+            ColumnType::UnsignedBigint(0),
+            false,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+
+    let tuples: Vec<Tuple> = vec![
+        vec![Data::Varchar(String::from("carla")), Data::UnsignedBigint(30)],
+        vec![Data::Varchar(String::from("bruno")), Data::Null],
+        vec![Data::Varchar(String::from("ana")), Data::UnsignedBigint(30)],
+        vec![Data::Varchar(String::from("davi")), Data::UnsignedBigint(25)]
+    ];
+
+    let keys: Vec<SortKey> = vec![
+        SortKey::new(Expression::ColName(String::from("age")), true, Some(false)),
+        SortKey::new(Expression::ColName(String::from("people.name")), false, None)
+    ];
+
+    let result_set = ResultSet::new_select(columns, tuples);
+    let new_set = result_set.sort(&keys, 1024).unwrap();
+
+    assert_eq!(new_set.line_count(), 4);
+    assert_eq!(new_set.column_count(), 2);
+    assert_eq!(new_set.get_value(0, &String::from("name")).unwrap(), Data::Varchar(String::from("ana")));
+    assert_eq!(new_set.get_value(1, &String::from("name")).unwrap(), Data::Varchar(String::from("carla")));
+    assert_eq!(new_set.get_value(2, &String::from("name")).unwrap(), Data::Varchar(String::from("davi")));
+    assert_eq!(new_set.get_value(3, &String::from("name")).unwrap(), Data::Varchar(String::from("bruno")));
+}
+
+#[test]
+pub fn test_aggregation_by_one_column_of_result_set() {
+    let columns: Vec<Column> = vec![
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("people"),
+            String::from("team"),
+            ColumnType::Varchar("".to_string()),
+            false,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("people"),
+            String::from("age"),
+            ColumnType::UnsignedBigint(0),
+            false,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+
+    let tuples: Vec<Tuple> = vec![
+        vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(30)],
+        vec![Data::Null, Data::UnsignedBigint(40)],
+        vec![Data::Varchar(String::from("blue")), Data::Null],
+        vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(25)],
+        vec![Data::Null, Data::UnsignedBigint(10)]
+    ];
+
+    let count = Aggregate {
+        aggregate_type: AggregateType::Count,
+        distinct: false,
+        arguments: vec![Expression::ColName(String::from("age"))],
+        name: String::from("count(age)")
+    };
+    let sum = Aggregate {
+        aggregate_type: AggregateType::Sum,
+        distinct: false,
+        arguments: vec![Expression::ColName(String::from("age"))],
+        name: String::from("sum(age)")
+    };
+    let group_by = vec![Expression::ColName(String::from("team"))];
+
+    let result_set = ResultSet::new_select(columns.clone(), tuples.clone());
+    let new_set = result_set.aggregation(&group_by, &vec![&count, &sum]).unwrap();
+
+    assert_eq!(new_set.line_count(), 3);
+    assert_eq!(new_set.column_count(), 4);
+    assert_eq!(new_set.tuples[0], vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(30), Data::UnsignedBigint(2), Data::UnsignedBigint(55)]);
+    assert_eq!(new_set.tuples[1], vec![Data::Null, Data::UnsignedBigint(40), Data::UnsignedBigint(2), Data::UnsignedBigint(50)]);
+    assert_eq!(new_set.tuples[2], vec![Data::Varchar(String::from("blue")), Data::Null, Data::UnsignedBigint(0), Data::Null]);
+
+    let empty_set = ResultSet::new_select(columns, Vec::new());
+    let new_set = empty_set.aggregation(&Vec::new(), &vec![&count, &sum]).unwrap();
+    assert_eq!(new_set.line_count(), 1);
+    assert_eq!(new_set.get_value(0, &String::from("count(age)")).unwrap(), Data::UnsignedBigint(0));
+    assert_eq!(new_set.get_value(0, &String::from("sum(age)")).unwrap(), Data::Null);
+}
+
+#[test]
+pub fn test_distinct_and_distinct_on_of_result_set() {
+    let columns: Vec<Column> = vec![
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("people"),
+            String::from("team"),
+            ColumnType::Varchar("".to_string()),
+            false,
+            false,
+            false,
+            String::from("")
+        ),
+        Column::new(
+            0u64,
+            String::from("rusticodb"),
+            String::from("people"),
+            String::from("age"),
+            ColumnType::UnsignedBigint(0),
+            false,
+            false,
+            false,
+            String::from("")
+        )
+    ];
+
+    let tuples: Vec<Tuple> = vec![
+        vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(30)],
+        vec![Data::Null, Data::Null],
+        vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(30)],
+        vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(25)],
+        vec![Data::Null, Data::Null]
+    ];
+
+    let result_set = ResultSet::new_select(columns, tuples);
+
+    let new_set = result_set.distinct();
+    assert_eq!(new_set.line_count(), 3);
+    assert_eq!(new_set.tuples[0], vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(30)]);
+    assert_eq!(new_set.tuples[1], vec![Data::Null, Data::Null]);
+    assert_eq!(new_set.tuples[2], vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(25)]);
+
+    let new_set = result_set.distinct_on(&vec![Expression::ColName(String::from("team"))]).unwrap();
+    assert_eq!(new_set.line_count(), 2);
+    assert_eq!(new_set.tuples[0], vec![Data::Varchar(String::from("red")), Data::UnsignedBigint(30)]);
+    assert_eq!(new_set.tuples[1], vec![Data::Null, Data::Null]);
+}
+
 #[test]
 pub fn test_cartesian_product_between_a_empty_and_full_result_sets() {
     let columns: Vec<Column> = Vec::new();
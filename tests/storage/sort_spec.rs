@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+use std::fs::read_dir;
+use std::fs::remove_file;
+use std::fs::create_dir;
+use std::fs::remove_dir;
+
+use rstest::rstest;
+
+use rusticodb::config::Config;
+use rusticodb::storage::Data;
+use rusticodb::storage::Expression;
+use rusticodb::storage::Tuple;
+use rusticodb::storage::SortKey;
+use rusticodb::storage::sort_compare;
+use rusticodb::storage::sort_tuples;
+use rusticodb::utils::ExecutionError;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn key(descending: bool, nulls_first: Option<bool>) -> SortKey {
+    SortKey::new(Expression::Empty, descending, nulls_first)
+}
+
+fn sort_files() -> usize {
+    return read_dir(Config::data_folder())
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().starts_with("rusticodb_sort_"))
+        .count();
+}
+
+#[rstest]
+#[case(false, None, Data::UnsignedBigint(1), Data::UnsignedBigint(2), Ordering::Less)]
+#[case(true, None, Data::UnsignedBigint(1), Data::UnsignedBigint(2), Ordering::Greater)]
+#[case(false, None, Data::Null, Data::UnsignedBigint(2), Ordering::Greater)]
+#[case(true, None, Data::Null, Data::UnsignedBigint(2), Ordering::Less)]
+#[case(false, Some(true), Data::Null, Data::UnsignedBigint(2), Ordering::Less)]
+#[case(true, Some(false), Data::Null, Data::UnsignedBigint(2), Ordering::Greater)]
+#[case(false, Some(true), Data::UnsignedBigint(2), Data::Null, Ordering::Greater)]
+#[case(true, None, Data::Null, Data::Null, Ordering::Equal)]
+#[case(false, None, Data::Varchar(String::from("b")), Data::Text(String::from("a")), Ordering::Greater)]
+pub fn test_sort_compare_one_key(
+    #[case] descending: bool,
+    #[case] nulls_first: Option<bool>,
+    #[case] a: Data,
+    #[case] b: Data,
+    #[case] expected: Ordering
+) {
+    let keys = vec![key(descending, nulls_first)];
+    assert_eq!(sort_compare(&keys, &vec![a], &vec![b]), expected);
+}
+
+#[test]
+pub fn test_sort_compare_goes_to_next_key_on_ties() {
+    let keys = vec![key(false, None), key(true, None)];
+
+    let a: Tuple = vec![Data::UnsignedBigint(1), Data::UnsignedBigint(5)];
+    let b: Tuple = vec![Data::UnsignedBigint(1), Data::UnsignedBigint(7)];
+    let c: Tuple = vec![Data::UnsignedBigint(0), Data::UnsignedBigint(1)];
+
+    assert_eq!(sort_compare(&keys, &a, &b), Ordering::Greater);
+    assert_eq!(sort_compare(&keys, &c, &a), Ordering::Less);
+    assert_eq!(sort_compare(&keys, &a, &a), Ordering::Equal);
+}
+
+#[test]
+pub fn test_sort_tuples_in_memory_is_stable() {
+    let keys = vec![key(false, None)];
+    let rows: Vec<(Tuple, Tuple)> = vec![
+        (vec![Data::UnsignedBigint(2)], vec![Data::Varchar(String::from("first two"))]),
+        (vec![Data::UnsignedBigint(1)], vec![Data::Varchar(String::from("one"))]),
+        (vec![Data::UnsignedBigint(2)], vec![Data::Varchar(String::from("second two"))]),
+        (vec![Data::Null], vec![Data::Varchar(String::from("null"))])
+    ];
+
+    let tuples = sort_tuples(&keys, rows.into_iter(), 1024 * 1024).unwrap();
+
+    assert_eq!(
+        tuples,
+        vec![
+            vec![Data::Varchar(String::from("one"))],
+            vec![Data::Varchar(String::from("first two"))],
+            vec![Data::Varchar(String::from("second two"))],
+            vec![Data::Varchar(String::from("null"))]
+        ]
+    );
+}
+
+#[test]
+pub fn test_sort_tuples_spilling_to_files() {
+    create_tmp_test_folder();
+
+    let keys = vec![key(true, None), key(false, None)];
+    let rows: Vec<(Tuple, Tuple)> = (0..500u64)
+        .map(|idx| {
+            let group = if idx % 50 == 0 { Data::Null } else { Data::UnsignedBigint(idx % 7) };
+            (
+                vec![group.clone(), Data::UnsignedBigint(idx)],
+                vec![group, Data::UnsignedBigint(idx), Data::Varchar(format!("row {}", idx))]
+            )
+        })
+        .collect();
+
+    let mut expected: Vec<(Tuple, Tuple)> = rows.clone();
+    expected.sort_by(|a, b| sort_compare(&keys, &a.0, &b.0));
+    let expected: Vec<Tuple> = expected.into_iter().map(|(_, tuple)| tuple).collect();
+
+    let tuples = sort_tuples(&keys, rows.into_iter(), 256).unwrap();
+
+    assert_eq!(tuples.len(), 500);
+    assert_eq!(tuples, expected);
+    assert_eq!(tuples[0][0], Data::Null);
+    assert_eq!(tuples[10][0], Data::UnsignedBigint(6));
+    assert_eq!(sort_files(), 0);
+}
+
+#[test]
+pub fn test_sort_tuples_with_a_missing_run_is_an_error() {
+    create_tmp_test_folder();
+
+    let keys = vec![key(false, None)];
+    let rows = (0..500u64).map(|idx| {
+        // Takes away the runs already spilled, as if the folder was cleaned.
+        if idx == 400 {
+            for entry in read_dir(Config::data_folder()).unwrap() {
+                let path = entry.unwrap().path();
+                if path.file_name().unwrap().to_string_lossy().starts_with("rusticodb_sort_") {
+                    remove_file(path).unwrap();
+                }
+            }
+        }
+        (vec![Data::UnsignedBigint(500 - idx)], vec![Data::UnsignedBigint(idx)])
+    });
+
+    let tuples = sort_tuples(&keys, rows, 256);
+
+    assert!(matches!(tuples, Err(ExecutionError::IoError(ref file, _)) if file.contains("rusticodb_sort_")));
+    assert_eq!(sort_files(), 0);
+}
+
+#[test]
+pub fn test_sort_tuples_with_a_run_that_can_not_be_written_is_an_error() {
+    create_tmp_test_folder();
+
+    let keys = vec![key(false, None)];
+    let mut blocked_run: Option<String> = None;
+    let rows = (0..500u64).map(|idx| {
+        // Puts a folder where the next run goes, so its file can't be written.
+        if idx == 200 {
+            let run_names: Vec<String> = read_dir(Config::data_folder())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+                .filter(|name| name.starts_with("rusticodb_sort_"))
+                .collect();
+            let sort_id = run_names[0].split('_').nth(2).unwrap().to_string();
+            let run_name = format!("{}/rusticodb_sort_{}_{}.tmp", Config::data_folder(), sort_id, run_names.len());
+            create_dir(&run_name).unwrap();
+            blocked_run = Some(run_name);
+        }
+        (vec![Data::UnsignedBigint(500 - idx)], vec![Data::UnsignedBigint(idx)])
+    });
+
+    let tuples = sort_tuples(&keys, rows, 256);
+
+    assert!(matches!(tuples, Err(ExecutionError::IoError(ref file, _)) if file.contains("rusticodb_sort_")));
+    // Only the folder in the way is left.
+    assert_eq!(sort_files(), 1);
+    remove_dir(blocked_run.unwrap()).unwrap();
+}
@@ -0,0 +1,143 @@
+use rstest::rstest;
+
+use rusticodb::storage::Data;
+use rusticodb::storage::MICROS_PER_DAY;
+use rusticodb::storage::MICROS_PER_HOUR;
+use rusticodb::storage::temporal_parse_date;
+use rusticodb::storage::temporal_parse_time;
+use rusticodb::storage::temporal_parse_timestamp;
+use rusticodb::storage::temporal_parse_interval;
+use rusticodb::storage::temporal_days_from_civil;
+use rusticodb::storage::temporal_civil_from_days;
+use rusticodb::storage::temporal_cast;
+use rusticodb::storage::temporal_extract;
+use rusticodb::storage::temporal_trunc;
+
+#[rstest]
+#[case("1970-01-01", Some(0))]
+#[case("2024-02-29", Some(19782))]
+#[case("1969-12-31", Some(-1))]
+#[case("2023-02-29", None)]
+#[case("2024-13-01", None)]
+#[case("2024/01/01", None)]
+pub fn test_parse_date(#[case] value: &str, #[case] expected: Option<i32>) {
+    assert_eq!(temporal_parse_date(value), expected);
+}
+
+#[rstest]
+#[case("10:30", Some(10 * 3600 * 1_000_000 + 30 * 60 * 1_000_000))]
+#[case("00:00:01.5", Some(1_500_000))]
+#[case("23:59:59.123456789", Some(86_399_123_456))]
+#[case("24:00:00", None)]
+#[case("10:61", None)]
+pub fn test_parse_time(#[case] value: &str, #[case] expected: Option<i64>) {
+    assert_eq!(temporal_parse_time(value), expected);
+}
+
+#[rstest]
+#[case("2024-01-01", Some((19723 * MICROS_PER_DAY, None)))]
+#[case("2024-01-01T10:00:00Z", Some((19723 * MICROS_PER_DAY + 10 * MICROS_PER_HOUR, Some(0))))]
+#[case("2024-01-01 10:00:00+02:00", Some((19723 * MICROS_PER_DAY + 10 * MICROS_PER_HOUR, Some(2 * MICROS_PER_HOUR))))]
+#[case("2024-01-01 10:00-0330", Some((19723 * MICROS_PER_DAY + 10 * MICROS_PER_HOUR, Some(-(3 * MICROS_PER_HOUR + 30 * 60_000_000)))))]
+#[case("2024-01-01 25:00", None)]
+pub fn test_parse_timestamp(#[case] value: &str, #[case] expected: Option<(i64, Option<i64>)>) {
+    assert_eq!(temporal_parse_timestamp(value), expected);
+}
+
+#[rstest]
+#[case("1 day", Some((0, 1, 0)))]
+#[case("1 year 2 mons 3 days 04:05:06", Some((14, 3, 4 * MICROS_PER_HOUR + 5 * 60_000_000 + 6_000_000)))]
+#[case("2 weeks", Some((0, 14, 0)))]
+#[case("-90 minutes", Some((0, 0, -90 * 60_000_000)))]
+#[case("1.5 seconds 10 ms", Some((0, 0, 1_510_000)))]
+#[case("3 days ago", Some((0, -3, 0)))]
+#[case("1.5 days", None)]
+#[case("1 fortnight", None)]
+pub fn test_parse_interval(#[case] value: &str, #[case] expected: Option<(i32, i32, i64)>) {
+    assert_eq!(temporal_parse_interval(value), expected);
+}
+
+#[test]
+pub fn test_civil_days_round_trip() {
+    for days in (-800_000i64..800_000).step_by(97) {
+        let (year, month, day) = temporal_civil_from_days(days);
+        assert_eq!(temporal_days_from_civil(year, month, day), days);
+    }
+}
+
+#[test]
+pub fn test_timestamps_with_time_zone_are_kept_in_utc() {
+    let value = temporal_cast(&Data::TimestampTz(0), &Data::Varchar(String::from("2024-01-01 02:30:00+02:00")));
+
+    assert_eq!(value.unwrap().to_string(), "2024-01-01 00:30:00+00");
+}
+
+#[test]
+pub fn test_temporal_values_format() {
+    assert_eq!(Data::Date(19723).to_string(), "2024-01-01");
+    assert_eq!(Data::Time(MICROS_PER_HOUR + 500).to_string(), "01:00:00.0005");
+    assert_eq!(Data::Timestamp(-1).to_string(), "1969-12-31 23:59:59.999999");
+    assert_eq!(Data::Interval(13, -2, -MICROS_PER_HOUR).to_string(), "1 year 1 mon -2 days -01:00:00");
+    assert_eq!(Data::Interval(0, 0, 0).to_string(), "00:00:00");
+}
+
+#[test]
+pub fn test_temporal_values_compare() {
+    assert!(Data::Date(19723) == Data::Timestamp(19723 * MICROS_PER_DAY));
+    assert!(Data::Date(19723) < Data::TimestampTz(19723 * MICROS_PER_DAY + 1));
+    assert!(Data::Date(19723) == Data::Varchar(String::from("2024-01-01")));
+    assert!(Data::Varchar(String::from("2023-12-31 23:59")) < Data::Timestamp(19723 * MICROS_PER_DAY));
+    assert!(Data::Interval(1, 0, 0) == Data::Interval(0, 30, 0));
+    assert!(Data::Interval(0, 1, 0) > Data::Interval(0, 0, 23 * MICROS_PER_HOUR));
+}
+
+#[test]
+pub fn test_temporal_arithmetic() {
+    let timestamp = Data::Timestamp(temporal_parse_timestamp("2024-01-31 10:00").unwrap().0);
+
+    assert_eq!(timestamp.clone().checked_add(Data::Interval(1, 0, 0)).unwrap().to_string(), "2024-02-29 10:00:00");
+    assert_eq!(timestamp.clone().checked_sub(Data::Interval(0, 1, MICROS_PER_HOUR)).unwrap().to_string(), "2024-01-30 09:00:00");
+    assert_eq!(Data::Date(19723).checked_add(Data::UnsignedBigint(31)).unwrap().to_string(), "2024-02-01");
+    assert_eq!(Data::Date(19754).checked_sub(Data::Date(19723)).unwrap(), Data::UnsignedBigint(31));
+    assert_eq!(Data::Date(19723).checked_add(Data::Time(MICROS_PER_HOUR)).unwrap().to_string(), "2024-01-01 01:00:00");
+    assert_eq!(timestamp.checked_sub(Data::Date(19722)).unwrap().to_string(), "31 days 10:00:00");
+    assert_eq!(Data::Interval(0, 1, 30).checked_mul(Data::UnsignedBigint(3)).unwrap(), Data::Interval(0, 3, 90));
+    assert_eq!(Data::Time(23 * MICROS_PER_HOUR).checked_add(Data::Interval(0, 0, 2 * MICROS_PER_HOUR)).unwrap().to_string(), "01:00:00");
+}
+
+#[rstest]
+#[case("year", Data::UnsignedBigint(2024))]
+#[case("quarter", Data::UnsignedBigint(1))]
+#[case("month", Data::UnsignedBigint(2))]
+#[case("day", Data::UnsignedBigint(29))]
+#[case("dow", Data::UnsignedBigint(4))]
+#[case("isodow", Data::UnsignedBigint(4))]
+#[case("doy", Data::UnsignedBigint(60))]
+#[case("week", Data::UnsignedBigint(9))]
+#[case("hour", Data::UnsignedBigint(13))]
+#[case("minute", Data::UnsignedBigint(45))]
+#[case("second", Data::Decimal(30_250_000, 38, 6))]
+#[case("milliseconds", Data::Decimal(30_250_000, 38, 3))]
+#[case("century", Data::UnsignedBigint(21))]
+#[case("epoch", Data::Decimal(1_709_214_330_250_000, 38, 6))]
+pub fn test_extract(#[case] field: &str, #[case] expected: Data) {
+    let value = temporal_cast(&Data::Timestamp(0), &Data::Varchar(String::from("2024-02-29 13:45:30.25"))).unwrap();
+
+    assert_eq!(temporal_extract(&String::from(field), &value), expected);
+}
+
+#[rstest]
+#[case("year", "2024-01-01 00:00:00")]
+#[case("quarter", "2024-01-01 00:00:00")]
+#[case("month", "2024-02-01 00:00:00")]
+#[case("week", "2024-02-26 00:00:00")]
+#[case("day", "2024-02-29 00:00:00")]
+#[case("hour", "2024-02-29 13:00:00")]
+#[case("second", "2024-02-29 13:45:30")]
+#[case("decade", "2020-01-01 00:00:00")]
+#[case("century", "2001-01-01 00:00:00")]
+pub fn test_date_trunc(#[case] field: &str, #[case] expected: &str) {
+    let value = temporal_cast(&Data::Timestamp(0), &Data::Varchar(String::from("2024-02-29 13:45:30.25"))).unwrap();
+
+    assert_eq!(temporal_trunc(&String::from(field), &value).to_string(), expected);
+}
@@ -4,6 +4,10 @@ use rusticodb::storage::tuple_new;
 use rusticodb::storage::tuple_serialize;
 use rusticodb::storage::tuple_deserialize;
 use rusticodb::storage::Data;
+use rusticodb::storage::json_parse;
+use rusticodb::utils::ExecutionError;
+
+use ordered_float::OrderedFloat;
 
 #[test]
 pub fn test_tuple_push_null() {
@@ -195,6 +199,92 @@ pub fn test_push_signed_bigint_to_u8() {
     assert_eq!(tuple_serialize(&tuple), buffer);
 }
 
+#[test]
+pub fn test_push_floats_to_u8() {
+    let mut buffer: Vec<u8> = Vec::new();
+
+    buffer.append(&mut 2u8.to_be_bytes().to_vec());
+    buffer.append(&mut 14u8.to_be_bytes().to_vec());
+    buffer.append(&mut 15u8.to_be_bytes().to_vec());
+    buffer.append(&mut 1.5f32.to_be_bytes().to_vec());
+    buffer.append(&mut (-0.25f64).to_be_bytes().to_vec());
+
+    let mut tuple = tuple_new();
+    tuple.push(Data::Float32(OrderedFloat(1.5)));
+    tuple.push(Data::Float64(OrderedFloat(-0.25)));
+
+    assert_eq!(tuple_serialize(&tuple), buffer);
+    assert_eq!(tuple_deserialize(&buffer), tuple);
+}
+
+#[test]
+pub fn test_push_decimal_to_u8() {
+    let mut buffer: Vec<u8> = Vec::new();
+
+    buffer.append(&mut 1u8.to_be_bytes().to_vec());
+    buffer.append(&mut 16u8.to_be_bytes().to_vec());
+    buffer.append(&mut vec![10u8, 2u8, 2u8]);
+    buffer.append(&mut (-1250i16).to_be_bytes().to_vec());
+
+    let mut tuple = tuple_new();
+    tuple.push(Data::Decimal(-1250, 10, 2));
+
+    assert_eq!(tuple_serialize(&tuple), buffer);
+    assert!(matches!(tuple_deserialize(&buffer)[0], Data::Decimal(-1250, 10, 2)));
+}
+
+#[test]
+pub fn test_push_temporal_values_to_u8() {
+    let mut tuple = tuple_new();
+    tuple.push(Data::Date(-1));
+    tuple.push(Data::Time(1_500_000));
+    tuple.push(Data::Timestamp(1_700_000_000_000_000));
+    tuple.push(Data::TimestampTz(-5));
+    tuple.push(Data::Interval(14, -3, 7));
+
+    let buffer = tuple_serialize(&tuple);
+
+    assert_eq!(&buffer[0..6], &[5u8, 17, 18, 19, 20, 21]);
+    assert_eq!(buffer.len(), 6 + 4 + 8 + 8 + 8 + 16);
+    assert_eq!(tuple_deserialize(&buffer), tuple);
+}
+
+#[test]
+pub fn test_push_bytes_to_u8() {
+    let mut tuple = tuple_new();
+    tuple.push(Data::Bytes(vec![0xde, 0xad]));
+    tuple.push(Data::Bytes(Vec::new()));
+
+    let buffer = tuple_serialize(&tuple);
+
+    assert_eq!(buffer, vec![2u8, 22, 22, 0, 0, 0, 2, 0xde, 0xad, 0, 0, 0, 0]);
+    assert_eq!(tuple_deserialize(&buffer), tuple);
+}
+
+#[test]
+pub fn test_push_uuid_to_u8() {
+    let mut tuple = tuple_new();
+    tuple.push(Data::Uuid(0xa0eebc999c0b4ef8bb6d6bb9bd380a11));
+
+    let buffer = tuple_serialize(&tuple);
+
+    assert_eq!(&buffer[0..2], &[1u8, 23]);
+    assert_eq!(&buffer[2..], &0xa0eebc999c0b4ef8bb6d6bb9bd380a11u128.to_be_bytes());
+    assert_eq!(tuple_deserialize(&buffer), tuple);
+}
+
+#[test]
+pub fn test_push_json_values_to_u8() {
+    let mut tuple = tuple_new();
+    tuple.push(Data::Json(String::from("{\"a\": 1}")));
+    tuple.push(Data::Jsonb(json_parse("{\"a\": [true, null]}").unwrap()));
+
+    let buffer = tuple_serialize(&tuple);
+
+    assert_eq!(&buffer[0..3], &[2u8, 24, 25]);
+    assert_eq!(tuple_deserialize(&buffer), tuple);
+}
+
 #[test]
 pub fn test_push_two_signed_bigint_to_u8() {
     let mut buffer: Vec<u8> = Vec::new();
@@ -533,7 +623,63 @@ pub fn test_data_combined_by_or_function(#[case] first: Data, #[case] second: Da
 #[case(Data::Varchar("a".to_string()), Data::Varchar("a".to_string()), Data::Varchar("aa".to_string()))]
 #[case(Data::Text("a".to_string()), Data::Text("a".to_string()), Data::Varchar("aa".to_string()))]
 pub fn test_data_combined_by_sum_operator(#[case] first: Data, #[case] second: Data, #[case] expected: Data) {
-    assert!(first.clone() + second.clone() == expected, "{}", format!("{:?} with {:?} should be {:?}", first, second, expected));
+    assert!(first.clone().checked_add(second.clone()).unwrap() == expected, "{}", format!("{:?} with {:?} should be {:?}", first, second, expected));
+}
+
+#[rstest]
+#[case(Data::Uuid(1), Data::Varchar(String::from("not-an-uuid")))]
+#[case(Data::Uuid(1), Data::UnsignedBigint(1))]
+#[case(Data::Bytes(vec![5]), Data::UnsignedBigint(5))]
+#[case(Data::Bytes(vec![0]), Data::Varchar(String::from("\\x0")))]
+#[case(Data::Boolean(true), Data::UnsignedBigint(1))]
+#[case(Data::Null, Data::Undefined)]
+pub fn test_data_that_cannot_be_compared_is_not_equal(#[case] first: Data, #[case] second: Data) {
+    assert!(first != second);
+    assert!(second != first);
+    assert_ne!(first.cmp(&second), std::cmp::Ordering::Equal);
+}
+
+#[rstest]
+#[case(Data::UnsignedTinyint(200), Data::UnsignedTinyint(100))]
+#[case(Data::SignedSmallint(i16::MAX), Data::SignedSmallint(1))]
+#[case(Data::UnsignedBigint(u64::MAX), Data::UnsignedBigint(1))]
+#[case(Data::UnsignedBigint(u64::MAX), Data::SignedInt(1))]
+pub fn test_data_sum_that_does_not_fit_is_an_overflow(#[case] first: Data, #[case] second: Data) {
+    assert!(matches!(first.checked_add(second), Err(ExecutionError::NumericOverflow(_))));
+}
+
+#[test]
+pub fn test_data_integer_arithmetic_checks_the_wide_result() {
+    let max = Data::UnsignedBigint(u64::MAX);
+
+    assert!(matches!(max.clone().checked_mul(max.clone()), Err(ExecutionError::NumericOverflow(_))));
+    assert!(matches!(Data::SignedBigint(i64::MIN).checked_sub(Data::UnsignedTinyint(1)), Err(ExecutionError::NumericOverflow(_))));
+    assert_eq!(max.clone().checked_sub(Data::UnsignedBigint(u64::MAX)).unwrap(), Data::UnsignedBigint(0));
+    assert_eq!(Data::SignedBigint(i64::MIN).checked_div(Data::SignedTinyint(-1)).unwrap(), Data::UnsignedBigint(1 << 63));
+    assert_eq!(Data::from_integer(i128::from(u64::MAX) + 1), None);
+    assert_eq!(Data::from_integer(i128::from(i64::MIN) - 1), None);
+}
+
+#[test]
+pub fn test_data_arithmetic_without_the_operator_is_an_error() {
+    let text = Data::Varchar(String::from("a"));
+
+    assert!(matches!(text.clone().checked_sub(Data::UnsignedBigint(1)), Err(ExecutionError::OperatorTypeNotMatch(_, _, _))));
+    assert!(matches!(text.clone().checked_mul(Data::UnsignedBigint(1)), Err(ExecutionError::OperatorTypeNotMatch(_, _, _))));
+    assert!(matches!(text.clone().checked_div(Data::UnsignedBigint(1)), Err(ExecutionError::OperatorTypeNotMatch(_, _, _))));
+    assert!(matches!(Data::Boolean(true).checked_add(Data::UnsignedBigint(1)), Err(ExecutionError::OperatorTypeNotMatch(_, _, _))));
+}
+
+#[rstest]
+#[case(Data::Null, Data::UnsignedBigint(2))]
+#[case(Data::SignedInt(2), Data::Null)]
+#[case(Data::Varchar("a".to_string()), Data::Null)]
+#[case(Data::Null, Data::Null)]
+pub fn test_data_arithmetic_with_null_is_null(#[case] first: Data, #[case] second: Data) {
+    assert_eq!(first.clone().checked_add(second.clone()).unwrap(), Data::Null);
+    assert_eq!(first.clone().checked_sub(second.clone()).unwrap(), Data::Null);
+    assert_eq!(first.clone().checked_mul(second.clone()).unwrap(), Data::Null);
+    assert_eq!(first.checked_div(second).unwrap(), Data::Null);
 }
 
 #[rstest]
@@ -600,6 +746,8 @@ pub fn test_data_not_operator(#[case] first: Data, #[case] expected: Data) {
 #[case(Data::Boolean(false), Data::Boolean(true))]
 #[case(Data::Varchar("a".to_string()), Data::Varchar("a".to_string()))]
 #[case(Data::Text("a".to_string()), Data::Text("a".to_string()))]
+#[case(Data::Float32(OrderedFloat(1.5)), Data::Float32(OrderedFloat(-1.5)))]
+#[case(Data::Float64(OrderedFloat(-2.5)), Data::Float64(OrderedFloat(2.5)))]
 pub fn test_data_neg_operator(#[case] first: Data, #[case] expected: Data) {
     assert!(-first.clone() == expected, "{}", format!("{:?} should be {:?}", first, expected));
 }
@@ -0,0 +1,50 @@
+use std::cmp::Ordering;
+
+use rstest::rstest;
+
+use rusticodb::storage::Data;
+use rusticodb::storage::uuid_parse;
+use rusticodb::storage::uuid_format;
+use rusticodb::storage::uuid_generate;
+use rusticodb::storage::uuid_compare;
+
+#[rstest]
+#[case("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11", Some(0xa0eebc999c0b4ef8bb6d6bb9bd380a11))]
+#[case("A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11", Some(0xa0eebc999c0b4ef8bb6d6bb9bd380a11))]
+#[case("{a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11}", Some(0xa0eebc999c0b4ef8bb6d6bb9bd380a11))]
+#[case("a0eebc999c0b4ef8bb6d6bb9bd380a11", Some(0xa0eebc999c0b4ef8bb6d6bb9bd380a11))]
+#[case("a0eebc99-9c0b4-ef8-bb6d-6bb9bd380a11", None)]
+#[case("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a1g", None)]
+#[case("a0eebc99", None)]
+pub fn test_uuid_parse(#[case] value: &str, #[case] expected: Option<u128>) {
+    assert_eq!(uuid_parse(value), expected);
+}
+
+#[test]
+pub fn test_uuid_format() {
+    assert_eq!(uuid_format(0xa0eebc999c0b4ef8bb6d6bb9bd380a11), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+    assert_eq!(uuid_format(1), "00000000-0000-0000-0000-000000000001");
+}
+
+#[test]
+pub fn test_uuid_generate_gives_distinct_version_4_uuids() {
+    let first = uuid_generate();
+    let second = uuid_generate();
+
+    assert_ne!(first, second);
+    for uuid in [first, second] {
+        let text = uuid_format(uuid);
+        assert_eq!(&text[14..15], "4");
+        assert!(matches!(&text[19..20], "8" | "9" | "a" | "b"));
+        assert_eq!(uuid_parse(&text), Some(uuid));
+    }
+}
+
+#[rstest]
+#[case(Data::Uuid(1), Data::Uuid(2), Some(Ordering::Less))]
+#[case(Data::Uuid(1), Data::Varchar(String::from("00000000-0000-0000-0000-000000000001")), Some(Ordering::Equal))]
+#[case(Data::Uuid(1), Data::Varchar(String::from("not an uuid")), None)]
+#[case(Data::UnsignedBigint(1), Data::UnsignedBigint(1), None)]
+pub fn test_uuid_compare(#[case] a: Data, #[case] b: Data, #[case] expected: Option<Ordering>) {
+    assert_eq!(uuid_compare(&a, &b), expected);
+}
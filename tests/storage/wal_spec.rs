@@ -0,0 +1,183 @@
+use rstest::rstest;
+
+use rusticodb::storage::format_table_name;
+use rusticodb::storage::format_database_name;
+use rusticodb::storage::format_wal_name;
+use rusticodb::storage::format_index_name;
+use rusticodb::storage::btree_create;
+use rusticodb::storage::btree_insert;
+use rusticodb::storage::btree_entries;
+use rusticodb::storage::TupleAddress;
+use rusticodb::storage::Pager;
+use rusticodb::storage::tuple_new;
+use rusticodb::storage::pager_insert_tuples;
+use rusticodb::storage::pager_read_tuples;
+use rusticodb::storage::pager_flush_page;
+use rusticodb::storage::pager_write_ahead_log;
+use rusticodb::storage::pager_write_pages;
+use rusticodb::storage::create_file;
+use rusticodb::storage::create_folder;
+use rusticodb::storage::destroy_folder;
+use rusticodb::storage::read_file;
+use rusticodb::storage::truncate_file;
+use rusticodb::storage::wal_recover;
+use rusticodb::storage::wal_read_transactions;
+use rusticodb::storage::Data;
+use rusticodb::utils::ExecutionError;
+
+use crate::test_utils::create_tmp_test_folder;
+
+fn insert_tuples(pager: &mut Pager, page_key: &String, amount: u64) {
+    for idx in 0..amount {
+        let mut tuple = tuple_new();
+        tuple.push(Data::UnsignedBigint(idx));
+        tuple.push(Data::Varchar(String::from("wal tuple")));
+
+        pager_insert_tuples(pager, page_key, &mut vec![tuple]);
+    }
+}
+
+fn setup_table() -> String {
+    let database1 = String::from("database1");
+    let table1 = String::from("table1");
+    let page_key = format_table_name(&database1, &table1);
+
+    create_tmp_test_folder();
+
+    create_folder(&format_database_name(&database1));
+    create_file(&page_key);
+
+    return page_key;
+}
+
+#[test]
+pub fn test_wal_is_empty_after_flush() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    insert_tuples(&mut pager, &page_key, 10);
+    pager_flush_page(&mut pager, &page_key);
+
+    assert_eq!(read_file(&format_wal_name()).unwrap().len(), 0);
+    assert_eq!(wal_read_transactions().unwrap().len(), 0);
+}
+
+#[test]
+pub fn test_recover_replays_committed_log() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    insert_tuples(&mut pager, &page_key, 200);
+    pager_write_ahead_log(&mut pager, &page_key);
+
+    assert_eq!(wal_read_transactions().unwrap().len(), 1);
+    assert_eq!(wal_recover().unwrap(), 1);
+    assert_eq!(read_file(&format_wal_name()).unwrap().len(), 0);
+
+    let mut pager_new = Pager::new();
+    assert_eq!(pager_read_tuples(&mut pager_new, &page_key).len(), 200);
+}
+
+#[rstest]
+#[case(1)]
+#[case(3)]
+#[case(100)]
+#[case(4096)]
+#[case(4200)]
+#[case(9000)]
+pub fn test_recover_discards_truncated_log(#[case] offset: u64) {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    insert_tuples(&mut pager, &page_key, 10);
+    pager_flush_page(&mut pager, &page_key);
+
+    insert_tuples(&mut pager, &page_key, 190);
+    pager_write_ahead_log(&mut pager, &page_key);
+
+    truncate_file(&format_wal_name(), offset);
+
+    assert_eq!(wal_recover().unwrap(), 0);
+    assert_eq!(read_file(&format_wal_name()).unwrap().len(), 0);
+
+    let mut pager_new = Pager::new();
+    assert_eq!(pager_read_tuples(&mut pager_new, &page_key).len(), 10);
+}
+
+#[test]
+pub fn test_recover_discards_log_without_commit_record() {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    insert_tuples(&mut pager, &page_key, 10);
+    pager_flush_page(&mut pager, &page_key);
+
+    insert_tuples(&mut pager, &page_key, 190);
+    pager_write_ahead_log(&mut pager, &page_key);
+
+    let log_size = read_file(&format_wal_name()).unwrap().len() as u64;
+    truncate_file(&format_wal_name(), log_size - 1);
+
+    assert_eq!(wal_recover().unwrap(), 0);
+
+    let mut pager_new = Pager::new();
+    assert_eq!(pager_read_tuples(&mut pager_new, &page_key).len(), 10);
+}
+
+#[rstest]
+#[case(0)]
+#[case(10)]
+#[case(4096)]
+#[case(4096 + 100)]
+#[case(8192 + 1)]
+pub fn test_recover_repairs_truncated_data_file(#[case] offset: u64) {
+    let page_key = setup_table();
+    let mut pager = Pager::new();
+
+    insert_tuples(&mut pager, &page_key, 10);
+    pager_flush_page(&mut pager, &page_key);
+
+    insert_tuples(&mut pager, &page_key, 190);
+    pager_write_ahead_log(&mut pager, &page_key);
+    pager_write_pages(&mut pager, &page_key);
+
+    truncate_file(&page_key, offset);
+
+    assert_eq!(wal_recover().unwrap(), 1);
+
+    let mut pager_new = Pager::new();
+    assert_eq!(pager_read_tuples(&mut pager_new, &page_key).len(), 200);
+}
+
+#[test]
+pub fn test_recover_fails_when_log_can_not_be_read() {
+    setup_table();
+
+    create_folder(&format_wal_name());
+
+    assert!(matches!(wal_read_transactions(), Err(ExecutionError::IoError(_, _))));
+    assert!(matches!(wal_recover(), Err(ExecutionError::IoError(_, _))));
+
+    destroy_folder(&format_wal_name());
+}
+
+#[test]
+pub fn test_recover_replays_index_blocks_with_the_rows() {
+    let page_key = setup_table();
+    let index_key = format_index_name(&String::from("database1"), &String::from("table1"), &String::from("index1"));
+    let mut pager = Pager::new();
+
+    btree_create(&mut pager, &index_key);
+    for idx in 0..200u64 {
+        let address = TupleAddress::new(1, idx as u16);
+        btree_insert(&mut pager, &index_key, &vec![Data::UnsignedBigint(idx)], &address).unwrap();
+    }
+    insert_tuples(&mut pager, &page_key, 200);
+    pager_write_ahead_log(&mut pager, &page_key);
+
+    assert_eq!(wal_recover().unwrap(), 1);
+
+    let mut pager_new = Pager::new();
+    assert_eq!(pager_read_tuples(&mut pager_new, &page_key).len(), 200);
+    assert_eq!(btree_entries(&pager_new, &index_key).unwrap().len(), 200);
+}